@@ -0,0 +1,92 @@
+//! Loads one service instance with a growing number of grooming, preventive-care and surgery
+//! rows and reports the SQL statements issued per detail request.
+//!
+//! Run with `cargo bench --bench service_instance_detail`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::fixtures::{
+    surgery_body, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::query_counter::QueryCounter;
+use common::TestApp;
+use serde_json::json;
+
+const SIZES: [usize; 5] = [1, 10, 50, 100, 250];
+const ITERATIONS: u32 = 20;
+
+#[tokio::main]
+async fn main() {
+    let counter = QueryCounter::install();
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    println!(
+        "{:>12} {:>10} {:>14}",
+        "sub-records", "queries", "mean latency"
+    );
+    let mut query_counts = Vec::new();
+    for size in SIZES {
+        let service_instance_id = seed_service_instance(&app, &pet_id, size).await;
+        let uri = format!("/api/v1/service-instances/{}", service_instance_id);
+
+        counter.reset();
+        app.get(&uri).await;
+        let queries = counter.count();
+
+        let started = Instant::now();
+        for _ in 0..ITERATIONS {
+            app.get(&uri).await;
+        }
+        let mean: Duration = started.elapsed() / ITERATIONS;
+
+        println!("{:>12} {:>10} {:>14.2?}", size * 3, queries, mean);
+        query_counts.push(queries);
+    }
+
+    assert!(
+        query_counts.iter().all(|count| *count == query_counts[0]),
+        "query count grew with sub-records: {:?}",
+        query_counts
+    );
+}
+
+/// Creates a service instance with `size` rows each of grooming, preventive care and
+/// surgery, every preventive-care and surgery row attended by a different vet.
+async fn seed_service_instance(app: &TestApp, pet_id: &str, size: usize) -> String {
+    let service_instance_id = ServiceInstanceBuilder::new(pet_id)
+        .service_types(&["Grooming", "Preventive Care", "Surgery"])
+        .grooming(&vec!["Bath"; size])
+        .create(app)
+        .await;
+
+    for _ in 0..size {
+        let vet_id = VetBuilder::new().create(app).await;
+        app.post(
+            &format!(
+                "/api/v1/service-instances/{}/preventive-care",
+                service_instance_id
+            ),
+            json!({
+                "treatment": ["Vaccine"],
+                "vet_id": vet_id,
+                "service_instance_id": service_instance_id,
+            }),
+        )
+        .await;
+        app.post(
+            &format!(
+                "/api/v1/service-instances/{}/surgeries",
+                service_instance_id
+            ),
+            surgery_body(&vet_id, "Spay"),
+        )
+        .await;
+    }
+
+    service_instance_id
+}
@@ -0,0 +1,176 @@
+//! Bulk-imports owners, pets or vets from a CSV or NDJSON file into the configured database.
+//!
+//! ```text
+//! pet-import --entity pets [--format csv|ndjson] [--map "Column=field"]...
+//!            [--mode all-or-nothing|best-effort] [--dry-run] [--report errors.csv]
+//!            [--clinic CLINIC_ID] FILE
+//! ```
+
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+use pet_api::{AppState, ImportEntity, ImportFormat, ImportMode, ImportRequest, DEFAULT_CLINIC_ID};
+
+const USAGE: &str = "usage: pet-import --entity owners|pets|vets [--format csv|ndjson] \
+[--map COLUMN=FIELD]... [--mode all-or-nothing|best-effort] [--dry-run] [--report PATH] \
+[--clinic CLINIC_ID] FILE";
+
+struct Args {
+    request: ImportRequest,
+    clinic_id: String,
+    report_path: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut entity = None;
+    let mut format = None;
+    let mut mapping = BTreeMap::new();
+    let mut mode = ImportMode::default();
+    let mut dry_run = false;
+    let mut report_path = None;
+    let mut clinic_id = DEFAULT_CLINIC_ID.to_string();
+    let mut file = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "--entity" => {
+                entity = Some(match value("--entity")?.as_str() {
+                    "owners" => ImportEntity::Owners,
+                    "pets" => ImportEntity::Pets,
+                    "vets" => ImportEntity::Vets,
+                    other => return Err(format!("unknown entity '{}'", other)),
+                })
+            }
+            "--format" => {
+                format = Some(match value("--format")?.as_str() {
+                    "csv" => ImportFormat::Csv,
+                    "ndjson" => ImportFormat::Ndjson,
+                    other => return Err(format!("unknown format '{}'", other)),
+                })
+            }
+            "--map" => {
+                let pair = value("--map")?;
+                let (column, field) = pair
+                    .rsplit_once('=')
+                    .ok_or(format!("--map expects COLUMN=FIELD, got '{}'", pair))?;
+                mapping.insert(column.to_string(), field.to_string());
+            }
+            "--mode" => {
+                mode = match value("--mode")?.as_str() {
+                    "all-or-nothing" => ImportMode::AllOrNothing,
+                    "best-effort" => ImportMode::BestEffort,
+                    other => return Err(format!("unknown mode '{}'", other)),
+                }
+            }
+            "--dry-run" => dry_run = true,
+            "--report" => report_path = Some(value("--report")?),
+            "--clinic" => clinic_id = value("--clinic")?,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let entity = entity.ok_or("--entity is required")?;
+    let file = file.ok_or("an input FILE is required")?;
+    let format = match format {
+        Some(format) => format,
+        None if file.ends_with(".ndjson") || file.ends_with(".jsonl") => ImportFormat::Ndjson,
+        None => ImportFormat::Csv,
+    };
+    let data =
+        std::fs::read_to_string(&file).map_err(|e| format!("failed to read {}: {}", file, e))?;
+
+    Ok(Args {
+        request: ImportRequest {
+            entity,
+            format,
+            data,
+            mapping,
+            mode,
+            dry_run,
+        },
+        clinic_id,
+        report_path,
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let app_state = match AppState::connect().await {
+        Ok(app_state) => app_state,
+        Err(e) => {
+            eprintln!("❌ Failed to connect to the Database: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = app_state.create_tables().await {
+        eprintln!("❌ Failed to create tables: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    let report = match app_state.import(&args.clinic_id, args.request).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("❌ Import failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for error in &report.errors {
+        eprintln!(
+            "row {}{}: {}",
+            error.row,
+            error
+                .field
+                .as_ref()
+                .map(|field| format!(" ({})", field))
+                .unwrap_or_default(),
+            error.message
+        );
+    }
+    if let Some(path) = args.report_path {
+        if let Err(e) = std::fs::write(&path, report.errors_csv()) {
+            eprintln!("❌ Failed to write the error report to {}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let outcome = if report.dry_run {
+        "would be imported (dry run)"
+    } else if report.committed {
+        "imported"
+    } else {
+        "imported; nothing was saved because some rows are invalid"
+    };
+    println!(
+        "{} {}: {} of {} rows {}, {} failed (import {})",
+        if report.failed_rows == 0 {
+            "✅"
+        } else {
+            "⚠️"
+        },
+        report.entity.as_str(),
+        report.imported_rows,
+        report.total_rows,
+        outcome,
+        report.failed_rows,
+        report.import_id
+    );
+
+    if report.failed_rows == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
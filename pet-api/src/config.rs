@@ -1,5 +1,31 @@
 use dotenv::dotenv;
 use std::env;
+use std::time::Duration;
+
+const DEFAULT_IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_PORTAL_LOGIN_URL: &str = "http://localhost:3000/portal/login";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbDriver {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+pub fn get_db_driver() -> Result<DbDriver, env::VarError> {
+    dotenv().ok();
+
+    match env::var("DB_DRIVER") {
+        Ok(driver) => match driver.trim().to_lowercase().as_str() {
+            "mysql" => Ok(DbDriver::MySql),
+            "postgres" | "postgresql" => Ok(DbDriver::Postgres),
+            "sqlite" => Ok(DbDriver::Sqlite),
+            _ => Err(env::VarError::NotPresent),
+        },
+        Err(env::VarError::NotPresent) => Ok(DbDriver::MySql),
+        Err(e) => Err(e),
+    }
+}
 
 pub fn get_db_config() -> Result<(String, u16, String, String, String), env::VarError> {
     dotenv().ok();
@@ -14,3 +40,135 @@ pub fn get_db_config() -> Result<(String, u16, String, String, String), env::Var
 
     Ok((db_host, db_port, db_name, db_user, db_password))
 }
+
+pub fn get_sqlite_path() -> Result<String, env::VarError> {
+    dotenv().ok();
+
+    env::var("DB_PATH")
+}
+
+/// How long a stored `Idempotency-Key` response is replayed, from `IDEMPOTENCY_KEY_TTL_SECONDS`.
+pub fn get_idempotency_key_ttl() -> Result<Duration, env::VarError> {
+    dotenv().ok();
+
+    match env::var("IDEMPOTENCY_KEY_TTL_SECONDS") {
+        Ok(seconds) => seconds
+            .trim()
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .map_err(|_| env::VarError::NotPresent),
+        Err(env::VarError::NotPresent) => Ok(DEFAULT_IDEMPOTENCY_KEY_TTL),
+        Err(e) => Err(e),
+    }
+}
+
+/// Clinic details printed on generated documents such as medical records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClinicBranding {
+    pub name: String,
+    pub address: Option<String>,
+    pub phone_number: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    /// Accent colour of headings and the letterhead band, as `#RRGGBB`.
+    pub accent_color: String,
+    /// Public base URL of this API, printed on certificates so they can be verified online.
+    pub public_api_url: Option<String>,
+}
+
+impl Default for ClinicBranding {
+    fn default() -> Self {
+        Self {
+            name: "Paws and Claws Veterinary Clinic".to_string(),
+            address: None,
+            phone_number: None,
+            email: None,
+            website: None,
+            accent_color: "#2F6F8F".to_string(),
+            public_api_url: None,
+        }
+    }
+}
+
+/// Reads `CLINIC_NAME`, `CLINIC_ADDRESS`, `CLINIC_PHONE_NUMBER`, `CLINIC_EMAIL`, `CLINIC_WEBSITE`,
+/// `CLINIC_ACCENT_COLOR` and `PUBLIC_API_URL`, keeping the defaults for any that are unset.
+pub fn get_clinic_branding() -> Result<ClinicBranding, env::VarError> {
+    dotenv().ok();
+
+    let optional = |name: &str| match env::var(name) {
+        Ok(value) if value.trim().is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value.trim().to_string())),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(e),
+    };
+    let defaults = ClinicBranding::default();
+
+    let accent_color = optional("CLINIC_ACCENT_COLOR")?.unwrap_or(defaults.accent_color);
+    let is_hex_color = accent_color.len() == 7
+        && accent_color.starts_with('#')
+        && accent_color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex_color {
+        return Err(env::VarError::NotPresent);
+    }
+
+    Ok(ClinicBranding {
+        name: optional("CLINIC_NAME")?.unwrap_or(defaults.name),
+        address: optional("CLINIC_ADDRESS")?,
+        phone_number: optional("CLINIC_PHONE_NUMBER")?,
+        email: optional("CLINIC_EMAIL")?,
+        website: optional("CLINIC_WEBSITE")?,
+        accent_color,
+        public_api_url: optional("PUBLIC_API_URL")?
+            .map(|url| url.trim_end_matches('/').to_string()),
+    })
+}
+
+/// Page of the owner portal that signs owners in with the token from an emailed link,
+/// from `PORTAL_LOGIN_URL`.
+pub fn get_portal_login_url() -> String {
+    dotenv().ok();
+
+    match env::var("PORTAL_LOGIN_URL") {
+        Ok(url) if !url.trim().is_empty() => url.trim().to_string(),
+        _ => DEFAULT_PORTAL_LOGIN_URL.to_string(),
+    }
+}
+
+/// How emails such as owner portal sign-in links are delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MailerConfig {
+    /// Through the relay at `SMTP_URL`, from the `MAIL_FROM` address.
+    Smtp { url: String, from: String },
+    /// Not at all; only the recipient and subject are logged. For local development.
+    Log,
+}
+
+/// Reads `MAILER`, `smtp` unless set to `log`, and for SMTP the `SMTP_URL` and `MAIL_FROM` it needs.
+pub fn get_mailer_config() -> Result<MailerConfig, env::VarError> {
+    dotenv().ok();
+
+    let mailer = match env::var("MAILER") {
+        Ok(mailer) => mailer.trim().to_lowercase(),
+        Err(env::VarError::NotPresent) => "smtp".to_string(),
+        Err(e) => return Err(e),
+    };
+    match mailer.as_str() {
+        "smtp" => Ok(MailerConfig::Smtp {
+            url: env::var("SMTP_URL")?.trim().to_string(),
+            from: env::var("MAIL_FROM")?.trim().to_string(),
+        }),
+        "log" => Ok(MailerConfig::Log),
+        _ => Err(env::VarError::NotPresent),
+    }
+}
+
+/// Secret that signs certificate contents, from `CERTIFICATE_SIGNING_KEY`. Certificates only
+/// verify while the server keeps the key they were issued under.
+pub fn get_certificate_signing_key() -> Option<String> {
+    dotenv().ok();
+
+    match env::var("CERTIFICATE_SIGNING_KEY") {
+        Ok(key) if !key.trim().is_empty() => Some(key.trim().to_string()),
+        _ => None,
+    }
+}
@@ -1,11 +1,75 @@
-use crate::config::get_db_config;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+
+use crate::config::{get_db_config, get_db_driver, get_sqlite_path, DbDriver};
+use crate::db::queries::{
+    boarding_queries::BoardingQueries, certificate_queries::CertificateQueries,
+    clinic_queries::ClinicQueries, clinical_code_queries::ClinicalCodeQueries,
+    clinical_note_queries::ClinicalNoteQueries, health_queries::HealthQueries,
+    idempotency_queries::IdempotencyQueries, identifier_queries::IdentifierQueries,
+    import_queries::ImportQueries, lab_queries::LabQueries, owner_queries::OwnerQueries,
+    pet_queries::PetQueries, portal_queries::PortalQueries, problem_queries::ProblemQueries,
+    service_instance_queries::ServiceInstanceQueries, staff_queries::StaffQueries,
+    statistic_queries::StatisticQueries, surgical_case_queries::SurgicalCaseQueries,
+    vet_queries::VetQueries,
+};
+use crate::db::repositories::{
+    boarding_repository::BoardingRepository, certificate_repository::CertificateRepository,
+    clinic_repository::ClinicRepository, clinical_code_repository::ClinicalCodeRepository,
+    clinical_note_repository::ClinicalNoteRepository, health_repository::HealthRepository,
+    idempotency_repository::IdempotencyRepository, identifier_repository::IdentifierRepository,
+    import_repository::ImportRepository, lab_repository::LabRepository,
+    owner_repository::OwnerRepository, pet_repository::PetRepository,
+    portal_repository::PortalRepository, problem_repository::ProblemRepository,
+    service_instance_repository::ServiceInstanceRepository, staff_repository::StaffRepository,
+    statistic_repository::StatisticRepository, surgical_case_repository::SurgicalCaseRepository,
+    vet_repository::VetRepository,
+};
+
+#[derive(Clone)]
+pub enum DbPool {
+    MySql(MySqlPool),
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+}
+
+pub async fn connect() -> Result<DbPool, sqlx::Error> {
+    let driver = match get_db_driver() {
+        Ok(driver) => driver,
+        Err(e) => panic!("❌ Failed to get the Database driver: {}", e),
+    };
+
+    if driver == DbDriver::Sqlite {
+        let db_path = match get_sqlite_path() {
+            Ok(path) => path,
+            Err(e) => panic!("❌ Failed to get the SQLite database path: {}", e),
+        };
+        return connect_sqlite(&db_path).await;
+    }
 
-pub async fn connect() -> Result<MySqlPool, sqlx::Error> {
     let (db_host, db_port, db_name, db_user, db_password) = match get_db_config() {
         Ok(config) => config,
         Err(e) => panic!("❌ Failed to get the Database configuration: {}", e),
     };
+
+    if driver == DbDriver::Postgres {
+        let connection_string = format!(
+            "postgres://{}:{}@{}:{}/{}",
+            db_user, db_password, db_host, db_port, db_name
+        );
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .max_lifetime(std::time::Duration::from_secs(1800))
+            .connect(&connection_string)
+            .await?;
+
+        return Ok(DbPool::Postgres(pool));
+    }
+
     let connection_string = format!(
         "mysql://{}:{}@{}:{}/{}",
         db_user, db_password, db_host, db_port, db_name
@@ -16,5 +80,117 @@ pub async fn connect() -> Result<MySqlPool, sqlx::Error> {
         .connect(&connection_string)
         .await?;
 
-    Ok(pool)
+    Ok(DbPool::MySql(pool))
+}
+
+pub async fn connect_sqlite(db_path: &str) -> Result<DbPool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(db_path)?
+        .create_if_missing(true)
+        .foreign_keys(true);
+
+    // An in-memory database only lives as long as its connection, so keep exactly one open.
+    let pool = if db_path == ":memory:" {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?
+    } else {
+        SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?
+    };
+
+    Ok(DbPool::Sqlite(pool))
+}
+
+/// Boxes the `$queries` of the database the pool connects to, made with `$arg`s.
+macro_rules! queries {
+    ($pool:expr, $queries:ident $(, $arg:expr)*) => {
+        match $pool {
+            DbPool::MySql(pool) => Box::new($queries::new(Arc::new(pool.clone()) $(, $arg)*)),
+            DbPool::Postgres(pool) => Box::new($queries::new(Arc::new(pool.clone()) $(, $arg)*)),
+            DbPool::Sqlite(pool) => Box::new($queries::new(Arc::new(pool.clone()) $(, $arg)*)),
+        }
+    };
+}
+
+impl DbPool {
+    pub fn boarding(&self, clinic_id: &str) -> Box<dyn BoardingRepository> {
+        queries!(self, BoardingQueries, clinic_id)
+    }
+
+    pub fn certificates(&self, clinic_id: &str) -> Box<dyn CertificateRepository> {
+        queries!(self, CertificateQueries, clinic_id)
+    }
+
+    pub fn clinics(&self) -> Box<dyn ClinicRepository> {
+        queries!(self, ClinicQueries)
+    }
+
+    pub fn clinical_codes(&self) -> Box<dyn ClinicalCodeRepository> {
+        queries!(self, ClinicalCodeQueries)
+    }
+
+    pub fn clinical_notes(&self, clinic_id: &str) -> Box<dyn ClinicalNoteRepository> {
+        queries!(self, ClinicalNoteQueries, clinic_id)
+    }
+
+    pub fn health(&self) -> Box<dyn HealthRepository> {
+        queries!(self, HealthQueries)
+    }
+
+    pub fn idempotency_keys(&self, clinic_id: &str) -> Box<dyn IdempotencyRepository> {
+        queries!(self, IdempotencyQueries, clinic_id)
+    }
+
+    pub fn identifiers(&self, clinic_id: &str) -> Box<dyn IdentifierRepository> {
+        queries!(self, IdentifierQueries, clinic_id)
+    }
+
+    pub fn imports(&self, clinic_id: &str) -> Box<dyn ImportRepository> {
+        queries!(self, ImportQueries, clinic_id)
+    }
+
+    pub fn labs(&self, clinic_id: &str) -> Box<dyn LabRepository> {
+        queries!(self, LabQueries, clinic_id)
+    }
+
+    pub fn owners(&self, clinic_id: &str) -> Box<dyn OwnerRepository> {
+        queries!(self, OwnerQueries, clinic_id)
+    }
+
+    pub fn pets(&self, clinic_id: &str) -> Box<dyn PetRepository> {
+        queries!(self, PetQueries, clinic_id)
+    }
+
+    pub fn portal(&self) -> Box<dyn PortalRepository> {
+        queries!(self, PortalQueries)
+    }
+
+    pub fn problems(&self, clinic_id: &str) -> Box<dyn ProblemRepository> {
+        queries!(self, ProblemQueries, clinic_id)
+    }
+
+    pub fn service_instances(&self, clinic_id: &str) -> Box<dyn ServiceInstanceRepository> {
+        queries!(self, ServiceInstanceQueries, clinic_id)
+    }
+
+    pub fn staff(&self, clinic_id: &str) -> Box<dyn StaffRepository> {
+        queries!(self, StaffQueries, clinic_id)
+    }
+
+    pub fn statistics(&self, clinic_id: &str) -> Box<dyn StatisticRepository> {
+        queries!(self, StatisticQueries, clinic_id)
+    }
+
+    pub fn surgical_cases(&self, clinic_id: &str) -> Box<dyn SurgicalCaseRepository> {
+        queries!(self, SurgicalCaseQueries, clinic_id)
+    }
+
+    pub fn vets(&self, clinic_id: &str) -> Box<dyn VetRepository> {
+        queries!(self, VetQueries, clinic_id)
+    }
 }
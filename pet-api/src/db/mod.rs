@@ -1,2 +1,3 @@
 pub(crate) mod connection;
 pub(crate) mod queries;
+pub(crate) mod repositories;
@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate};
+use sqlx::pool::PoolConnection;
+use sqlx::{Database, Executor, MySql, Pool, Postgres, Row, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::boarding_repository::{BoardingRepository, BookingCheck};
+use crate::models::boarding_model::{BoardingStayModel, CareLogEntry, KennelModel};
+use crate::schemas::boarding_schema::{AddCareLogEntry, AddKennel, BoardingStayOptions};
+use crate::utils::rules::RuleViolation;
+
+pub struct BoardingQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> BoardingQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+const SELECT_STAYS: &str = r#"SELECT boarding_stay.stay_id, boarding_stay.kennel_id, kennel.kennel_name,
+    boarding_stay.pet_id, pet.pet_name, pet.pet_type, boarding_stay.check_in_date,
+    boarding_stay.check_out_date, boarding_stay.stay_status, boarding_stay.feeding_instructions,
+    boarding_stay.medication_instructions, boarding_stay.reserved_at, boarding_stay.checked_in_at,
+    boarding_stay.checked_out_at
+    FROM boarding_stay
+    JOIN kennel ON kennel.kennel_id = boarding_stay.kennel_id
+    JOIN pet ON pet.pet_id = boarding_stay.pet_id"#;
+
+/// Limits `kennel_id` to the kennels of the clinic bound in its place.
+const CLINIC_KENNELS: &str = "kennel_id IN (SELECT kennel_id FROM kennel WHERE clinic_id = ?)";
+
+/// Limits `stay_id` to the stays of the clinic bound in its place.
+const CLINIC_STAYS: &str = "stay_id IN (SELECT boarding_stay.stay_id FROM boarding_stay \
+    JOIN kennel ON kennel.kennel_id = boarding_stay.kennel_id WHERE kennel.clinic_id = ?)";
+
+fn stay_filters<DB: Dialect>(
+    clinic_id: &str,
+    filter: &BoardingStayOptions,
+) -> (String, Vec<String>) {
+    let mut conditions = vec!["kennel.clinic_id = ?".to_string()];
+    let mut params = vec![clinic_id.to_string()];
+
+    if let Some(status) = filter.status {
+        params.push(status.as_str().to_string());
+        conditions.push("boarding_stay.stay_status = ?".to_string());
+    }
+    if let Some(pet_id) = &filter.pet_id {
+        params.push(pet_id.clone());
+        conditions.push("boarding_stay.pet_id = ?".to_string());
+    }
+    if let Some(kennel_id) = &filter.kennel_id {
+        params.push(kennel_id.clone());
+        conditions.push("boarding_stay.kennel_id = ?".to_string());
+    }
+    if let Some(night) = &filter.night {
+        params.push(night.clone());
+        params.push(night.clone());
+        conditions.push(format!(
+            "boarding_stay.check_in_date <= {0} AND boarding_stay.check_out_date > {0}",
+            DB::text_as("DATE")
+        ));
+    }
+
+    (format!(" WHERE {}", conditions.join(" AND ")), params)
+}
+
+/// A pooled connection inside a transaction begun with [`Dialect::BEGIN_WRITE`], which sqlx's
+/// own transactions can't start. Dropped before `finish`, e.g. when the request is cancelled,
+/// the connection is closed rather than returned to the pool mid-transaction.
+struct WriteTransaction<DB: Database> {
+    conn: Option<PoolConnection<DB>>,
+}
+
+impl<DB: Dialect> WriteTransaction<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    async fn begin(pool: &Pool<DB>) -> Result<Self, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        conn.execute(DB::BEGIN_WRITE).await?;
+        Ok(Self { conn: Some(conn) })
+    }
+
+    /// Commits, or rolls back when `commit` is false.
+    async fn finish(mut self, commit: bool) -> Result<(), sqlx::Error> {
+        let Some(conn) = self.conn.as_mut() else {
+            return Ok(());
+        };
+        conn.execute(if commit { "COMMIT" } else { "ROLLBACK" })
+            .await?;
+        self.conn.take();
+        Ok(())
+    }
+}
+
+impl<DB: Database> std::ops::Deref for WriteTransaction<DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &DB::Connection {
+        self.conn.as_ref().expect("the transaction is finished")
+    }
+}
+
+impl<DB: Database> std::ops::DerefMut for WriteTransaction<DB> {
+    fn deref_mut(&mut self) -> &mut DB::Connection {
+        self.conn.as_mut().expect("the transaction is finished")
+    }
+}
+
+impl<DB: Database> Drop for WriteTransaction<DB> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            drop(conn.detach());
+        }
+    }
+}
+
+macro_rules! boarding_queries {
+    ($db:ty) => {
+        impl BoardingQueries<$db> {
+            async fn insert_stay<'e>(
+                executor: impl Executor<'e, Database = $db>,
+                clinic_id: &str,
+                stay: &BoardingStayModel,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(
+                    r#"INSERT INTO boarding_stay (stay_id, kennel_id, pet_id, check_in_date, check_out_date,
+                    stay_status, feeding_instructions, medication_instructions, reserved_at)
+                    SELECT ?, kennel.kennel_id, pet.pet_id, ?, ?, ?, ?, ?, ?
+                    FROM kennel JOIN pet ON pet.clinic_id = kennel.clinic_id
+                    WHERE kennel.kennel_id = ? AND pet.pet_id = ? AND kennel.clinic_id = ?"#,
+                ))
+                .bind(&stay.stay_id)
+                .bind(stay.check_in_date)
+                .bind(stay.check_out_date)
+                .bind(&stay.stay_status)
+                .bind(&stay.feeding_instructions)
+                .bind(&stay.medication_instructions)
+                .bind(stay.reserved_at)
+                .bind(&stay.kennel_id)
+                .bind(&stay.pet_id)
+                .bind(clinic_id)
+                .execute(executor)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn overlapping_stays<'e>(
+                executor: impl Executor<'e, Database = $db>,
+                clinic_id: &str,
+                from: NaiveDate,
+                to: NaiveDate,
+            ) -> Result<Vec<BoardingStayModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"{} WHERE kennel.clinic_id = ? AND boarding_stay.stay_status <> 'cancelled'
+                    AND boarding_stay.check_in_date <= ? AND boarding_stay.check_out_date > ?
+                    ORDER BY boarding_stay.check_in_date"#,
+                    SELECT_STAYS
+                )))
+                .bind(clinic_id)
+                .bind(to)
+                .bind(from)
+                .fetch_all(executor)
+                .await
+            }
+
+            async fn update_stay_with<'e>(
+                executor: impl Executor<'e, Database = $db>,
+                clinic_id: &str,
+                stay: &BoardingStayModel,
+                expected_status: &str,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(&format!(
+                    r#"
+                    UPDATE boarding_stay
+                    SET kennel_id = ?, check_in_date = ?, check_out_date = ?, stay_status = ?,
+                        feeding_instructions = ?, medication_instructions = ?, checked_in_at = ?,
+                        checked_out_at = ?
+                    WHERE stay_id = ? AND stay_status = ? AND {}
+                        AND EXISTS (SELECT 1 FROM kennel WHERE kennel_id = ? AND clinic_id = ?)
+                    "#,
+                    CLINIC_KENNELS
+                )))
+                .bind(&stay.kennel_id)
+                .bind(stay.check_in_date)
+                .bind(stay.check_out_date)
+                .bind(&stay.stay_status)
+                .bind(&stay.feeding_instructions)
+                .bind(&stay.medication_instructions)
+                .bind(stay.checked_in_at)
+                .bind(stay.checked_out_at)
+                .bind(&stay.stay_id)
+                .bind(expected_status)
+                .bind(clinic_id)
+                .bind(&stay.kennel_id)
+                .bind(clinic_id)
+                .execute(executor)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            /// Checks the stay against the others sharing a night with it and writes it, inside
+            /// a [`WriteTransaction`].
+            async fn book_stay_with(
+                conn: &mut <$db as Database>::Connection,
+                clinic_id: &str,
+                stay: &BoardingStayModel,
+                expected_status: Option<&str>,
+                check: BookingCheck<'_>,
+            ) -> Result<Result<u64, RuleViolation>, sqlx::Error> {
+                // Concurrent bookings of the kennel or the pet wait here until this one is
+                // committed, so each is checked against the stays booked before it. The kennel is
+                // locked before the pet, always, so that two bookings can't each hold what the
+                // other waits for.
+                let kennel = sqlx::query(&<$db>::sql(&format!(
+                    "SELECT kennel_id FROM kennel WHERE kennel_id = ? AND clinic_id = ?{}",
+                    <$db>::FOR_UPDATE
+                )))
+                .bind(&stay.kennel_id)
+                .bind(clinic_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+                let pet = sqlx::query(&<$db>::sql(&format!(
+                    "SELECT pet_id FROM pet WHERE pet_id = ? AND clinic_id = ?{}",
+                    <$db>::FOR_UPDATE
+                )))
+                .bind(&stay.pet_id)
+                .bind(clinic_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+                if kennel.is_none() || pet.is_none() {
+                    return Ok(Ok(0));
+                }
+
+                let others = Self::overlapping_stays(
+                    &mut *conn,
+                    clinic_id,
+                    stay.check_in_date,
+                    stay.check_out_date - Days::new(1),
+                )
+                .await?;
+                if let Err(violation) = check(&others) {
+                    return Ok(Err(violation));
+                }
+
+                match expected_status {
+                    Some(expected_status) => {
+                        Self::update_stay_with(conn, clinic_id, stay, expected_status).await
+                    }
+                    None => Self::insert_stay(conn, clinic_id, stay).await,
+                }
+                .map(Ok)
+            }
+        }
+
+        #[async_trait]
+        impl BoardingRepository for BoardingQueries<$db> {
+            async fn insert_kennel(
+                &self,
+                kennel_id: String,
+                kennel: &AddKennel,
+                created_at: i64,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO kennel (kennel_id, clinic_id, kennel_name, capacity, species, created_at)
+                    VALUES (?, ?, ?, ?, ?, ?)"#)
+                )
+                .bind(kennel_id)
+                .bind(&self.clinic_id)
+                .bind(kennel.kennel_name.trim())
+                .bind(kennel.capacity)
+                .bind(kennel.species.as_str())
+                .bind(created_at)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_kennel(&self, kennel_id: &str) -> Result<KennelModel, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT kennel_id, kennel_name, capacity, species, created_at FROM kennel
+                    WHERE kennel_id = ? AND clinic_id = ?"#)
+                )
+                .bind(kennel_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_all_kennels(&self) -> Result<Vec<KennelModel>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT kennel_id, kennel_name, capacity, species, created_at FROM kennel
+                    WHERE clinic_id = ? ORDER BY kennel_name"#)
+                )
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn book_stay(
+                &self,
+                stay: &BoardingStayModel,
+                expected_status: Option<&str>,
+                check: BookingCheck<'_>,
+            ) -> Result<Result<u64, RuleViolation>, sqlx::Error> {
+                let mut tx = WriteTransaction::begin(&self.db).await?;
+                let booked =
+                    Self::book_stay_with(&mut tx, &self.clinic_id, stay, expected_status, check).await;
+                tx.finish(matches!(booked, Ok(Ok(_)))).await?;
+                booked
+            }
+
+            async fn select_overlapping_stays(
+                &self,
+                from: NaiveDate,
+                to: NaiveDate,
+            ) -> Result<Vec<BoardingStayModel>, sqlx::Error> {
+                Self::overlapping_stays(&*self.db, &self.clinic_id, from, to).await
+            }
+
+            async fn update_stay(
+                &self,
+                stay: &BoardingStayModel,
+                expected_status: &str,
+            ) -> Result<u64, sqlx::Error> {
+                Self::update_stay_with(&*self.db, &self.clinic_id, stay, expected_status).await
+            }
+
+            async fn select_stay(&self, stay_id: &str) -> Result<BoardingStayModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE boarding_stay.stay_id = ? AND kennel.clinic_id = ?",
+                    SELECT_STAYS
+                )))
+                .bind(stay_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_stays(
+                &self,
+                filter: &BoardingStayOptions,
+                limit: i32,
+                offset: i32,
+            ) -> Result<Vec<BoardingStayModel>, sqlx::Error> {
+                let (filters, params) = stay_filters::<$db>(&self.clinic_id, filter);
+                let query_string = format!(
+                    "{}{} ORDER BY boarding_stay.check_in_date DESC, pet.pet_name LIMIT ? OFFSET ?",
+                    SELECT_STAYS, filters
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql);
+                for param in params {
+                    query = query.bind(param);
+                }
+
+                query.bind(limit).bind(offset).fetch_all(&*self.db).await
+            }
+
+            async fn count_stays(&self, filter: &BoardingStayOptions) -> Result<i64, sqlx::Error> {
+                let (filters, params) = stay_filters::<$db>(&self.clinic_id, filter);
+                let query_string = format!(
+                    r#"SELECT COUNT(*) as count FROM boarding_stay
+                    JOIN kennel ON kennel.kennel_id = boarding_stay.kennel_id{}"#,
+                    filters
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+                for param in params {
+                    query = query.bind(param);
+                }
+
+                query
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get("count"))
+            }
+
+            async fn insert_care_log_entry(
+                &self,
+                stay_id: &str,
+                care_date: NaiveDate,
+                recorded_at: i64,
+                entry: &AddCareLogEntry,
+            ) -> Result<i32, sqlx::Error> {
+                let query_string = format!(
+                    r#"INSERT INTO care_log_entry (stay_id, care_date, recorded_at, fed, medication_given,
+                    exercised, notes)
+                    SELECT stay_id, ?, ?, ?, ?, ?, ? FROM boarding_stay WHERE stay_id = ? AND {}{}"#,
+                    CLINIC_KENNELS,
+                    <$db>::returning("care_log_entry_id")
+                );
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(care_date)
+                .bind(recorded_at)
+                .bind(entry.fed)
+                .bind(entry.medication_given)
+                .bind(entry.exercised)
+                .bind(&entry.notes)
+                .bind(stay_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_care_log(&self, stay_id: &str) -> Result<Vec<CareLogEntry>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"SELECT care_log_entry_id, care_date, recorded_at, fed, medication_given, exercised,
+                    notes FROM care_log_entry WHERE stay_id = ? AND {}
+                    ORDER BY care_date, care_log_entry_id"#,
+                    CLINIC_STAYS
+                )))
+                .bind(stay_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+boarding_queries!(MySql);
+boarding_queries!(Postgres);
+boarding_queries!(Sqlite);
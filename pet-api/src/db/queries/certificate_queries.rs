@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::certificate_repository::CertificateRepository;
+use crate::models::certificate_model::{CertificateModel, CertifiedTreatment};
+
+pub struct CertificateQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> CertificateQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+macro_rules! certificate_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl CertificateRepository for CertificateQueries<$db> {
+            async fn select_preventive_care_of_pet(
+                &self,
+                pet_id: String,
+            ) -> Result<Vec<CertifiedTreatment>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"
+                    SELECT pc.preventive_care_id, pc.treatment, si.service_date AS administered_on,
+                           v.vet_name, v.vet_license_number
+                    FROM preventive_care pc
+                    JOIN service_instance si ON si.service_instance_id = pc.service_instance_id
+                    JOIN veterinarian v ON v.vet_id = pc.vet_id
+                    WHERE si.pet_id = ? AND si.clinic_id = ?
+                    ORDER BY si.service_date, pc.preventive_care_id
+                    "#)
+                )
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn insert_certificate(
+                &self,
+                certificate_type: &str,
+                pet_id: String,
+                vet_id: String,
+                verification_code: String,
+                issued_on: NaiveDate,
+                valid_until: Option<NaiveDate>,
+                content: String,
+                content_digest: String,
+            ) -> Result<i32, sqlx::Error> {
+                let query_string = format!(r#"INSERT INTO certificate (certificate_type, pet_id, vet_id, verification_code,
+                    issued_on, valid_until, content, content_digest)
+                    SELECT ?, pet_id, ?, ?, ?, ?, ?, ? FROM pet WHERE pet_id = ? AND clinic_id = ?{}"#, <$db>::returning("certificate_id"));
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(certificate_type)
+                .bind(vet_id)
+                .bind(verification_code)
+                .bind(issued_on)
+                .bind(valid_until)
+                .bind(content)
+                .bind(content_digest)
+                .bind(pet_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_certificate(
+                &self,
+                certificate_id: i32,
+            ) -> Result<CertificateModel, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT * FROM certificate WHERE certificate_id = ?
+                    AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?
+                    UNION SELECT pet_id FROM pet_share WHERE clinic_id = ?)"#)
+                )
+                .bind(certificate_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_certificate_by_code(
+                &self,
+                verification_code: String,
+            ) -> Result<CertificateModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM certificate WHERE verification_code = ?"))
+                    .bind(verification_code)
+                    .fetch_one(&*self.db)
+                    .await
+            }
+
+            async fn select_certificates_of_pet(
+                &self,
+                pet_id: String,
+            ) -> Result<Vec<CertificateModel>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT * FROM certificate WHERE pet_id = ?
+                    AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?
+                    UNION SELECT pet_id FROM pet_share WHERE clinic_id = ?)
+                    ORDER BY issued_on DESC, certificate_id DESC"#)
+                )
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn revoke_certificate(
+                &self,
+                certificate_id: i32,
+                revoked_on: NaiveDate,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"UPDATE certificate SET revoked_on = ? WHERE certificate_id = ? AND revoked_on IS NULL
+                    AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?)"#)
+                )
+                .bind(revoked_on)
+                .bind(certificate_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+        }
+    };
+}
+
+certificate_queries!(MySql);
+certificate_queries!(Postgres);
+certificate_queries!(Sqlite);
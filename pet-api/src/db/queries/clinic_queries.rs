@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::clinic_repository::ClinicRepository;
+use crate::models::clinic_model::ClinicModel;
+
+pub struct ClinicQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> ClinicQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>) -> Self {
+        Self { db }
+    }
+}
+
+macro_rules! clinic_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ClinicRepository for ClinicQueries<$db> {
+            async fn insert_clinic(
+                &self,
+                clinic_id: String,
+                clinic_name: String,
+                created_at: i64,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(
+                    "INSERT INTO clinic (clinic_id, clinic_name, created_at) VALUES (?, ?, ?)",
+                ))
+                .bind(clinic_id)
+                .bind(clinic_name)
+                .bind(created_at)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_clinic(
+                &self,
+                clinic_id: &str,
+            ) -> Result<Option<ClinicModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM clinic WHERE clinic_id = ?"))
+                    .bind(clinic_id)
+                    .fetch_optional(&*self.db)
+                    .await
+            }
+
+            async fn select_all_clinics(&self) -> Result<Vec<ClinicModel>, sqlx::Error> {
+                sqlx::query_as("SELECT * FROM clinic ORDER BY clinic_name")
+                    .fetch_all(&*self.db)
+                    .await
+            }
+        }
+    };
+}
+
+clinic_queries!(MySql);
+clinic_queries!(Postgres);
+clinic_queries!(Sqlite);
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::{placeholders, Dialect};
+use crate::db::repositories::clinical_code_repository::ClinicalCodeRepository;
+use crate::models::clinical_code_model::{ClinicalCode, CodeMatch, NewClinicalCode};
+use crate::schemas::clinical_code_schema::CodeType;
+
+pub struct ClinicalCodeQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> ClinicalCodeQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>) -> Self {
+        Self { db }
+    }
+}
+
+/// Codes the uncoded records whose text is a synonym, bumping the row versions that tag them.
+const RECODE_RECORDS: [&str; 3] = [
+    r#"UPDATE service_instance SET diagnosis_code = (SELECT code FROM clinical_code_synonym
+        WHERE code_type = 'diagnosis' AND synonym = LOWER(TRIM(service_instance.general_diagnosis))),
+        row_version = row_version + 1
+    WHERE diagnosis_code IS NULL AND EXISTS (SELECT 1 FROM clinical_code_synonym
+        WHERE code_type = 'diagnosis' AND synonym = LOWER(TRIM(service_instance.general_diagnosis)))"#,
+    r#"UPDATE surgery SET surgery_code = (SELECT code FROM clinical_code_synonym
+        WHERE code_type = 'procedure' AND synonym = LOWER(TRIM(surgery.surgery_name))),
+        row_version = row_version + 1
+    WHERE surgery_code IS NULL AND EXISTS (SELECT 1 FROM clinical_code_synonym
+        WHERE code_type = 'procedure' AND synonym = LOWER(TRIM(surgery.surgery_name)))"#,
+    r#"UPDATE preventive_care SET treatment_code = (SELECT code FROM clinical_code_synonym
+        WHERE code_type = 'treatment' AND synonym = LOWER(TRIM(preventive_care.treatment)))
+    WHERE treatment_code IS NULL AND EXISTS (SELECT 1 FROM clinical_code_synonym
+        WHERE code_type = 'treatment' AND synonym = LOWER(TRIM(preventive_care.treatment)))"#,
+];
+
+macro_rules! clinical_code_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ClinicalCodeRepository for ClinicalCodeQueries<$db> {
+            async fn upsert_clinical_codes(&self, codes: &[NewClinicalCode]) -> Result<u64, sqlx::Error> {
+                let mut tx = self.db.begin().await?;
+                for code in codes {
+                    sqlx::query(&<$db>::sql(&format!(
+                        "INSERT INTO clinical_code (code, code_type, term) VALUES (?, ?, ?) {}",
+                        <$db>::on_conflict("code", &["code_type", "term"])
+                    )))
+                    .bind(&code.code)
+                    .bind(&code.code_type)
+                    .bind(&code.term)
+                    .execute(&mut *tx)
+                    .await?;
+                    sqlx::query(&<$db>::sql("DELETE FROM clinical_code_synonym WHERE code = ?"))
+                        .bind(&code.code)
+                        .execute(&mut *tx)
+                        .await?;
+                    for synonym in &code.synonyms {
+                        sqlx::query(&<$db>::sql(&format!(
+                            "INSERT INTO clinical_code_synonym (code_type, synonym, code) VALUES (?, ?, ?) {}",
+                            <$db>::on_conflict("code_type, synonym", &["code"])
+                        )))
+                        .bind(&code.code_type)
+                        .bind(synonym)
+                        .bind(&code.code)
+                        .execute(&mut *tx)
+                        .await?;
+                    }
+                }
+
+                let mut recoded = 0;
+                for statement in RECODE_RECORDS {
+                    recoded += sqlx::query(&<$db>::sql(statement))
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+                }
+                tx.commit().await?;
+
+                Ok(recoded)
+            }
+
+            async fn select_clinical_codes(
+                &self,
+                codes: &[String],
+            ) -> Result<Vec<ClinicalCode>, sqlx::Error> {
+                if codes.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let query_string = format!(
+                    "SELECT code, code_type, term FROM clinical_code WHERE code IN ({})",
+                    placeholders(codes.len())
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql);
+                for code in codes {
+                    query = query.bind(code);
+                }
+
+                query.fetch_all(&*self.db).await
+            }
+
+            async fn search_clinical_codes(
+                &self,
+                q: &str,
+                code_type: Option<CodeType>,
+            ) -> Result<Vec<CodeMatch>, sqlx::Error> {
+                let q = q.to_lowercase();
+                let code_type = code_type.map(CodeType::as_str);
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT clinical_code.code, clinical_code.code_type, clinical_code.term,
+                        clinical_code_synonym.synonym
+                    FROM clinical_code
+                    JOIN clinical_code_synonym ON clinical_code_synonym.code = clinical_code.code
+                    WHERE (? IS NULL OR clinical_code.code_type = ?)
+                    AND ((LOWER(clinical_code.code) LIKE ?
+                        AND clinical_code_synonym.synonym = LOWER(clinical_code.term))
+                        OR clinical_code_synonym.synonym LIKE ?)
+                    ORDER BY clinical_code.code, clinical_code_synonym.synonym"#)
+                )
+                .bind(code_type)
+                .bind(code_type)
+                .bind(format!("{}%", q))
+                .bind(format!("%{}%", q))
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn select_codes_by_synonym(
+                &self,
+                code_type: CodeType,
+                synonyms: &[String],
+            ) -> Result<HashMap<String, String>, sqlx::Error> {
+                if synonyms.is_empty() {
+                    return Ok(HashMap::new());
+                }
+                let query_string = format!(
+                    "SELECT synonym, code FROM clinical_code_synonym WHERE code_type = ? AND synonym IN ({})",
+                    placeholders(synonyms.len())
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as::<_, (String, String)>(&sql).bind(code_type.as_str());
+                for synonym in synonyms {
+                    query = query.bind(synonym);
+                }
+
+                Ok(query.fetch_all(&*self.db).await?.into_iter().collect())
+            }
+        }
+    };
+}
+
+clinical_code_queries!(MySql);
+clinical_code_queries!(Postgres);
+clinical_code_queries!(Sqlite);
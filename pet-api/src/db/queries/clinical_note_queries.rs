@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::clinical_note_repository::ClinicalNoteRepository;
+use crate::models::clinical_note_model::ClinicalNoteModel;
+use crate::schemas::clinical_note_schema::{
+    AddClinicalNote, ClinicalNoteSearchOptions, SoapSection,
+};
+
+pub struct ClinicalNoteQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> ClinicalNoteQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+const SELECT_NOTES: &str = r#"SELECT clinical_note.note_id, clinical_note.service_instance_id,
+    service_instance.service_date, service_instance.pet_id, clinical_note.section,
+    clinical_note.author_id, staff.staff_name AS author_name, staff.staff_role AS author_role,
+    clinical_note.body, clinical_note.recorded_at
+    FROM clinical_note
+    JOIN service_instance ON service_instance.service_instance_id = clinical_note.service_instance_id
+    JOIN staff ON staff.staff_id = clinical_note.author_id"#;
+
+/// Limits `service_instance` to the clinic's and those of pets shared with it, binding the
+/// clinic twice.
+const VISIBLE_SERVICE_INSTANCES: &str = "(service_instance.clinic_id = ? OR \
+    service_instance.pet_id IN (SELECT pet_id FROM pet_share WHERE clinic_id = ?))";
+
+/// Matches a pet's entries against a search; bind it with [`search_params`].
+fn search_filter<DB: Dialect>() -> String {
+    format!(
+        r#"service_instance.pet_id = ?
+        AND (? IS NULL OR clinical_note.section = ?)
+        AND (? IS NULL OR clinical_note.author_id = ?)
+        AND (? IS NULL OR clinical_note.body {ilike} ?)
+        AND (? IS NULL OR service_instance.service_date >= {date})
+        AND (? IS NULL OR service_instance.service_date <= {date})"#,
+        ilike = DB::ILIKE,
+        date = DB::text_as("DATE")
+    )
+}
+
+fn search_params(
+    pet_id: &str,
+    filter: &ClinicalNoteSearchOptions,
+    clinic_id: &str,
+) -> Vec<Option<String>> {
+    let mut params = vec![Some(pet_id.to_string())];
+    for value in [
+        filter.section.map(|section| section.as_str().to_string()),
+        filter.author_id.clone(),
+        filter.q.as_ref().map(|q| format!("%{}%", q)),
+        filter.start_date.clone(),
+        filter.end_date.clone(),
+    ] {
+        params.push(value.clone());
+        params.push(value);
+    }
+    params.push(Some(clinic_id.to_string()));
+    params.push(Some(clinic_id.to_string()));
+    params
+}
+
+macro_rules! clinical_note_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ClinicalNoteRepository for ClinicalNoteQueries<$db> {
+            async fn insert_clinical_note(
+                &self,
+                service_instance_id: &str,
+                note: &AddClinicalNote,
+                recorded_at: i64,
+            ) -> Result<i32, sqlx::Error> {
+                let query_string = format!(r#"INSERT INTO clinical_note (service_instance_id, author_id, section, body,
+                    recorded_at)
+                    SELECT service_instance_id, ?, ?, ?, ? FROM service_instance
+                    WHERE service_instance_id = ? AND clinic_id = ?{}"#, <$db>::returning("note_id"));
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(&note.author_id)
+                .bind(note.section.as_str())
+                .bind(&note.body)
+                .bind(recorded_at)
+                .bind(service_instance_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_clinical_note(&self, note_id: i32) -> Result<ClinicalNoteModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE clinical_note.note_id = ? AND {}",
+                    SELECT_NOTES, VISIBLE_SERVICE_INSTANCES
+                )))
+                .bind(note_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_clinical_notes(
+                &self,
+                service_instance_id: &str,
+                section: Option<SoapSection>,
+                author_id: Option<&str>,
+            ) -> Result<Vec<ClinicalNoteModel>, sqlx::Error> {
+                let section = section.map(SoapSection::as_str);
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"{} WHERE clinical_note.service_instance_id = ?
+                    AND (? IS NULL OR clinical_note.section = ?)
+                    AND (? IS NULL OR clinical_note.author_id = ?) AND {}
+                    ORDER BY clinical_note.recorded_at, clinical_note.note_id"#,
+                    SELECT_NOTES, VISIBLE_SERVICE_INSTANCES
+                )))
+                .bind(service_instance_id)
+                .bind(section)
+                .bind(section)
+                .bind(author_id)
+                .bind(author_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn search_clinical_notes(
+                &self,
+                pet_id: &str,
+                filter: &ClinicalNoteSearchOptions,
+                limit: i32,
+                offset: i32,
+            ) -> Result<Vec<ClinicalNoteModel>, sqlx::Error> {
+                let query_string = format!(
+                    r#"{} WHERE {} AND {}
+                    ORDER BY service_instance.service_date DESC, clinical_note.recorded_at DESC,
+                        clinical_note.note_id DESC
+                    LIMIT ? OFFSET ?"#,
+                    SELECT_NOTES, search_filter::<$db>(), VISIBLE_SERVICE_INSTANCES
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql);
+                for param in search_params(pet_id, filter, &self.clinic_id) {
+                    query = query.bind(param);
+                }
+
+                query.bind(limit).bind(offset).fetch_all(&*self.db).await
+            }
+
+            async fn count_clinical_notes(
+                &self,
+                pet_id: &str,
+                filter: &ClinicalNoteSearchOptions,
+            ) -> Result<i64, sqlx::Error> {
+                let query_string = format!(
+                    r#"SELECT COUNT(*) FROM clinical_note
+                    JOIN service_instance ON service_instance.service_instance_id = clinical_note.service_instance_id
+                    WHERE {} AND {}"#,
+                    search_filter::<$db>(), VISIBLE_SERVICE_INSTANCES
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_scalar(&sql);
+                for param in search_params(pet_id, filter, &self.clinic_id) {
+                    query = query.bind(param);
+                }
+
+                query.fetch_one(&*self.db).await
+            }
+        }
+    };
+}
+
+clinical_note_queries!(MySql);
+clinical_note_queries!(Postgres);
+clinical_note_queries!(Sqlite);
@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+use std::future::Future;
+
+use sqlx::database::HasArguments;
+use sqlx::query::Query;
+use sqlx::{Database, Executor};
+
+/// Where a backend's SQL departs from the SQL the queries are written in, with `?` placeholders,
+/// which MySQL and SQLite mostly run as is.
+pub(crate) trait Dialect: Database {
+    /// Today's date, in the database's time zone.
+    const CURRENT_DATE: &'static str;
+
+    /// `LIKE`, ignoring case.
+    const ILIKE: &'static str;
+
+    /// The type to `CAST` an integer to for it to decode as `i64`.
+    const BIGINT: &'static str;
+
+    /// Begins a transaction that is going to write.
+    const BEGIN_WRITE: &'static str;
+
+    /// Locks the rows a `SELECT` reads until the transaction ends. Empty where
+    /// [`Dialect::BEGIN_WRITE`] locks the whole database instead.
+    const FOR_UPDATE: &'static str;
+
+    /// `query` with its `?` placeholders written this backend's way.
+    fn sql(query: &str) -> Cow<'_, str> {
+        Cow::Borrowed(query)
+    }
+
+    /// A placeholder for text the database reads as `sql_type`.
+    fn text_as(_sql_type: &str) -> Cow<'static, str> {
+        Cow::Borrowed("?")
+    }
+
+    /// The `expression` of each row of the group, in `order_by` order, joined with ", ".
+    fn string_agg(expression: &str, order_by: &str) -> String;
+
+    /// Ends an `INSERT` so that a row whose `key` is taken has its `columns` overwritten
+    /// instead, or is left as it is when there are none.
+    fn on_conflict(key: &str, columns: &[&str]) -> String;
+
+    /// Ends an `INSERT` so that [`Dialect::generated_id`] can read back its `id_column`.
+    fn returning(_id_column: &str) -> String {
+        String::new()
+    }
+
+    /// Runs an `INSERT` ending with [`Dialect::returning`] and returns the id generated for
+    /// its row, or `RowNotFound` when it inserted none.
+    fn generated_id<'q, 'e>(
+        query: Query<'q, Self, <Self as HasArguments<'q>>::Arguments>,
+        executor: impl Executor<'e, Database = Self> + 'q,
+    ) -> impl Future<Output = Result<i32, sqlx::Error>> + Send
+    where
+        'q: 'e;
+}
+
+/// `?, ?, ...` for a list of `count` values.
+pub(crate) fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::health_repository::HealthRepository;
+
+pub struct HealthQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> HealthQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>) -> Self {
+        Self { db }
+    }
+}
+
+macro_rules! health_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl HealthRepository for HealthQueries<$db> {
+            fn is_pool_closed(&self) -> bool {
+                self.db.is_closed()
+            }
+
+            fn pool_size(&self) -> u32 {
+                self.db.size()
+            }
+
+            fn idle_connections(&self) -> usize {
+                self.db.num_idle()
+            }
+
+            async fn ping(&self) -> Result<Duration, sqlx::Error> {
+                let started = Instant::now();
+                sqlx::query("SELECT 1").execute(&*self.db).await?;
+
+                Ok(started.elapsed())
+            }
+
+            async fn select_schema_version(&self) -> Result<Option<i64>, sqlx::Error> {
+                sqlx::query_scalar(&format!(
+                    "SELECT CAST(MAX(version) AS {}) FROM schema_version",
+                    <$db>::BIGINT
+                ))
+                .fetch_one(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+health_queries!(MySql);
+health_queries!(Postgres);
+health_queries!(Sqlite);
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::idempotency_repository::IdempotencyRepository;
+use crate::models::idempotency_model::IdempotencyKeyModel;
+
+pub struct IdempotencyQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> IdempotencyQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+macro_rules! idempotency_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl IdempotencyRepository for IdempotencyQueries<$db> {
+            async fn insert_key(
+                &self,
+                idempotency_key: &str,
+                request_fingerprint: &str,
+                created_at: i64,
+                expires_at: i64,
+            ) -> Result<bool, sqlx::Error> {
+                let result = sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO idempotency_key
+                    (clinic_id, idempotency_key, request_fingerprint, created_at, expires_at)
+                    VALUES (?, ?, ?, ?, ?)"#)
+                )
+                .bind(&self.clinic_id)
+                .bind(idempotency_key)
+                .bind(request_fingerprint)
+                .bind(created_at)
+                .bind(expires_at)
+                .execute(&*self.db)
+                .await;
+
+                match result {
+                    Ok(_) => Ok(true),
+                    Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => Ok(false),
+                    Err(e) => Err(e),
+                }
+            }
+
+            async fn select_key(
+                &self,
+                idempotency_key: &str,
+            ) -> Result<Option<IdempotencyKeyModel>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT request_fingerprint, response_status, response_body
+                    FROM idempotency_key WHERE clinic_id = ? AND idempotency_key = ?"#)
+                )
+                .bind(&self.clinic_id)
+                .bind(idempotency_key)
+                .fetch_optional(&*self.db)
+                .await
+            }
+
+            async fn save_response(
+                &self,
+                idempotency_key: &str,
+                response_status: i32,
+                response_body: &str,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql("UPDATE idempotency_key SET response_status = ?, response_body = ?
+                    WHERE clinic_id = ? AND idempotency_key = ?")
+                )
+                .bind(response_status)
+                .bind(response_body)
+                .bind(&self.clinic_id)
+                .bind(idempotency_key)
+                .execute(&*self.db)
+                .await
+                .map(|result| result.rows_affected())
+            }
+
+            async fn delete_key(&self, idempotency_key: &str) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM idempotency_key WHERE clinic_id = ? AND idempotency_key = ?"))
+                    .bind(&self.clinic_id)
+                    .bind(idempotency_key)
+                    .execute(&*self.db)
+                    .await
+                    .map(|result| result.rows_affected())
+            }
+
+            async fn delete_expired_keys(&self, now: i64) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM idempotency_key WHERE expires_at <= ?"))
+                    .bind(now)
+                    .execute(&*self.db)
+                    .await
+                    .map(|result| result.rows_affected())
+            }
+        }
+    };
+}
+
+idempotency_queries!(MySql);
+idempotency_queries!(Postgres);
+idempotency_queries!(Sqlite);
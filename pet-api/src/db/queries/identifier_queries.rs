@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::identifier_repository::IdentifierRepository;
+use crate::models::identifier_model::{IdentifiedPetModel, PetIdentifierModel};
+
+pub struct IdentifierQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> IdentifierQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+macro_rules! identifier_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl IdentifierRepository for IdentifierQueries<$db> {
+            async fn insert_identifier(&self, identifier: &PetIdentifierModel) -> Result<i32, sqlx::Error> {
+                let query_string = format!(r#"INSERT INTO pet_identifier (pet_id, identifier_type, identifier_value, implant_date,
+                    implant_location, recorded_at)
+                    SELECT pet_id, ?, ?, ?, ?, ? FROM pet WHERE pet_id = ? AND clinic_id = ?{}"#, <$db>::returning("identifier_id"));
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(&identifier.identifier_type)
+                .bind(&identifier.identifier_value)
+                .bind(identifier.implant_date)
+                .bind(&identifier.implant_location)
+                .bind(identifier.recorded_at)
+                .bind(&identifier.pet_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_identifiers(
+                &self,
+                pet_id: &str,
+            ) -> Result<Vec<PetIdentifierModel>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT identifier_id, pet_id, identifier_type, identifier_value, implant_date,
+                    implant_location, recorded_at
+                    FROM pet_identifier
+                    WHERE pet_id = ? AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?
+                        UNION SELECT pet_id FROM pet_share WHERE clinic_id = ?)
+                    ORDER BY identifier_id"#)
+                )
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn delete_identifier(
+                &self,
+                pet_id: &str,
+                identifier_id: i32,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"DELETE FROM pet_identifier WHERE identifier_id = ? AND pet_id = ?
+                    AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?)"#)
+                )
+                .bind(identifier_id)
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_pets_by_identifier(
+                &self,
+                identifier_value: &str,
+            ) -> Result<Vec<IdentifiedPetModel>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT pet_identifier.identifier_type, pet_identifier.identifier_value,
+                        pet.pet_id, pet.pet_name, pet.pet_type, pet.pet_breed, pet.pet_color, pet.pet_status,
+                        owner.owner_name, owner.owner_email, owner.owner_phone_number,
+                        clinic.clinic_id, clinic.clinic_name
+                    FROM pet_identifier
+                    INNER JOIN pet ON pet.pet_id = pet_identifier.pet_id
+                    INNER JOIN owner ON owner.owner_id = pet.owner_id
+                    INNER JOIN clinic ON clinic.clinic_id = pet.clinic_id
+                    WHERE pet_identifier.identifier_value = ?
+                    ORDER BY pet_identifier.identifier_id"#)
+                )
+                .bind(identifier_value)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+identifier_queries!(MySql);
+identifier_queries!(Postgres);
+identifier_queries!(Sqlite);
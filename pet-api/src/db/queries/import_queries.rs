@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Connection, Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::{placeholders, Dialect};
+use crate::db::repositories::import_repository::ImportRepository;
+use crate::models::import_model::ImportReport;
+use crate::schemas::import_schema::ImportEntity;
+
+pub struct ImportQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> ImportQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+fn insert_statement<DB: Dialect>(entity: ImportEntity) -> String {
+    match entity {
+        ImportEntity::Owners => format!(
+            "INSERT INTO owner (owner_id, owner_name, owner_email, owner_phone_number, owner_address, created_on, clinic_id) VALUES (?, ?, ?, ?, ?, {}, ?)",
+            DB::CURRENT_DATE
+        ),
+        ImportEntity::Pets => format!(
+            "INSERT INTO pet (pet_id, pet_name, pet_birth_date, pet_type, pet_breed, pet_weight, pet_color, owner_id, created_on, clinic_id) SELECT ?, ?, {}, ?, ?, {}, ?, owner_id, {}, clinic_id FROM owner WHERE owner_id = ? AND clinic_id = ?",
+            DB::text_as("DATE"),
+            DB::text_as("NUMERIC"),
+            DB::CURRENT_DATE
+        ),
+        ImportEntity::Vets => "INSERT INTO staff (staff_id, staff_name, staff_email, staff_phone_number, license_number, staff_role, clinic_id) VALUES (?, ?, ?, ?, ?, 'veterinarian', ?)".to_string(),
+    }
+}
+
+macro_rules! import_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ImportRepository for ImportQueries<$db> {
+            async fn select_owner_ids_by_email(
+                &self,
+                owner_emails: &[String],
+            ) -> Result<Vec<(String, String)>, sqlx::Error> {
+                if owner_emails.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let query = format!(
+                    "SELECT owner_email, owner_id FROM owner WHERE clinic_id = ? AND owner_email IN ({})",
+                    placeholders(owner_emails.len())
+                );
+                let sql = <$db>::sql(&query);
+                let mut query = sqlx::query_as(&sql).bind(&self.clinic_id);
+                for owner_email in owner_emails {
+                    query = query.bind(owner_email);
+                }
+
+                query.fetch_all(&*self.db).await
+            }
+
+            async fn insert_rows(
+                &self,
+                entity: ImportEntity,
+                rows: &[Vec<String>],
+                atomic: bool,
+                commit: bool,
+            ) -> Result<Vec<(usize, sqlx::Error)>, sqlx::Error> {
+                let statement = insert_statement::<$db>(entity);
+                let statement = <$db>::sql(&statement);
+                let mut rejected = Vec::new();
+                let mut tx = self.db.begin().await?;
+
+                for (index, row) in rows.iter().enumerate() {
+                    let mut savepoint = tx.begin().await?;
+                    let mut query = sqlx::query(&statement);
+                    for value in row {
+                        query = query.bind(value);
+                    }
+                    query = query.bind(&self.clinic_id);
+
+                    let result = match query.execute(&mut *savepoint).await {
+                        // A pet whose owner belongs to another clinic inserts nothing.
+                        Ok(done) if done.rows_affected() == 0 => Err(sqlx::Error::RowNotFound),
+                        result => result,
+                    };
+                    match result {
+                        Ok(_) => savepoint.commit().await?,
+                        Err(e) => {
+                            savepoint.rollback().await?;
+                            rejected.push((index, e));
+                        }
+                    }
+                }
+
+                if commit && (!atomic || rejected.is_empty()) {
+                    tx.commit().await?;
+                } else {
+                    tx.rollback().await?;
+                }
+
+                Ok(rejected)
+            }
+
+            async fn insert_import_job(&self, report: &ImportReport) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO import_job (import_id, entity, import_mode, dry_run, committed,
+                    total_rows, imported_rows, failed_rows, error_report, clinic_id)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#)
+                )
+                .bind(&report.import_id)
+                .bind(report.entity.as_str())
+                .bind(report.mode.as_str())
+                .bind(report.dry_run)
+                .bind(report.committed)
+                .bind(report.total_rows as i32)
+                .bind(report.imported_rows as i32)
+                .bind(report.failed_rows as i32)
+                .bind(report.errors_csv())
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_error_report(&self, import_id: String) -> Result<String, sqlx::Error> {
+                sqlx::query_scalar(
+                    &<$db>::sql("SELECT error_report FROM import_job WHERE import_id = ? AND clinic_id = ?")
+                )
+                .bind(import_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+import_queries!(MySql);
+import_queries!(Postgres);
+import_queries!(Sqlite);
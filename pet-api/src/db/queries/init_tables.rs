@@ -1,11 +1,297 @@
-pub async fn create_tables(pool: &sqlx::MySqlPool) -> Result<(), sqlx::Error> {
-    let sql = include_str!("../../sql/create_tables.sql");
-    let statements: Vec<&str> = sql.split(';').collect();
+use sqlx::{Connection, MySqlPool, PgPool, SqlitePool};
 
-    for statement in statements {
-        if !statement.trim().is_empty() {
+use crate::db::connection::DbPool;
+
+pub const SCHEMA_VERSION: i64 = 20;
+
+const MYSQL_MIGRATIONS: [(i64, &str); 20] = [
+    (1, include_str!("../../sql/mysql/create_tables.sql")),
+    (2, include_str!("../../sql/mysql/add_row_versions.sql")),
+    (
+        3,
+        include_str!("../../sql/mysql/create_idempotency_keys.sql"),
+    ),
+    (4, include_str!("../../sql/mysql/create_import_jobs.sql")),
+    (5, include_str!("../../sql/mysql/create_certificates.sql")),
+    (6, include_str!("../../sql/mysql/add_created_on.sql")),
+    (7, include_str!("../../sql/mysql/create_portal_tokens.sql")),
+    (8, include_str!("../../sql/mysql/create_surgical_cases.sql")),
+    (9, include_str!("../../sql/mysql/create_staff.sql")),
+    (10, include_str!("../../sql/mysql/create_clinics.sql")),
+    (11, include_str!("../../sql/mysql/create_boarding.sql")),
+    (12, include_str!("../../sql/mysql/create_lab_orders.sql")),
+    (13, include_str!("../../sql/mysql/create_pet_problems.sql")),
+    (
+        14,
+        include_str!("../../sql/mysql/create_clinical_notes.sql"),
+    ),
+    (
+        15,
+        include_str!("../../sql/mysql/create_clinical_codes.sql"),
+    ),
+    (
+        16,
+        include_str!("../../sql/mysql/create_pet_identifiers.sql"),
+    ),
+    (17, include_str!("../../sql/mysql/add_pet_status.sql")),
+    (
+        18,
+        include_str!("../../sql/mysql/scope_contact_uniques.sql"),
+    ),
+    (
+        19,
+        include_str!("../../sql/mysql/scope_idempotency_keys.sql"),
+    ),
+    (
+        20,
+        include_str!("../../sql/mysql/add_staff_email_unique.sql"),
+    ),
+];
+
+const POSTGRES_MIGRATIONS: [(i64, &str); 20] = [
+    (1, include_str!("../../sql/postgres/create_tables.sql")),
+    (2, include_str!("../../sql/postgres/add_row_versions.sql")),
+    (
+        3,
+        include_str!("../../sql/postgres/create_idempotency_keys.sql"),
+    ),
+    (4, include_str!("../../sql/postgres/create_import_jobs.sql")),
+    (
+        5,
+        include_str!("../../sql/postgres/create_certificates.sql"),
+    ),
+    (6, include_str!("../../sql/postgres/add_created_on.sql")),
+    (
+        7,
+        include_str!("../../sql/postgres/create_portal_tokens.sql"),
+    ),
+    (
+        8,
+        include_str!("../../sql/postgres/create_surgical_cases.sql"),
+    ),
+    (9, include_str!("../../sql/postgres/create_staff.sql")),
+    (10, include_str!("../../sql/postgres/create_clinics.sql")),
+    (11, include_str!("../../sql/postgres/create_boarding.sql")),
+    (12, include_str!("../../sql/postgres/create_lab_orders.sql")),
+    (
+        13,
+        include_str!("../../sql/postgres/create_pet_problems.sql"),
+    ),
+    (
+        14,
+        include_str!("../../sql/postgres/create_clinical_notes.sql"),
+    ),
+    (
+        15,
+        include_str!("../../sql/postgres/create_clinical_codes.sql"),
+    ),
+    (
+        16,
+        include_str!("../../sql/postgres/create_pet_identifiers.sql"),
+    ),
+    (17, include_str!("../../sql/postgres/add_pet_status.sql")),
+    (
+        18,
+        include_str!("../../sql/postgres/scope_contact_uniques.sql"),
+    ),
+    (
+        19,
+        include_str!("../../sql/postgres/scope_idempotency_keys.sql"),
+    ),
+    (
+        20,
+        include_str!("../../sql/postgres/add_staff_email_unique.sql"),
+    ),
+];
+
+const SQLITE_MIGRATIONS: [(i64, &str); 20] = [
+    (1, include_str!("../../sql/sqlite/create_tables.sql")),
+    (2, include_str!("../../sql/sqlite/add_row_versions.sql")),
+    (
+        3,
+        include_str!("../../sql/sqlite/create_idempotency_keys.sql"),
+    ),
+    (4, include_str!("../../sql/sqlite/create_import_jobs.sql")),
+    (5, include_str!("../../sql/sqlite/create_certificates.sql")),
+    (6, include_str!("../../sql/sqlite/add_created_on.sql")),
+    (7, include_str!("../../sql/sqlite/create_portal_tokens.sql")),
+    (
+        8,
+        include_str!("../../sql/sqlite/create_surgical_cases.sql"),
+    ),
+    (9, include_str!("../../sql/sqlite/create_staff.sql")),
+    (10, include_str!("../../sql/sqlite/create_clinics.sql")),
+    (11, include_str!("../../sql/sqlite/create_boarding.sql")),
+    (12, include_str!("../../sql/sqlite/create_lab_orders.sql")),
+    (13, include_str!("../../sql/sqlite/create_pet_problems.sql")),
+    (
+        14,
+        include_str!("../../sql/sqlite/create_clinical_notes.sql"),
+    ),
+    (
+        15,
+        include_str!("../../sql/sqlite/create_clinical_codes.sql"),
+    ),
+    (
+        16,
+        include_str!("../../sql/sqlite/create_pet_identifiers.sql"),
+    ),
+    (17, include_str!("../../sql/sqlite/add_pet_status.sql")),
+    (
+        18,
+        include_str!("../../sql/sqlite/scope_contact_uniques.sql"),
+    ),
+    (
+        19,
+        include_str!("../../sql/sqlite/scope_idempotency_keys.sql"),
+    ),
+    (
+        20,
+        include_str!("../../sql/sqlite/add_staff_email_unique.sql"),
+    ),
+];
+
+pub async fn create_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
+    match pool {
+        DbPool::MySql(pool) => create_mysql_tables(pool).await,
+        DbPool::Postgres(pool) => create_postgres_tables(pool).await,
+        DbPool::Sqlite(pool) => create_sqlite_tables(pool).await,
+    }
+}
+
+fn split_statements(sql: &str) -> Vec<&str> {
+    sql.split(';')
+        .filter(|statement| !statement.trim().is_empty())
+        .collect()
+}
+
+async fn create_mysql_tables(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS schema_version (
+            version INT PRIMARY KEY NOT NULL,
+            applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )"#,
+    )
+    .execute(pool)
+    .await?;
+
+    let applied_version: Option<i64> =
+        sqlx::query_scalar("SELECT CAST(MAX(version) AS SIGNED) FROM schema_version")
+            .fetch_one(pool)
+            .await?;
+
+    for (version, sql) in MYSQL_MIGRATIONS {
+        if version <= applied_version.unwrap_or(0) {
+            continue;
+        }
+
+        for statement in split_statements(sql) {
             sqlx::query(statement).execute(pool).await?;
         }
+
+        sqlx::query("INSERT INTO schema_version (version) VALUES (?)")
+            .bind(version)
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn create_postgres_tables(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS schema_version (
+            version BIGINT PRIMARY KEY NOT NULL,
+            applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )"#,
+    )
+    .execute(pool)
+    .await?;
+
+    let applied_version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM schema_version")
+            .fetch_one(pool)
+            .await?;
+
+    for (version, sql) in POSTGRES_MIGRATIONS {
+        if version <= applied_version.unwrap_or(0) {
+            continue;
+        }
+
+        let mut tx = pool.begin().await?;
+        for statement in split_statements(sql) {
+            sqlx::query(statement).execute(&mut *tx).await?;
+        }
+
+        sqlx::query("INSERT INTO schema_version (version) VALUES ($1)")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
+
+async fn create_sqlite_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY NOT NULL,
+            applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )"#,
+    )
+    .execute(pool)
+    .await?;
+
+    let applied_version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM schema_version")
+            .fetch_one(pool)
+            .await?;
+
+    // Rebuilding a table drops the old one, which would cascade to the rows referencing it, so
+    // foreign keys are off while migrating and checked before each migration commits.
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await?;
+    let migrated = migrate_sqlite(&mut conn, applied_version.unwrap_or(0)).await;
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await?;
+
+    migrated
+}
+
+async fn migrate_sqlite(
+    conn: &mut sqlx::SqliteConnection,
+    applied_version: i64,
+) -> Result<(), sqlx::Error> {
+    for (version, sql) in SQLITE_MIGRATIONS {
+        if version <= applied_version {
+            continue;
+        }
+
+        let mut tx = conn.begin().await?;
+        for statement in split_statements(sql) {
+            sqlx::query(statement).execute(&mut *tx).await?;
+        }
+
+        let violations = sqlx::query("PRAGMA foreign_key_check")
+            .fetch_all(&mut *tx)
+            .await?;
+        if !violations.is_empty() {
+            return Err(sqlx::Error::Protocol(format!(
+                "migration {} left {} rows with dangling foreign keys",
+                version,
+                violations.len()
+            )));
+        }
+
+        sqlx::query("INSERT INTO schema_version (version) VALUES (?)")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
     }
 
     Ok(())
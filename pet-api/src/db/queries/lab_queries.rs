@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Row, Sqlite};
+
+use crate::db::queries::dialect::{placeholders, Dialect};
+use crate::db::repositories::lab_repository::LabRepository;
+use crate::models::lab_model::{
+    LabOrderModel, LabResultModel, NewLabResult, ReferenceRange, TrendPoint,
+};
+use crate::schemas::lab_schema::AddLabOrder;
+
+pub struct LabQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> LabQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+const SELECT_ORDERS: &str = r#"SELECT lab_order.lab_order_id, lab_order.service_instance_id,
+    service_instance.service_date, service_instance.pet_id, pet.pet_type, lab_order.vet_id,
+    staff.staff_name AS vet_name, lab_order.panel, lab_order.sample_type, lab_order.notes,
+    lab_order.ordered_at, lab_order.resulted_at
+    FROM lab_order
+    JOIN service_instance ON service_instance.service_instance_id = lab_order.service_instance_id
+    JOIN pet ON pet.pet_id = service_instance.pet_id
+    JOIN staff ON staff.staff_id = lab_order.vet_id"#;
+
+/// Limits `service_instance` to the clinic's and those of pets shared with it, binding the
+/// clinic twice.
+const VISIBLE_SERVICE_INSTANCES: &str = "(service_instance.clinic_id = ? OR \
+    service_instance.pet_id IN (SELECT pet_id FROM pet_share WHERE clinic_id = ?))";
+
+macro_rules! lab_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl LabRepository for LabQueries<$db> {
+            async fn insert_lab_order(
+                &self,
+                service_instance_id: &str,
+                order: &AddLabOrder,
+                ordered_at: i64,
+            ) -> Result<i32, sqlx::Error> {
+                let query_string = format!(r#"INSERT INTO lab_order (service_instance_id, vet_id, panel, sample_type, notes,
+                    ordered_at)
+                    SELECT service_instance_id, ?, ?, ?, ?, ? FROM service_instance
+                    WHERE service_instance_id = ? AND clinic_id = ?{}"#, <$db>::returning("lab_order_id"));
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(&order.vet_id)
+                .bind(&order.panel)
+                .bind(order.sample_type.as_str())
+                .bind(&order.notes)
+                .bind(ordered_at)
+                .bind(service_instance_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_lab_order(&self, lab_order_id: i32) -> Result<LabOrderModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE lab_order.lab_order_id = ? AND {}",
+                    SELECT_ORDERS, VISIBLE_SERVICE_INSTANCES
+                )))
+                .bind(lab_order_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_lab_orders(
+                &self,
+                service_instance_id: &str,
+            ) -> Result<Vec<LabOrderModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE lab_order.service_instance_id = ? AND {} ORDER BY lab_order.lab_order_id",
+                    SELECT_ORDERS, VISIBLE_SERVICE_INSTANCES
+                )))
+                .bind(service_instance_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn select_lab_results(
+                &self,
+                lab_order_ids: &[i32],
+            ) -> Result<Vec<LabResultModel>, sqlx::Error> {
+                if lab_order_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let query_string = format!(
+                    r#"SELECT lab_result_id, lab_order_id, analyte, result_value, unit, reference_low,
+                    reference_high, flag, recorded_at FROM lab_result WHERE lab_order_id IN ({})
+                    ORDER BY lab_order_id, lab_result_id"#,
+                    placeholders(lab_order_ids.len())
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql);
+                for lab_order_id in lab_order_ids {
+                    query = query.bind(lab_order_id);
+                }
+
+                query.fetch_all(&*self.db).await
+            }
+
+            async fn insert_lab_results(
+                &self,
+                lab_order_id: i32,
+                results: &[NewLabResult],
+                recorded_at: i64,
+            ) -> Result<u64, sqlx::Error> {
+                let mut tx = self.db.begin().await?;
+
+                let owned: i64 = sqlx::query(
+                    &<$db>::sql(r#"SELECT COUNT(*) as count FROM lab_order
+                    JOIN service_instance ON service_instance.service_instance_id = lab_order.service_instance_id
+                    WHERE lab_order.lab_order_id = ? AND service_instance.clinic_id = ?"#)
+                )
+                .bind(lab_order_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map(|row: <$db as Database>::Row| row.get("count"))?;
+                if owned == 0 {
+                    return Ok(0);
+                }
+
+                for result in results {
+                    sqlx::query(
+                        &<$db>::sql(r#"INSERT INTO lab_result (lab_order_id, analyte, result_value, unit,
+                        reference_low, reference_high, flag, recorded_at)
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#)
+                    )
+                    .bind(lab_order_id)
+                    .bind(&result.analyte)
+                    .bind(result.value)
+                    .bind(&result.unit)
+                    .bind(result.reference_low)
+                    .bind(result.reference_high)
+                    .bind(result.flag)
+                    .bind(recorded_at)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+
+                sqlx::query(
+                    &<$db>::sql("UPDATE lab_order SET resulted_at = COALESCE(resulted_at, ?) WHERE lab_order_id = ?")
+                )
+                .bind(recorded_at)
+                .bind(lab_order_id)
+                .execute(&mut *tx)
+                .await?;
+
+                tx.commit().await?;
+                Ok(results.len() as u64)
+            }
+
+            async fn select_reference_ranges(
+                &self,
+                species: Option<&str>,
+            ) -> Result<Vec<ReferenceRange>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT analyte, species, analyte_name, unit, reference_low, reference_high
+                    FROM lab_reference_range WHERE ? IS NULL OR species = ?
+                    ORDER BY analyte, species"#)
+                )
+                .bind(species)
+                .bind(species)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn select_lab_trends(
+                &self,
+                pet_id: &str,
+                analyte: Option<&str>,
+            ) -> Result<Vec<TrendPoint>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"SELECT lab_result.analyte, service_instance.service_date,
+                    service_instance.service_instance_id, lab_result.lab_order_id, lab_result.result_value,
+                    lab_result.unit, lab_result.flag
+                    FROM lab_result
+                    JOIN lab_order ON lab_order.lab_order_id = lab_result.lab_order_id
+                    JOIN service_instance ON service_instance.service_instance_id = lab_order.service_instance_id
+                    WHERE service_instance.pet_id = ? AND (? IS NULL OR lab_result.analyte = ?) AND {}
+                    ORDER BY lab_result.analyte, service_instance.service_date, lab_result.lab_order_id"#,
+                    VISIBLE_SERVICE_INSTANCES
+                )))
+                .bind(pet_id)
+                .bind(analyte)
+                .bind(analyte)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+lab_queries!(MySql);
+lab_queries!(Postgres);
+lab_queries!(Sqlite);
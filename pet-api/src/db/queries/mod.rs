@@ -1,6 +1,24 @@
+pub(crate) mod boarding_queries;
+pub(crate) mod certificate_queries;
+pub(crate) mod clinic_queries;
+pub(crate) mod clinical_code_queries;
+pub(crate) mod clinical_note_queries;
+pub(crate) mod dialect;
+pub(crate) mod health_queries;
+pub(crate) mod idempotency_queries;
+pub(crate) mod identifier_queries;
+pub(crate) mod import_queries;
 pub(crate) mod init_tables;
+pub(crate) mod lab_queries;
+mod mysql;
 pub(crate) mod owner_queries;
 pub(crate) mod pet_queries;
+pub(crate) mod portal_queries;
+mod postgres;
+pub(crate) mod problem_queries;
 pub(crate) mod service_instance_queries;
+mod sqlite;
+pub(crate) mod staff_queries;
 pub(crate) mod statistic_queries;
+pub(crate) mod surgical_case_queries;
 pub(crate) mod vet_queries;
@@ -0,0 +1,69 @@
+use rust_decimal::Decimal;
+use sqlx::database::HasArguments;
+use sqlx::error::BoxDynError;
+use sqlx::mysql::{MySqlTypeInfo, MySqlValueRef};
+use sqlx::query::Query;
+use sqlx::{Decode, Executor, MySql, Type};
+
+use crate::db::queries::dialect::Dialect;
+use crate::models::pet_model::PetWeight;
+
+impl Dialect for MySql {
+    const CURRENT_DATE: &'static str = "CURRENT_DATE";
+    const ILIKE: &'static str = "LIKE";
+    const BIGINT: &'static str = "SIGNED";
+    const BEGIN_WRITE: &'static str = "BEGIN";
+    const FOR_UPDATE: &'static str = " FOR UPDATE";
+
+    fn string_agg(expression: &str, order_by: &str) -> String {
+        format!(
+            "GROUP_CONCAT({} ORDER BY {} SEPARATOR ', ')",
+            expression, order_by
+        )
+    }
+
+    fn on_conflict(key: &str, columns: &[&str]) -> String {
+        if columns.is_empty() {
+            let column = key.split(',').next().unwrap_or(key).trim();
+            return format!("ON DUPLICATE KEY UPDATE {0} = {0}", column);
+        }
+
+        let updates: Vec<_> = columns
+            .iter()
+            .map(|column| format!("{0} = VALUES({0})", column))
+            .collect();
+        format!("ON DUPLICATE KEY UPDATE {}", updates.join(", "))
+    }
+
+    async fn generated_id<'q, 'e>(
+        query: Query<'q, Self, <Self as HasArguments<'q>>::Arguments>,
+        executor: impl Executor<'e, Database = Self> + 'q,
+    ) -> Result<i32, sqlx::Error>
+    where
+        'q: 'e,
+    {
+        let done = query.execute(executor).await?;
+        if done.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(done.last_insert_id() as i32)
+    }
+}
+
+// `pet_weight` is a DECIMAL(5,2).
+impl Type<MySql> for PetWeight {
+    fn type_info() -> MySqlTypeInfo {
+        <Decimal as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <Decimal as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, MySql> for PetWeight {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        <Decimal as Decode<MySql>>::decode(value).map(PetWeight)
+    }
+}
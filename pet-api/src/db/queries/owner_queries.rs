@@ -1,161 +1,199 @@
 use std::sync::Arc;
 
-use sqlx::Row;
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Row, Sqlite};
 
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::owner_repository::OwnerRepository;
 use crate::models::{
     owner_model::{OwnerModel, OwnerWithPets},
     pet_model::PetModel,
 };
 
-pub struct OwnerQueries {
-    db: Arc<sqlx::MySqlPool>,
+pub struct OwnerQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
 }
 
-impl OwnerQueries {
-    pub fn new(db: Arc<sqlx::MySqlPool>) -> Self {
-        Self { db }
-    }
-
-    pub async fn insert_owner(
-        &self,
-        owner_id: String,
-        owner_name: String,
-        owner_email: String,
-        owner_phone_number: String,
-        owner_address: String,
-    ) -> Result<u64, sqlx::Error> {
-        sqlx::query(r#"INSERT INTO owner (owner_id, owner_name, owner_email, owner_phone_number, owner_address) VALUES (?, ?, ?, ?, ?)"#)
-            .bind(owner_id)
-            .bind(owner_name)
-            .bind(owner_email)
-            .bind(owner_phone_number)
-            .bind(owner_address)
-            .execute(&*self.db)
-            .await
-            .map(|done| done.rows_affected())
-    }
-
-    pub async fn select_owner(&self, owner_id: String) -> Result<OwnerModel, sqlx::Error> {
-        sqlx::query_as("SELECT * FROM owner WHERE owner_id = ?")
-            .bind(owner_id)
-            .fetch_one(&*self.db)
-            .await
-    }
-
-    pub async fn select_all_owners(
-        &self,
-        limit: i32,
-        offset: i32,
-        search: Option<String>,
-    ) -> Result<Vec<OwnerModel>, sqlx::Error> {
-        let mut query = String::from("SELECT * FROM owner ");
-
-        if let Some(search_term) = search {
-            query.push_str("WHERE owner_name LIKE ? ");
-            query.push_str("ORDER BY owner_name ");
-            query.push_str("LIMIT ? OFFSET ?");
-
-            sqlx::query_as::<_, OwnerModel>(&query)
-                .bind(format!("%{}%", search_term))
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.db)
-                .await
-        } else {
-            query.push_str("ORDER BY owner_name ");
-            query.push_str("LIMIT ? OFFSET ?");
-
-            sqlx::query_as::<_, OwnerModel>(&query)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.db)
-                .await
-        }
-    }
-
-    pub async fn delete_owner(&self, owner_id: String) -> Result<u64, sqlx::Error> {
-        sqlx::query("DELETE FROM owner WHERE owner_id = ?")
-            .bind(owner_id)
-            .execute(&*self.db)
-            .await
-            .map(|done| done.rows_affected())
-    }
-    pub async fn update_owner(
-        &self,
-        owner_id: String,
-        owner_name: Option<String>,
-        owner_email: Option<String>,
-        owner_phone_number: Option<String>,
-        owner_address: Option<String>,
-    ) -> Result<u64, sqlx::Error> {
-        let mut query_string = String::from("UPDATE owner SET ");
-        let mut params = Vec::new();
-
-        if let Some(name) = owner_name {
-            query_string.push_str("owner_name = ?, ");
-            params.push(name);
-        }
-
-        if let Some(email) = owner_email {
-            query_string.push_str("owner_email = ?, ");
-            params.push(email);
-        }
-
-        if let Some(phone_number) = owner_phone_number {
-            query_string.push_str("owner_phone_number = ?, ");
-            params.push(phone_number);
-        }
-
-        if let Some(address) = owner_address {
-            query_string.push_str("owner_address = ?, ");
-            params.push(address);
-        }
-
-        if query_string.ends_with(", ") {
-            query_string.truncate(query_string.len() - 2);
-        }
-
-        query_string.push_str(" WHERE owner_id = ?");
-        params.push(owner_id);
-
-        let mut query = sqlx::query(&query_string);
-
-        for param in params {
-            query = query.bind(param);
+impl<DB: Database> OwnerQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
         }
-
-        let result = query.execute(&*self.db).await?;
-
-        Ok(result.rows_affected())
     }
+}
 
-    pub async fn get_owner_and_pets(&self, owner_id: String) -> Result<OwnerWithPets, sqlx::Error> {
-        let owner = match self.select_owner(owner_id.clone()).await {
-            Ok(owner) => owner,
-            Err(_) => return Err(sqlx::Error::RowNotFound),
-        };
-
-        let pets =
-            sqlx::query_as::<_, PetModel>("SELECT * FROM PET WHERE owner_id = ? ORDER BY pet_name")
-                .bind(owner_id)
-                .fetch_all(&*self.db)
-                .await?;
-
-        Ok(OwnerWithPets { owner, pets })
-    }
-
-    pub async fn count_all_owners(&self, search: Option<String>) -> Result<i64, sqlx::Error> {
-        let mut query = String::from("SELECT COUNT(*) as count FROM owner ");
-
-        if let Some(search_term) = search {
-            query.push_str(&format!("WHERE owner_name LIKE '%{}%' ", search_term));
+macro_rules! owner_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl OwnerRepository for OwnerQueries<$db> {
+            async fn insert_owner(
+                &self,
+                owner_id: String,
+                owner_name: String,
+                owner_email: String,
+                owner_phone_number: String,
+                owner_address: String,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(&format!(
+                    "INSERT INTO owner (owner_id, owner_name, owner_email, owner_phone_number, owner_address, clinic_id, created_on) VALUES (?, ?, ?, ?, ?, ?, {})",
+                    <$db>::CURRENT_DATE
+                )))
+                    .bind(owner_id)
+                    .bind(owner_name)
+                    .bind(owner_email)
+                    .bind(owner_phone_number)
+                    .bind(owner_address)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
+
+            async fn select_owner(&self, owner_id: String) -> Result<OwnerModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM owner WHERE owner_id = ? AND clinic_id = ?"))
+                    .bind(owner_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
+
+            async fn select_all_owners(
+                &self,
+                limit: i32,
+                offset: i32,
+                search: Option<String>,
+            ) -> Result<Vec<OwnerModel>, sqlx::Error> {
+                let mut query = String::from("SELECT * FROM owner WHERE clinic_id = ? ");
+
+                if let Some(search_term) = search {
+                    query.push_str(&format!("AND owner_name {} ? ", <$db>::ILIKE));
+                    query.push_str("ORDER BY owner_name ");
+                    query.push_str("LIMIT ? OFFSET ?");
+
+                    sqlx::query_as::<_, OwnerModel>(&<$db>::sql(&query))
+                        .bind(&self.clinic_id)
+                        .bind(format!("%{}%", search_term))
+                        .bind(limit)
+                        .bind(offset)
+                        .fetch_all(&*self.db)
+                        .await
+                } else {
+                    query.push_str("ORDER BY owner_name ");
+                    query.push_str("LIMIT ? OFFSET ?");
+
+                    sqlx::query_as::<_, OwnerModel>(&<$db>::sql(&query))
+                        .bind(&self.clinic_id)
+                        .bind(limit)
+                        .bind(offset)
+                        .fetch_all(&*self.db)
+                        .await
+                }
+            }
+
+            async fn delete_owner(&self, owner_id: String) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM owner WHERE owner_id = ? AND clinic_id = ?"))
+                    .bind(owner_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
+            async fn update_owner(
+                &self,
+                owner_id: String,
+                owner_name: Option<String>,
+                owner_email: Option<String>,
+                owner_phone_number: Option<String>,
+                owner_address: Option<String>,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut query_string = String::from("UPDATE owner SET ");
+                let mut params = Vec::new();
+
+                if let Some(name) = owner_name {
+                    query_string.push_str("owner_name = ?, ");
+                    params.push(name);
+                }
+
+                if let Some(email) = owner_email {
+                    query_string.push_str("owner_email = ?, ");
+                    params.push(email);
+                }
+
+                if let Some(phone_number) = owner_phone_number {
+                    query_string.push_str("owner_phone_number = ?, ");
+                    params.push(phone_number);
+                }
+
+                if let Some(address) = owner_address {
+                    query_string.push_str("owner_address = ?, ");
+                    params.push(address);
+                }
+
+                query_string.push_str("row_version = row_version + 1");
+
+                query_string.push_str(" WHERE owner_id = ? AND clinic_id = ?");
+                params.push(owner_id);
+                params.push(self.clinic_id.clone());
+                if let Some(version) = expected_version {
+                    query_string.push_str(&format!(" AND row_version = {}", <$db>::text_as("INT")));
+                    params.push(version.to_string());
+                }
+
+
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+
+                for param in params {
+                    query = query.bind(param);
+                }
+
+                let result = query.execute(&*self.db).await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn get_owner_and_pets(&self, owner_id: String) -> Result<OwnerWithPets, sqlx::Error> {
+                let owner = match self.select_owner(owner_id.clone()).await {
+                    Ok(owner) => owner,
+                    Err(_) => return Err(sqlx::Error::RowNotFound),
+                };
+
+                let pets =
+                    sqlx::query_as::<_, PetModel>(&<$db>::sql("SELECT * FROM pet WHERE owner_id = ? ORDER BY pet_name"))
+                        .bind(owner_id)
+                        .fetch_all(&*self.db)
+                        .await?;
+
+                Ok(OwnerWithPets { owner, pets })
+            }
+
+            async fn count_all_owners(&self, search: Option<String>) -> Result<i64, sqlx::Error> {
+                let mut query = String::from("SELECT COUNT(*) as count FROM owner WHERE clinic_id = ? ");
+
+                if search.is_some() {
+                    query.push_str(&format!("AND owner_name {} ? ", <$db>::ILIKE));
+                }
+
+
+                let sql = <$db>::sql(&query);
+                let mut query = sqlx::query(&sql).bind(&self.clinic_id);
+
+                if let Some(search_term) = search {
+                    query = query.bind(format!("%{}%", search_term));
+                }
+
+                query
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get("count"))
+            }
         }
-
-        let query = sqlx::query(&query);
-
-        query
-            .fetch_one(&*self.db)
-            .await
-            .map(|row: sqlx::mysql::MySqlRow| row.get("count"))
-    }
+    };
 }
+
+owner_queries!(MySql);
+owner_queries!(Postgres);
+owner_queries!(Sqlite);
@@ -1,191 +1,373 @@
 use std::sync::Arc;
 
-use sqlx::Row;
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Row, Sqlite};
 
-use crate::models::pet_model::{PetModel, PetModelResponse};
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::pet_repository::PetRepository;
+use crate::models::clinic_model::PetShare;
+use crate::models::pet_model::{PetModel, PetModelResponse, PetStatusModel};
 
-pub struct PetQueries {
-    db: Arc<sqlx::MySqlPool>,
+pub struct PetQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
 }
 
-impl PetQueries {
-    pub fn new(db: Arc<sqlx::MySqlPool>) -> Self {
-        Self { db }
+impl<DB: Database> PetQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
     }
+}
 
-    pub async fn insert_pet(
-        &self,
-        pet_id: String,
-        pet_name: String,
-        pet_birth_date: String,
-        pet_type: String,
-        pet_breed: String,
-        pet_weight: f32,
-        pet_color: String,
-        owner_id: String,
-    ) -> Result<u64, sqlx::Error> {
-        sqlx::query(r#"INSERT INTO pet (pet_id, pet_name, pet_birth_date, pet_type, pet_breed, pet_weight, pet_color, owner_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#)
-            .bind(pet_id)
-            .bind(pet_name)
-            .bind(pet_birth_date)
-            .bind(pet_type)
-            .bind(pet_breed)
-            .bind(pet_weight)
-            .bind(pet_color)
-            .bind(owner_id)
-            .execute(&*self.db)
-            .await
-            .map(|done| done.rows_affected())
-    }
+macro_rules! pet_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl PetRepository for PetQueries<$db> {
+            async fn insert_pet(
+                &self,
+                pet_id: String,
+                pet_name: String,
+                pet_birth_date: String,
+                pet_type: String,
+                pet_breed: String,
+                pet_weight: f32,
+                pet_color: String,
+                owner_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(&format!(
+                    "INSERT INTO pet (pet_id, pet_name, pet_birth_date, pet_type, pet_breed, pet_weight, pet_color, owner_id, created_on, clinic_id) SELECT ?, ?, {}, ?, ?, {}, ?, owner_id, {}, clinic_id FROM owner WHERE owner_id = ? AND clinic_id = ?",
+                    <$db>::text_as("DATE"),
+                    <$db>::text_as("NUMERIC"),
+                    <$db>::CURRENT_DATE
+                )))
+                    .bind(pet_id)
+                    .bind(pet_name)
+                    .bind(pet_birth_date)
+                    .bind(pet_type)
+                    .bind(pet_breed)
+                    .bind(pet_weight)
+                    .bind(pet_color)
+                    .bind(owner_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
 
-    pub async fn select_pet(&self, pet_id: String) -> Result<PetModel, sqlx::Error> {
-        sqlx::query_as("SELECT * FROM pet WHERE pet_id = ?")
-            .bind(pet_id)
-            .fetch_one(&*self.db)
-            .await
-    }
+            async fn select_pet(&self, pet_id: String) -> Result<PetModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM pet WHERE pet_id = ? AND clinic_id = ?"))
+                    .bind(pet_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
 
-    pub async fn select_all_pets(
-        &self,
-        limit: i32,
-        offset: i32,
-        search: Option<String>,
-    ) -> Result<Vec<PetModelResponse>, sqlx::Error> {
-        let mut query = String::from(
-            r#"
-    SELECT pet.*, owner.owner_name, owner.owner_email
-    FROM pet
-    INNER JOIN owner ON pet.owner_id = owner.owner_id
-    "#,
-        );
-
-        if let Some(search_term) = search {
-            query.push_str("WHERE pet_name LIKE ? ");
-            query.push_str("ORDER BY pet_type ");
-            query.push_str("LIMIT ? OFFSET ?");
-
-            sqlx::query_as::<_, PetModelResponse>(&query)
-                .bind(format!("%{}%", search_term))
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.db)
-                .await
-        } else {
-            query.push_str("ORDER BY pet_type ");
-            query.push_str("LIMIT ? OFFSET ?");
+            async fn select_all_pets(
+                &self,
+                limit: i32,
+                offset: i32,
+                search: Option<String>,
+                pet_status: Option<String>,
+            ) -> Result<Vec<PetModelResponse>, sqlx::Error> {
+                let mut query = String::from(
+                    r#"
+            SELECT pet.*, owner.owner_name, owner.owner_email
+            FROM pet
+            INNER JOIN owner ON pet.owner_id = owner.owner_id
+            WHERE pet.clinic_id = ?
+            AND (pet.pet_status = ? OR (? IS NULL AND pet.pet_status <> 'deceased'))
+            "#,
+                );
 
-            sqlx::query_as::<_, PetModelResponse>(&query)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.db)
-                .await
-        }
-    }
+                if let Some(search_term) = search {
+                    query.push_str(&format!("AND pet_name {} ? ", <$db>::ILIKE));
+                    query.push_str("ORDER BY pet_type ");
+                    query.push_str("LIMIT ? OFFSET ?");
 
-    pub async fn delete_pet(&self, pet_id: String) -> Result<u64, sqlx::Error> {
-        sqlx::query("DELETE FROM pet WHERE pet_id = ?")
-            .bind(pet_id)
-            .execute(&*self.db)
-            .await
-            .map(|done| done.rows_affected())
-    }
+                    sqlx::query_as::<_, PetModelResponse>(&<$db>::sql(&query))
+                        .bind(&self.clinic_id)
+                        .bind(&pet_status)
+                        .bind(&pet_status)
+                        .bind(format!("%{}%", search_term))
+                        .bind(limit)
+                        .bind(offset)
+                        .fetch_all(&*self.db)
+                        .await
+                } else {
+                    query.push_str("ORDER BY pet_type ");
+                    query.push_str("LIMIT ? OFFSET ?");
 
-    pub async fn update_pet(
-        &self,
-        pet_id: String,
-        pet_name: Option<String>,
-        pet_birth_date: Option<String>,
-        pet_type: Option<String>,
-        pet_breed: Option<String>,
-        pet_weight: Option<f32>,
-        pet_color: Option<String>,
-        owner_id: Option<String>,
-    ) -> Result<u64, sqlx::Error> {
-        let mut query_string = String::from("UPDATE pet SET ");
-        let mut params = Vec::new();
-
-        if let Some(name) = pet_name {
-            query_string.push_str("pet_name = ?, ");
-            params.push(name);
-        }
+                    sqlx::query_as::<_, PetModelResponse>(&<$db>::sql(&query))
+                        .bind(&self.clinic_id)
+                        .bind(&pet_status)
+                        .bind(&pet_status)
+                        .bind(limit)
+                        .bind(offset)
+                        .fetch_all(&*self.db)
+                        .await
+                }
+            }
 
-        if let Some(birth_date) = pet_birth_date {
-            query_string.push_str("pet_birth_date = ?, ");
-            params.push(birth_date);
-        }
+            async fn delete_pet(&self, pet_id: String) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM pet WHERE pet_id = ? AND clinic_id = ?"))
+                    .bind(pet_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
 
-        if let Some(pet_type) = pet_type {
-            query_string.push_str("pet_type = ?, ");
-            params.push(pet_type);
-        }
+            async fn update_pet(
+                &self,
+                pet_id: String,
+                pet_name: Option<String>,
+                pet_birth_date: Option<String>,
+                pet_type: Option<String>,
+                pet_breed: Option<String>,
+                pet_weight: Option<f32>,
+                pet_color: Option<String>,
+                owner_id: Option<String>,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut query_string = String::from("UPDATE pet SET ");
+                let mut params = Vec::new();
 
-        if let Some(breed) = pet_breed {
-            query_string.push_str("pet_breed = ?, ");
-            params.push(breed);
-        }
+                if let Some(name) = pet_name {
+                    query_string.push_str("pet_name = ?, ");
+                    params.push(name);
+                }
 
-        if let Some(weight) = pet_weight {
-            query_string.push_str("pet_weight = ?, ");
-            params.push(weight.to_string());
-        }
+                if let Some(birth_date) = pet_birth_date {
+                    query_string.push_str(&format!("pet_birth_date = {}, ", <$db>::text_as("DATE")));
+                    params.push(birth_date);
+                }
 
-        if let Some(color) = pet_color {
-            query_string.push_str("pet_color = ?, ");
-            params.push(color);
-        }
+                if let Some(pet_type) = pet_type {
+                    query_string.push_str("pet_type = ?, ");
+                    params.push(pet_type);
+                }
 
-        if let Some(owner_id) = owner_id {
-            query_string.push_str("owner_id = ?, ");
-            params.push(owner_id);
-        }
+                if let Some(breed) = pet_breed {
+                    query_string.push_str("pet_breed = ?, ");
+                    params.push(breed);
+                }
 
-        if query_string.ends_with(", ") {
-            query_string.truncate(query_string.len() - 2);
-        }
+                if let Some(weight) = pet_weight {
+                    query_string.push_str(&format!("pet_weight = {}, ", <$db>::text_as("NUMERIC")));
+                    params.push(weight.to_string());
+                }
 
-        query_string.push_str(" WHERE pet_id = ?");
-        params.push(pet_id);
+                if let Some(color) = pet_color {
+                    query_string.push_str("pet_color = ?, ");
+                    params.push(color);
+                }
 
-        let mut query = sqlx::query(&query_string);
+                if let Some(owner_id) = owner_id {
+                    query_string.push_str("owner_id = ?, ");
+                    params.push(owner_id);
+                }
 
-        for param in params {
-            query = query.bind(param);
-        }
+                query_string.push_str("row_version = row_version + 1");
 
-        let result = query.execute(&*self.db).await?;
+                query_string.push_str(" WHERE pet_id = ? AND clinic_id = ?");
+                params.push(pet_id);
+                params.push(self.clinic_id.clone());
+                if let Some(version) = expected_version {
+                    query_string.push_str(&format!(" AND row_version = {}", <$db>::text_as("INT")));
+                    params.push(version.to_string());
+                }
 
-        Ok(result.rows_affected())
-    }
 
-    pub async fn count_all_pets(&self, search: Option<String>) -> Result<i64, sqlx::Error> {
-        let mut query = String::from("SELECT COUNT(*) as count FROM pet ");
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
 
-        if let Some(search_term) = search {
-            query.push_str(&format!("WHERE pet_name LIKE '%{}%' ", search_term));
-        }
+                for param in params {
+                    query = query.bind(param);
+                }
 
-        let query = sqlx::query(&query);
+                let result = query.execute(&*self.db).await?;
 
-        query
-            .fetch_one(&*self.db)
-            .await
-            .map(|row: sqlx::mysql::MySqlRow| row.get("count"))
-    }
+                Ok(result.rows_affected())
+            }
 
-    pub async fn select_pet_details(
-        &self,
-        pet_id: String,
-    ) -> Result<PetModelResponse, sqlx::Error> {
-        let query = r#"
-        SELECT pet.*, owner.*
-        FROM pet
-        INNER JOIN owner ON pet.owner_id = owner.owner_id
-        WHERE pet.pet_id = ?
-        "#;
-
-        sqlx::query_as::<_, PetModelResponse>(query)
-            .bind(pet_id)
-            .fetch_one(&*self.db)
-            .await
-    }
+            async fn count_all_pets(
+                &self,
+                search: Option<String>,
+                pet_status: Option<String>,
+            ) -> Result<i64, sqlx::Error> {
+                let mut query = String::from(
+                    "SELECT COUNT(*) as count FROM pet WHERE clinic_id = ? \
+                    AND (pet_status = ? OR (? IS NULL AND pet_status <> 'deceased')) ",
+                );
+
+                if search.is_some() {
+                    query.push_str(&format!("AND pet_name {} ? ", <$db>::ILIKE));
+                }
+
+
+                let sql = <$db>::sql(&query);
+                let mut query = sqlx::query(&sql)
+                    .bind(&self.clinic_id)
+                    .bind(&pet_status)
+                    .bind(&pet_status);
+
+                if let Some(search_term) = search {
+                    query = query.bind(format!("%{}%", search_term));
+                }
+
+                query
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get("count"))
+            }
+
+            async fn select_pet_details(&self, pet_id: String) -> Result<PetModelResponse, sqlx::Error> {
+                let query = r#"
+                SELECT pet.*, owner.owner_name, owner.owner_email
+                FROM pet
+                INNER JOIN owner ON pet.owner_id = owner.owner_id
+                WHERE pet.pet_id = ?
+                AND (pet.clinic_id = ? OR EXISTS (
+                    SELECT 1 FROM pet_share WHERE pet_share.pet_id = pet.pet_id AND pet_share.clinic_id = ?
+                ))
+                "#;
+
+                sqlx::query_as::<_, PetModelResponse>(&<$db>::sql(query))
+                    .bind(pet_id)
+                    .bind(&self.clinic_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
+
+            async fn insert_pet_share(
+                &self,
+                pet_id: String,
+                clinic_id: String,
+                shared_at: i64,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO pet_share (pet_id, clinic_id, shared_at)
+                    SELECT pet_id, ?, ? FROM pet WHERE pet_id = ? AND clinic_id = ?"#)
+                )
+                .bind(clinic_id)
+                .bind(shared_at)
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn delete_pet_share(
+                &self,
+                pet_id: String,
+                clinic_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"DELETE FROM pet_share WHERE pet_id = ? AND clinic_id = ?
+                    AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?)"#)
+                )
+                .bind(pet_id)
+                .bind(clinic_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_pet_shares(&self, pet_id: String) -> Result<Vec<PetShare>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT pet_share.clinic_id, clinic.clinic_name, pet_share.shared_at
+                    FROM pet_share
+                    INNER JOIN clinic ON clinic.clinic_id = pet_share.clinic_id
+                    INNER JOIN pet ON pet.pet_id = pet_share.pet_id
+                    WHERE pet_share.pet_id = ? AND pet.clinic_id = ?
+                    ORDER BY clinic.clinic_name"#)
+                )
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn select_pet_status(&self, pet_id: String) -> Result<PetStatusModel, sqlx::Error> {
+                let mut status: PetStatusModel = sqlx::query_as(
+                    &<$db>::sql(r#"SELECT pet_id, pet_status, status_changed_on, memorial_safe FROM pet
+                    WHERE pet_id = ? AND (clinic_id = ? OR EXISTS (
+                        SELECT 1 FROM pet_share WHERE pet_share.pet_id = pet.pet_id AND pet_share.clinic_id = ?
+                    ))"#)
+                )
+                .bind(&pet_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await?;
+                status.death = sqlx::query_as(
+                    &<$db>::sql(r#"SELECT date_of_death, cause_of_death, euthanasia, consent_given_by, consent_given_on,
+                    vet_id, recorded_at FROM pet_death WHERE pet_id = ?"#)
+                )
+                .bind(&pet_id)
+                .fetch_optional(&*self.db)
+                .await?;
+
+                Ok(status)
+            }
+
+            async fn update_pet_status(
+                &self,
+                status: &PetStatusModel,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut tx = self.db.begin().await?;
+                let updated = sqlx::query(
+                    &<$db>::sql(r#"UPDATE pet SET pet_status = ?, status_changed_on = ?, memorial_safe = ?,
+                    row_version = row_version + 1
+                    WHERE pet_id = ? AND clinic_id = ? AND (? IS NULL OR row_version = ?)"#)
+                )
+                .bind(&status.pet_status)
+                .bind(status.status_changed_on)
+                .bind(status.memorial_safe)
+                .bind(&status.pet_id)
+                .bind(&self.clinic_id)
+                .bind(expected_version)
+                .bind(expected_version)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+                if updated == 0 {
+                    return Ok(0);
+                }
+
+                if let Some(death) = &status.death {
+                    sqlx::query(&<$db>::sql(&format!(
+                        r#"INSERT INTO pet_death (pet_id, date_of_death, cause_of_death, euthanasia,
+                        consent_given_by, consent_given_on, vet_id, recorded_at)
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?) {}"#,
+                        <$db>::on_conflict("pet_id", &[])
+                    )))
+                    .bind(&status.pet_id)
+                    .bind(death.date_of_death)
+                    .bind(&death.cause_of_death)
+                    .bind(death.euthanasia)
+                    .bind(&death.consent_given_by)
+                    .bind(death.consent_given_on)
+                    .bind(&death.vet_id)
+                    .bind(death.recorded_at)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                tx.commit().await?;
+
+                Ok(updated)
+            }
+        }
+    };
 }
+
+pet_queries!(MySql);
+pet_queries!(Postgres);
+pet_queries!(Sqlite);
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::portal_repository::PortalRepository;
+use crate::models::owner_model::OwnerModel;
+use crate::models::portal_model::UpcomingFollowup;
+
+pub struct PortalQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> PortalQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>) -> Self {
+        Self { db }
+    }
+}
+
+macro_rules! portal_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl PortalRepository for PortalQueries<$db> {
+            async fn select_owner_by_email(
+                &self,
+                clinic_id: &str,
+                owner_email: &str,
+            ) -> Result<Option<OwnerModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM owner WHERE clinic_id = ? AND LOWER(owner_email) = LOWER(?)"))
+                    .bind(clinic_id)
+                    .bind(owner_email)
+                    .fetch_optional(&*self.db)
+                    .await
+            }
+
+            async fn insert_token(
+                &self,
+                token_hash: &str,
+                token_kind: &str,
+                owner_id: &str,
+                created_at: i64,
+                expires_at: i64,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO portal_token (token_hash, token_kind, owner_id, created_at, expires_at)
+                    VALUES (?, ?, ?, ?, ?)"#)
+                )
+                .bind(token_hash)
+                .bind(token_kind)
+                .bind(owner_id)
+                .bind(created_at)
+                .bind(expires_at)
+                .execute(&*self.db)
+                .await
+                .map(|result| result.rows_affected())
+            }
+
+            async fn use_login_token(
+                &self,
+                token_hash: &str,
+                now: i64,
+            ) -> Result<Option<String>, sqlx::Error> {
+                let used = sqlx::query(
+                    &<$db>::sql(r#"UPDATE portal_token SET used_at = ?
+                    WHERE token_hash = ? AND token_kind = 'login' AND used_at IS NULL AND expires_at > ?"#)
+                )
+                .bind(now)
+                .bind(token_hash)
+                .bind(now)
+                .execute(&*self.db)
+                .await?
+                .rows_affected();
+                if used == 0 {
+                    return Ok(None);
+                }
+
+                sqlx::query_scalar(&<$db>::sql("SELECT owner_id FROM portal_token WHERE token_hash = ?"))
+                    .bind(token_hash)
+                    .fetch_optional(&*self.db)
+                    .await
+            }
+
+            async fn select_session_owner(
+                &self,
+                token_hash: &str,
+                now: i64,
+            ) -> Result<Option<(String, String)>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"SELECT portal_token.owner_id, owner.clinic_id FROM portal_token
+                    JOIN owner ON owner.owner_id = portal_token.owner_id
+                    WHERE token_hash = ? AND token_kind = 'session' AND expires_at > ?"#)
+                )
+                .bind(token_hash)
+                .bind(now)
+                .fetch_optional(&*self.db)
+                .await
+            }
+
+            async fn delete_token(&self, token_hash: &str) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM portal_token WHERE token_hash = ?"))
+                    .bind(token_hash)
+                    .execute(&*self.db)
+                    .await
+                    .map(|result| result.rows_affected())
+            }
+
+            async fn delete_expired_tokens(&self, now: i64) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql("DELETE FROM portal_token WHERE expires_at <= ?"))
+                    .bind(now)
+                    .execute(&*self.db)
+                    .await
+                    .map(|result| result.rows_affected())
+            }
+
+            async fn select_upcoming_followups(
+                &self,
+                owner_id: &str,
+                from: NaiveDate,
+            ) -> Result<Vec<UpcomingFollowup>, sqlx::Error> {
+                sqlx::query_as(
+                    &<$db>::sql(r#"
+                        SELECT service_instance.service_instance_id, pet.pet_id, pet.pet_name,
+                        service_instance.service_date, service_instance.service_reason,
+                        service_instance.followup_date
+                        FROM service_instance
+                        JOIN pet ON service_instance.pet_id = pet.pet_id
+                        WHERE pet.owner_id = ?
+                        AND service_instance.requires_followup = TRUE
+                        AND pet.pet_status <> 'deceased' AND pet.memorial_safe = FALSE
+                        AND service_instance.followup_date >= ?
+                        ORDER BY service_instance.followup_date, pet.pet_name
+                    "#)
+                )
+                .bind(owner_id)
+                .bind(from)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+portal_queries!(MySql);
+portal_queries!(Postgres);
+portal_queries!(Sqlite);
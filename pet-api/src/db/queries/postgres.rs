@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use rust_decimal::Decimal;
+use sqlx::database::HasArguments;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgTypeInfo, PgValueRef};
+use sqlx::query::Query;
+use sqlx::{Decode, Executor, Postgres, Row, Type};
+
+use crate::db::queries::dialect::Dialect;
+use crate::models::pet_model::PetWeight;
+
+impl Dialect for Postgres {
+    const CURRENT_DATE: &'static str = "CURRENT_DATE";
+    const ILIKE: &'static str = "ILIKE";
+    const BIGINT: &'static str = "BIGINT";
+    const BEGIN_WRITE: &'static str = "BEGIN";
+    const FOR_UPDATE: &'static str = " FOR UPDATE";
+
+    /// Numbers the placeholders `$1`, `$2`, ... in order, skipping quoted text.
+    fn sql(query: &str) -> Cow<'_, str> {
+        let mut sql = String::with_capacity(query.len() + 16);
+        let mut params = 0;
+        let mut quote = None;
+        for c in query.chars() {
+            match quote {
+                None if c == '?' => {
+                    params += 1;
+                    let _ = write!(sql, "${}", params);
+                    continue;
+                }
+                None if c == '\'' || c == '"' => quote = Some(c),
+                Some(open) if c == open => quote = None,
+                _ => {}
+            }
+            sql.push(c);
+        }
+
+        Cow::Owned(sql)
+    }
+
+    /// Postgres won't read text bound where another type is expected without a cast.
+    fn text_as(sql_type: &str) -> Cow<'static, str> {
+        Cow::Owned(format!("CAST(? AS {})", sql_type))
+    }
+
+    fn string_agg(expression: &str, order_by: &str) -> String {
+        format!("STRING_AGG({}, ', ' ORDER BY {})", expression, order_by)
+    }
+
+    fn on_conflict(key: &str, columns: &[&str]) -> String {
+        if columns.is_empty() {
+            return format!("ON CONFLICT ({}) DO NOTHING", key);
+        }
+
+        let updates: Vec<_> = columns
+            .iter()
+            .map(|column| format!("{0} = EXCLUDED.{0}", column))
+            .collect();
+        format!("ON CONFLICT ({}) DO UPDATE SET {}", key, updates.join(", "))
+    }
+
+    fn returning(id_column: &str) -> String {
+        format!(" RETURNING {}", id_column)
+    }
+
+    async fn generated_id<'q, 'e>(
+        query: Query<'q, Self, <Self as HasArguments<'q>>::Arguments>,
+        executor: impl Executor<'e, Database = Self> + 'q,
+    ) -> Result<i32, sqlx::Error>
+    where
+        'q: 'e,
+    {
+        query.fetch_one(executor).await?.try_get(0)
+    }
+}
+
+// `pet_weight` is a NUMERIC(5,2).
+impl Type<Postgres> for PetWeight {
+    fn type_info() -> PgTypeInfo {
+        <Decimal as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Decimal as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PetWeight {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        <Decimal as Decode<Postgres>>::decode(value).map(PetWeight)
+    }
+}
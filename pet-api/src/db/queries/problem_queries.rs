@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::{placeholders, Dialect};
+use crate::db::repositories::problem_repository::ProblemRepository;
+use crate::models::problem_model::PetProblemModel;
+
+pub struct ProblemQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> ProblemQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+const SELECT_PROBLEMS: &str = r#"SELECT problem_id, pet_id, problem_type, description, severity,
+    onset_date, resolved_date, notes, recorded_at, updated_at FROM pet_problem"#;
+
+/// Limits `pet_id` to the clinic's pets and those shared with it, binding the clinic twice.
+const VISIBLE_PETS: &str = "pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ? \
+    UNION SELECT pet_id FROM pet_share WHERE clinic_id = ?)";
+
+macro_rules! problem_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ProblemRepository for ProblemQueries<$db> {
+            async fn insert_problem(&self, problem: &PetProblemModel) -> Result<i32, sqlx::Error> {
+                let query_string = format!(r#"INSERT INTO pet_problem (pet_id, problem_type, description, severity, onset_date,
+                    resolved_date, notes, recorded_at, updated_at)
+                    SELECT pet_id, ?, ?, ?, ?, ?, ?, ?, ? FROM pet WHERE pet_id = ? AND clinic_id = ?{}"#, <$db>::returning("problem_id"));
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(&problem.problem_type)
+                .bind(&problem.description)
+                .bind(&problem.severity)
+                .bind(problem.onset_date)
+                .bind(problem.resolved_date)
+                .bind(&problem.notes)
+                .bind(problem.recorded_at)
+                .bind(problem.updated_at)
+                .bind(&problem.pet_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_problem(
+                &self,
+                pet_id: &str,
+                problem_id: i32,
+            ) -> Result<PetProblemModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE problem_id = ? AND pet_id = ? AND {}",
+                    SELECT_PROBLEMS, VISIBLE_PETS
+                )))
+                .bind(problem_id)
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn select_problems(
+                &self,
+                pet_id: &str,
+                include_resolved: bool,
+            ) -> Result<Vec<PetProblemModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "{} WHERE pet_id = ? AND (? OR resolved_date IS NULL) AND {} ORDER BY problem_id",
+                    SELECT_PROBLEMS, VISIBLE_PETS
+                )))
+                .bind(pet_id)
+                .bind(include_resolved)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn update_problem(&self, problem: &PetProblemModel) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"
+                    UPDATE pet_problem
+                    SET description = ?, severity = ?, onset_date = ?, resolved_date = ?, notes = ?,
+                        updated_at = ?
+                    WHERE problem_id = ? AND pet_id = ?
+                        AND pet_id IN (SELECT pet_id FROM pet WHERE clinic_id = ?)
+                    "#)
+                )
+                .bind(&problem.description)
+                .bind(&problem.severity)
+                .bind(problem.onset_date)
+                .bind(problem.resolved_date)
+                .bind(&problem.notes)
+                .bind(problem.updated_at)
+                .bind(problem.problem_id)
+                .bind(&problem.pet_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_active_problems(
+                &self,
+                pet_ids: &[String],
+            ) -> Result<Vec<PetProblemModel>, sqlx::Error> {
+                if pet_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let query_string = format!(
+                    "{} WHERE pet_id IN ({}) AND resolved_date IS NULL AND {} ORDER BY problem_id",
+                    SELECT_PROBLEMS,
+                    placeholders(pet_ids.len()),
+                    VISIBLE_PETS
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql);
+                for pet_id in pet_ids {
+                    query = query.bind(pet_id);
+                }
+
+                query
+                    .bind(&self.clinic_id)
+                    .bind(&self.clinic_id)
+                    .fetch_all(&*self.db)
+                    .await
+            }
+
+            async fn select_active_problems_of_service_instance(
+                &self,
+                service_instance_id: &str,
+            ) -> Result<Vec<PetProblemModel>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"{} WHERE pet_id IN (SELECT pet_id FROM service_instance WHERE service_instance_id = ?)
+                    AND resolved_date IS NULL AND {} ORDER BY problem_id"#,
+                    SELECT_PROBLEMS, VISIBLE_PETS
+                )))
+                .bind(service_instance_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+problem_queries!(MySql);
+problem_queries!(Postgres);
+problem_queries!(Sqlite);
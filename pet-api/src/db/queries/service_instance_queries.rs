@@ -1,651 +1,935 @@
 use core::option::Option;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use sqlx::Row;
+use async_trait::async_trait;
+use sqlx::database::HasArguments;
+use sqlx::query::Query;
+use sqlx::{Database, Encode, MySql, Pool, Postgres, Row, Sqlite, Type};
 
+use crate::db::queries::dialect::{placeholders, Dialect};
+use crate::db::repositories::service_instance_repository::ServiceInstanceRepository;
 use crate::models::service_instance_model::{
     AllServiceInstanceModel, GetServicesHistoryModel, GroomingModel, PreventiveCareModel,
     ServiceInstanceModel, SimplePetModel, SurgeryModel,
 };
 use crate::models::vet_model::VetModelForService;
+use crate::schemas::helper_schema::ServiceInstanceFilterOptions;
 use crate::schemas::service_instance_schema::{
     AddPreventiveCare, AddPreventiveCareToExisting, AddSurgery, Grooming, PreventiveCare,
     ServiceInstance, Surgery, UpdateServiceInstance, UpdateSurgery,
 };
 
-
-pub struct ServiceInstanceQueries {
-    db: Arc<sqlx::MySqlPool>,
-    pub create_service_instance_type: &'static str,
-    pub create_service_instance: &'static str,
-    pub create_grooming: &'static str,
-    pub create_preventive_care: &'static str,
-    pub create_surgery: &'static str,
-    pub get_specific_service_instance: &'static str,
-    pub get_grooming_of_service_instance: &'static str,
-    pub get_preventive_care_of_service_instance: &'static str,
-    pub get_surgery_of_service_instance: &'static str,
-    pub get_service_instance_type: &'static str,
-    pub get_all_service_instance: &'static str,
+pub struct ServiceInstanceQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
 }
 
-impl ServiceInstanceQueries {
-    pub fn new(db: Arc<sqlx::MySqlPool>) -> Self {
+impl<DB: Database> ServiceInstanceQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
         Self {
             db,
-            create_service_instance_type: r#"INSERT INTO service_type ( service_type_name, service_instance_id) VALUES (?, ?)"#,
-            create_service_instance: r#"INSERT INTO service_instance (service_instance_id, service_date, service_reason,
-            general_diagnosis, requires_followup, followup_date, pet_id) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
-            create_grooming: r#"INSERT INTO grooming (grooming_type, service_instance_id) VALUES (?, ?)"#,
-            create_preventive_care: r#"INSERT INTO preventive_care (treatment, vet_id,
-            service_instance_id) VALUES (?, ?, ?)"#,
-            create_surgery: r#"INSERT INTO surgery (surgery_name,
-            veterinarian_diagnosis, anesthesia_used, complications,
-             outcome, service_instance_id, vet_id) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
-            get_specific_service_instance: r#"SELECT * FROM service_instance WHERE service_instance_id = ?"#,
-            get_all_service_instance: r#"SELECT * FROM service_instance"#,
-            get_grooming_of_service_instance: r#"SELECT * FROM grooming WHERE service_instance_id = ?"#,
-            get_preventive_care_of_service_instance: r#"SELECT * FROM preventive_care WHERE service_instance_id = ?"#,
-            get_surgery_of_service_instance: r#"SELECT * FROM surgery WHERE service_instance_id = ?"#,
-            get_service_instance_type: r#"SELECT service_type_name FROM service_type WHERE service_instance_id = ?"#,
+            clinic_id: clinic_id.to_string(),
         }
     }
+}
 
-    pub async fn insert_service_instance(
-        &self,
-        service_instance_id: String,
-        service_date: String,
-        service_type: Vec<String>,
-        service_reason: String,
-        general_diagnosis: String,
-        requires_followup: bool,
-        followup_date: Option<String>,
-        pet_id: String,
-        grooming_type: Option<Vec<String>>,
-        preventive_care: Option<AddPreventiveCare>,
-        surgery: Option<AddSurgery>,
-    ) -> Result<ServiceInstance, sqlx::Error> {
-        let mut tx = self.db.begin().await?;
-
-        let _service = sqlx::query(&self.create_service_instance)
-            .bind(service_instance_id.clone())
-            .bind(service_date.clone())
-            .bind(service_reason.clone())
-            .bind(general_diagnosis.clone())
-            .bind(requires_followup)
-            .bind(followup_date.clone())
-            .bind(pet_id.clone())
-            .execute(&mut *tx)
-            .await?;
-
-        let mut service_types = Vec::new();
-        for service in &service_type {
-            sqlx::query(&self.create_service_instance_type)
-                .bind(service.clone())
-                .bind(service_instance_id.clone())
-                .execute(&mut *tx)
-                .await?;
-            service_types.push(service.to_string());
-        }
+const CREATE_SERVICE_INSTANCE_TYPE: &str =
+    r#"INSERT INTO service_type ( service_type_name, service_instance_id) VALUES (?, ?)"#;
+
+fn create_service_instance<DB: Dialect>() -> String {
+    format!(
+        r#"INSERT INTO service_instance (service_instance_id, service_date, service_reason,
+        general_diagnosis, diagnosis_code, requires_followup, followup_date, pet_id, clinic_id)
+        SELECT ?, {date}, ?, ?, ?, ?, {date}, pet_id, clinic_id FROM pet WHERE pet_id = ? AND clinic_id = ?"#,
+        date = DB::text_as("DATE")
+    )
+}
 
-        let mut groomings = Vec::new();
-        if let Some(grooming_type) = grooming_type {
-            for grooming in grooming_type {
-                let row = sqlx::query(&self.create_grooming)
-                    .bind(grooming.clone())
+const CREATE_GROOMING: &str = r#"INSERT INTO grooming (grooming_type, service_instance_id)
+    SELECT ?, service_instance_id FROM service_instance WHERE service_instance_id = ? AND clinic_id = ?"#;
+
+const CREATE_PREVENTIVE_CARE: &str = r#"INSERT INTO preventive_care (treatment, treatment_code,
+    vet_id, service_instance_id) SELECT ?, ?, ?, service_instance_id FROM service_instance
+    WHERE service_instance_id = ? AND clinic_id = ?"#;
+
+const CREATE_SURGERY: &str = r#"INSERT INTO surgery (surgery_name, surgery_code,
+    veterinarian_diagnosis, anesthesia_used, complications,
+    outcome, vet_id, service_instance_id) SELECT ?, ?, ?, ?, ?, ?, ?, service_instance_id
+    FROM service_instance WHERE service_instance_id = ? AND clinic_id = ?"#;
+
+macro_rules! service_instance_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl ServiceInstanceRepository for ServiceInstanceQueries<$db> {
+            async fn insert_service_instance(
+                &self,
+                service_instance_id: String,
+                service_date: String,
+                service_type: Vec<String>,
+                service_reason: String,
+                general_diagnosis: String,
+                diagnosis_code: Option<String>,
+                requires_followup: bool,
+                followup_date: Option<String>,
+                pet_id: String,
+                grooming_type: Option<Vec<String>>,
+                preventive_care: Option<AddPreventiveCare>,
+                surgery: Option<AddSurgery>,
+            ) -> Result<ServiceInstance, sqlx::Error> {
+                let mut tx = self.db.begin().await?;
+
+                let _service = sqlx::query(&<$db>::sql(&create_service_instance::<$db>()))
                     .bind(service_instance_id.clone())
+                    .bind(service_date.clone())
+                    .bind(service_reason.clone())
+                    .bind(general_diagnosis.clone())
+                    .bind(diagnosis_code.clone())
+                    .bind(requires_followup)
+                    .bind(followup_date.clone())
+                    .bind(pet_id.clone())
+                    .bind(&self.clinic_id)
                     .execute(&mut *tx)
                     .await?;
+                if _service.rows_affected() == 0 {
+                    return Err(sqlx::Error::RowNotFound);
+                }
 
-                let grooming_id = row.last_insert_id() as i32;
+                let mut service_types = Vec::new();
+                for service in &service_type {
+                    sqlx::query(&<$db>::sql(CREATE_SERVICE_INSTANCE_TYPE))
+                        .bind(service.clone())
+                        .bind(service_instance_id.clone())
+                        .execute(&mut *tx)
+                        .await?;
+                    service_types.push(service.to_string());
+                }
 
-                groomings.push(Grooming {
-                    grooming_id: Some(grooming_id),
-                    grooming_type: grooming,
-                    service_instance_id: service_instance_id.clone(),
-                });
+                let mut groomings = Vec::new();
+                if let Some(grooming_type) = grooming_type {
+                    for grooming in grooming_type {
+                        let query_string = format!("{}{}", CREATE_GROOMING, <$db>::returning("grooming_id"));
+                        let sql = <$db>::sql(&query_string);
+                        let query = sqlx::query(&sql)
+                            .bind(grooming.clone())
+                            .bind(service_instance_id.clone())
+                            .bind(&self.clinic_id);
+                        let grooming_id = <$db>::generated_id(query, &mut *tx).await?;
+
+                        groomings.push(Grooming {
+                            grooming_id: Some(grooming_id),
+                            grooming_type: grooming,
+                            service_instance_id: service_instance_id.clone(),
+                        });
+                    }
+                }
+
+                let mut preventive_cares = Vec::new();
+                if let Some(preventive_care) = preventive_care {
+                    for (treatment, treatment_code) in preventive_care.coded_treatments() {
+                        let query_string = format!("{}{}", CREATE_PREVENTIVE_CARE, <$db>::returning("preventive_care_id"));
+                        let sql = <$db>::sql(&query_string);
+                        let query = sqlx::query(&sql)
+                            .bind(treatment.clone())
+                            .bind(treatment_code.clone())
+                            .bind(preventive_care.vet_id.clone())
+                            .bind(service_instance_id.clone())
+                            .bind(&self.clinic_id);
+                        let preventive_care_id = <$db>::generated_id(query, &mut *tx).await?;
+                        preventive_cares.push(PreventiveCare {
+                            preventive_care_id: Some(preventive_care_id),
+                            treatment,
+                            treatment_code,
+                            service_instance_id: service_instance_id.clone(),
+                            vet_id: preventive_care.vet_id.clone(),
+                        });
+                    }
+                }
+
+                let mut surgeries = Vec::new();
+                if let Some(surgery) = surgery {
+                    let query_string = format!("{}{}", CREATE_SURGERY, <$db>::returning("surgery_id"));
+                    let sql = <$db>::sql(&query_string);
+                    let query = sqlx::query(&sql)
+                        .bind(surgery.surgery_name.clone())
+                        .bind(surgery.surgery_code.clone())
+                        .bind(surgery.veterinarian_diagnosis.clone())
+                        .bind(surgery.anesthesia_used.clone())
+                        .bind(surgery.complications.clone())
+                        .bind(surgery.outcome.clone())
+                        .bind(surgery.vet_id.clone())
+                        .bind(service_instance_id.clone())
+                        .bind(&self.clinic_id);
+                    let surgery_id = <$db>::generated_id(query, &mut *tx).await?;
+                    surgeries.push(Surgery {
+                        surgery_id: Some(surgery_id),
+                        surgery_name: surgery.surgery_name.clone(),
+                        surgery_code: surgery.surgery_code,
+                        anesthesia_used: surgery.anesthesia_used,
+                        veterinarian_diagnosis: surgery.veterinarian_diagnosis,
+                        complications: surgery.complications,
+                        outcome: surgery.outcome,
+                        vet_id: surgery.vet_id,
+                        service_instance_id: service_instance_id.clone(),
+                    });
+                }
+
+                tx.commit().await?;
+
+                Ok(ServiceInstance {
+                    service_instance_id,
+                    service_date,
+                    service_type,
+                    service_reason,
+                    general_diagnosis,
+                    diagnosis_code,
+                    requires_followup,
+                    followup_date,
+                    pet_id,
+                    grooming: if groomings.is_empty() {
+                        None
+                    } else {
+                        Some(groomings)
+                    },
+                    preventive_care: if preventive_cares.is_empty() {
+                        None
+                    } else {
+                        Some(preventive_cares)
+                    },
+                    surgery: if surgeries.is_empty() {
+                        None
+                    } else {
+                        Some(surgeries[0].clone())
+                    },
+                })
             }
-        }
 
-        let mut preventive_cares = Vec::new();
-        if let Some(preventive_care) = preventive_care {
-            for treatment in preventive_care.treatment {
-                let row = sqlx::query(&self.create_preventive_care)
-                    .bind(treatment.clone())
-                    .bind(preventive_care.vet_id.clone())
-                    .bind(service_instance_id.clone())
-                    .execute(&mut *tx)
-                    .await?;
-                let preventive_care_id = row.last_insert_id() as i32;
-                preventive_cares.push(PreventiveCare {
-                    preventive_care_id: Some(preventive_care_id),
-                    treatment,
-                    service_instance_id: service_instance_id.clone(),
-                    vet_id: preventive_care.vet_id.clone(),
-                });
+            async fn check_pet_exists(&self, pet_id: &str) -> Result<bool, sqlx::Error> {
+                let result = sqlx::query(
+                    &<$db>::sql(r#"SELECT EXISTS(SELECT 1 FROM pet WHERE pet_id = ? AND (clinic_id = ?
+                    OR pet_id IN (SELECT pet_id FROM pet_share WHERE clinic_id = ?))) AS pet_exists"#)
+                )
+                .bind(pet_id)
+                .bind(&self.clinic_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await?
+                .try_get::<bool, _>("pet_exists")?;
+
+                Ok(result)
             }
-        }
+            async fn get_services_history_of_pet(
+                &self,
+                pet_id: String,
+                start_date: String,
+                end_date: String,
+            ) -> Result<Vec<GetServicesHistoryModel>, sqlx::Error> {
+                if !self.check_pet_exists(&pet_id).await? {
+                    return Err(sqlx::Error::RowNotFound);
+                }
+                let rows = sqlx::query(&<$db>::sql(&format!(
+                    r#"
+                SELECT service_instance.*, service_type.service_type_name
+                FROM service_instance
+                LEFT JOIN service_type ON service_instance.service_instance_id = service_type.service_instance_id
+                WHERE service_instance.pet_id = ?
+                AND service_instance.service_date BETWEEN {date} AND {date}
+                ORDER BY service_instance.service_instance_id
+                "#,
+                    date = <$db>::text_as("DATE")
+                )))
+                    .bind(pet_id)
+                    .bind(start_date.clone())
+                    .bind(end_date.clone())
+                    .fetch_all(&*self.db)
+                    .await?;
 
-        let mut surgeries = Vec::new();
-        if let Some(surgery) = surgery {
-            let row = sqlx::query(&self.create_surgery)
-                .bind(surgery.surgery_name.clone())
-                .bind(surgery.veterinarian_diagnosis.clone())
-                .bind(surgery.anesthesia_used.clone())
-                .bind(surgery.complications.clone())
-                .bind(surgery.outcome.clone())
-                .bind(service_instance_id.clone())
-                .bind(surgery.vet_id.clone())
-                .execute(&mut *tx)
-                .await?;
+                let mut services = Vec::new();
+                let mut current_service: Option<GetServicesHistoryModel> = None;
+
+                for row in rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    // The LEFT JOIN leaves the name NULL for a visit without service types.
+                    let service_type_name: Option<String> = row.get("service_type_name");
+
+                    match current_service.as_mut() {
+                        Some(service) if service.service_instance_id == service_instance_id => {
+                            service.service_type.extend(service_type_name);
+                        }
+                        _ => {
+                            if let Some(service) = current_service.take() {
+                                services.push(service);
+                            }
+
+                            current_service = Some(GetServicesHistoryModel {
+                                service_instance_id: service_instance_id.clone(),
+                                service_date: row.get("service_date"),
+                                service_type: service_type_name.into_iter().collect(),
+                                service_reason: row.get("service_reason"),
+                                general_diagnosis: row.get("general_diagnosis"),
+                                requires_followup: row.get("requires_followup"),
+                                followup_date: row.get("followup_date"),
+                            });
+                        }
+                    }
+                }
 
-            let surgery_id = row.last_insert_id() as i32;
-            surgeries.push(Surgery {
-                surgery_id: Some(surgery_id),
-                surgery_name: surgery.surgery_name.clone(),
-                anesthesia_used: surgery.anesthesia_used,
-                veterinarian_diagnosis: surgery.veterinarian_diagnosis,
-                complications: surgery.complications,
-                outcome: surgery.outcome,
-                vet_id: surgery.vet_id,
-                service_instance_id: service_instance_id.clone(),
-            });
-        }
+                if let Some(service) = current_service {
+                    services.push(service);
+                }
 
-        tx.commit().await?;
-
-        Ok(ServiceInstance {
-            service_instance_id,
-            service_date,
-            service_type,
-            service_reason,
-            general_diagnosis,
-            requires_followup,
-            followup_date,
-            pet_id,
-            grooming: if groomings.is_empty() {
-                None
-            } else {
-                Some(groomings)
-            },
-            preventive_care: if preventive_cares.is_empty() {
-                None
-            } else {
-                Some(preventive_cares)
-            },
-            surgery: if surgeries.is_empty() {
-                None
-            } else {
-                Some(surgeries[0].clone())
-            },
-        })
-    }
+                Ok(services)
+            }
+            async fn get_all_service_instances(
+                &self,
+                filter: &ServiceInstanceFilterOptions,
+                limit: i32,
+                offset: i32,
+            ) -> Result<Vec<AllServiceInstanceModel>, sqlx::Error> {
+                let (where_clause, params) = service_instance_filters::<$db>(&self.clinic_id, filter);
+                let query_string = format!(
+                    "
+                SELECT si.service_instance_id, si.service_date,
+                       {} AS service_type,
+                       p.pet_id, p.pet_name, p.pet_type, p.pet_breed, o.owner_name
+                FROM service_instance si
+                JOIN pet p ON si.pet_id = p.pet_id
+                JOIN owner o ON p.owner_id = o.owner_id
+                LEFT JOIN service_type st ON si.service_instance_id = st.service_instance_id
+                {}
+                GROUP BY si.service_instance_id, p.pet_id, o.owner_id
+                ORDER BY {} {}, si.service_instance_id
+                LIMIT ? OFFSET ?
+                ",
+                    <$db>::string_agg("st.service_type_name", "st.service_type_id"),
+                    where_clause,
+                    filter.sort_by.unwrap_or_default().column(),
+                    filter.order.unwrap_or_default().keyword()
+                );
+
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+                for param in &params {
+                    query = query.bind(param);
+                }
+                let rows = query.bind(limit).bind(offset).fetch_all(&*self.db).await?;
+
+                let mut service_instances = Vec::new();
+                for row in rows {
+                    let service_instance = AllServiceInstanceModel {
+                        service_instance_id: row.try_get("service_instance_id")?,
+                        service_date: row.try_get("service_date")?,
+                        service_type: row
+                            .try_get::<Option<String>, _>("service_type")?
+                            .map(|service_types| {
+                                service_types
+                                    .split(',')
+                                    .map(|service_type| service_type.trim().to_string())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        pet: SimplePetModel {
+                            pet_id: row.try_get("pet_id")?,
+                            pet_name: row.try_get("pet_name")?,
+                            pet_type: row.try_get("pet_type")?,
+                            pet_breed: row.try_get("pet_breed")?,
+                            owner_name: row.try_get("owner_name")?,
+                        },
+                    };
+                    service_instances.push(service_instance);
+                }
 
-    pub async fn check_pet_exists(&self, pet_id: &str) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("SELECT EXISTS(SELECT 1 FROM pet WHERE pet_id = ?) AS `exists`")
-            .bind(pet_id)
-            .fetch_one(&*self.db)
-            .await?
-            .try_get::<bool, _>("exists")?;
+                Ok(service_instances)
+            }
 
-        Ok(result)
-    }
-    pub async fn get_services_history_of_pet(
-        &self,
-        pet_id: String,
-        start_date: String,
-        end_date: String,
-    ) -> Result<Vec<GetServicesHistoryModel>, sqlx::Error> {
-        if !self.check_pet_exists(&pet_id).await? {
-            return Err(sqlx::Error::RowNotFound);
-        }
-        let rows = sqlx::query (
-            r#"
-        SELECT service_instance.*, service_type.service_type_name
-        FROM service_instance
-        LEFT JOIN service_type ON service_instance.service_instance_id = service_type.service_instance_id
-        WHERE service_instance.pet_id = ?
-        AND service_instance.service_date BETWEEN ? AND ?
-        ORDER BY service_instance.service_instance_id
-        "#,
-        )
-            .bind(pet_id)
-            .bind(start_date.clone())
-            .bind(end_date.clone())
-            .fetch_all(&*self.db)
-            .await?;
-
-        let mut services = Vec::new();
-        let mut current_service: Option<GetServicesHistoryModel> = None;
-
-        for row in rows {
-            let service_instance_id: String = row.get("service_instance_id");
-            let service_type_name: String = row.get("service_type_name");
-
-            match current_service.as_mut() {
-                Some(service) if service.service_instance_id == service_instance_id => {
-                    service.service_type.push(service_type_name);
-                }
-                _ => {
-                    if let Some(service) = current_service.take() {
-                        services.push(service);
-                    }
+            async fn count_all_service_instances(
+                &self,
+                filter: &ServiceInstanceFilterOptions,
+            ) -> Result<i64, sqlx::Error> {
+                let (where_clause, params) = service_instance_filters::<$db>(&self.clinic_id, filter);
+                let query_string = format!(
+                    "SELECT COUNT(*) AS count
+                FROM service_instance si
+                JOIN pet p ON si.pet_id = p.pet_id
+                {}",
+                    where_clause
+                );
+
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+                for param in &params {
+                    query = query.bind(param);
+                }
+                query
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get("count"))
+            }
 
-                    current_service = Some(GetServicesHistoryModel {
-                        service_instance_id: service_instance_id.clone(),
+            async fn get_specific_instances(
+                &self,
+                service_instance_ids: &[String],
+            ) -> Result<Vec<ServiceInstanceModel>, sqlx::Error> {
+                if service_instance_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let instance_placeholders = placeholders(service_instance_ids.len());
+
+                let sql = format!(
+                    r#"SELECT * FROM service_instance WHERE service_instance_id IN ({})
+                    AND (clinic_id = ? OR pet_id IN (SELECT pet_id FROM pet_share WHERE clinic_id = ?))"#,
+                    instance_placeholders
+                );
+                let instance_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), service_instance_ids)
+                    .bind(&self.clinic_id)
+                    .bind(&self.clinic_id)
+                    .fetch_all(&*self.db)
+                    .await?;
+
+                let mut service_instances = Vec::new();
+                let mut positions = HashMap::new();
+                for row in instance_rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    positions.insert(service_instance_id.clone(), service_instances.len());
+                    service_instances.push(ServiceInstanceModel {
+                        service_instance_id,
                         service_date: row.get("service_date"),
-                        service_type: vec![service_type_name],
+                        service_type: Vec::new(),
                         service_reason: row.get("service_reason"),
                         general_diagnosis: row.get("general_diagnosis"),
+                        diagnosis_code: row.get("diagnosis_code"),
                         requires_followup: row.get("requires_followup"),
                         followup_date: row.get("followup_date"),
+                        pet_id: row.get("pet_id"),
+                        grooming: None,
+                        preventive_care: None,
+                        surgery: Some(Vec::new()),
+                        row_version: row.get("row_version"),
                     });
                 }
-            }
-        }
 
-        if let Some(service) = current_service {
-            services.push(service);
-        }
+                let sql = format!(
+                    "SELECT service_instance_id, service_type_name FROM service_type WHERE service_instance_id IN ({}) ORDER BY service_type_id",
+                    instance_placeholders
+                );
+                let service_type_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), service_instance_ids)
+                    .fetch_all(&*self.db)
+                    .await?;
 
-        Ok(services)
-    }
-    pub async fn get_all_service_instances(
-        &self,
-    ) -> Result<Vec<AllServiceInstanceModel>, sqlx::Error> {
-        let rows = sqlx::query(
-            "
-        SELECT si.service_instance_id, si.service_date,
-               GROUP_CONCAT(st.service_type_name SEPARATOR ', ') AS service_type,
-               p.pet_id, p.pet_name, p.pet_type, p.pet_breed, o.owner_name
-        FROM service_instance si
-        JOIN pet p ON si.pet_id = p.pet_id
-        JOIN owner o ON p.owner_id = o.owner_id
-        JOIN service_type st ON si.service_instance_id = st.service_instance_id
-        GROUP BY si.service_instance_id, p.pet_id, o.owner_id
-        ORDER BY si.service_date DESC
-        ",
-        )
-        .fetch_all(&*self.db)
-        .await?;
-
-        let mut service_instances = Vec::new();
-        for row in rows {
-            let service_instance = AllServiceInstanceModel {
-                service_instance_id: row.try_get("service_instance_id")?,
-                service_date: row.try_get("service_date")?,
-                service_type: row
-                    .try_get::<String, _>("service_type")?
-                    .split(',')
-                    .map(String::from)
-                    .collect(),
-                pet: SimplePetModel {
-                    pet_id: row.try_get("pet_id")?,
-                    pet_name: row.try_get("pet_name")?,
-                    pet_type: row.try_get("pet_type")?,
-                    pet_breed: row.try_get("pet_breed")?,
-                    owner_name: row.try_get("owner_name")?,
-                },
-            };
-            service_instances.push(service_instance);
-        }
+                for row in service_type_rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    if let Some(&position) = positions.get(&service_instance_id) {
+                        service_instances[position]
+                            .service_type
+                            .push(row.get("service_type_name"));
+                    }
+                }
 
-        Ok(service_instances)
-    }
+                let sql = format!(
+                    "SELECT * FROM grooming WHERE service_instance_id IN ({}) ORDER BY grooming_id",
+                    instance_placeholders
+                );
+                let grooming_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), service_instance_ids)
+                    .fetch_all(&*self.db)
+                    .await?;
 
-    pub async fn count_all_service_instances(&self) -> Result<i64, sqlx::Error> {
-        let query = String::from("SELECT COUNT(*) as count FROM service_instance");
+                for row in grooming_rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    if let Some(&position) = positions.get(&service_instance_id) {
+                        service_instances[position]
+                            .grooming
+                            .get_or_insert_with(Vec::new)
+                            .push(GroomingModel {
+                                grooming_id: row.get("grooming_id"),
+                                grooming_type: row.get("grooming_type"),
+                            });
+                    }
+                }
 
-        let count = sqlx::query(&query);
+                let sql = format!(
+                    "SELECT * FROM preventive_care WHERE service_instance_id IN ({}) ORDER BY preventive_care_id",
+                    instance_placeholders
+                );
+                let preventive_care_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), service_instance_ids)
+                    .fetch_all(&*self.db)
+                    .await?;
 
-        count
-            .fetch_one(&*self.db)
-            .await
-            .map(|row: sqlx::mysql::MySqlRow| row.get("count"))
-    }
-    pub async fn get_specific_instance(
-        &self,
-        service_instance_id: String,
-    ) -> Result<ServiceInstanceModel, sqlx::Error> {
-        let row = sqlx::query(&self.get_specific_service_instance)
-            .bind(service_instance_id.clone())
-            .fetch_one(&*self.db)
-            .await?;
-
-        let service_instance = ServiceInstanceModel {
-            service_instance_id: row.get("service_instance_id"),
-            service_date: row.get("service_date"),
-            service_type: Vec::new(),
-            service_reason: row.get("service_reason"),
-            general_diagnosis: row.get("general_diagnosis"),
-            requires_followup: row.get("requires_followup"),
-            followup_date: row.get("followup_date"),
-            pet_id: row.get("pet_id"),
-            grooming: None,
-            preventive_care: None,
-            surgery: None,
-        };
-
-        let service_type_rows = sqlx::query(&self.get_service_instance_type)
-            .bind(&service_instance_id)
-            .fetch_all(&*self.db)
-            .await?;
-
-        let mut service_types = Vec::new();
-        for row in service_type_rows {
-            service_types.push(row.get("service_type_name"));
-        }
+                let sql = format!(
+                    "SELECT * FROM surgery WHERE service_instance_id IN ({}) ORDER BY surgery_id",
+                    instance_placeholders
+                );
+                let surgery_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), service_instance_ids)
+                    .fetch_all(&*self.db)
+                    .await?;
 
-        let grooming_rows = sqlx::query(&self.get_grooming_of_service_instance)
-            .bind(&service_instance_id)
-            .fetch_all(&*self.db)
-            .await?;
-
-        let mut groomings = Vec::new();
-        for row in grooming_rows {
-            groomings.push(GroomingModel {
-                grooming_id: row.get("grooming_id"),
-                grooming_type: row.get("grooming_type"),
-            });
-        }
+                let vet_ids: Vec<String> = preventive_care_rows
+                    .iter()
+                    .chain(surgery_rows.iter())
+                    .map(|row| row.get::<String, _>("vet_id"))
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+
+                let mut vets = HashMap::new();
+                if !vet_ids.is_empty() {
+                    let vet_placeholders = placeholders(vet_ids.len());
+                    let sql = format!(
+                        "SELECT * FROM veterinarian WHERE vet_id IN ({})",
+                        vet_placeholders
+                    );
+                    let vet_rows = bind_all(sqlx::query(&<$db>::sql(&sql)), &vet_ids)
+                        .fetch_all(&*self.db)
+                        .await?;
+
+                    for vet_row in vet_rows {
+                        let vet = VetModelForService {
+                            vet_id: vet_row.get("vet_id"),
+                            vet_name: vet_row.get("vet_name"),
+                            vet_email: vet_row.get("vet_email"),
+                            vet_phone_number: vet_row.get("vet_phone_number"),
+                            vet_license_number: vet_row.get("vet_license_number"),
+                        };
+                        vets.insert(vet.vet_id.clone(), vet);
+                    }
+                }
+                let vet_of = |row: &<$db as Database>::Row| {
+                    vets.get(&row.get::<String, _>("vet_id"))
+                        .cloned()
+                        .ok_or(sqlx::Error::RowNotFound)
+                };
+
+                for row in &preventive_care_rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    if let Some(&position) = positions.get(&service_instance_id) {
+                        let preventive_care = PreventiveCareModel {
+                            preventive_care_id: row.get("preventive_care_id"),
+                            treatment: row.get("treatment"),
+                            treatment_code: row.get("treatment_code"),
+                            vet: vet_of(row)?,
+                        };
+                        service_instances[position]
+                            .preventive_care
+                            .get_or_insert_with(Vec::new)
+                            .push(preventive_care);
+                    }
+                }
 
-        let preventive_care_rows = sqlx::query(&self.get_preventive_care_of_service_instance)
-            .bind(&service_instance_id)
-            .fetch_all(&*self.db)
-            .await?;
+                for row in &surgery_rows {
+                    let service_instance_id: String = row.get("service_instance_id");
+                    if let Some(&position) = positions.get(&service_instance_id) {
+                        let surgery = SurgeryModel {
+                            surgery_id: row.get("surgery_id"),
+                            surgery_name: row.get("surgery_name"),
+                            surgery_code: row.get("surgery_code"),
+                            veterinarian_diagnosis: row.get("veterinarian_diagnosis"),
+                            anesthesia_used: row.get("anesthesia_used"),
+                            complications: row.get("complications"),
+                            outcome: row.get("outcome"),
+                            vet: vet_of(row)?,
+                            row_version: row.get("row_version"),
+                        };
+                        service_instances[position]
+                            .surgery
+                            .get_or_insert_with(Vec::new)
+                            .push(surgery);
+                    }
+                }
 
-        let mut preventive_cares = Vec::new();
-        for row in preventive_care_rows {
-            let vet_id: String = row.get("vet_id");
-            let vet_row = sqlx::query("SELECT * FROM veterinarian WHERE vet_id = ?")
-                .bind(&vet_id)
-                .fetch_one(&*self.db)
-                .await?;
-            let vet = VetModelForService {
-                vet_id: vet_row.get("vet_id"),
-                vet_name: vet_row.get("vet_name"),
-                vet_email: vet_row.get("vet_email"),
-                vet_phone_number: vet_row.get("vet_phone_number"),
-                vet_license_number: vet_row.get("vet_license_number"),
-            };
-            preventive_cares.push(PreventiveCareModel {
-                preventive_care_id: row.get("preventive_care_id"),
-                treatment: row.get("treatment"),
-                vet,
-            });
-        }
+                service_instances.sort_by_key(|service_instance| {
+                    service_instance_ids
+                        .iter()
+                        .position(|id| *id == service_instance.service_instance_id)
+                });
 
-        let surgery_rows = sqlx::query(&self.get_surgery_of_service_instance)
-            .bind(&service_instance_id)
-            .fetch_all(&*self.db)
-            .await?;
+                Ok(service_instances)
+            }
 
-        let mut surgeries = Vec::new();
+            async fn delete_grooming(&self, grooming_id: i32) -> Result<u64, sqlx::Error> {
+                let row = sqlx::query(
+                    &<$db>::sql(r#"DELETE FROM grooming WHERE grooming_id = ?
+                    AND service_instance_id IN (SELECT service_instance_id FROM service_instance WHERE clinic_id = ?)"#)
+                )
+                    .bind(grooming_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await?;
 
-        for row in surgery_rows {
-            let vet_id: String = row.get("vet_id");
-            let vet_row = sqlx::query("SELECT * FROM veterinarian WHERE vet_id = ?")
-                .bind(&vet_id)
-                .fetch_one(&*self.db)
-                .await?;
-            let vet = VetModelForService {
-                vet_id: vet_row.get("vet_id"),
-                vet_name: vet_row.get("vet_name"),
-                vet_email: vet_row.get("vet_email"),
-                vet_phone_number: vet_row.get("vet_phone_number"),
-                vet_license_number: vet_row.get("vet_license_number"),
-            };
-            surgeries.push(SurgeryModel {
-                surgery_id: row.get("surgery_id"),
-                surgery_name: row.get("surgery_name"),
-                veterinarian_diagnosis: row.get("veterinarian_diagnosis"),
-                anesthesia_used: row.get("anesthesia_used"),
-                complications: row.get("complications"),
-                outcome: row.get("outcome"),
-                vet,
-            });
-        }
+                Ok(row.rows_affected())
+            }
 
-        let service_instance = ServiceInstanceModel {
-            service_type: service_types,
-            grooming: if groomings.is_empty() {
-                None
-            } else {
-                Some(groomings)
-            },
-            preventive_care: if preventive_cares.is_empty() {
-                None
-            } else {
-                Some(preventive_cares)
-            },
-            surgery: Option::from(surgeries),
-            ..service_instance
-        };
-
-        Ok(service_instance)
-    }
+            async fn delete_preventive_care(&self, preventive_care_id: i32) -> Result<u64, sqlx::Error> {
+                let row = sqlx::query(
+                    &<$db>::sql(r#"DELETE FROM preventive_care WHERE preventive_care_id = ?
+                    AND service_instance_id IN (SELECT service_instance_id FROM service_instance WHERE clinic_id = ?)"#)
+                )
+                    .bind(preventive_care_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await?;
+
+                Ok(row.rows_affected())
+            }
+            async fn delete_surgery(&self, surgery_id: i32) -> Result<u64, sqlx::Error> {
+                let row = sqlx::query(
+                    &<$db>::sql(r#"DELETE FROM surgery WHERE surgery_id = ?
+                    AND service_instance_id IN (SELECT service_instance_id FROM service_instance WHERE clinic_id = ?)"#)
+                )
+                    .bind(surgery_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await?;
 
-    pub async fn delete_grooming(&self, grooming_id: i32) -> Result<u64, sqlx::Error> {
-        let row = sqlx::query("DELETE FROM grooming WHERE grooming_id = ?")
-            .bind(grooming_id)
-            .execute(&*self.db)
-            .await?;
+                Ok(row.rows_affected())
+            }
 
-        Ok(row.rows_affected())
-    }
+            async fn delete_service_instance(
+                &self,
+                service_instance_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                let row = sqlx::query(
+                    &<$db>::sql("DELETE FROM service_instance WHERE service_instance_id = ? AND clinic_id = ?")
+                )
+                .bind(service_instance_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await?;
 
-    pub async fn delete_preventive_care(
-        &self,
-        preventive_care_id: i32,
-    ) -> Result<u64, sqlx::Error> {
-        let row = sqlx::query("DELETE FROM preventive_care WHERE preventive_care_id = ?")
-            .bind(preventive_care_id)
-            .execute(&*self.db)
-            .await?;
+                Ok(row.rows_affected())
+            }
 
-        Ok(row.rows_affected())
-    }
-    pub async fn delete_surgery(&self, surgery_id: i32) -> Result<u64, sqlx::Error> {
-        let row = sqlx::query("DELETE FROM surgery WHERE surgery_id = ?")
-            .bind(surgery_id)
-            .execute(&*self.db)
-            .await?;
+            async fn select_surgery_version(&self, surgery_id: i32) -> Result<i32, sqlx::Error> {
+                sqlx::query_scalar(
+                    &<$db>::sql(r#"SELECT row_version FROM surgery WHERE surgery_id = ?
+                    AND service_instance_id IN (SELECT service_instance_id FROM service_instance WHERE clinic_id = ?)"#)
+                )
+                    .bind(surgery_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
 
-        Ok(row.rows_affected())
-    }
+            async fn select_service_instance_version(
+                &self,
+                service_instance_id: String,
+            ) -> Result<i32, sqlx::Error> {
+                sqlx::query_scalar(
+                    &<$db>::sql("SELECT row_version FROM service_instance WHERE service_instance_id = ? AND clinic_id = ?")
+                )
+                    .bind(service_instance_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
 
-    pub async fn delete_service_instance(
-        &self,
-        service_instance_id: String,
-    ) -> Result<u64, sqlx::Error> {
-        let row = sqlx::query("DELETE FROM service_instance WHERE service_instance_id = ?")
-            .bind(service_instance_id)
-            .execute(&*self.db)
-            .await?;
+            async fn update_surgery(
+                &self,
+                update_surgery: UpdateSurgery,
+                surgery_id: i32,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let renamed = update_surgery.surgery_name.is_some();
+                let mut query_string = String::from("UPDATE surgery SET ");
+                let mut params = Vec::new();
+
+                if let Some(surgery_name) = update_surgery.surgery_name {
+                    query_string.push_str("surgery_name = ?, ");
+                    params.push(surgery_name);
+                }
 
-        Ok(row.rows_affected())
-    }
+                match update_surgery.surgery_code {
+                    Some(surgery_code) => {
+                        query_string.push_str("surgery_code = ?, ");
+                        params.push(surgery_code);
+                    }
+                    None if renamed => query_string.push_str("surgery_code = NULL, "),
+                    None => {}
+                }
 
-    pub async fn update_surgery(
-        &self,
-        update_surgery: UpdateSurgery,
-        surgery_id: i32,
-    ) -> Result<u64, sqlx::Error> {
-        let mut query_string = String::from("UPDATE surgery SET ");
-        let mut params = Vec::new();
-
-        if let Some(surgery_name) = update_surgery.surgery_name {
-            query_string.push_str("surgery_name = ?, ");
-            params.push(surgery_name);
-        }
+                if let Some(anesthesia_used) = update_surgery.anesthesia_used {
+                    query_string.push_str("anesthesia_used = ?, ");
+                    params.push(anesthesia_used);
+                }
 
-        if let Some(anesthesia_used) = update_surgery.anesthesia_used {
-            query_string.push_str("anesthesia_used = ?, ");
-            params.push(anesthesia_used);
-        }
+                if let Some(veterinarian_diagnosis) = update_surgery.veterinarian_diagnosis {
+                    query_string.push_str("veterinarian_diagnosis = ?, ");
+                    params.push(veterinarian_diagnosis);
+                }
 
-        if let Some(veterinarian_diagnosis) = update_surgery.veterinarian_diagnosis {
-            query_string.push_str("veterinarian_diagnosis = ?, ");
-            params.push(veterinarian_diagnosis);
-        }
+                if let Some(complications) = update_surgery.complications {
+                    query_string.push_str("complications = ?, ");
+                    params.push(complications);
+                }
 
-        if let Some(complications) = update_surgery.complications {
-            query_string.push_str("complications = ?, ");
-            params.push(complications);
-        }
+                if let Some(outcome) = update_surgery.outcome {
+                    query_string.push_str("outcome = ?, ");
+                    params.push(outcome);
+                }
 
-        if let Some(outcome) = update_surgery.outcome {
-            query_string.push_str("outcome = ?, ");
-            params.push(outcome);
-        }
+                if let Some(vet_id) = update_surgery.vet_id {
+                    query_string.push_str("vet_id = ?, ");
+                    params.push(vet_id);
+                }
 
-        if let Some(vet_id) = update_surgery.vet_id {
-            query_string.push_str("vet_id = ?, ");
-            params.push(vet_id);
-        }
+                query_string.push_str("row_version = row_version + 1");
+
+                query_string.push_str(&format!(" WHERE surgery_id = {}", <$db>::text_as("INT")));
+                params.push(surgery_id.to_string());
+                query_string.push_str(
+                    " AND service_instance_id IN (SELECT service_instance_id FROM service_instance WHERE clinic_id = ?)",
+                );
+                params.push(self.clinic_id.clone());
+                if let Some(version) = expected_version {
+                    query_string.push_str(&format!(" AND row_version = {}", <$db>::text_as("INT")));
+                    params.push(version.to_string());
+                }
 
-        if query_string.ends_with(", ") {
-            query_string.truncate(query_string.len() - 2);
-        }
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+                for param in params {
+                    query = query.bind(param);
+                }
 
-        query_string.push_str(" WHERE surgery_id = ?");
-        params.push(surgery_id.to_string());
+                let result = query.execute(&*self.db).await?;
+                Ok(result.rows_affected())
+            }
 
-        let mut query = sqlx::query(&query_string);
-        for param in params {
-            query = query.bind(param);
-        }
+            async fn update_service_instance(
+                &self,
+                update_service_instance: UpdateServiceInstance,
+                service_instance_id: String,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let rediagnosed = update_service_instance.general_diagnosis.is_some();
+                let mut query_string = String::from("UPDATE service_instance SET ");
+                let mut params = Vec::new();
+
+                if let Some(service_date) = update_service_instance.service_date {
+                    query_string.push_str(&format!("service_date = {}, ", <$db>::text_as("DATE")));
+                    params.push(service_date.to_string());
+                }
 
-        let result = query.execute(&*self.db).await?;
-        Ok(result.rows_affected())
-    }
+                if let Some(service_reason) = update_service_instance.service_reason {
+                    query_string.push_str("service_reason = ?, ");
+                    params.push(service_reason);
+                }
 
-    pub async fn update_service_instance(
-        &self,
-        update_service_instance: UpdateServiceInstance,
-        service_instance_id: String,
-    ) -> Result<u64, sqlx::Error> {
-        let mut query_string = String::from("UPDATE service_instance SET ");
-        let mut params = Vec::new();
+                if let Some(general_diagnosis) = update_service_instance.general_diagnosis {
+                    query_string.push_str("general_diagnosis = ?, ");
+                    params.push(general_diagnosis);
+                }
 
-        let mut tx = self.db.begin().await?;
+                match update_service_instance.diagnosis_code {
+                    Some(diagnosis_code) => {
+                        query_string.push_str("diagnosis_code = ?, ");
+                        params.push(diagnosis_code);
+                    }
+                    None if rediagnosed => query_string.push_str("diagnosis_code = NULL, "),
+                    None => {}
+                }
 
-        if let Some(service_types) = update_service_instance.service_type {
-            sqlx::query("DELETE FROM service_type WHERE service_instance_id = ?")
-                .bind(&service_instance_id)
-                .execute(&mut *tx)
-                .await?;
+                if let Some(requires_followup) = update_service_instance.requires_followup {
+                    let requires_followup_int = if requires_followup { 1 } else { 0 };
+                    query_string.push_str(&format!("requires_followup = {}, ", <$db>::text_as("BOOLEAN")));
+                    params.push(requires_followup_int.to_string());
 
-            for service_type in service_types {
-                sqlx::query(&self.create_service_instance_type)
-                    .bind(service_type.clone())
-                    .bind(service_instance_id.clone())
-                    .execute(&mut *tx)
-                    .await?;
-            }
-        }
+                    if !requires_followup {
+                        query_string.push_str("followup_date = NULL, ");
+                    }
+                }
 
-        tx.commit().await?;
+                if let Some(followup_date) = update_service_instance.followup_date {
+                    if update_service_instance.requires_followup != Some(false) {
+                        query_string.push_str(&format!("followup_date = {}, ", <$db>::text_as("DATE")));
+                        params.push(followup_date.to_string());
+                    }
+                }
 
-        if let Some(service_date) = update_service_instance.service_date {
-            query_string.push_str("service_date = ?, ");
-            params.push(service_date.to_string());
-        }
+                query_string.push_str("row_version = row_version + 1");
 
-        if let Some(service_reason) = update_service_instance.service_reason {
-            query_string.push_str("service_reason = ?, ");
-            params.push(service_reason);
-        }
+                query_string.push_str(" WHERE service_instance_id = ? AND clinic_id = ?");
+                params.push(service_instance_id.clone());
+                params.push(self.clinic_id.clone());
+                if let Some(version) = expected_version {
+                    query_string.push_str(&format!(" AND row_version = {}", <$db>::text_as("INT")));
+                    params.push(version.to_string());
+                }
 
-        if let Some(general_diagnosis) = update_service_instance.general_diagnosis {
-            query_string.push_str("general_diagnosis = ?, ");
-            params.push(general_diagnosis);
-        }
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+                for param in params {
+                    query = query.bind(param);
+                }
 
-        if let Some(requires_followup) = update_service_instance.requires_followup {
-            let requires_followup_int = if requires_followup { 1 } else { 0 };
-            query_string.push_str("requires_followup = ?, ");
-            params.push(requires_followup_int.to_string());
+                let mut tx = self.db.begin().await?;
+                let rows_affected = query.execute(&mut *tx).await?.rows_affected();
+                if rows_affected == 0 {
+                    return Ok(0);
+                }
 
-            if !requires_followup {
-                query_string.push_str("followup_date = NULL, ");
-            }
-        }
+                if let Some(service_types) = update_service_instance.service_type {
+                    sqlx::query(&<$db>::sql("DELETE FROM service_type WHERE service_instance_id = ?"))
+                        .bind(&service_instance_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    for service_type in service_types {
+                        sqlx::query(&<$db>::sql(CREATE_SERVICE_INSTANCE_TYPE))
+                            .bind(service_type.clone())
+                            .bind(service_instance_id.clone())
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                }
 
-        if let Some(followup_date) = update_service_instance.followup_date {
-            if update_service_instance.requires_followup != Some(false) {
-                query_string.push_str("followup_date = ?, ");
-                params.push(followup_date.to_string());
-            }
-        }
+                tx.commit().await?;
 
-        if query_string.ends_with(", ") {
-            query_string.truncate(query_string.len() - 2);
-        }
+                Ok(rows_affected)
+            }
 
-        query_string.push_str(" WHERE service_instance_id = ?");
-        params.push(service_instance_id);
+            async fn add_surgery(
+                &self,
+                add_surgery: AddSurgery,
+                service_instance_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                let row = sqlx::query(&<$db>::sql(CREATE_SURGERY))
+                    .bind(add_surgery.surgery_name.clone())
+                    .bind(add_surgery.surgery_code.clone())
+                    .bind(add_surgery.veterinarian_diagnosis.clone())
+                    .bind(add_surgery.anesthesia_used.clone())
+                    .bind(add_surgery.complications.clone())
+                    .bind(add_surgery.outcome.clone())
+                    .bind(add_surgery.vet_id.clone())
+                    .bind(service_instance_id.clone())
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await?;
+                if row.rows_affected() == 0 {
+                    return Err(sqlx::Error::RowNotFound);
+                }
+                Ok(row.rows_affected())
+            }
 
-        let mut query = sqlx::query(&query_string);
-        for param in params {
-            query = query.bind(param);
+            async fn add_preventive_care(
+                &self,
+                add_preventive_care_to_existing: AddPreventiveCareToExisting,
+                service_instance_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                let mut total_rows_affected = 0;
+                for (treatment, treatment_code) in add_preventive_care_to_existing.coded_treatments() {
+                    let row = sqlx::query(&<$db>::sql(CREATE_PREVENTIVE_CARE))
+                        .bind(treatment)
+                        .bind(treatment_code)
+                        .bind(add_preventive_care_to_existing.vet_id.clone())
+                        .bind(service_instance_id.clone())
+                        .bind(&self.clinic_id)
+                        .execute(&*self.db)
+                        .await?;
+                    if row.rows_affected() == 0 {
+                        return Err(sqlx::Error::RowNotFound);
+                    }
+                    total_rows_affected += row.rows_affected();
+                }
+                Ok(total_rows_affected)
+            }
+            async fn add_grooming(
+                &self,
+                service_instance_id: String,
+                grooming_types: Vec<String>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut total_rows_affected = 0;
+                for grooming_type in grooming_types {
+                    let row = sqlx::query(&<$db>::sql(CREATE_GROOMING))
+                        .bind(grooming_type)
+                        .bind(&service_instance_id)
+                        .bind(&self.clinic_id)
+                        .execute(&*self.db)
+                        .await?;
+                    if row.rows_affected() == 0 {
+                        return Err(sqlx::Error::RowNotFound);
+                    }
+                    total_rows_affected += row.rows_affected();
+                }
+                Ok(total_rows_affected)
+            }
         }
+    };
+}
 
-        let result = query.execute(&*self.db).await?;
-
-        Ok(result.rows_affected())
+service_instance_queries!(MySql);
+service_instance_queries!(Postgres);
+service_instance_queries!(Sqlite);
+
+/// Builds the `WHERE` clause of the service instance feed and the values to bind, in order.
+fn service_instance_filters<DB: Dialect>(
+    clinic_id: &str,
+    filter: &ServiceInstanceFilterOptions,
+) -> (String, Vec<String>) {
+    let mut conditions = vec!["si.clinic_id = ?".to_string()];
+    let mut params = vec![clinic_id.to_string()];
+
+    if let Some(start_date) = &filter.start_date {
+        params.push(start_date.clone());
+        conditions.push(format!("si.service_date >= {}", DB::text_as("DATE")));
     }
-
-    pub async fn add_surgery(
-        &self,
-        add_surgery: AddSurgery,
-        service_instance_id: String,
-    ) -> Result<u64, sqlx::Error> {
-        let row = sqlx::query(&self.create_surgery)
-            .bind(add_surgery.surgery_name.clone())
-            .bind(add_surgery.anesthesia_used.clone())
-            .bind(add_surgery.complications.clone())
-            .bind(add_surgery.outcome.clone())
-            .bind(service_instance_id.clone())
-            .bind(add_surgery.vet_id.clone())
-            .execute(&*self.db)
-            .await?;
-        Ok(row.rows_affected())
+    if let Some(end_date) = &filter.end_date {
+        params.push(end_date.clone());
+        conditions.push(format!("si.service_date <= {}", DB::text_as("DATE")));
     }
-
-    pub async fn add_preventive_care(
-        &self,
-        add_preventive_care_to_existing: AddPreventiveCareToExisting,
-        service_instance_id: String,
-    ) -> Result<u64, sqlx::Error> {
-        let mut total_rows_affected = 0;
-        for treatment in add_preventive_care_to_existing.treatment {
-            let row = sqlx::query(&self.create_preventive_care)
-                .bind(treatment)
-                .bind(add_preventive_care_to_existing.vet_id.clone())
-                .bind(service_instance_id.clone())
-                .execute(&*self.db)
-                .await?;
-            total_rows_affected += row.rows_affected();
-        }
-        Ok(total_rows_affected)
+    if let Some(pet_id) = &filter.pet_id {
+        params.push(pet_id.clone());
+        conditions.push("si.pet_id = ?".to_string());
+    }
+    if let Some(owner_id) = &filter.owner_id {
+        params.push(owner_id.clone());
+        conditions.push("p.owner_id = ?".to_string());
+    }
+    if let Some(pet_type) = &filter.pet_type {
+        params.push(pet_type.clone());
+        conditions.push("p.pet_type = ?".to_string());
+    }
+    if let Some(service_type) = &filter.service_type {
+        params.push(service_type.clone());
+        conditions.push(
+            "EXISTS (SELECT 1 FROM service_type st2 \
+                WHERE st2.service_instance_id = si.service_instance_id \
+                AND st2.service_type_name = ?)"
+                .to_string(),
+        );
     }
-    pub async fn add_grooming(
-        &self,
-        service_instance_id: String,
-        grooming_types: Vec<String>,
-    ) -> Result<u64, sqlx::Error> {
-        let mut total_rows_affected = 0;
-        for grooming_type in grooming_types {
-            let row = sqlx::query(
-                "INSERT INTO grooming (grooming_type, service_instance_id) VALUES  (?, ?)",
-            )
-            .bind(grooming_type)
-            .bind(&service_instance_id)
-            .execute(&*self.db)
-            .await?;
-            total_rows_affected += row.rows_affected();
+    if let Some(vet_id) = &filter.vet_id {
+        params.push(vet_id.clone());
+        params.push(vet_id.clone());
+        conditions.push(
+            "(EXISTS (SELECT 1 FROM preventive_care pc \
+                WHERE pc.service_instance_id = si.service_instance_id AND pc.vet_id = ?) \
+                OR EXISTS (SELECT 1 FROM surgery su \
+                WHERE su.service_instance_id = si.service_instance_id AND su.vet_id = ?))"
+                .to_string(),
+        );
+    }
+    match filter.requires_followup {
+        Some(true) => {
+            conditions.push("si.requires_followup = TRUE".to_string());
+            // Nobody is reminded about a pet that died or whose owner mustn't be contacted.
+            conditions.push("p.pet_status <> 'deceased' AND p.memorial_safe = FALSE".to_string());
         }
-        Ok(total_rows_affected)
+        Some(false) => conditions
+            .push("(si.requires_followup IS NULL OR si.requires_followup = FALSE)".to_string()),
+        None => {}
+    }
+
+    (format!("WHERE {}", conditions.join(" AND ")), params)
+}
+
+fn bind_all<'q, DB: Database>(
+    mut query: Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+    values: &'q [String],
+) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments>
+where
+    &'q str: Encode<'q, DB> + Type<DB>,
+{
+    for value in values {
+        query = query.bind(value.as_str());
     }
+    query
 }
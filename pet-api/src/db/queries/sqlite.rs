@@ -0,0 +1,73 @@
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use sqlx::database::HasArguments;
+use sqlx::error::BoxDynError;
+use sqlx::query::Query;
+use sqlx::sqlite::{SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Executor, Sqlite, Type};
+
+use crate::db::queries::dialect::Dialect;
+use crate::models::pet_model::PetWeight;
+
+impl Dialect for Sqlite {
+    const CURRENT_DATE: &'static str = "date('now', 'localtime')";
+    const ILIKE: &'static str = "LIKE";
+    const BIGINT: &'static str = "BIGINT";
+    // SQLite has no row locks. Taking the write lock up front makes concurrent writers wait
+    // until this transaction commits, where a deferred one would only take it at its first
+    // write, after it has read what it checks.
+    const BEGIN_WRITE: &'static str = "BEGIN IMMEDIATE";
+    const FOR_UPDATE: &'static str = "";
+
+    fn string_agg(expression: &str, order_by: &str) -> String {
+        format!("GROUP_CONCAT({}, ', ' ORDER BY {})", expression, order_by)
+    }
+
+    fn on_conflict(key: &str, columns: &[&str]) -> String {
+        if columns.is_empty() {
+            return format!("ON CONFLICT ({}) DO NOTHING", key);
+        }
+
+        let updates: Vec<_> = columns
+            .iter()
+            .map(|column| format!("{0} = excluded.{0}", column))
+            .collect();
+        format!("ON CONFLICT ({}) DO UPDATE SET {}", key, updates.join(", "))
+    }
+
+    async fn generated_id<'q, 'e>(
+        query: Query<'q, Self, <Self as HasArguments<'q>>::Arguments>,
+        executor: impl Executor<'e, Database = Self> + 'q,
+    ) -> Result<i32, sqlx::Error>
+    where
+        'q: 'e,
+    {
+        let done = query.execute(executor).await?;
+        if done.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(done.last_insert_rowid() as i32)
+    }
+}
+
+// `pet_weight` is a REAL, rounded to the two decimal places the other backends keep.
+impl Type<Sqlite> for PetWeight {
+    fn type_info() -> SqliteTypeInfo {
+        <f64 as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <f64 as Type<Sqlite>>::compatible(ty) || <i64 as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for PetWeight {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let weight = <f64 as Decode<Sqlite>>::decode(value)?;
+
+        Decimal::from_f64(weight)
+            .map(|weight| PetWeight(weight.round_dp(2)))
+            .ok_or_else(|| format!("{} is not a valid pet weight", weight).into())
+    }
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Row, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::staff_repository::StaffRepository;
+use crate::models::staff_model::{ProcedureStaffMember, StaffModel};
+use crate::schemas::staff_schema::{AddStaff, StaffedProcedure, UpdateStaff};
+
+pub struct StaffQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> StaffQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        StaffQueries {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+fn staff_filter(role: Option<&str>, active: Option<bool>) -> String {
+    let mut filter = String::from(" WHERE clinic_id = ?");
+    if role.is_some() {
+        filter.push_str(" AND staff_role = ?");
+    }
+    if active.is_some() {
+        filter.push_str(" AND is_active = ?");
+    }
+    filter
+}
+
+macro_rules! staff_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl StaffRepository for StaffQueries<$db> {
+            async fn insert_staff(&self, staff_id: String, staff: &AddStaff) -> Result<u64, sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO staff (staff_id, staff_name, staff_email, staff_phone_number, staff_role,
+                    license_number, credentials, clinic_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#)
+                )
+                .bind(staff_id)
+                .bind(&staff.staff_name)
+                .bind(&staff.staff_email)
+                .bind(&staff.staff_phone_number)
+                .bind(staff.staff_role.as_str())
+                .bind(&staff.license_number)
+                .bind(&staff.credentials)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn select_staff(&self, staff_id: String) -> Result<StaffModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql("SELECT * FROM staff WHERE staff_id = ? AND clinic_id = ?"))
+                    .bind(staff_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+            }
+
+            async fn select_all_staff(
+                &self,
+                role: Option<&str>,
+                active: Option<bool>,
+                limit: i32,
+                offset: i32,
+            ) -> Result<Vec<StaffModel>, sqlx::Error> {
+                let query_string = format!(
+                    "SELECT * FROM staff{} ORDER BY staff_name LIMIT ? OFFSET ?",
+                    staff_filter(role, active)
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query_as(&sql).bind(&self.clinic_id);
+                if let Some(role) = role {
+                    query = query.bind(role);
+                }
+                if let Some(active) = active {
+                    query = query.bind(active);
+                }
+
+                query.bind(limit).bind(offset).fetch_all(&*self.db).await
+            }
+
+            async fn count_staff(
+                &self,
+                role: Option<&str>,
+                active: Option<bool>,
+            ) -> Result<i64, sqlx::Error> {
+                let query_string = format!(
+                    "SELECT COUNT(*) as count FROM staff{}",
+                    staff_filter(role, active)
+                );
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql).bind(&self.clinic_id);
+                if let Some(role) = role {
+                    query = query.bind(role);
+                }
+                if let Some(active) = active {
+                    query = query.bind(active);
+                }
+
+                query
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get("count"))
+            }
+
+            async fn update_staff(
+                &self,
+                staff_id: String,
+                staff: &UpdateStaff,
+                expected_version: Option<i32>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut query_string = String::from("UPDATE staff SET ");
+                let mut params = Vec::new();
+
+                for (column, value) in [
+                    ("staff_name", &staff.staff_name),
+                    ("staff_email", &staff.staff_email),
+                    ("staff_phone_number", &staff.staff_phone_number),
+                    ("license_number", &staff.license_number),
+                    ("credentials", &staff.credentials),
+                ] {
+                    if let Some(value) = value {
+                        query_string.push_str(&format!("{} = ?, ", column));
+                        params.push(value.clone());
+                    }
+                }
+
+                match staff.is_active {
+                    Some(true) => query_string.push_str("is_active = TRUE, "),
+                    Some(false) => query_string.push_str("is_active = FALSE, "),
+                    None => {}
+                }
+
+                query_string.push_str("row_version = row_version + 1");
+
+                query_string.push_str(" WHERE staff_id = ? AND clinic_id = ?");
+                params.push(staff_id);
+                params.push(self.clinic_id.clone());
+                if let Some(version) = expected_version {
+                    query_string.push_str(&format!(" AND row_version = {}", <$db>::text_as("INT")));
+                    params.push(version.to_string());
+                }
+
+
+                let sql = <$db>::sql(&query_string);
+                let mut query = sqlx::query(&sql);
+
+                for param in params {
+                    query = query.bind(param);
+                }
+
+                let result = query.execute(&*self.db).await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn procedure_exists(
+                &self,
+                procedure: StaffedProcedure,
+                procedure_id: i32,
+            ) -> Result<bool, sqlx::Error> {
+                let query_string = format!(
+                    r#"SELECT COUNT(*) as count FROM {} p
+                    JOIN service_instance si ON si.service_instance_id = p.service_instance_id
+                    WHERE p.{} = ? AND si.clinic_id = ?"#,
+                    procedure.table(),
+                    procedure.id_column()
+                );
+                sqlx::query(&<$db>::sql(&query_string))
+                    .bind(procedure_id)
+                    .bind(&self.clinic_id)
+                    .fetch_one(&*self.db)
+                    .await
+                    .map(|row: <$db as Database>::Row| row.get::<i64, _>("count") > 0)
+            }
+
+            async fn select_procedure_staff(
+                &self,
+                procedure: StaffedProcedure,
+                procedure_id: i32,
+            ) -> Result<Vec<ProcedureStaffMember>, sqlx::Error> {
+                let query_string = format!(
+                    r#"SELECT s.staff_id, s.staff_name, s.staff_role, ps.staff_function
+                    FROM {}_staff ps
+                    JOIN staff s ON s.staff_id = ps.staff_id
+                    WHERE ps.{} = ? AND s.clinic_id = ?
+                    ORDER BY ps.staff_function DESC, s.staff_name"#,
+                    procedure.table(),
+                    procedure.id_column()
+                );
+                sqlx::query_as(&<$db>::sql(&query_string))
+                    .bind(procedure_id)
+                    .bind(&self.clinic_id)
+                    .fetch_all(&*self.db)
+                    .await
+            }
+
+            async fn insert_procedure_staff(
+                &self,
+                procedure: StaffedProcedure,
+                procedure_id: i32,
+                staff_id: String,
+                staff_function: &str,
+            ) -> Result<u64, sqlx::Error> {
+                let query_string = format!(
+                    r#"INSERT INTO {}_staff ({}, staff_id, staff_function)
+                    SELECT ?, staff_id, ? FROM staff WHERE staff_id = ? AND clinic_id = ?"#,
+                    procedure.table(),
+                    procedure.id_column()
+                );
+                sqlx::query(&<$db>::sql(&query_string))
+                    .bind(procedure_id)
+                    .bind(staff_function)
+                    .bind(staff_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
+
+            async fn delete_procedure_staff(
+                &self,
+                procedure: StaffedProcedure,
+                procedure_id: i32,
+                staff_id: String,
+            ) -> Result<u64, sqlx::Error> {
+                let query_string = format!(
+                    r#"DELETE FROM {}_staff WHERE {} = ? AND staff_id = ?
+                    AND staff_id IN (SELECT staff_id FROM staff WHERE clinic_id = ?)"#,
+                    procedure.table(),
+                    procedure.id_column()
+                );
+                sqlx::query(&<$db>::sql(&query_string))
+                    .bind(procedure_id)
+                    .bind(staff_id)
+                    .bind(&self.clinic_id)
+                    .execute(&*self.db)
+                    .await
+                    .map(|done| done.rows_affected())
+            }
+        }
+    };
+}
+
+staff_queries!(MySql);
+staff_queries!(Postgres);
+staff_queries!(Sqlite);
@@ -1,47 +1,244 @@
 use std::sync::Arc;
 
-use sqlx::Error;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{Database, Error, MySql, Pool, Postgres, Sqlite};
 
-use crate::models::statistics_model::{PetVisitSummary, ServiceTypeCount};
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::statistic_repository::StatisticRepository;
+use crate::models::clinical_code_model::CodeCount;
+use crate::models::statistics_model::{
+    DailyCount, DailyServiceTypeCount, PetVisitSummary, ServiceTypeCount, VetProcedure,
+};
 
-pub struct StatisticQueries {
-    db: Arc<sqlx::MySqlPool>,
+pub struct StatisticQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
 }
 
-impl StatisticQueries {
-    pub fn new(db: Arc<sqlx::MySqlPool>) -> Self {
-        StatisticQueries { db }
+impl<DB: Database> StatisticQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        StatisticQueries {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
     }
+}
 
-    pub async fn count_services_by_type(&self) -> Result<Vec<ServiceTypeCount>, Error> {
-        let records = sqlx::query_as::<_, ServiceTypeCount>(
-            r#"
-                SELECT service_type.service_type_name, COUNT(*) as total
-                FROM service_instance
-                JOIN service_type on service_instance.service_instance_id = service_type
-                .service_instance_id
-                GROUP BY service_type.service_type_name
-            "#,
-        )
-        .fetch_all(&*self.db)
-        .await?;
-
-        Ok(records)
-    }
+macro_rules! statistic_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl StatisticRepository for StatisticQueries<$db> {
+            async fn count_services_by_type(&self) -> Result<Vec<ServiceTypeCount>, Error> {
+                let records = sqlx::query_as::<_, ServiceTypeCount>(
+                    &<$db>::sql(r#"
+                        SELECT service_type.service_type_name, COUNT(*) as total
+                        FROM service_instance
+                        JOIN service_type on service_instance.service_instance_id = service_type
+                        .service_instance_id
+                        WHERE service_instance.clinic_id = ?
+                        GROUP BY service_type.service_type_name
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await?;
 
-    pub async fn get_pet_type_visit_summary(&self) -> Result<Vec<PetVisitSummary>, Error> {
-        sqlx::query_as::<_, PetVisitSummary>(
-            r#"
-                SELECT pet.pet_type, COUNT(service_instance.service_instance_id) AS total_visits
-                FROM pet
-                JOIN service_instance ON pet.pet_id = service_instance.pet_id
-                WHERE pet.pet_type IN ('Dog', 'Cat')
-                GROUP BY pet.pet_type
-                HAVING COUNT(service_instance.service_instance_id) > 1
-                ORDER BY total_visits DESC
-            "#,
-        )
-        .fetch_all(&*self.db)
-        .await
-    }
+                Ok(records)
+            }
+
+            async fn get_pet_type_visit_summary(&self) -> Result<Vec<PetVisitSummary>, Error> {
+                sqlx::query_as::<_, PetVisitSummary>(
+                    &<$db>::sql(r#"
+                        SELECT pet.pet_type, COUNT(service_instance.service_instance_id) AS total_visits
+                        FROM pet
+                        JOIN service_instance ON pet.pet_id = service_instance.pet_id
+                        WHERE pet.clinic_id = ? AND pet.pet_type IN ('Dog', 'Cat')
+                        GROUP BY pet.pet_type
+                        HAVING COUNT(service_instance.service_instance_id) > 1
+                        ORDER BY total_visits DESC
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn count_visits_by_day(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<DailyCount>, Error> {
+                sqlx::query_as::<_, DailyCount>(
+                    &<$db>::sql(r#"
+                        SELECT service_date AS day, COUNT(*) AS total
+                        FROM service_instance
+                        WHERE clinic_id = ? AND service_date BETWEEN ? AND ?
+                        GROUP BY service_date
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn count_new_pets_by_day(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<DailyCount>, Error> {
+                sqlx::query_as::<_, DailyCount>(
+                    &<$db>::sql(r#"
+                        SELECT created_on AS day, COUNT(*) AS total
+                        FROM pet
+                        WHERE clinic_id = ? AND created_on BETWEEN ? AND ?
+                        GROUP BY created_on
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn count_new_owners_by_day(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<DailyCount>, Error> {
+                sqlx::query_as::<_, DailyCount>(
+                    &<$db>::sql(r#"
+                        SELECT created_on AS day, COUNT(*) AS total
+                        FROM owner
+                        WHERE clinic_id = ? AND created_on BETWEEN ? AND ?
+                        GROUP BY created_on
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn count_service_types_by_day(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<DailyServiceTypeCount>, Error> {
+                sqlx::query_as::<_, DailyServiceTypeCount>(
+                    &<$db>::sql(r#"
+                        SELECT service_instance.service_date AS day, service_type.service_type_name,
+                        COUNT(*) AS total
+                        FROM service_instance
+                        JOIN service_type ON service_instance.service_instance_id = service_type
+                        .service_instance_id
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                        GROUP BY service_instance.service_date, service_type.service_type_name
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn select_vet_procedures(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<VetProcedure>, Error> {
+                sqlx::query_as::<_, VetProcedure>(
+                    &<$db>::sql(r#"
+                        SELECT preventive_care.vet_id, 'preventive_care' AS procedure_type,
+                        service_instance.service_date, NULL AS surgery_name, NULL AS complications,
+                        NULL AS outcome, pet.pet_id, pet.pet_type
+                        FROM preventive_care
+                        JOIN service_instance ON preventive_care.service_instance_id = service_instance
+                        .service_instance_id
+                        JOIN pet ON service_instance.pet_id = pet.pet_id
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                        UNION ALL
+                        SELECT surgery.vet_id, 'surgery' AS procedure_type, service_instance.service_date,
+                        surgery.surgery_name, surgery.complications, surgery.outcome, pet.pet_id,
+                        pet.pet_type
+                        FROM surgery
+                        JOIN service_instance ON surgery.service_instance_id = service_instance
+                        .service_instance_id
+                        JOIN pet ON service_instance.pet_id = pet.pet_id
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+
+            async fn count_codes(
+                &self,
+                start_date: NaiveDate,
+                end_date: NaiveDate,
+            ) -> Result<Vec<CodeCount>, Error> {
+                sqlx::query_as::<_, CodeCount>(
+                    &<$db>::sql(r#"
+                        SELECT 'diagnosis' AS code_type, clinical_code.code, clinical_code.term,
+                        COUNT(*) AS total
+                        FROM service_instance
+                        LEFT JOIN clinical_code ON clinical_code.code = service_instance.diagnosis_code
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                        AND TRIM(service_instance.general_diagnosis) <> ''
+                        GROUP BY clinical_code.code, clinical_code.term
+                        UNION ALL
+                        SELECT 'procedure' AS code_type, clinical_code.code, clinical_code.term,
+                        COUNT(*) AS total
+                        FROM surgery
+                        JOIN service_instance ON surgery.service_instance_id = service_instance
+                        .service_instance_id
+                        LEFT JOIN clinical_code ON clinical_code.code = surgery.surgery_code
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                        GROUP BY clinical_code.code, clinical_code.term
+                        UNION ALL
+                        SELECT 'treatment' AS code_type, clinical_code.code, clinical_code.term,
+                        COUNT(*) AS total
+                        FROM preventive_care
+                        JOIN service_instance ON preventive_care.service_instance_id = service_instance
+                        .service_instance_id
+                        LEFT JOIN clinical_code ON clinical_code.code = preventive_care.treatment_code
+                        WHERE service_instance.clinic_id = ?
+                        AND service_instance.service_date BETWEEN ? AND ?
+                        GROUP BY clinical_code.code, clinical_code.term
+                    "#)
+                )
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .bind(&self.clinic_id)
+                .bind(start_date)
+                .bind(end_date)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
 }
+
+statistic_queries!(MySql);
+statistic_queries!(Postgres);
+statistic_queries!(Sqlite);
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{Database, MySql, Pool, Postgres, Sqlite};
+
+use crate::db::queries::dialect::Dialect;
+use crate::db::repositories::surgical_case_repository::SurgicalCaseRepository;
+use crate::models::surgical_case_model::{AnesthesiaLogEntry, SurgicalCaseModel};
+use crate::schemas::surgical_case_schema::AddAnesthesiaLogEntry;
+
+pub struct SurgicalCaseQueries<DB: Database> {
+    db: Arc<Pool<DB>>,
+    clinic_id: String,
+}
+
+impl<DB: Database> SurgicalCaseQueries<DB> {
+    pub fn new(db: Arc<Pool<DB>>, clinic_id: &str) -> Self {
+        Self {
+            db,
+            clinic_id: clinic_id.to_string(),
+        }
+    }
+}
+
+/// Limits `surgery_id` to the surgeries of the clinic bound in its place.
+const CLINIC_SURGERIES: &str = "surgery_id IN (SELECT surgery.surgery_id FROM surgery \
+    JOIN service_instance ON service_instance.service_instance_id = surgery.service_instance_id \
+    WHERE service_instance.clinic_id = ?)";
+
+macro_rules! surgical_case_queries {
+    ($db:ty) => {
+        #[async_trait]
+        impl SurgicalCaseRepository for SurgicalCaseQueries<$db> {
+            async fn insert_case(&self, surgery_id: i32, opened_at: i64) -> Result<(), sqlx::Error> {
+                sqlx::query(
+                    &<$db>::sql(r#"INSERT INTO surgical_case (surgery_id, case_status, opened_at)
+                    SELECT surgery.surgery_id, 'planned', ? FROM surgery
+                    JOIN service_instance ON service_instance.service_instance_id = surgery.service_instance_id
+                    WHERE surgery.surgery_id = ? AND service_instance.clinic_id = ?"#)
+                )
+                .bind(opened_at)
+                .bind(surgery_id)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|_| ())
+            }
+
+            async fn select_case(&self, surgery_id: i32) -> Result<SurgicalCaseModel, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    "SELECT * FROM surgical_case WHERE surgery_id = ? AND {}",
+                    CLINIC_SURGERIES
+                )))
+                .bind(surgery_id)
+                .bind(&self.clinic_id)
+                .fetch_one(&*self.db)
+                .await
+            }
+
+            async fn update_case(
+                &self,
+                case: &SurgicalCaseModel,
+                expected_status: &str,
+            ) -> Result<u64, sqlx::Error> {
+                sqlx::query(&<$db>::sql(&format!(
+                    r#"
+                    UPDATE surgical_case
+                    SET case_status = ?, fasting_confirmed = ?, bloodwork_reviewed = ?,
+                        iv_access_placed = ?, surgical_site_prepared = ?, consent_given_by = ?,
+                        consent_given_at = ?, preop_notes = ?, intraop_notes = ?, postop_instructions = ?,
+                        started_at = ?, recovery_at = ?, discharged_at = ?
+                    WHERE surgery_id = ? AND case_status = ? AND {}
+                    "#,
+                    CLINIC_SURGERIES
+                )))
+                .bind(&case.case_status)
+                .bind(case.fasting_confirmed)
+                .bind(case.bloodwork_reviewed)
+                .bind(case.iv_access_placed)
+                .bind(case.surgical_site_prepared)
+                .bind(&case.consent_given_by)
+                .bind(case.consent_given_at)
+                .bind(&case.preop_notes)
+                .bind(&case.intraop_notes)
+                .bind(&case.postop_instructions)
+                .bind(case.started_at)
+                .bind(case.recovery_at)
+                .bind(case.discharged_at)
+                .bind(case.surgery_id)
+                .bind(expected_status)
+                .bind(&self.clinic_id)
+                .execute(&*self.db)
+                .await
+                .map(|done| done.rows_affected())
+            }
+
+            async fn insert_anesthesia_log_entry(
+                &self,
+                surgery_id: i32,
+                recorded_at: i64,
+                entry: &AddAnesthesiaLogEntry,
+            ) -> Result<i32, sqlx::Error> {
+                let query_string = format!(
+                    r#"INSERT INTO anesthesia_log_entry (surgery_id, recorded_at, drug, dose, heart_rate,
+                    respiratory_rate, temperature_c, spo2, notes)
+                    SELECT surgery_id, ?, ?, ?, ?, ?, ?, ?, ? FROM surgical_case
+                    WHERE surgery_id = ? AND {}{}"#,
+                    CLINIC_SURGERIES,
+                    <$db>::returning("anesthesia_log_entry_id")
+                );
+                let sql = <$db>::sql(&query_string);
+                let query = sqlx::query(&sql)
+                .bind(recorded_at)
+                .bind(&entry.drug)
+                .bind(&entry.dose)
+                .bind(entry.heart_rate)
+                .bind(entry.respiratory_rate)
+                .bind(entry.temperature_c)
+                .bind(entry.spo2)
+                .bind(&entry.notes)
+                .bind(surgery_id)
+                .bind(&self.clinic_id);
+
+                <$db>::generated_id(query, &*self.db).await
+            }
+
+            async fn select_anesthesia_log(
+                &self,
+                surgery_id: i32,
+            ) -> Result<Vec<AnesthesiaLogEntry>, sqlx::Error> {
+                sqlx::query_as(&<$db>::sql(&format!(
+                    r#"SELECT anesthesia_log_entry_id, recorded_at, drug, dose, heart_rate, respiratory_rate,
+                    temperature_c, spo2, notes FROM anesthesia_log_entry WHERE surgery_id = ? AND {}
+                    ORDER BY recorded_at, anesthesia_log_entry_id"#,
+                    CLINIC_SURGERIES
+                )))
+                .bind(surgery_id)
+                .bind(&self.clinic_id)
+                .fetch_all(&*self.db)
+                .await
+            }
+        }
+    };
+}
+
+surgical_case_queries!(MySql);
+surgical_case_queries!(Postgres);
+surgical_case_queries!(Sqlite);
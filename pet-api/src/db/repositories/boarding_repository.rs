@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::models::boarding_model::{BoardingStayModel, CareLogEntry, KennelModel};
+use crate::schemas::boarding_schema::{AddCareLogEntry, AddKennel, BoardingStayOptions};
+use crate::utils::rules::RuleViolation;
+
+/// Decides whether a stay may be booked, given the other stays that aren't cancelled and share
+/// a night with it.
+pub type BookingCheck<'a> =
+    &'a (dyn Fn(&[BoardingStayModel]) -> Result<(), RuleViolation> + Send + Sync);
+
+#[async_trait]
+pub trait BoardingRepository: Send + Sync {
+    async fn insert_kennel(
+        &self,
+        kennel_id: String,
+        kennel: &AddKennel,
+        created_at: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_kennel(&self, kennel_id: &str) -> Result<KennelModel, sqlx::Error>;
+
+    /// The clinic's kennels by name.
+    async fn select_all_kennels(&self) -> Result<Vec<KennelModel>, sqlx::Error>;
+
+    /// Runs `check` against the stays sharing a night with `stay` and, if it passes, writes the
+    /// stay, in one transaction that first locks its kennel and pet so that concurrent bookings
+    /// are checked one after another. Inserts the stay, or given `expected_status` updates it
+    /// while it is still in that status. Returns 0 when the pet or kennel isn't the clinic's, or
+    /// the stay changed status meanwhile.
+    async fn book_stay(
+        &self,
+        stay: &BoardingStayModel,
+        expected_status: Option<&str>,
+        check: BookingCheck<'_>,
+    ) -> Result<Result<u64, RuleViolation>, sqlx::Error>;
+
+    async fn select_stay(&self, stay_id: &str) -> Result<BoardingStayModel, sqlx::Error>;
+
+    /// Stays matching the filters, latest check-in first.
+    async fn select_stays(
+        &self,
+        filter: &BoardingStayOptions,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<BoardingStayModel>, sqlx::Error>;
+
+    async fn count_stays(&self, filter: &BoardingStayOptions) -> Result<i64, sqlx::Error>;
+
+    /// Stays that aren't cancelled and have a night between `from` and `to`, both included.
+    async fn select_overlapping_stays(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<BoardingStayModel>, sqlx::Error>;
+
+    /// Writes the stay's kennel, dates, instructions and status, but only while it is still in
+    /// `expected_status`. Returns 0 when another request changed it first. Changes to a stay's
+    /// nights or kennel go through `book_stay` instead.
+    async fn update_stay(
+        &self,
+        stay: &BoardingStayModel,
+        expected_status: &str,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Returns the new entry's id.
+    async fn insert_care_log_entry(
+        &self,
+        stay_id: &str,
+        care_date: NaiveDate,
+        recorded_at: i64,
+        entry: &AddCareLogEntry,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// The stay's care log by day, in the order it was recorded.
+    async fn select_care_log(&self, stay_id: &str) -> Result<Vec<CareLogEntry>, sqlx::Error>;
+}
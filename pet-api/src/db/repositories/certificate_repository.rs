@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::models::certificate_model::{CertificateModel, CertifiedTreatment};
+
+#[async_trait]
+pub trait CertificateRepository: Send + Sync {
+    /// Every preventive care record of the pet, oldest visit first.
+    async fn select_preventive_care_of_pet(
+        &self,
+        pet_id: String,
+    ) -> Result<Vec<CertifiedTreatment>, sqlx::Error>;
+
+    /// Returns the new certificate's id.
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_certificate(
+        &self,
+        certificate_type: &str,
+        pet_id: String,
+        vet_id: String,
+        verification_code: String,
+        issued_on: NaiveDate,
+        valid_until: Option<NaiveDate>,
+        content: String,
+        content_digest: String,
+    ) -> Result<i32, sqlx::Error>;
+
+    async fn select_certificate(
+        &self,
+        certificate_id: i32,
+    ) -> Result<CertificateModel, sqlx::Error>;
+
+    async fn select_certificate_by_code(
+        &self,
+        verification_code: String,
+    ) -> Result<CertificateModel, sqlx::Error>;
+
+    async fn select_certificates_of_pet(
+        &self,
+        pet_id: String,
+    ) -> Result<Vec<CertificateModel>, sqlx::Error>;
+
+    /// Marks the certificate revoked unless it already is.
+    async fn revoke_certificate(
+        &self,
+        certificate_id: i32,
+        revoked_on: NaiveDate,
+    ) -> Result<u64, sqlx::Error>;
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use crate::models::clinic_model::ClinicModel;
+
+/// The clinics of the group. Unlike the other repositories this one is not scoped to a clinic.
+#[async_trait]
+pub trait ClinicRepository: Send + Sync {
+    async fn insert_clinic(
+        &self,
+        clinic_id: String,
+        clinic_name: String,
+        created_at: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_clinic(&self, clinic_id: &str) -> Result<Option<ClinicModel>, sqlx::Error>;
+
+    async fn select_all_clinics(&self) -> Result<Vec<ClinicModel>, sqlx::Error>;
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::models::clinical_code_model::{ClinicalCode, CodeMatch, NewClinicalCode};
+use crate::schemas::clinical_code_schema::CodeType;
+
+/// The diagnosis, procedure and treatment catalogue shared by every clinic, so like the clinic
+/// repository it is not scoped to one.
+#[async_trait]
+pub trait ClinicalCodeRepository: Send + Sync {
+    /// Adds the codes, replacing the term and synonyms of those already catalogued, then codes
+    /// every uncoded diagnosis, surgery and treatment whose text is now a synonym. Returns how
+    /// many records were coded.
+    async fn upsert_clinical_codes(&self, codes: &[NewClinicalCode]) -> Result<u64, sqlx::Error>;
+
+    async fn select_clinical_codes(
+        &self,
+        codes: &[String],
+    ) -> Result<Vec<ClinicalCode>, sqlx::Error>;
+
+    /// One row per synonym containing `q`, plus every synonym of codes starting with it.
+    async fn search_clinical_codes(
+        &self,
+        q: &str,
+        code_type: Option<CodeType>,
+    ) -> Result<Vec<CodeMatch>, sqlx::Error>;
+
+    /// The code of each lower-case term that is a synonym of the type.
+    async fn select_codes_by_synonym(
+        &self,
+        code_type: CodeType,
+        synonyms: &[String],
+    ) -> Result<HashMap<String, String>, sqlx::Error>;
+}
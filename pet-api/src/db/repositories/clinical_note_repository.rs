@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+
+use crate::models::clinical_note_model::ClinicalNoteModel;
+use crate::schemas::clinical_note_schema::{
+    AddClinicalNote, ClinicalNoteSearchOptions, SoapSection,
+};
+
+#[async_trait]
+pub trait ClinicalNoteRepository: Send + Sync {
+    /// Returns the new entry's id, or `RowNotFound` when the service instance isn't the
+    /// clinic's.
+    async fn insert_clinical_note(
+        &self,
+        service_instance_id: &str,
+        note: &AddClinicalNote,
+        recorded_at: i64,
+    ) -> Result<i32, sqlx::Error>;
+
+    async fn select_clinical_note(&self, note_id: i32) -> Result<ClinicalNoteModel, sqlx::Error>;
+
+    /// The visit's entries in the order they were written.
+    async fn select_clinical_notes(
+        &self,
+        service_instance_id: &str,
+        section: Option<SoapSection>,
+        author_id: Option<&str>,
+    ) -> Result<Vec<ClinicalNoteModel>, sqlx::Error>;
+
+    /// The pet's entries across visits, latest visit first.
+    async fn search_clinical_notes(
+        &self,
+        pet_id: &str,
+        filter: &ClinicalNoteSearchOptions,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<ClinicalNoteModel>, sqlx::Error>;
+
+    async fn count_clinical_notes(
+        &self,
+        pet_id: &str,
+        filter: &ClinicalNoteSearchOptions,
+    ) -> Result<i64, sqlx::Error>;
+}
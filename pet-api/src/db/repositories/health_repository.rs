@@ -0,0 +1,16 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait HealthRepository: Send + Sync {
+    fn is_pool_closed(&self) -> bool;
+
+    fn pool_size(&self) -> u32;
+
+    fn idle_connections(&self) -> usize;
+
+    async fn ping(&self) -> Result<Duration, sqlx::Error>;
+
+    async fn select_schema_version(&self) -> Result<Option<i64>, sqlx::Error>;
+}
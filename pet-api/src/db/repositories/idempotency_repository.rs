@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::models::idempotency_model::IdempotencyKeyModel;
+
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// Reserves `idempotency_key` for a request to this clinic; `false` means the clinic already
+    /// used the key.
+    async fn insert_key(
+        &self,
+        idempotency_key: &str,
+        request_fingerprint: &str,
+        created_at: i64,
+        expires_at: i64,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn select_key(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<Option<IdempotencyKeyModel>, sqlx::Error>;
+
+    async fn save_response(
+        &self,
+        idempotency_key: &str,
+        response_status: i32,
+        response_body: &str,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn delete_key(&self, idempotency_key: &str) -> Result<u64, sqlx::Error>;
+
+    async fn delete_expired_keys(&self, now: i64) -> Result<u64, sqlx::Error>;
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use crate::models::identifier_model::{IdentifiedPetModel, PetIdentifierModel};
+
+#[async_trait]
+pub trait IdentifierRepository: Send + Sync {
+    /// Returns the new identifier's id, or `RowNotFound` when the pet isn't the clinic's.
+    async fn insert_identifier(&self, identifier: &PetIdentifierModel) -> Result<i32, sqlx::Error>;
+
+    /// The pet's identifiers in the order they were recorded.
+    async fn select_identifiers(
+        &self,
+        pet_id: &str,
+    ) -> Result<Vec<PetIdentifierModel>, sqlx::Error>;
+
+    /// Returns 0 when there is no such identifier on one of the clinic's own pets.
+    async fn delete_identifier(&self, pet_id: &str, identifier_id: i32)
+        -> Result<u64, sqlx::Error>;
+
+    /// Every pet carrying the identifier, whichever clinic it is registered with, as a found
+    /// animal may be brought to any of them.
+    async fn select_pets_by_identifier(
+        &self,
+        identifier_value: &str,
+    ) -> Result<Vec<IdentifiedPetModel>, sqlx::Error>;
+}
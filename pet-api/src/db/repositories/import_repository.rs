@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::models::import_model::ImportReport;
+use crate::schemas::import_schema::ImportEntity;
+
+#[async_trait]
+pub trait ImportRepository: Send + Sync {
+    /// `(owner_email, owner_id)` of every owner with one of the given emails.
+    async fn select_owner_ids_by_email(
+        &self,
+        owner_emails: &[String],
+    ) -> Result<Vec<(String, String)>, sqlx::Error>;
+
+    /// Inserts each row, columns in table order, behind its own savepoint in one transaction,
+    /// returning the index and error of every row the database rejected. The transaction is
+    /// only committed when `commit` is set and, if `atomic`, no row was rejected.
+    async fn insert_rows(
+        &self,
+        entity: ImportEntity,
+        rows: &[Vec<String>],
+        atomic: bool,
+        commit: bool,
+    ) -> Result<Vec<(usize, sqlx::Error)>, sqlx::Error>;
+
+    async fn insert_import_job(&self, report: &ImportReport) -> Result<u64, sqlx::Error>;
+
+    async fn select_error_report(&self, import_id: String) -> Result<String, sqlx::Error>;
+}
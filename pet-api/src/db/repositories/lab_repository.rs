@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+
+use crate::models::lab_model::{
+    LabOrderModel, LabResultModel, NewLabResult, ReferenceRange, TrendPoint,
+};
+use crate::schemas::lab_schema::AddLabOrder;
+
+#[async_trait]
+pub trait LabRepository: Send + Sync {
+    /// Returns the new order's id, or `RowNotFound` when the service instance isn't the
+    /// clinic's.
+    async fn insert_lab_order(
+        &self,
+        service_instance_id: &str,
+        order: &AddLabOrder,
+        ordered_at: i64,
+    ) -> Result<i32, sqlx::Error>;
+
+    async fn select_lab_order(&self, lab_order_id: i32) -> Result<LabOrderModel, sqlx::Error>;
+
+    /// The service instance's orders in the order they were placed.
+    async fn select_lab_orders(
+        &self,
+        service_instance_id: &str,
+    ) -> Result<Vec<LabOrderModel>, sqlx::Error>;
+
+    /// The results of the given orders by analyte.
+    async fn select_lab_results(
+        &self,
+        lab_order_ids: &[i32],
+    ) -> Result<Vec<LabResultModel>, sqlx::Error>;
+
+    /// Stores all of the results or none, and marks the order resulted. Returns 0 when the
+    /// order isn't the clinic's.
+    async fn insert_lab_results(
+        &self,
+        lab_order_id: i32,
+        results: &[NewLabResult],
+        recorded_at: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_reference_ranges(
+        &self,
+        species: Option<&str>,
+    ) -> Result<Vec<ReferenceRange>, sqlx::Error>;
+
+    /// The pet's results by analyte, oldest visit first.
+    async fn select_lab_trends(
+        &self,
+        pet_id: &str,
+        analyte: Option<&str>,
+    ) -> Result<Vec<TrendPoint>, sqlx::Error>;
+}
@@ -0,0 +1,19 @@
+pub(crate) mod boarding_repository;
+pub(crate) mod certificate_repository;
+pub(crate) mod clinic_repository;
+pub(crate) mod clinical_code_repository;
+pub(crate) mod clinical_note_repository;
+pub(crate) mod health_repository;
+pub(crate) mod idempotency_repository;
+pub(crate) mod identifier_repository;
+pub(crate) mod import_repository;
+pub(crate) mod lab_repository;
+pub(crate) mod owner_repository;
+pub(crate) mod pet_repository;
+pub(crate) mod portal_repository;
+pub(crate) mod problem_repository;
+pub(crate) mod service_instance_repository;
+pub(crate) mod staff_repository;
+pub(crate) mod statistic_repository;
+pub(crate) mod surgical_case_repository;
+pub(crate) mod vet_repository;
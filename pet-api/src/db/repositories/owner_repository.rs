@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::models::owner_model::{OwnerModel, OwnerWithPets};
+
+#[async_trait]
+pub trait OwnerRepository: Send + Sync {
+    async fn insert_owner(
+        &self,
+        owner_id: String,
+        owner_name: String,
+        owner_email: String,
+        owner_phone_number: String,
+        owner_address: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_owner(&self, owner_id: String) -> Result<OwnerModel, sqlx::Error>;
+
+    async fn select_all_owners(
+        &self,
+        limit: i32,
+        offset: i32,
+        search: Option<String>,
+    ) -> Result<Vec<OwnerModel>, sqlx::Error>;
+
+    async fn delete_owner(&self, owner_id: String) -> Result<u64, sqlx::Error>;
+
+    async fn update_owner(
+        &self,
+        owner_id: String,
+        owner_name: Option<String>,
+        owner_email: Option<String>,
+        owner_phone_number: Option<String>,
+        owner_address: Option<String>,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn get_owner_and_pets(&self, owner_id: String) -> Result<OwnerWithPets, sqlx::Error>;
+
+    async fn count_all_owners(&self, search: Option<String>) -> Result<i64, sqlx::Error>;
+}
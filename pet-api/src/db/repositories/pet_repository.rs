@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+
+use crate::models::clinic_model::PetShare;
+use crate::models::pet_model::{PetModel, PetModelResponse, PetStatusModel};
+
+#[async_trait]
+pub trait PetRepository: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_pet(
+        &self,
+        pet_id: String,
+        pet_name: String,
+        pet_birth_date: String,
+        pet_type: String,
+        pet_breed: String,
+        pet_weight: f32,
+        pet_color: String,
+        owner_id: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_pet(&self, pet_id: String) -> Result<PetModel, sqlx::Error>;
+
+    /// Only pets with `pet_status` when given, otherwise every pet but the deceased.
+    async fn select_all_pets(
+        &self,
+        limit: i32,
+        offset: i32,
+        search: Option<String>,
+        pet_status: Option<String>,
+    ) -> Result<Vec<PetModelResponse>, sqlx::Error>;
+
+    async fn delete_pet(&self, pet_id: String) -> Result<u64, sqlx::Error>;
+
+    /// Bumps `row_version`; given `expected_version`, a pet at any other version is left untouched.
+    #[allow(clippy::too_many_arguments)]
+    async fn update_pet(
+        &self,
+        pet_id: String,
+        pet_name: Option<String>,
+        pet_birth_date: Option<String>,
+        pet_type: Option<String>,
+        pet_breed: Option<String>,
+        pet_weight: Option<f32>,
+        pet_color: Option<String>,
+        owner_id: Option<String>,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn count_all_pets(
+        &self,
+        search: Option<String>,
+        pet_status: Option<String>,
+    ) -> Result<i64, sqlx::Error>;
+
+    /// Finds a pet of this clinic, or one another clinic has shared with it.
+    async fn select_pet_details(&self, pet_id: String) -> Result<PetModelResponse, sqlx::Error>;
+
+    /// Shares one of this clinic's pets with another clinic; no rows when the pet isn't this clinic's.
+    async fn insert_pet_share(
+        &self,
+        pet_id: String,
+        clinic_id: String,
+        shared_at: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn delete_pet_share(&self, pet_id: String, clinic_id: String)
+        -> Result<u64, sqlx::Error>;
+
+    async fn select_pet_shares(&self, pet_id: String) -> Result<Vec<PetShare>, sqlx::Error>;
+
+    /// The status of a pet of this clinic or one shared with it, with its death record if any.
+    async fn select_pet_status(&self, pet_id: String) -> Result<PetStatusModel, sqlx::Error>;
+
+    /// Writes the status and `memorial_safe` of one of this clinic's pets, bumping `row_version`,
+    /// and records its death unless already recorded. Returns 0 when the pet isn't the clinic's,
+    /// or, given `expected_version`, is at any other version.
+    async fn update_pet_status(
+        &self,
+        status: &PetStatusModel,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+}
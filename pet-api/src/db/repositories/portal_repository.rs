@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::models::owner_model::OwnerModel;
+use crate::models::portal_model::UpcomingFollowup;
+
+#[async_trait]
+pub trait PortalRepository: Send + Sync {
+    /// The clinic's owner with this email, matched case-insensitively as owners type it.
+    async fn select_owner_by_email(
+        &self,
+        clinic_id: &str,
+        owner_email: &str,
+    ) -> Result<Option<OwnerModel>, sqlx::Error>;
+
+    async fn insert_token(
+        &self,
+        token_hash: &str,
+        token_kind: &str,
+        owner_id: &str,
+        created_at: i64,
+        expires_at: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Marks an unexpired, unused sign-in token as used and returns its owner, so each
+    /// link signs in once.
+    async fn use_login_token(
+        &self,
+        token_hash: &str,
+        now: i64,
+    ) -> Result<Option<String>, sqlx::Error>;
+
+    /// The owner of a live session token with the clinic they belong to.
+    async fn select_session_owner(
+        &self,
+        token_hash: &str,
+        now: i64,
+    ) -> Result<Option<(String, String)>, sqlx::Error>;
+
+    async fn delete_token(&self, token_hash: &str) -> Result<u64, sqlx::Error>;
+
+    async fn delete_expired_tokens(&self, now: i64) -> Result<u64, sqlx::Error>;
+
+    /// Follow-ups of the owner's pets due on or after `from`, soonest first.
+    async fn select_upcoming_followups(
+        &self,
+        owner_id: &str,
+        from: NaiveDate,
+    ) -> Result<Vec<UpcomingFollowup>, sqlx::Error>;
+}
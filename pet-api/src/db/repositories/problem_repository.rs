@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+
+use crate::models::problem_model::PetProblemModel;
+
+#[async_trait]
+pub trait ProblemRepository: Send + Sync {
+    /// Returns the new entry's id, or `RowNotFound` when the pet isn't the clinic's.
+    async fn insert_problem(&self, problem: &PetProblemModel) -> Result<i32, sqlx::Error>;
+
+    async fn select_problem(
+        &self,
+        pet_id: &str,
+        problem_id: i32,
+    ) -> Result<PetProblemModel, sqlx::Error>;
+
+    /// The pet's problem list in the order it was recorded.
+    async fn select_problems(
+        &self,
+        pet_id: &str,
+        include_resolved: bool,
+    ) -> Result<Vec<PetProblemModel>, sqlx::Error>;
+
+    /// Writes the entry's description, severity, dates and notes. Returns 0 when the pet
+    /// isn't the clinic's.
+    async fn update_problem(&self, problem: &PetProblemModel) -> Result<u64, sqlx::Error>;
+
+    /// The unresolved problems of each given pet.
+    async fn select_active_problems(
+        &self,
+        pet_ids: &[String],
+    ) -> Result<Vec<PetProblemModel>, sqlx::Error>;
+
+    /// The unresolved problems of the service instance's pet.
+    async fn select_active_problems_of_service_instance(
+        &self,
+        service_instance_id: &str,
+    ) -> Result<Vec<PetProblemModel>, sqlx::Error>;
+}
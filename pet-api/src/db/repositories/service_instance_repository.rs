@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+
+use crate::models::service_instance_model::{
+    AllServiceInstanceModel, GetServicesHistoryModel, ServiceInstanceModel,
+};
+use crate::schemas::helper_schema::ServiceInstanceFilterOptions;
+use crate::schemas::service_instance_schema::{
+    AddPreventiveCare, AddPreventiveCareToExisting, AddSurgery, ServiceInstance,
+    UpdateServiceInstance, UpdateSurgery,
+};
+
+#[async_trait]
+pub trait ServiceInstanceRepository: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_service_instance(
+        &self,
+        service_instance_id: String,
+        service_date: String,
+        service_type: Vec<String>,
+        service_reason: String,
+        general_diagnosis: String,
+        diagnosis_code: Option<String>,
+        requires_followup: bool,
+        followup_date: Option<String>,
+        pet_id: String,
+        grooming_type: Option<Vec<String>>,
+        preventive_care: Option<AddPreventiveCare>,
+        surgery: Option<AddSurgery>,
+    ) -> Result<ServiceInstance, sqlx::Error>;
+
+    async fn check_pet_exists(&self, pet_id: &str) -> Result<bool, sqlx::Error>;
+
+    async fn get_services_history_of_pet(
+        &self,
+        pet_id: String,
+        start_date: String,
+        end_date: String,
+    ) -> Result<Vec<GetServicesHistoryModel>, sqlx::Error>;
+
+    async fn get_all_service_instances(
+        &self,
+        filter: &ServiceInstanceFilterOptions,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<AllServiceInstanceModel>, sqlx::Error>;
+
+    async fn count_all_service_instances(
+        &self,
+        filter: &ServiceInstanceFilterOptions,
+    ) -> Result<i64, sqlx::Error>;
+
+    /// Loads full details for every given id that exists, in the order requested.
+    async fn get_specific_instances(
+        &self,
+        service_instance_ids: &[String],
+    ) -> Result<Vec<ServiceInstanceModel>, sqlx::Error>;
+
+    async fn get_specific_instance(
+        &self,
+        service_instance_id: String,
+    ) -> Result<ServiceInstanceModel, sqlx::Error> {
+        self.get_specific_instances(&[service_instance_id])
+            .await?
+            .pop()
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn delete_grooming(&self, grooming_id: i32) -> Result<u64, sqlx::Error>;
+
+    async fn delete_preventive_care(&self, preventive_care_id: i32) -> Result<u64, sqlx::Error>;
+
+    async fn delete_surgery(&self, surgery_id: i32) -> Result<u64, sqlx::Error>;
+
+    async fn delete_service_instance(
+        &self,
+        service_instance_id: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_surgery_version(&self, surgery_id: i32) -> Result<i32, sqlx::Error>;
+
+    async fn select_service_instance_version(
+        &self,
+        service_instance_id: String,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// Bumps `row_version`; given `expected_version`, a surgery at any other version is left untouched.
+    /// A new `surgery_name` without a `surgery_code` clears the old code.
+    async fn update_surgery(
+        &self,
+        update_surgery: UpdateSurgery,
+        surgery_id: i32,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// A new `general_diagnosis` without a `diagnosis_code` clears the old code.
+    async fn update_service_instance(
+        &self,
+        update_service_instance: UpdateServiceInstance,
+        service_instance_id: String,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn add_surgery(
+        &self,
+        add_surgery: AddSurgery,
+        service_instance_id: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn add_preventive_care(
+        &self,
+        add_preventive_care_to_existing: AddPreventiveCareToExisting,
+        service_instance_id: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn add_grooming(
+        &self,
+        service_instance_id: String,
+        grooming_types: Vec<String>,
+    ) -> Result<u64, sqlx::Error>;
+}
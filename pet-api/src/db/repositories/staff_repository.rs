@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+
+use crate::models::staff_model::{ProcedureStaffMember, StaffModel};
+use crate::schemas::staff_schema::{AddStaff, StaffedProcedure, UpdateStaff};
+
+#[async_trait]
+pub trait StaffRepository: Send + Sync {
+    async fn insert_staff(&self, staff_id: String, staff: &AddStaff) -> Result<u64, sqlx::Error>;
+
+    async fn select_staff(&self, staff_id: String) -> Result<StaffModel, sqlx::Error>;
+
+    async fn select_all_staff(
+        &self,
+        role: Option<&str>,
+        active: Option<bool>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<StaffModel>, sqlx::Error>;
+
+    async fn count_staff(
+        &self,
+        role: Option<&str>,
+        active: Option<bool>,
+    ) -> Result<i64, sqlx::Error>;
+
+    async fn update_staff(
+        &self,
+        staff_id: String,
+        staff: &UpdateStaff,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn procedure_exists(
+        &self,
+        procedure: StaffedProcedure,
+        procedure_id: i32,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// The performer first, then assistants by name.
+    async fn select_procedure_staff(
+        &self,
+        procedure: StaffedProcedure,
+        procedure_id: i32,
+    ) -> Result<Vec<ProcedureStaffMember>, sqlx::Error>;
+
+    async fn insert_procedure_staff(
+        &self,
+        procedure: StaffedProcedure,
+        procedure_id: i32,
+        staff_id: String,
+        staff_function: &str,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn delete_procedure_staff(
+        &self,
+        procedure: StaffedProcedure,
+        procedure_id: i32,
+        staff_id: String,
+    ) -> Result<u64, sqlx::Error>;
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::models::clinical_code_model::CodeCount;
+use crate::models::statistics_model::{
+    DailyCount, DailyServiceTypeCount, PetVisitSummary, ServiceTypeCount, VetProcedure,
+};
+
+#[async_trait]
+pub trait StatisticRepository: Send + Sync {
+    async fn count_services_by_type(&self) -> Result<Vec<ServiceTypeCount>, sqlx::Error>;
+
+    async fn get_pet_type_visit_summary(&self) -> Result<Vec<PetVisitSummary>, sqlx::Error>;
+
+    /// Visits per service date within `start_date..=end_date`; days without visits are left out.
+    async fn count_visits_by_day(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyCount>, sqlx::Error>;
+
+    async fn count_new_pets_by_day(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyCount>, sqlx::Error>;
+
+    async fn count_new_owners_by_day(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyCount>, sqlx::Error>;
+
+    async fn count_service_types_by_day(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyServiceTypeCount>, sqlx::Error>;
+
+    /// Preventive care and surgeries from visits within `start_date..=end_date`.
+    async fn select_vet_procedures(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<VetProcedure>, sqlx::Error>;
+
+    /// Diagnoses, surgeries and treatments from visits within `start_date..=end_date`, counted
+    /// per catalogue code; each type's uncoded records are counted together without a code.
+    async fn count_codes(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<CodeCount>, sqlx::Error>;
+}
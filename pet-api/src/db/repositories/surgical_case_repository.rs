@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use crate::models::surgical_case_model::{AnesthesiaLogEntry, SurgicalCaseModel};
+use crate::schemas::surgical_case_schema::AddAnesthesiaLogEntry;
+
+#[async_trait]
+pub trait SurgicalCaseRepository: Send + Sync {
+    async fn insert_case(&self, surgery_id: i32, opened_at: i64) -> Result<(), sqlx::Error>;
+
+    async fn select_case(&self, surgery_id: i32) -> Result<SurgicalCaseModel, sqlx::Error>;
+
+    /// Writes every column of the case, but only while it is still in `expected_status`.
+    /// Returns the number of rows changed, so 0 means another request moved it first.
+    async fn update_case(
+        &self,
+        case: &SurgicalCaseModel,
+        expected_status: &str,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Returns the new entry's id.
+    async fn insert_anesthesia_log_entry(
+        &self,
+        surgery_id: i32,
+        recorded_at: i64,
+        entry: &AddAnesthesiaLogEntry,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// The case's anesthesia log in the order it was recorded.
+    async fn select_anesthesia_log(
+        &self,
+        surgery_id: i32,
+    ) -> Result<Vec<AnesthesiaLogEntry>, sqlx::Error>;
+}
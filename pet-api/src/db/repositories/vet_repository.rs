@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use crate::models::vet_model::{GetVets, VetModel};
+
+#[async_trait]
+pub trait VetRepository: Send + Sync {
+    async fn insert_vet(
+        &self,
+        vet_id: String,
+        vet_name: String,
+        vet_email: String,
+        vet_phone_number: String,
+        vet_license_number: String,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn select_vet(&self, vet_id: String) -> Result<VetModel, sqlx::Error>;
+
+    async fn select_all_vets(&self, limit: i32, offset: i32) -> Result<Vec<VetModel>, sqlx::Error>;
+
+    async fn vet_lists(&self) -> Result<Vec<GetVets>, sqlx::Error>;
+
+    async fn delete_vet(&self, vet_id: String) -> Result<u64, sqlx::Error>;
+
+    async fn update_vet(
+        &self,
+        vet_id: String,
+        vet_name: Option<String>,
+        vet_email: Option<String>,
+        vet_phone_number: Option<String>,
+        vet_license_number: Option<String>,
+        expected_version: Option<i32>,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn count_all_vets(&self) -> Result<i64, sqlx::Error>;
+}
@@ -0,0 +1,525 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Days;
+use serde_json::json;
+
+use crate::models::boarding_model::{BoardingStayModel, KennelModel};
+use crate::schemas::boarding_schema::{
+    AddBoardingStay, AddCareLogEntry, AddKennel, BoardingStayOptions, BoardingStayStatus,
+    BoardingStayTransition, OccupancyOptions, UpdateBoardingStay,
+};
+use crate::utils::boarding::{
+    apply_update, check_conflicts, check_species, clean, occupancy, parse_date, transition,
+    validate_care_entry, validate_dates, MAX_CALENDAR_NIGHTS,
+};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::pagination::page_window;
+use crate::utils::tenant::Tenant;
+use crate::utils::validator::validate_field;
+use crate::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/boarding/kennels",
+    tag = "boarding",
+    responses(
+        (status = 200, description = "The clinic's kennels and runs, by name", body = KennelsResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_kennels(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let kennels = data
+        .db
+        .boarding(&tenant.clinic_id)
+        .select_all_kennels()
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Kennels fetched successfully",
+            "kennels": kennels,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/boarding/kennels",
+    tag = "boarding",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddKennel,
+    responses(
+        (status = 201, description = "Kennel added", body = KennelResponse),
+        (status = 400, description = "The name is blank or the capacity below 1", body = MessageResponse),
+        (status = 409, description = "The clinic already has a kennel with this name, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_kennel(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddKennel>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_field(
+        &Some(body.kennel_name.clone()),
+        "Kennel name cannot be empty",
+    )
+    .map_err(|message| fail(StatusCode::BAD_REQUEST, &message))?;
+    if body.capacity < 1 {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "A kennel's capacity must be at least 1",
+        ));
+    }
+
+    let kennel_id = uuid::Uuid::new_v4().to_string();
+    let boarding_queries = data.db.boarding(&tenant.clinic_id);
+    boarding_queries
+        .insert_kennel(kennel_id.clone(), &body, chrono::Utc::now().timestamp())
+        .await
+        .map_err(|err| match handle_duplicate_entry_error(err, "Kennel") {
+            Ok(response) | Err(response) => response,
+        })?;
+    let kennel = boarding_queries
+        .select_kennel(&kennel_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Kennel added successfully",
+            "kennel": kennel,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/boarding/occupancy",
+    tag = "boarding",
+    params(OccupancyOptions),
+    responses(
+        (status = 200, description = "Every kennel's bookings night by night", body = OccupancyResponse),
+        (status = 400, description = "A date is invalid, or the range is reversed or longer than 62 nights", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_occupancy(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    opts: Option<Query<OccupancyOptions>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    let start_date = match clean(opts.start_date) {
+        Some(start_date) => parse_date("start_date", &start_date).map_err(rule_violation)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let end_date = match clean(opts.end_date) {
+        Some(end_date) => parse_date("end_date", &end_date).map_err(rule_violation)?,
+        None => start_date + Days::new(13),
+    };
+    if start_date > end_date {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "start_date is after end_date",
+        ));
+    }
+    if end_date > start_date + Days::new(MAX_CALENDAR_NIGHTS - 1) {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "The calendar can span at most {} nights",
+                MAX_CALENDAR_NIGHTS
+            ),
+        ));
+    }
+
+    let boarding_queries = data.db.boarding(&tenant.clinic_id);
+    let kennels = boarding_queries
+        .select_all_kennels()
+        .await
+        .map_err(database_error)?;
+    let stays = boarding_queries
+        .select_overlapping_stays(start_date, end_date)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Occupancy fetched successfully",
+            "start_date": start_date,
+            "end_date": end_date,
+            "kennels": occupancy(kennels, &stays, start_date, end_date),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/boarding/stays",
+    tag = "boarding",
+    params(BoardingStayOptions),
+    responses(
+        (status = 200, description = "Page of boarding stays, latest check-in first", body = BoardingStaysResponse),
+        (status = 400, description = "The night is not a valid date, or the page or limit is out of range", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_boarding_stays(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    opts: Option<Query<BoardingStayOptions>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(mut opts) = opts.unwrap_or_default();
+    opts.night = match clean(opts.night) {
+        Some(night) => Some(
+            parse_date("night", &night)
+                .map_err(rule_violation)?
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    let window = page_window(opts.page, opts.limit, 10).map_err(rule_violation)?;
+
+    let boarding_queries = data.db.boarding(&tenant.clinic_id);
+    let total_stays = boarding_queries
+        .count_stays(&opts)
+        .await
+        .map_err(database_error)?;
+    let total_pages = window.total_pages(total_stays);
+
+    let stays = boarding_queries
+        .select_stays(&opts, window.limit, window.offset)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Boarding stays fetched successfully",
+            "stays": stays,
+            "total_pages": total_pages,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/boarding/stays",
+    tag = "boarding",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddBoardingStay,
+    responses(
+        (status = 201, description = "Kennel reserved for the pet", body = BoardingStayResponse),
+        (status = 400, description = "A date is invalid, or check-out isn't after check-in", body = MessageResponse),
+        (status = 409, description = "The kennel is fully booked on a night of the stay, the pet already has a stay then, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The pet or kennel doesn't exist, or the kennel isn't for the pet's species", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_boarding_stay(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddBoardingStay>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let check_in_date = parse_date("check_in_date", &body.check_in_date).map_err(rule_violation)?;
+    let check_out_date =
+        parse_date("check_out_date", &body.check_out_date).map_err(rule_violation)?;
+    let pet = match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet(body.pet_id.clone())
+        .await
+    {
+        Ok(pet) => pet,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::UNPROCESSABLE_ENTITY, "Pet not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let kennel = find_kennel(&data, &tenant, &body.kennel_id).await?;
+
+    let stay = BoardingStayModel {
+        stay_id: uuid::Uuid::new_v4().to_string(),
+        kennel_id: kennel.kennel_id.clone(),
+        kennel_name: kennel.kennel_name.clone(),
+        pet_id: pet.pet_id,
+        pet_name: pet.pet_name,
+        pet_type: pet.pet_type,
+        check_in_date,
+        check_out_date,
+        stay_status: BoardingStayStatus::Reserved.as_str().to_string(),
+        feeding_instructions: clean(body.feeding_instructions),
+        medication_instructions: clean(body.medication_instructions),
+        reserved_at: chrono::Utc::now().timestamp(),
+        checked_in_at: None,
+        checked_out_at: None,
+    };
+    validate_dates(&stay).map_err(rule_violation)?;
+    check_species(&kennel, &stay.pet_type).map_err(rule_violation)?;
+    if book_stay(&data, &tenant, &stay, &kennel, None).await? == 0 {
+        return Err(fail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Pet or kennel not found",
+        ));
+    }
+
+    let (_, response) =
+        stay_response(&data, &tenant, stay, "Boarding stay reserved successfully").await?;
+    Ok((StatusCode::CREATED, response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/boarding/stays/{stay_id}",
+    tag = "boarding",
+    params(("stay_id" = String, Path, description = "Boarding stay id")),
+    responses(
+        (status = 200, description = "The stay with its care log", body = BoardingStayResponse),
+        (status = 404, description = "Boarding stay not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_boarding_stay(
+    Path(stay_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let stay = select_stay(&data, &tenant, &stay_id).await?;
+    stay_response(&data, &tenant, stay, "Boarding stay fetched successfully").await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/boarding/stays/{stay_id}",
+    tag = "boarding",
+    params(("stay_id" = String, Path, description = "Boarding stay id")),
+    request_body = UpdateBoardingStay,
+    responses(
+        (status = 200, description = "Boarding stay updated", body = BoardingStayResponse),
+        (status = 400, description = "A date is invalid, or check-out isn't after check-in", body = MessageResponse),
+        (status = 404, description = "Boarding stay not found", body = MessageResponse),
+        (status = 409, description = "A field can't change in the stay's status, the new dates or kennel are booked, or the stay changed meanwhile", body = MessageResponse),
+        (status = 422, description = "The kennel doesn't exist or isn't for the pet's species", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_boarding_stay(
+    Path(stay_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<UpdateBoardingStay>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut stay = select_stay(&data, &tenant, &stay_id).await?;
+    let status = stay.status();
+    apply_update(&mut stay, body).map_err(rule_violation)?;
+
+    let kennel = find_kennel(&data, &tenant, &stay.kennel_id).await?;
+    stay.kennel_name = kennel.kennel_name.clone();
+    check_species(&kennel, &stay.pet_type).map_err(rule_violation)?;
+    if book_stay(&data, &tenant, &stay, &kennel, Some(status.as_str())).await? == 0 {
+        return Err(stay_changed());
+    }
+
+    stay_response(&data, &tenant, stay, "Boarding stay updated successfully").await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/boarding/stays/{stay_id}/transitions",
+    tag = "boarding",
+    params(("stay_id" = String, Path, description = "Boarding stay id")),
+    request_body = BoardingStayTransition,
+    responses(
+        (status = 200, description = "The stay moved to the new status", body = BoardingStayResponse),
+        (status = 404, description = "Boarding stay not found", body = MessageResponse),
+        (status = 409, description = "The stay can't move from its status to this one, or changed meanwhile", body = MessageResponse),
+        (status = 422, description = "Checking in before the stay's check-in date", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn transition_boarding_stay(
+    Path(stay_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<BoardingStayTransition>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut stay = select_stay(&data, &tenant, &stay_id).await?;
+    let status = stay.status();
+    transition(
+        &mut stay,
+        body.status,
+        chrono::Local::now().date_naive(),
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(rule_violation)?;
+    save_stay(&data, &tenant, &stay, status.as_str()).await?;
+
+    stay_response(
+        &data,
+        &tenant,
+        stay,
+        &format!("Boarding stay moved to {}", body.status.as_str()),
+    )
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/boarding/stays/{stay_id}/care-log",
+    tag = "boarding",
+    params(("stay_id" = String, Path, description = "Boarding stay id")),
+    request_body = AddCareLogEntry,
+    responses(
+        (status = 201, description = "Entry added to the stay's care log", body = BoardingStayResponse),
+        (status = 400, description = "The entry is empty, or its date is invalid or outside the stay", body = MessageResponse),
+        (status = 404, description = "Boarding stay not found", body = MessageResponse),
+        (status = 409, description = "The pet isn't checked in", body = MessageResponse),
+        (status = 422, description = "Medication given on a stay without medication instructions", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_care_log_entry(
+    Path(stay_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(mut body): Json<AddCareLogEntry>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let stay = select_stay(&data, &tenant, &stay_id).await?;
+    let care_date = validate_care_entry(&stay, &mut body, chrono::Local::now().date_naive())
+        .map_err(rule_violation)?;
+    data.db
+        .boarding(&tenant.clinic_id)
+        .insert_care_log_entry(&stay_id, care_date, chrono::Utc::now().timestamp(), &body)
+        .await
+        .map_err(database_error)?;
+
+    let (_, response) =
+        stay_response(&data, &tenant, stay, "Care log entry added successfully").await?;
+    Ok((StatusCode::CREATED, response))
+}
+
+async fn find_kennel(
+    data: &AppState,
+    tenant: &Tenant,
+    kennel_id: &str,
+) -> Result<KennelModel, ErrorResponse> {
+    match data
+        .db
+        .boarding(&tenant.clinic_id)
+        .select_kennel(kennel_id.trim())
+        .await
+    {
+        Ok(kennel) => Ok(kennel),
+        Err(sqlx::Error::RowNotFound) => {
+            Err(fail(StatusCode::UNPROCESSABLE_ENTITY, "Kennel not found"))
+        }
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/// Writes the stay unless it clashes with the other bookings of its nights, checking and writing
+/// in one transaction. Inserts it, or given `expected_status` updates it.
+async fn book_stay(
+    data: &AppState,
+    tenant: &Tenant,
+    stay: &BoardingStayModel,
+    kennel: &KennelModel,
+    expected_status: Option<&str>,
+) -> Result<u64, ErrorResponse> {
+    data.db
+        .boarding(&tenant.clinic_id)
+        .book_stay(stay, expected_status, &|others| {
+            check_conflicts(stay, kennel, others)
+        })
+        .await
+        .map_err(database_error)?
+        .map_err(rule_violation)
+}
+
+async fn select_stay(
+    data: &AppState,
+    tenant: &Tenant,
+    stay_id: &str,
+) -> Result<BoardingStayModel, ErrorResponse> {
+    match data
+        .db
+        .boarding(&tenant.clinic_id)
+        .select_stay(stay_id)
+        .await
+    {
+        Ok(stay) => Ok(stay),
+        Err(sqlx::Error::RowNotFound) => {
+            Err(fail(StatusCode::NOT_FOUND, "Boarding stay not found"))
+        }
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/// Writes the stay unless another request changed its status since it was read.
+async fn save_stay(
+    data: &AppState,
+    tenant: &Tenant,
+    stay: &BoardingStayModel,
+    expected_status: &str,
+) -> Result<(), ErrorResponse> {
+    match data
+        .db
+        .boarding(&tenant.clinic_id)
+        .update_stay(stay, expected_status)
+        .await
+    {
+        Ok(0) => Err(stay_changed()),
+        Ok(_) => Ok(()),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+async fn stay_response(
+    data: &AppState,
+    tenant: &Tenant,
+    stay: BoardingStayModel,
+    message: &str,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let care_log = data
+        .db
+        .boarding(&tenant.clinic_id)
+        .select_care_log(&stay.stay_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": message,
+            "stay": stay,
+            "next_statuses": stay.status().next(),
+            "care_log": care_log,
+        })),
+    ))
+}
+
+fn stay_changed() -> ErrorResponse {
+    fail(
+        StatusCode::CONFLICT,
+        "The boarding stay changed status meanwhile; fetch it and try again",
+    )
+}
@@ -0,0 +1,394 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::models::certificate_model::{
+    CertificateContent, CertifiedPet, CertifiedTreatment, CertifyingVet,
+};
+use crate::schemas::certificate_schema::{CertificateType, IssueCertificate};
+use crate::utils::certificate::{
+    content_digest, new_verification_code, normalize_verification_code, render_certificate,
+    to_certificate, verify,
+};
+use crate::utils::handler_error::{database_error, fail, ErrorResponse};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pets/{pet_id}/certificates",
+    tag = "certificates",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = IssueCertificate,
+    responses(
+        (status = 201, description = "Certificate issued", body = CertificateResponse),
+        (status = 400, description = "The expiry date is malformed or already past", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The vet can't issue certificates or there is nothing to certify", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+        (status = 503, description = "No certificate signing key is configured", body = MessageResponse),
+    )
+)]
+pub async fn issue_certificate(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<IssueCertificate>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let signing_key = signing_key(&data)?;
+    let today = chrono::Local::now().date_naive();
+    let valid_until = match body.valid_until.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(date) => match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) if date >= today => Some(date),
+            Ok(_) => return Err(fail(StatusCode::BAD_REQUEST, "valid_until is in the past")),
+            Err(_) => {
+                return Err(fail(
+                    StatusCode::BAD_REQUEST,
+                    "Invalid valid_until: expected YYYY-MM-DD",
+                ))
+            }
+        },
+    };
+    let remarks = body
+        .remarks
+        .map(|remarks| remarks.trim().to_string())
+        .filter(|remarks| !remarks.is_empty());
+
+    let pet = match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(pet) => pet,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({"status": "error", "message": "Pet not found"})),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let vet = match data
+        .db
+        .vets(&tenant.clinic_id)
+        .select_vet(body.vet_id.clone())
+        .await
+    {
+        Ok(vet) => vet,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::UNPROCESSABLE_ENTITY, "Vet not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    if vet.vet_license_number.trim().is_empty() {
+        return Err(fail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Only a vet with a license number can issue certificates",
+        ));
+    }
+
+    let certificate_queries = data.db.certificates(&tenant.clinic_id);
+    let preventive_care = certificate_queries
+        .select_preventive_care_of_pet(pet_id.clone())
+        .await
+        .map_err(database_error)?;
+    let treatments: Vec<CertifiedTreatment> = match &body.preventive_care_ids {
+        None => preventive_care,
+        Some(ids) => {
+            if let Some(id) = ids
+                .iter()
+                .find(|id| !preventive_care.iter().any(|t| t.preventive_care_id == **id))
+            {
+                return Err(fail(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    &format!("Preventive care {} is not a record of this pet", id),
+                ));
+            }
+            preventive_care
+                .into_iter()
+                .filter(|treatment| ids.contains(&treatment.preventive_care_id))
+                .collect()
+        }
+    };
+    match body.certificate_type {
+        CertificateType::Vaccination if treatments.is_empty() => {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "The pet has no preventive care records to certify",
+            ))
+        }
+        CertificateType::Health if remarks.is_none() => {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "A health certificate needs remarks stating the pet's condition",
+            ))
+        }
+        _ => {}
+    }
+
+    let content = CertificateContent {
+        certificate_type: body.certificate_type,
+        pet: CertifiedPet {
+            pet_name: pet.pet_name,
+            pet_type: pet.pet_type,
+            pet_breed: pet.pet_breed,
+            pet_color: pet.pet_color,
+            pet_birth_date: pet.pet_birth_date,
+            owner_name: pet.owner_name,
+        },
+        issued_by: CertifyingVet {
+            vet_name: vet.vet_name,
+            vet_license_number: vet.vet_license_number,
+        },
+        treatments,
+        remarks,
+        issued_on: today,
+        valid_until,
+    };
+    let content = serde_json::to_string(&content).map_err(content_error)?;
+    let digest = content_digest(signing_key, &content);
+
+    let certificate_id = certificate_queries
+        .insert_certificate(
+            body.certificate_type.as_str(),
+            pet_id,
+            vet.vet_id,
+            new_verification_code(),
+            today,
+            valid_until,
+            content,
+            digest,
+        )
+        .await
+        .map_err(|e| match e {
+            // Clinics the pet is only shared with can't issue certificates for it.
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"status": "error", "message": "Pet not found"})),
+            ),
+            e => database_error(e),
+        })?;
+
+    let certificate = certificate_queries
+        .select_certificate(certificate_id)
+        .await
+        .map_err(database_error)?;
+    let certificate = to_certificate(certificate).map_err(content_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Certificate issued successfully",
+            "certificate": certificate,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/certificates",
+    tag = "certificates",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "Certificates issued for the pet, newest first", body = CertificatesResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_certificates(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let certificates = data
+        .db
+        .certificates(&tenant.clinic_id)
+        .select_certificates_of_pet(pet_id)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(to_certificate)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(content_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Certificates fetched successfully",
+            "certificates": certificates,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/certificates/{certificate_id}/certificate.pdf",
+    tag = "certificates",
+    params(("certificate_id" = i32, Path, description = "Certificate id")),
+    responses(
+        (status = 200, description = "Printable certificate with its verification code", body = [u8], content_type = "application/pdf"),
+        (status = 404, description = "Certificate not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_certificate_pdf(
+    Path(certificate_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let certificate = match data
+        .db
+        .certificates(&tenant.clinic_id)
+        .select_certificate(certificate_id)
+        .await
+    {
+        Ok(certificate) => certificate,
+        Err(sqlx::Error::RowNotFound) => return Err(certificate_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+    let certificate = to_certificate(certificate).map_err(content_error)?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "inline; filename=\"certificate-{}.pdf\"",
+                    certificate.certificate_number
+                ),
+            ),
+        ],
+        render_certificate(&data.clinic, &certificate),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/certificates/{certificate_id}/revoke",
+    tag = "certificates",
+    params(("certificate_id" = i32, Path, description = "Certificate id")),
+    responses(
+        (status = 200, description = "Certificate revoked; verification now reports it as revoked", body = CertificateResponse),
+        (status = 404, description = "Certificate not found", body = MessageResponse),
+        (status = 409, description = "Certificate already revoked", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn revoke_certificate(
+    Path(certificate_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let certificate_queries = data.db.certificates(&tenant.clinic_id);
+    let revoked = certificate_queries
+        .revoke_certificate(certificate_id, chrono::Local::now().date_naive())
+        .await
+        .map_err(database_error)?;
+
+    let certificate = match certificate_queries.select_certificate(certificate_id).await {
+        Ok(certificate) => certificate,
+        Err(sqlx::Error::RowNotFound) => return Err(certificate_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+    if revoked == 0 {
+        // Clinics the pet is shared with can read its certificates but not revoke them.
+        if certificate.revoked_on.is_none() {
+            return Err(certificate_not_found());
+        }
+        return Err(fail(StatusCode::CONFLICT, "Certificate is already revoked"));
+    }
+    let certificate = to_certificate(certificate).map_err(content_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Certificate revoked successfully",
+            "certificate": certificate,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/certificates/verify/{verification_code}",
+    tag = "certificates",
+    params(("verification_code" = String, Path, description = "Code printed on the certificate, e.g. `7QK2-M9XD-4HPA`")),
+    responses(
+        (status = 200, description = "The certificate's status and certified contents", body = CertificateVerificationResponse),
+        (status = 404, description = "No certificate has this code", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+        (status = 503, description = "No certificate signing key is configured", body = MessageResponse),
+    )
+)]
+pub async fn verify_certificate(
+    Path(verification_code): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let signing_key = signing_key(&data)?;
+    let Some(verification_code) = normalize_verification_code(&verification_code) else {
+        return Err(certificate_not_found());
+    };
+    let certificate = match data
+        .db
+        .certificates(&tenant.clinic_id)
+        .select_certificate_by_code(verification_code)
+        .await
+    {
+        Ok(certificate) => certificate,
+        Err(sqlx::Error::RowNotFound) => return Err(certificate_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+    let verification = verify(certificate, signing_key, chrono::Local::now().date_naive())
+        .map_err(content_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Certificate verified",
+            "verification": verification,
+        })),
+    ))
+}
+
+/// The key certificate digests are signed with; certificates are refused without one.
+fn signing_key(data: &AppState) -> Result<&[u8], ErrorResponse> {
+    data.certificate_signing_key
+        .as_deref()
+        .map(str::as_bytes)
+        .ok_or_else(|| {
+            fail(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Certificate signing is not configured",
+            )
+        })
+}
+
+fn certificate_not_found() -> ErrorResponse {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({"status": "error", "message": "Certificate not found"})),
+    )
+}
+
+fn content_error(e: serde_json::Error) -> ErrorResponse {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"status": "error", "message": format!("Invalid certificate content: {}", e)})),
+    )
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::clinic_schema::{AddClinic, SharePet};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, ErrorResponse};
+use crate::utils::tenant::Tenant;
+use crate::utils::validator::validate_field;
+use crate::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/clinics",
+    tag = "clinics",
+    responses(
+        (status = 200, description = "Every clinic of the group, by name", body = ClinicsResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_clinics(
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let clinics = data
+        .db
+        .clinics()
+        .select_all_clinics()
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Clinics fetched successfully",
+            "clinics": clinics,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/clinics",
+    tag = "clinics",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddClinic,
+    responses(
+        (status = 201, description = "Clinic added; send its id as `X-Clinic-Id` to work in it", body = ClinicResponse),
+        (status = 400, description = "The clinic name is blank", body = MessageResponse),
+        (status = 409, description = "A clinic already has this name, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_clinic(
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddClinic>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let clinic_name = body.clinic_name.trim().to_string();
+    validate_field(&Some(clinic_name.clone()), "Clinic name cannot be empty")
+        .map_err(|message| fail(StatusCode::BAD_REQUEST, &message))?;
+
+    let clinic_id = uuid::Uuid::new_v4().to_string();
+    let clinic_queries = data.db.clinics();
+    clinic_queries
+        .insert_clinic(
+            clinic_id.clone(),
+            clinic_name,
+            chrono::Utc::now().timestamp(),
+        )
+        .await
+        .map_err(|err| match handle_duplicate_entry_error(err, "Clinic") {
+            Ok(response) | Err(response) => response,
+        })?;
+    let clinic = clinic_queries
+        .select_clinic(&clinic_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Clinic added successfully",
+            "clinic": clinic,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/shares",
+    tag = "clinics",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "Other clinics that can read the pet's records", body = PetSharesResponse),
+        (status = 404, description = "This clinic has no such pet", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_shares(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    check_own_pet(&data, &tenant, &pet_id).await?;
+    shares_response(
+        &data,
+        &tenant,
+        pet_id,
+        StatusCode::OK,
+        "Pet shares fetched successfully",
+    )
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pets/{pet_id}/shares",
+    tag = "clinics",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = SharePet,
+    responses(
+        (status = 201, description = "The clinic can now read the pet, its visits and certificates, but not change them", body = PetSharesResponse),
+        (status = 404, description = "This clinic has no such pet", body = MessageResponse),
+        (status = 409, description = "The pet is already shared with the clinic, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The clinic doesn't exist or is the pet's own", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn share_pet(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<SharePet>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let clinic_id = body.clinic_id.trim().to_string();
+    if clinic_id == tenant.clinic_id {
+        return Err(fail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "A pet can't be shared with its own clinic",
+        ));
+    }
+    match data.db.clinics().select_clinic(&clinic_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(fail(StatusCode::UNPROCESSABLE_ENTITY, "Unknown clinic")),
+        Err(e) => return Err(database_error(e)),
+    }
+    check_own_pet(&data, &tenant, &pet_id).await?;
+
+    data.db
+        .pets(&tenant.clinic_id)
+        .insert_pet_share(pet_id.clone(), clinic_id, chrono::Utc::now().timestamp())
+        .await
+        .map_err(|err| match handle_duplicate_entry_error(err, "Pet share") {
+            Ok(response) | Err(response) => response,
+        })?;
+
+    shares_response(
+        &data,
+        &tenant,
+        pet_id,
+        StatusCode::CREATED,
+        "Pet shared successfully",
+    )
+    .await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pets/{pet_id}/shares/{clinic_id}",
+    tag = "clinics",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("clinic_id" = String, Path, description = "Clinic the pet is shared with"),
+    ),
+    responses(
+        (status = 200, description = "The clinic can no longer read the pet", body = PetSharesResponse),
+        (status = 404, description = "The pet isn't shared with the clinic", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn unshare_pet(
+    Path((pet_id, clinic_id)): Path<(String, String)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let deleted = data
+        .db
+        .pets(&tenant.clinic_id)
+        .delete_pet_share(pet_id.clone(), clinic_id)
+        .await
+        .map_err(database_error)?;
+    if deleted == 0 {
+        return Err(fail(StatusCode::NOT_FOUND, "Pet share not found"));
+    }
+
+    shares_response(
+        &data,
+        &tenant,
+        pet_id,
+        StatusCode::OK,
+        "Pet unshared successfully",
+    )
+    .await
+}
+
+/// Only the clinic a pet belongs to can see or change who it is shared with.
+async fn check_own_pet(
+    data: &AppState,
+    tenant: &Tenant,
+    pet_id: &str,
+) -> Result<(), ErrorResponse> {
+    match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet(pet_id.to_string())
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+async fn shares_response(
+    data: &AppState,
+    tenant: &Tenant,
+    pet_id: String,
+    status: StatusCode,
+    message: &str,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let shares = data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_shares(pet_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        status,
+        Json(json!({
+            "status": "success",
+            "message": message,
+            "shares": shares,
+        })),
+    ))
+}
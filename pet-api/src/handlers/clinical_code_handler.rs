@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::models::clinical_code_model::CatalogueImport;
+use crate::schemas::clinical_code_schema::{ClinicalCodeOptions, ImportClinicalCodes};
+use crate::utils::clinical_code::{check_code_types, read_catalogue, suggestions};
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/clinical-codes/imports",
+    tag = "clinical_codes",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = ImportClinicalCodes,
+    responses(
+        (status = 201, description = "Codes added or replaced, and matching uncoded records coded", body = CatalogueImportResponse),
+        (status = 400, description = "The catalogue can't be read, or a row is invalid", body = MessageResponse),
+        (status = 409, description = "A code is already catalogued with another type, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn import_clinical_codes(
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<ImportClinicalCodes>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let codes = read_catalogue(body.format, &body.data).map_err(rule_violation)?;
+    let code_queries = data.db.clinical_codes();
+    for chunk in codes.chunks(500) {
+        let catalogued = code_queries
+            .select_clinical_codes(
+                &chunk
+                    .iter()
+                    .map(|code| code.code.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .await
+            .map_err(database_error)?;
+        check_code_types(chunk, &catalogued).map_err(rule_violation)?;
+    }
+
+    let recoded = code_queries
+        .upsert_clinical_codes(&codes)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Clinical codes imported successfully",
+            "import": CatalogueImport {
+                codes: codes.len(),
+                synonyms: codes.iter().map(|code| code.synonyms.len()).sum(),
+                recoded,
+            },
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/clinical-codes",
+    tag = "clinical_codes",
+    params(ClinicalCodeOptions),
+    responses(
+        (status = 200, description = "Codes matching the search, best match first", body = CodeSuggestionsResponse),
+        (status = 400, description = "No search text was given", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_clinical_codes(
+    opts: Option<Query<ClinicalCodeOptions>>,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    let Some(q) = opts.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) else {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "Search text q cannot be empty",
+        ));
+    };
+
+    let matches = data
+        .db
+        .clinical_codes()
+        .search_clinical_codes(q, opts.code_type)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Clinical codes fetched successfully",
+        "codes": suggestions(q, matches, opts.limit),
+    })))
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::clinical_note_schema::{
+    AddClinicalNote, ClinicalNoteOptions, ClinicalNoteSearchOptions,
+};
+use crate::utils::clinical_note::{check_author, normalize_search, soap, validate_note};
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::pagination::page_window;
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/{service_instance_id}/clinical-notes",
+    tag = "clinical_notes",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddClinicalNote,
+    responses(
+        (status = 201, description = "Note entry added to the visit", body = ClinicalNoteResponse),
+        (status = 400, description = "The body is empty or too long", body = MessageResponse),
+        (status = 404, description = "Service instance not found", body = MessageResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The author doesn't work at this clinic, is inactive, or is neither a veterinarian nor a technician", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_clinical_note(
+    Path(service_instance_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(mut body): Json<AddClinicalNote>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_note(&mut body).map_err(rule_violation)?;
+    let author = match data
+        .db
+        .staff(&tenant.clinic_id)
+        .select_staff(body.author_id.clone())
+        .await
+    {
+        Ok(author) => author,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                &format!(
+                    "Staff member {} doesn't work at this clinic",
+                    body.author_id
+                ),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    check_author(&author).map_err(rule_violation)?;
+
+    let note_queries = data.db.clinical_notes(&tenant.clinic_id);
+    let note_id = match note_queries
+        .insert_clinical_note(&service_instance_id, &body, chrono::Utc::now().timestamp())
+        .await
+    {
+        Ok(note_id) => note_id,
+        // Shared pets' visits can be read but not written to.
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::NOT_FOUND, "Service instance not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let note = note_queries
+        .select_clinical_note(note_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Clinical note added successfully",
+            "note": note,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/service-instances/{service_instance_id}/clinical-notes",
+    tag = "clinical_notes",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ClinicalNoteOptions,
+    ),
+    responses(
+        (status = 200, description = "The visit's note entries by SOAP section", body = SoapNotesResponse),
+        (status = 404, description = "Service instance not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_clinical_notes(
+    Path(service_instance_id): Path<String>,
+    opts: Option<Query<ClinicalNoteOptions>>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    match data
+        .db
+        .service_instances(&tenant.clinic_id)
+        .get_specific_instance(service_instance_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::NOT_FOUND, "Service instance not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let author_id = opts
+        .author_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|author_id| !author_id.is_empty());
+    let notes = data
+        .db
+        .clinical_notes(&tenant.clinic_id)
+        .select_clinical_notes(&service_instance_id, opts.section, author_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Clinical notes fetched successfully",
+        "service_instance_id": service_instance_id,
+        "soap": soap(notes),
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/clinical-notes",
+    tag = "clinical_notes",
+    params(("pet_id" = String, Path, description = "Pet id"), ClinicalNoteSearchOptions),
+    responses(
+        (status = 200, description = "Page of the pet's note entries, latest visit first", body = ClinicalNotesResponse),
+        (status = 400, description = "A date is malformed, or the page or limit is out of range", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn search_clinical_notes(
+    Path(pet_id): Path<String>,
+    opts: Option<Query<ClinicalNoteSearchOptions>>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(mut opts) = opts.unwrap_or_default();
+    normalize_search(&mut opts).map_err(rule_violation)?;
+    match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let window = page_window(opts.page, opts.limit, 20).map_err(rule_violation)?;
+    let note_queries = data.db.clinical_notes(&tenant.clinic_id);
+    let total_notes = note_queries
+        .count_clinical_notes(&pet_id, &opts)
+        .await
+        .map_err(database_error)?;
+    let total_pages = window.total_pages(total_notes);
+    let notes = note_queries
+        .search_clinical_notes(&pet_id, &opts, window.limit, window.offset)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Clinical notes fetched successfully",
+        "notes": notes,
+        "total_pages": total_pages,
+    })))
+}
@@ -0,0 +1,46 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{Html, IntoResponse};
+
+use crate::openapi::openapi_json;
+
+// Swagger UI 5.17.14, vendored so the docs load without reaching a CDN.
+const SWAGGER_UI_CSS: &str = include_str!("../assets/swagger-ui/swagger-ui.css");
+const SWAGGER_UI_BUNDLE: &str = include_str!("../assets/swagger-ui/swagger-ui-bundle.js");
+
+pub async fn openapi_document() -> impl IntoResponse {
+    ([(CONTENT_TYPE, "application/json")], openapi_json())
+}
+
+pub async fn swagger_ui() -> impl IntoResponse {
+    Html(SWAGGER_UI)
+}
+
+pub async fn swagger_ui_css() -> impl IntoResponse {
+    ([(CONTENT_TYPE, "text/css; charset=utf-8")], SWAGGER_UI_CSS)
+}
+
+pub async fn swagger_ui_bundle() -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        SWAGGER_UI_BUNDLE,
+    )
+}
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <title>Paws and Claws API</title>
+    <link rel="stylesheet" href="/api/docs/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="/api/docs/swagger-ui-bundle.js"></script>
+    <script>
+        window.onload = () => {
+            window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+        };
+    </script>
+</body>
+</html>
+"##;
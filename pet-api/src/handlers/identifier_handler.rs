@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::identifier_schema::AddPetIdentifier;
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::identifier::{new_identifier, normalize_identifier};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/identifiers",
+    tag = "identifiers",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "The pet's microchips, tattoos and license tags", body = PetIdentifiersResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_identifiers(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let identifiers = data
+        .db
+        .identifiers(&tenant.clinic_id)
+        .select_identifiers(&pet_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Identifiers fetched successfully",
+        "identifiers": identifiers,
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pets/{pet_id}/identifiers",
+    tag = "identifiers",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddPetIdentifier,
+    responses(
+        (status = 201, description = "Identifier registered to the pet", body = PetIdentifierResponse),
+        (status = 400, description = "The number is malformed, e.g. a microchip that isn't a valid ISO 11784 code, or an implant date or location was given for something other than a microchip", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 409, description = "The identifier is already registered to a pet, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_pet_identifier(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddPetIdentifier>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let label = body.identifier_type.label();
+    let mut identifier = new_identifier(
+        pet_id,
+        body,
+        chrono::Local::now().date_naive(),
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(rule_violation)?;
+
+    identifier.identifier_id = match data
+        .db
+        .identifiers(&tenant.clinic_id)
+        .insert_identifier(&identifier)
+        .await
+    {
+        Ok(identifier_id) => identifier_id,
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e)
+            if e.as_database_error()
+                .is_some_and(|db_err| db_err.is_unique_violation()) =>
+        {
+            return Err(fail(
+                StatusCode::CONFLICT,
+                &format!(
+                    "{} {} is already registered to a pet",
+                    label, identifier.identifier_value
+                ),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Identifier added successfully",
+            "identifier": identifier,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pets/{pet_id}/identifiers/{identifier_id}",
+    tag = "identifiers",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("identifier_id" = i32, Path, description = "Identifier id"),
+    ),
+    responses(
+        (status = 200, description = "Identifier removed, e.g. after a tag expired", body = MessageResponse),
+        (status = 404, description = "Identifier not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn delete_pet_identifier(
+    Path((pet_id, identifier_id)): Path<(String, i32)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .db
+        .identifiers(&tenant.clinic_id)
+        .delete_identifier(&pet_id, identifier_id)
+        .await
+    {
+        // Shared pets' identifiers can be read but not changed.
+        Ok(0) => Err(fail(StatusCode::NOT_FOUND, "Identifier not found")),
+        Ok(_) => Ok(Json(json!({
+            "status": "success",
+            "message": "Identifier deleted successfully",
+        }))),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/by-identifier/{code}",
+    tag = "identifiers",
+    params(("code" = String, Path, description = "A microchip, tattoo or license tag number, as read or printed")),
+    responses(
+        (status = 200, description = "The pets carrying the identifier, whichever clinic they are registered with, and who to contact", body = IdentifiedPetsResponse),
+        (status = 400, description = "The code has no letters or digits", body = MessageResponse),
+        (status = 404, description = "No pet carries the identifier", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_by_identifier(
+    Path(code): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let identifier_value = normalize_identifier(&code);
+    if identifier_value.is_empty() {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "Identifiers contain letters or digits",
+        ));
+    }
+
+    let pets = data
+        .db
+        .identifiers(&tenant.clinic_id)
+        .select_pets_by_identifier(&identifier_value)
+        .await
+        .map_err(database_error)?;
+    if pets.is_empty() {
+        return Err(fail(
+            StatusCode::NOT_FOUND,
+            &format!("No pet carries identifier {}", identifier_value),
+        ));
+    }
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Pet found",
+        "pets": pets,
+    })))
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::import_schema::ImportRequest;
+use crate::utils::bulk_import::{run_import, ImportFailure};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/imports",
+    tag = "imports",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = ImportRequest,
+    responses(
+        (status = 200, description = "Dry run checked every row without saving", body = ImportResponse),
+        (status = 201, description = "Valid rows saved; any rejected rows are listed", body = ImportResponse),
+        (status = 400, description = "The document or mapping can't be imported", body = MessageResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "All-or-nothing import rejected because some rows are invalid", body = ImportResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn import_records(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<ImportRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match run_import(&data.db, &tenant.clinic_id, body).await {
+        Ok(report) => {
+            let (status, kind, message) = if report.dry_run {
+                (StatusCode::OK, "success", "Import checked without saving")
+            } else if report.committed {
+                (StatusCode::CREATED, "success", "Import saved")
+            } else {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "fail",
+                    "Import rejected because some rows are invalid",
+                )
+            };
+            let response = json!({
+                "status": kind,
+                "message": message,
+                "import": report,
+            });
+            Ok((status, Json(response)))
+        }
+        Err(ImportFailure::Invalid(message)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"status": "fail", "message": message})),
+        )),
+        Err(ImportFailure::Database(e)) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"status": "error", "message": format!("{:?}", e)})),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/imports/{import_id}/errors",
+    tag = "imports",
+    params(("import_id" = String, Path, description = "Import id from the import report")),
+    responses(
+        (status = 200, description = "The rejected rows as a row,field,message CSV download", body = String, content_type = "text/csv"),
+        (status = 404, description = "Import not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_import_errors(
+    Path(import_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let error_report = data
+        .db
+        .imports(&tenant.clinic_id)
+        .select_error_report(import_id.clone())
+        .await;
+
+    match error_report {
+        Ok(error_report) => Ok((
+            [
+                (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"import-{}-errors.csv\"", import_id),
+                ),
+            ],
+            error_report,
+        )),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Import not found"})),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"status": "error", "message": format!("{:?}", e)})),
+        )),
+    }
+}
@@ -1,11 +1,23 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
-use sqlx::MySqlPool;
+use tokio::time::timeout;
 
+use crate::db::queries::init_tables::SCHEMA_VERSION;
+use crate::db::repositories::health_repository::HealthRepository;
+use crate::models::health_model::{DependencyState, DependencyStatus};
 use crate::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api",
+    tag = "index",
+    responses(
+        (status = 200, description = "Server is running", body = IndexResponse),
+    )
+)]
 pub async fn index() -> impl IntoResponse {
     let response = json!({
         "message": "Welcome to Paws and Claws API. Server is running!"
@@ -14,9 +26,17 @@ pub async fn index() -> impl IntoResponse {
     (StatusCode::OK, Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/health_check",
+    tag = "index",
+    responses(
+        (status = 200, description = "Database is reachable", body = HealthCheckResponse),
+        (status = 500, description = "Database is unreachable", body = HealthCheckResponse),
+    )
+)]
 pub async fn health_check(State(data): State<Arc<AppState>>) -> impl IntoResponse {
-    let pool: &MySqlPool = &data.db;
-    let result = sqlx::query("SELECT 1").execute(pool).await;
+    let result = data.db.health().ping().await;
 
     match result {
         Ok(_) => {
@@ -35,3 +55,156 @@ pub async fn health_check(State(data): State<Arc<AppState>>) -> impl IntoRespons
         }
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/livez",
+    tag = "index",
+    responses(
+        (status = 200, description = "Process is alive", body = LivenessResponse),
+    )
+)]
+pub async fn livez() -> impl IntoResponse {
+    let response = json!({
+        "status": "alive",
+        "message": "Server is running"
+    });
+
+    (StatusCode::OK, Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/readyz",
+    tag = "index",
+    responses(
+        (status = 200, description = "All dependencies are up", body = ReadinessResponse),
+        (status = 503, description = "A dependency is down", body = ReadinessResponse),
+    )
+)]
+pub async fn readyz(State(data): State<Arc<AppState>>) -> impl IntoResponse {
+    let health_queries = data.db.health();
+    let probe_timeout = data.readiness_probe_timeout;
+
+    let database = check_database(health_queries.as_ref(), probe_timeout).await;
+    let schema = if database.status == DependencyState::Up {
+        check_schema(health_queries.as_ref(), probe_timeout).await
+    } else {
+        DependencyStatus {
+            name: "schema".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: "Skipped because the database is unavailable".to_string(),
+            details: Some(json!({ "expected_version": SCHEMA_VERSION })),
+        }
+    };
+
+    let dependencies = vec![database, schema];
+    let is_ready = dependencies
+        .iter()
+        .all(|dependency| dependency.status == DependencyState::Up);
+
+    let response = json!({
+        "status": if is_ready { "ready" } else { "not_ready" },
+        "dependencies": dependencies,
+    });
+
+    if is_ready {
+        (StatusCode::OK, Json(response))
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Json(response))
+    }
+}
+
+async fn check_database(
+    health_queries: &dyn HealthRepository,
+    probe_timeout: Duration,
+) -> DependencyStatus {
+    let details = json!({
+        "pool_size": health_queries.pool_size(),
+        "idle_connections": health_queries.idle_connections(),
+    });
+
+    if health_queries.is_pool_closed() {
+        return DependencyStatus {
+            name: "database".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: "Connection pool is closed".to_string(),
+            details: Some(details),
+        };
+    }
+
+    match timeout(probe_timeout, health_queries.ping()).await {
+        Ok(Ok(latency)) => DependencyStatus {
+            name: "database".to_string(),
+            status: DependencyState::Up,
+            latency_ms: Some(latency.as_secs_f64() * 1000.0),
+            message: "Probe query succeeded".to_string(),
+            details: Some(details),
+        },
+        Ok(Err(e)) => DependencyStatus {
+            name: "database".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: format!("Probe query failed: {}", e),
+            details: Some(details),
+        },
+        Err(_) => DependencyStatus {
+            name: "database".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: timed_out(probe_timeout),
+            details: Some(details),
+        },
+    }
+}
+
+async fn check_schema(
+    health_queries: &dyn HealthRepository,
+    probe_timeout: Duration,
+) -> DependencyStatus {
+    match timeout(probe_timeout, health_queries.select_schema_version()).await {
+        Ok(Ok(applied_version)) if applied_version == Some(SCHEMA_VERSION) => DependencyStatus {
+            name: "schema".to_string(),
+            status: DependencyState::Up,
+            latency_ms: None,
+            message: "Schema is up to date".to_string(),
+            details: Some(json!({
+                "expected_version": SCHEMA_VERSION,
+                "applied_version": applied_version,
+            })),
+        },
+        Ok(Ok(applied_version)) => DependencyStatus {
+            name: "schema".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: "Applied schema version does not match the expected version".to_string(),
+            details: Some(json!({
+                "expected_version": SCHEMA_VERSION,
+                "applied_version": applied_version,
+            })),
+        },
+        Ok(Err(e)) => DependencyStatus {
+            name: "schema".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: format!("Failed to read the schema version: {}", e),
+            details: Some(json!({ "expected_version": SCHEMA_VERSION })),
+        },
+        Err(_) => DependencyStatus {
+            name: "schema".to_string(),
+            status: DependencyState::Down,
+            latency_ms: None,
+            message: timed_out(probe_timeout),
+            details: Some(json!({ "expected_version": SCHEMA_VERSION })),
+        },
+    }
+}
+
+fn timed_out(probe_timeout: Duration) -> String {
+    format!(
+        "Probe query timed out after {} ms",
+        probe_timeout.as_millis()
+    )
+}
@@ -0,0 +1,345 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::models::lab_model::{LabOrder, LabOrderModel};
+use crate::schemas::lab_schema::{
+    AddLabOrder, AddLabResults, LabTrendOptions, ReferenceRangeOptions,
+};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::lab::{prepare_results, to_lab_order, trends, validate_order};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/{service_instance_id}/lab-orders",
+    tag = "labs",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddLabOrder,
+    responses(
+        (status = 201, description = "Lab order placed", body = LabOrderResponse),
+        (status = 400, description = "The panel is missing or too long", body = MessageResponse),
+        (status = 404, description = "Service instance not found", body = MessageResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The vet doesn't work at this clinic", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_lab_order(
+    Path(service_instance_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(mut body): Json<AddLabOrder>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_order(&mut body).map_err(rule_violation)?;
+    match data
+        .db
+        .vets(&tenant.clinic_id)
+        .select_vet(body.vet_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                &format!("Vet {} doesn't work at this clinic", body.vet_id),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let lab_queries = data.db.labs(&tenant.clinic_id);
+    let lab_order_id = match lab_queries
+        .insert_lab_order(&service_instance_id, &body, chrono::Utc::now().timestamp())
+        .await
+    {
+        Ok(lab_order_id) => lab_order_id,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::NOT_FOUND, "Service instance not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let order = lab_queries
+        .select_lab_order(lab_order_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Lab order placed successfully",
+            "lab_order": to_lab_order(order, Vec::new()),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/service-instances/{service_instance_id}/lab-orders",
+    tag = "labs",
+    params(("service_instance_id" = String, Path, description = "Service instance id")),
+    responses(
+        (status = 200, description = "The visit's lab orders with their results", body = LabOrdersResponse),
+        (status = 404, description = "Service instance not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_lab_orders(
+    Path(service_instance_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .db
+        .service_instances(&tenant.clinic_id)
+        .get_specific_instance(service_instance_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::NOT_FOUND, "Service instance not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let orders = data
+        .db
+        .labs(&tenant.clinic_id)
+        .select_lab_orders(&service_instance_id)
+        .await
+        .map_err(database_error)?;
+    let lab_orders = with_results(&data, &tenant, orders).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Lab orders fetched successfully",
+        "lab_orders": lab_orders,
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/lab-orders/{lab_order_id}",
+    tag = "labs",
+    params(("lab_order_id" = i32, Path, description = "Lab order id")),
+    responses(
+        (status = 200, description = "The lab order with its results", body = LabOrderResponse),
+        (status = 404, description = "Lab order not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_lab_order(
+    Path(lab_order_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let order = select_order(&data, &tenant, lab_order_id).await?;
+    order_response(&data, &tenant, order, "Lab order fetched successfully").await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/lab-orders/{lab_order_id}/results",
+    tag = "labs",
+    params(
+        ("lab_order_id" = i32, Path, description = "Lab order id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddLabResults,
+    responses(
+        (status = 201, description = "Results entered and flagged against their reference ranges", body = LabOrderResponse),
+        (status = 400, description = "No results, an analyte is blank, repeated or has no unit, or a range is inverted", body = MessageResponse),
+        (status = 404, description = "Lab order not found", body = MessageResponse),
+        (status = 409, description = "An analyte already has a result on this order", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_lab_results(
+    Path(lab_order_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddLabResults>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let order = select_order(&data, &tenant, lab_order_id).await?;
+    let lab_queries = data.db.labs(&tenant.clinic_id);
+    let ranges = lab_queries
+        .select_reference_ranges(Some(&order.pet_type))
+        .await
+        .map_err(database_error)?;
+    let results = prepare_results(body.results, &ranges).map_err(rule_violation)?;
+
+    let inserted = lab_queries
+        .insert_lab_results(lab_order_id, &results, chrono::Utc::now().timestamp())
+        .await
+        .map_err(
+            |err| match handle_duplicate_entry_error(err, "A result for this analyte") {
+                Ok(response) | Err(response) => response,
+            },
+        )?;
+    if inserted == 0 {
+        // Shared pets' lab orders can be read but not added to.
+        return Err(fail(StatusCode::NOT_FOUND, "Lab order not found"));
+    }
+
+    let order = select_order(&data, &tenant, lab_order_id).await?;
+    let (_, response) =
+        order_response(&data, &tenant, order, "Lab results entered successfully").await?;
+    Ok((StatusCode::CREATED, response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/lab-trends",
+    tag = "labs",
+    params(("pet_id" = String, Path, description = "Pet id"), LabTrendOptions),
+    responses(
+        (status = 200, description = "Each analyte's results across the pet's visits", body = LabTrendsResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_lab_trends(
+    Path(pet_id): Path<String>,
+    opts: Option<Query<LabTrendOptions>>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    let pet = match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(pet) => pet,
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let analyte = opts
+        .analyte
+        .map(|analyte| analyte.trim().to_uppercase())
+        .filter(|analyte| !analyte.is_empty());
+    let lab_queries = data.db.labs(&tenant.clinic_id);
+    let points = lab_queries
+        .select_lab_trends(&pet_id, analyte.as_deref())
+        .await
+        .map_err(database_error)?;
+    let ranges = lab_queries
+        .select_reference_ranges(Some(&pet.pet_type))
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Lab trends fetched successfully",
+        "pet_id": pet.pet_id,
+        "trends": trends(points, &ranges),
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/lab-reference-ranges",
+    tag = "labs",
+    params(ReferenceRangeOptions),
+    responses(
+        (status = 200, description = "Reference ranges by analyte and species", body = ReferenceRangesResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_reference_ranges(
+    opts: Option<Query<ReferenceRangeOptions>>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    let reference_ranges = data
+        .db
+        .labs(&tenant.clinic_id)
+        .select_reference_ranges(opts.species.as_deref().map(str::trim))
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Reference ranges fetched successfully",
+        "reference_ranges": reference_ranges,
+    })))
+}
+
+async fn select_order(
+    data: &AppState,
+    tenant: &Tenant,
+    lab_order_id: i32,
+) -> Result<LabOrderModel, ErrorResponse> {
+    match data
+        .db
+        .labs(&tenant.clinic_id)
+        .select_lab_order(lab_order_id)
+        .await
+    {
+        Ok(order) => Ok(order),
+        Err(sqlx::Error::RowNotFound) => Err(fail(StatusCode::NOT_FOUND, "Lab order not found")),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/// Loads the results of every order in one query.
+async fn with_results(
+    data: &AppState,
+    tenant: &Tenant,
+    orders: Vec<LabOrderModel>,
+) -> Result<Vec<LabOrder>, ErrorResponse> {
+    let lab_order_ids: Vec<i32> = orders.iter().map(|order| order.lab_order_id).collect();
+    let mut results = data
+        .db
+        .labs(&tenant.clinic_id)
+        .select_lab_results(&lab_order_ids)
+        .await
+        .map_err(database_error)?;
+
+    Ok(orders
+        .into_iter()
+        .map(|order| {
+            let (own, rest) = std::mem::take(&mut results)
+                .into_iter()
+                .partition(|result| result.lab_order_id == order.lab_order_id);
+            results = rest;
+            to_lab_order(order, own)
+        })
+        .collect())
+}
+
+async fn order_response(
+    data: &AppState,
+    tenant: &Tenant,
+    order: LabOrderModel,
+    message: &str,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let results = data
+        .db
+        .labs(&tenant.clinic_id)
+        .select_lab_results(&[order.lab_order_id])
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": message,
+            "lab_order": to_lab_order(order, results),
+        })),
+    ))
+}
@@ -1,6 +1,19 @@
+pub(crate) mod boarding_handler;
+pub(crate) mod certificate_handler;
+pub(crate) mod clinic_handler;
+pub(crate) mod clinical_code_handler;
+pub(crate) mod clinical_note_handler;
+pub(crate) mod docs_handler;
+pub(crate) mod identifier_handler;
+pub(crate) mod import_handler;
 pub(crate) mod index_handler;
+pub(crate) mod lab_handler;
 pub(crate) mod owner_handler;
 pub(crate) mod pet_handler;
+pub(crate) mod portal_handler;
+pub(crate) mod problem_handler;
 pub(crate) mod service_instance_handler;
+pub(crate) mod staff_handler;
 pub(crate) mod statistics_handler;
+pub(crate) mod surgical_case_handler;
 pub(crate) mod vet_handler;
@@ -1,42 +1,57 @@
 use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{header::ETAG, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde_json::json;
 
 use crate::utils::validator::validate_field;
 use crate::{
-    db::queries::owner_queries::OwnerQueries,
     schemas::{
         helper_schema::FilterOptions,
         owner_schema::{AddOwner, UpdateOwner},
     },
     utils::{
-        handle_duplicate_error::handle_duplicate_entry_error, model_to_response::filter_db_record,
+        etag::{check_if_match, is_not_modified, representation_etag, row_etag},
+        handle_duplicate_error::handle_duplicate_entry_error,
+        handler_error::rule_violation,
+        model_to_response::filter_db_record,
+        pagination::page_window,
+        tenant::Tenant,
     },
     AppState,
 };
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/owners",
+    tag = "owners",
+    params(FilterOptions),
+    responses(
+        (status = 200, description = "Page of owners", body = OwnersResponse),
+        (status = 400, description = "The page or limit is out of range", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_owners(
     opts: Option<Query<FilterOptions>>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let Query(opts) = opts.unwrap_or_default();
 
-    let owner_queries = OwnerQueries::new(Arc::new(data.db.clone()));
-    let limit = opts.limit.unwrap_or(10);
-    let offset = (opts.page.unwrap_or(1) - 1) * limit;
+    let owner_queries = data.db.owners(&tenant.clinic_id);
+    let window = page_window(opts.page, opts.limit, 10).map_err(rule_violation)?;
     let search = opts.search.clone();
     let total_owners = owner_queries
         .count_all_owners(search.clone())
         .await
         .unwrap_or_default();
-    let total_pages = (total_owners as f64 / limit as f64).ceil() as i32;
+    let total_pages = window.total_pages(total_owners);
 
     let owners = owner_queries
-        .select_all_owners(limit as i32, offset as i32, search)
+        .select_all_owners(window.limit, window.offset, search)
         .await;
 
     match owners {
@@ -58,11 +73,26 @@ pub async fn get_owners(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/owners/{owner_id}",
+    tag = "owners",
+    params(("owner_id" = String, Path, description = "Owner id")),
+    responses(
+        (status = 200, description = "Owner with their pets", body = OwnerAndPetsResponse,
+            headers(("ETag" = String, description = "Changes whenever the owner or one of their pets does"))),
+        (status = 304, description = "The `If-None-Match` tag is still current"),
+        (status = 404, description = "Owner not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_owner_and_pets(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     Path(owner_id): Path<String>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let owner_queries = OwnerQueries::new(Arc::new(data.db.clone()));
+    let owner_queries = data.db.owners(&tenant.clinic_id);
 
     match owner_queries.get_owner_and_pets(owner_id.clone()).await {
         Ok(owner_with_pets) => {
@@ -72,9 +102,18 @@ pub async fn get_owner_and_pets(
                     "owner": filter_db_record(&owner_with_pets.owner),
                     "pets": owner_with_pets.pets.into_iter().map(|model| filter_db_record(&model)).collect::<Vec<_>>()
             });
+            let etag = representation_etag(owner_with_pets.owner.row_version, &response);
 
-            Ok((StatusCode::OK, Json(response)))
+            if is_not_modified(&headers, &etag) {
+                return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(ETAG, etag)], Json(response)).into_response())
         }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Owner not found"})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"status": "error", "message": format!("{:?}", e)})),
@@ -82,13 +121,26 @@ pub async fn get_owner_and_pets(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/owners",
+    tag = "owners",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddOwner,
+    responses(
+        (status = 201, description = "Owner created", body = OwnerResponse),
+        (status = 409, description = "Owner email already exists, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn add_owner(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     Json(body): Json<AddOwner>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let owner_id = uuid::Uuid::new_v4().to_string();
 
-    let owner_queries = OwnerQueries::new(Arc::new(data.db.clone()));
+    let owner_queries = data.db.owners(&tenant.clinic_id);
 
     let query_result = owner_queries
         .insert_owner(
@@ -121,9 +173,30 @@ pub async fn add_owner(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/v1/owners/{owner_id}",
+    tag = "owners",
+    params(
+        ("owner_id" = String, Path, description = "Owner id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the owner still has this ETag"),
+    ),
+    request_body = UpdateOwner,
+    responses(
+        (status = 200, description = "Owner updated", body = OwnerResponse,
+            headers(("ETag" = String, description = "Tag of the updated owner"))),
+        (status = 400, description = "A field is blank", body = MessageResponse),
+        (status = 404, description = "Owner not found", body = MessageResponse),
+        (status = 409, description = "Owner email already exists", body = MessageResponse),
+        (status = 412, description = "The owner changed since the `If-Match` tag", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn update_owner(
     Path(owner_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(body): Json<UpdateOwner>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     if let Err(message) = validate_owner_fields(&body) {
@@ -133,12 +206,15 @@ pub async fn update_owner(
         ));
     }
 
-    let owner_queries = OwnerQueries::new(Arc::new(data.db.clone()));
+    let owner_queries = data.db.owners(&tenant.clinic_id);
 
     let owner = owner_queries.select_owner(owner_id.clone()).await;
 
     match owner {
-        Ok(_) => {
+        Ok(owner) => {
+            let expected_version =
+                check_if_match(&headers, owner.row_version).map_err(|_| owner_modified_error())?;
+
             let owner_name = body.owner_name.clone();
             let owner_email = body.owner_email.clone();
             let owner_phone_number = body.owner_phone_number.clone();
@@ -151,11 +227,17 @@ pub async fn update_owner(
                     owner_email,
                     owner_phone_number,
                     owner_address,
+                    expected_version,
                 )
                 .await;
 
-            if let Err(err) = query_result {
-                return handle_duplicate_entry_error(err, "Owner");
+            match query_result {
+                Ok(0) if expected_version.is_some() => return Err(owner_modified_error()),
+                Ok(_) => {}
+                Err(err) => {
+                    return handle_duplicate_entry_error(err, "Owner")
+                        .map(IntoResponse::into_response)
+                }
             }
 
             match owner_queries.select_owner(owner_id.clone()).await {
@@ -166,7 +248,12 @@ pub async fn update_owner(
                             "owner": filter_db_record(&owner)
                     });
 
-                    Ok((StatusCode::OK, Json(response)))
+                    Ok((
+                        StatusCode::OK,
+                        [(ETAG, row_etag(owner.row_version))],
+                        Json(response),
+                    )
+                        .into_response())
                 }
                 Err(e) => Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -181,11 +268,23 @@ pub async fn update_owner(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/owners/{owner_id}",
+    tag = "owners",
+    params(("owner_id" = String, Path, description = "Owner id")),
+    responses(
+        (status = 200, description = "Owner deleted", body = MessageResponse),
+        (status = 404, description = "Owner not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn delete_owner(
     Path(owner_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let owner_queries = OwnerQueries::new(Arc::new(data.db.clone()));
+    let owner_queries = data.db.owners(&tenant.clinic_id);
 
     match owner_queries.delete_owner(owner_id).await {
         Ok(rows_affected) => {
@@ -210,6 +309,13 @@ pub async fn delete_owner(
     }
 }
 
+fn owner_modified_error() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        Json(json!({"status": "fail", "message": "Owner was modified by another request"})),
+    )
+}
+
 fn validate_owner_fields(body: &UpdateOwner) -> Result<(), String> {
     validate_field(&body.owner_name, "Owner name cannot be empty")?;
     validate_field(&body.owner_email, "Owner email cannot be empty")?;
@@ -1,45 +1,89 @@
 use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde_json::json;
 
-use crate::db::queries::pet_queries::PetQueries;
-use crate::schemas::helper_schema::FilterOptions;
-use crate::schemas::pet_schema::{AddPet, UpdatePet};
+use crate::models::problem_model::PetWithAlerts;
+use crate::schemas::helper_schema::{
+    DateRangeOptions, FilterOptions, ServiceInstanceFilterOptions, ServiceInstanceSortField,
+    SortOrder,
+};
+use crate::schemas::pet_schema::{AddPet, PetListOptions, UpdatePet, UpdatePetStatus};
+use crate::utils::etag::{check_if_match, is_not_modified, representation_etag, row_etag};
 use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, rule_violation};
+use crate::utils::medical_record::{render_medical_record, MedicalRecord};
+use crate::utils::pagination::page_window;
+use crate::utils::pet_status::apply_status_change;
+use crate::utils::problem_list::{alerts, alerts_by_pet};
+use crate::utils::tenant::Tenant;
 use crate::utils::{model_to_response::filter_db_record, validator::validate_field};
 use crate::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets",
+    tag = "pets",
+    params(FilterOptions, PetListOptions),
+    responses(
+        (status = 200, description = "Page of pets", body = PetsResponse),
+        (status = 400, description = "The page or limit is out of range", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_pets(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     opts: Option<Query<FilterOptions>>,
+    list_opts: Option<Query<PetListOptions>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let Query(opts) = opts.unwrap_or_default();
+    let Query(list_opts) = list_opts.unwrap_or_default();
+    let pet_status = list_opts.status.map(|status| status.as_str().to_string());
 
-    let pet_queries = PetQueries::new(Arc::new(data.db.clone()));
+    let pet_queries = data.db.pets(&tenant.clinic_id);
 
-    let limit = opts.limit.unwrap_or(10);
-    let offset = (opts.page.unwrap_or(1) - 1) * limit;
+    let window = page_window(opts.page, opts.limit, 10).map_err(rule_violation)?;
     let search = opts.search.clone();
     let total_pets = pet_queries
-        .count_all_pets(search.clone())
+        .count_all_pets(search.clone(), pet_status.clone())
         .await
         .unwrap_or_default();
-    let total_pages = (total_pets as f64 / limit as f64).ceil() as i32;
+    let total_pages = window.total_pages(total_pets);
 
     let pets = pet_queries
-        .select_all_pets(limit as i32, offset as i32, search)
+        .select_all_pets(window.limit, window.offset, search, pet_status)
         .await;
 
     match pets {
         Ok(pets) => {
+            let pet_ids: Vec<String> = pets.iter().map(|pet| pet.pet_id.clone()).collect();
+            let mut alerts = match data
+                .db
+                .problems(&tenant.clinic_id)
+                .select_active_problems(&pet_ids)
+                .await
+            {
+                Ok(problems) => alerts_by_pet(problems),
+                Err(e) => {
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({"status": "error", "message": format!("{:?}", e)})),
+                    ))
+                }
+            };
+            let pets = pets.into_iter().map(|pet| PetWithAlerts {
+                alerts: alerts.remove(&pet.pet_id).unwrap_or_default(),
+                pet,
+            });
             let response = json!({
                 "status":"success",
                 "message":"Pets fetched successfully",
-                "pets": pets.into_iter().map(|model| filter_db_record(&model)).collect::<Vec<_>>(),
+                "pets": pets.map(|model| filter_db_record(&model)).collect::<Vec<_>>(),
                 "total_pages": total_pages,
 
             });
@@ -52,13 +96,27 @@ pub async fn get_pets(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/pets",
+    tag = "pets",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddPet,
+    responses(
+        (status = 201, description = "Pet created", body = PetResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The owner isn't one of this clinic's", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn add_pet(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     Json(body): Json<AddPet>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let pet_id = uuid::Uuid::new_v4().to_string();
-    let pet_queries = PetQueries::new(Arc::new(data.db.clone()));
-    let _pet = pet_queries
+    let pet_queries = data.db.pets(&tenant.clinic_id);
+    let inserted = pet_queries
         .insert_pet(
             pet_id.clone(),
             body.pet_name.to_string(),
@@ -70,6 +128,9 @@ pub async fn add_pet(
             body.owner_id.to_string(),
         )
         .await;
+    if let Ok(0) = inserted {
+        return Err(owner_not_found());
+    }
 
     match pet_queries.select_pet(pet_id.clone()).await {
         Ok(pet) => {
@@ -87,11 +148,23 @@ pub async fn add_pet(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pets/{pet_id}",
+    tag = "pets",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "Pet and its whole history deleted; to retire a pet and keep its records, change its status instead", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn delete_pet(
     Path(pet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let pet_queries = PetQueries::new(Arc::new(data.db.clone()));
+    let pet_queries = data.db.pets(&tenant.clinic_id);
     match pet_queries.delete_pet(pet_id).await {
         Ok(rows_affected) => {
             if rows_affected > 0 {
@@ -111,9 +184,30 @@ pub async fn delete_pet(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/v1/pets/{pet_id}",
+    tag = "pets",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the pet still has this ETag"),
+    ),
+    request_body = UpdatePet,
+    responses(
+        (status = 200, description = "Pet updated", body = PetResponse,
+            headers(("ETag" = String, description = "Tag of the updated pet"))),
+        (status = 400, description = "A field is blank or the weight is negative", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 412, description = "The pet changed since the `If-Match` tag", body = MessageResponse),
+        (status = 422, description = "The new owner isn't one of this clinic's", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn update_pet(
     Path(pet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(body): Json<UpdatePet>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     if let Err(message) = validate_pet_fields(&body) {
@@ -123,16 +217,32 @@ pub async fn update_pet(
         ));
     }
 
-    let pet_queries = PetQueries::new(Arc::new(data.db.clone()));
+    if let Some(owner_id) = &body.owner_id {
+        match data
+            .db
+            .owners(&tenant.clinic_id)
+            .select_owner(owner_id.clone())
+            .await
+        {
+            Ok(_) => {}
+            Err(sqlx::Error::RowNotFound) => return Err(owner_not_found()),
+            Err(e) => return Err(database_error(e)),
+        }
+    }
+
+    let pet_queries = data.db.pets(&tenant.clinic_id);
     let pet = pet_queries.select_pet(pet_id.clone()).await;
 
     match pet {
-        Ok(_) => {
+        Ok(pet) => {
+            let expected_version =
+                check_if_match(&headers, pet.row_version).map_err(|_| pet_modified_error())?;
+
             let pet_name = body.pet_name.clone();
             let pet_birth_date = body.pet_birth_date.clone();
             let pet_type = body.pet_type.clone();
             let pet_breed = body.pet_breed.clone();
-            let pet_weight = body.pet_weight.clone();
+            let pet_weight = body.pet_weight;
             let pet_color = body.pet_color.clone();
             let owner_id = body.owner_id.clone();
 
@@ -146,11 +256,17 @@ pub async fn update_pet(
                     pet_weight,
                     pet_color,
                     owner_id,
+                    expected_version,
                 )
                 .await;
 
-            if let Err(err) = query_result {
-                return handle_duplicate_entry_error(err, "Pet");
+            match query_result {
+                Ok(0) if expected_version.is_some() => return Err(pet_modified_error()),
+                Ok(_) => {}
+                Err(err) => {
+                    return handle_duplicate_entry_error(err, "Pet")
+                        .map(IntoResponse::into_response)
+                }
             }
 
             match pet_queries.select_pet(pet_id.clone()).await {
@@ -161,7 +277,12 @@ pub async fn update_pet(
                             "pet": filter_db_record(&pet)
                     });
 
-                    Ok((StatusCode::OK, Json(response)))
+                    Ok((
+                        StatusCode::OK,
+                        [(ETAG, row_etag(pet.row_version))],
+                        Json(response),
+                    )
+                        .into_response())
                 }
                 Err(e) => Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -176,21 +297,57 @@ pub async fn update_pet(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}",
+    tag = "pets",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "Pet with owner details", body = PetDetailsResponse,
+            headers(("ETag" = String, description = "Changes whenever the pet or its owner's details do"))),
+        (status = 304, description = "The `If-None-Match` tag is still current"),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_pet(
     Path(pet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let pet_queries = PetQueries::new(Arc::new(data.db.clone()));
+    let pet_queries = data.db.pets(&tenant.clinic_id);
     match pet_queries.select_pet_details(pet_id).await {
         Ok(pet) => {
+            let problems = data
+                .db
+                .problems(&tenant.clinic_id)
+                .select_active_problems(std::slice::from_ref(&pet.pet_id))
+                .await
+                .map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({"status": "error", "message": format!("{:?}", e)})),
+                    )
+                })?;
+            let row_version = pet.row_version;
             let response = json!({
                 "status":"success",
                 "message":"Pet fetched successfully",
-                "pet": filter_db_record(&pet)
+                "pet": filter_db_record(&PetWithAlerts { pet, alerts: alerts(problems) })
             });
+            let etag = representation_etag(row_version, &response);
 
-            Ok((StatusCode::OK, Json(response)))
+            if is_not_modified(&headers, &etag) {
+                return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(ETAG, etag)], Json(response)).into_response())
         }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Pet not found"})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"status": "error", "message": format!("{:?}", e)})),
@@ -198,6 +355,20 @@ pub async fn get_pet(
     }
 }
 
+fn owner_not_found() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(json!({"status": "error", "message": "Owner not found"})),
+    )
+}
+
+fn pet_modified_error() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        Json(json!({"status": "fail", "message": "Pet was modified by another request"})),
+    )
+}
+
 fn validate_pet_fields(body: &UpdatePet) -> Result<(), String> {
     validate_field(&body.pet_name, "Pet name cannot be empty")?;
     validate_field(&body.pet_type, "Pet type cannot be empty")?;
@@ -213,3 +384,270 @@ fn validate_pet_fields(body: &UpdatePet) -> Result<(), String> {
 
     Ok(())
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/record.pdf",
+    tag = "pets",
+    params(("pet_id" = String, Path, description = "Pet id"), DateRangeOptions),
+    responses(
+        (status = 200, description = "Printable medical record with the pet, its owner and every visit in the date range",
+            body = [u8], content_type = "application/pdf"),
+        (status = 400, description = "A date is malformed or the range is reversed", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_record_pdf(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Query(opts): Query<DateRangeOptions>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let mut range = [None, None];
+    for (date, (name, value)) in range.iter_mut().zip([
+        ("start_date", &opts.start_date),
+        ("end_date", &opts.end_date),
+    ]) {
+        let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        match chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+            Ok(parsed) => *date = Some(parsed),
+            Err(_) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "status": "fail",
+                        "message": format!("Invalid {}: expected YYYY-MM-DD", name)
+                    })),
+                ))
+            }
+        }
+    }
+    let [start_date, end_date] = range;
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if start > end {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"status": "fail", "message": "start_date is after end_date"})),
+            ));
+        }
+    }
+
+    let pet = match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(pet) => pet,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({"status": "error", "message": "Pet not found"})),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let owner = data
+        .db
+        .owners(&tenant.clinic_id)
+        .select_owner(pet.owner_id.clone())
+        .await
+        .map_err(database_error)?;
+
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
+    let filter = ServiceInstanceFilterOptions {
+        start_date: start_date.map(|date| date.to_string()),
+        end_date: end_date.map(|date| date.to_string()),
+        pet_id: Some(pet_id),
+        sort_by: Some(ServiceInstanceSortField::ServiceDate),
+        order: Some(SortOrder::Asc),
+        ..Default::default()
+    };
+    const PAGE_SIZE: i32 = 100;
+    let mut service_instance_ids = Vec::new();
+    loop {
+        let page = service_instance_queries
+            .get_all_service_instances(&filter, PAGE_SIZE, service_instance_ids.len() as i32)
+            .await
+            .map_err(database_error)?;
+        let page_len = page.len();
+        service_instance_ids.extend(page.into_iter().map(|visit| visit.service_instance_id));
+        if page_len < PAGE_SIZE as usize {
+            break;
+        }
+    }
+    let visits = service_instance_queries
+        .get_specific_instances(&service_instance_ids)
+        .await
+        .map_err(database_error)?;
+
+    let pdf = render_medical_record(&MedicalRecord {
+        clinic: &data.clinic,
+        pet: &pet,
+        owner: &owner,
+        visits: &visits,
+        start_date,
+        end_date,
+        generated_on: chrono::Local::now().date_naive(),
+    });
+    let file_name = pet
+        .pet_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>();
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "inline; filename=\"{}-medical-record.pdf\"",
+                    if file_name.is_empty() {
+                        "pet"
+                    } else {
+                        &file_name
+                    }
+                ),
+            ),
+        ],
+        pdf,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/status",
+    tag = "pets",
+    params(("pet_id" = String, Path, description = "Pet id")),
+    responses(
+        (status = 200, description = "The pet's lifecycle status, with its death record if it is deceased", body = PetStatusResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_status(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_status(pet_id)
+        .await
+    {
+        Ok(pet_status) => Ok(Json(json!({
+            "status": "success",
+            "message": "Pet status fetched successfully",
+            "pet_status": pet_status,
+        }))),
+        Err(sqlx::Error::RowNotFound) => Err(pet_not_found()),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/pets/{pet_id}/status",
+    tag = "pets",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("If-Match" = Option<String>, Header, description = "Only change the status if the pet still has this ETag"),
+    ),
+    request_body = UpdatePetStatus,
+    responses(
+        (status = 200, description = "Status changed. A deceased pet's death is recorded, and it leaves the pet list and follow-up reminders", body = PetStatusResponse,
+            headers(("ETag" = String, description = "Tag of the updated pet"))),
+        (status = 400, description = "The date of death is missing, in the future or before the pet was born, the euthanasia consent is incomplete or postdates the death, or death details were given for a pet that isn't deceased", body = MessageResponse),
+        (status = 404, description = "Pet not found, or only shared with this clinic", body = MessageResponse),
+        (status = 409, description = "The pet is already deceased", body = MessageResponse),
+        (status = 412, description = "The pet changed since the `If-Match` tag", body = MessageResponse),
+        (status = 422, description = "The euthanizing vet doesn't work at this clinic", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_pet_status(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(body): Json<UpdatePetStatus>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let pet_queries = data.db.pets(&tenant.clinic_id);
+    let pet = match pet_queries.select_pet_details(pet_id.clone()).await {
+        Ok(pet) => pet,
+        Err(sqlx::Error::RowNotFound) => return Err(pet_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+    let expected_version =
+        check_if_match(&headers, pet.row_version).map_err(|_| pet_modified_error())?;
+    let mut pet_status = pet_queries
+        .select_pet_status(pet_id.clone())
+        .await
+        .map_err(database_error)?;
+
+    if let Some(euthanasia) = &body.euthanasia {
+        match data
+            .db
+            .vets(&tenant.clinic_id)
+            .select_vet(euthanasia.vet_id.clone())
+            .await
+        {
+            Ok(_) => {}
+            Err(sqlx::Error::RowNotFound) => {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "status": "fail",
+                        "message": format!("Vet {} doesn't work at this clinic", euthanasia.vet_id),
+                    })),
+                ))
+            }
+            Err(e) => return Err(database_error(e)),
+        }
+    }
+
+    apply_status_change(
+        &mut pet_status,
+        body,
+        pet.pet_birth_date,
+        chrono::Local::now().date_naive(),
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(rule_violation)?;
+
+    match pet_queries
+        .update_pet_status(&pet_status, expected_version)
+        .await
+    {
+        Ok(0) if expected_version.is_some() => return Err(pet_modified_error()),
+        // Shared pets can be read but not changed.
+        Ok(0) => return Err(pet_not_found()),
+        Ok(_) => {}
+        Err(e) => return Err(database_error(e)),
+    }
+    let pet = pet_queries
+        .select_pet(pet_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        [(ETAG, row_etag(pet.row_version))],
+        Json(json!({
+            "status": "success",
+            "message": "Pet status updated successfully",
+            "pet_status": pet_status,
+        })),
+    ))
+}
+
+fn pet_not_found() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({"status": "error", "message": "Pet not found"})),
+    )
+}
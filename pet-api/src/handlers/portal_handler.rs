@@ -0,0 +1,373 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::models::portal_model::PortalSession;
+use crate::schemas::helper_schema::DateRangeOptions;
+use crate::schemas::portal_schema::{CreatePortalSession, RequestLoginLink, UpdateContactDetails};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, ErrorResponse};
+use crate::utils::model_to_response::filter_db_record;
+use crate::utils::portal::{
+    login_link_email, new_token, token_hash, PortalOwner, LOGIN_LINK_TTL, SESSION_TTL,
+};
+use crate::utils::tenant::Tenant;
+use crate::utils::validator::validate_field;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/portal/login-links",
+    tag = "portal",
+    request_body = RequestLoginLink,
+    responses(
+        (status = 202, description = "A sign-in link was emailed if an owner of the clinic has this email; the response is the same either way", body = MessageResponse),
+        (status = 400, description = "The email is blank, or the clinic is unknown", body = MessageResponse),
+        (status = 500, description = "Database error or the email could not be sent", body = MessageResponse),
+    )
+)]
+pub async fn request_login_link(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<RequestLoginLink>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let owner_email = body.owner_email.trim();
+    if owner_email.is_empty() {
+        return Err(fail(StatusCode::BAD_REQUEST, "Owner email cannot be empty"));
+    }
+
+    let portal_queries = data.db.portal();
+    let now = chrono::Utc::now().timestamp();
+    portal_queries
+        .delete_expired_tokens(now)
+        .await
+        .map_err(database_error)?;
+
+    if let Some(owner) = portal_queries
+        .select_owner_by_email(&tenant.clinic_id, owner_email)
+        .await
+        .map_err(database_error)?
+    {
+        let token = new_token();
+        portal_queries
+            .insert_token(
+                &token_hash(&token),
+                "login",
+                &owner.owner_id,
+                now,
+                now + LOGIN_LINK_TTL,
+            )
+            .await
+            .map_err(database_error)?;
+
+        let separator = if data.portal_login_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let link = format!("{}{}token={}", data.portal_login_url, separator, token);
+        let email = login_link_email(&data.clinic, &owner.owner_name, &owner.owner_email, &link);
+        if let Err(e) = data.mailer.send(email).await {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to send the sign-in email: {}", e),
+                })),
+            ));
+        }
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "status": "success",
+            "message": "If an owner has this email, a sign-in link is on its way",
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/portal/session",
+    tag = "portal",
+    request_body = CreatePortalSession,
+    responses(
+        (status = 201, description = "Signed in; use the session token as a bearer token", body = PortalSessionResponse),
+        (status = 401, description = "The sign-in link is invalid, already used or expired", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn create_portal_session(
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<CreatePortalSession>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let portal_queries = data.db.portal();
+    let now = chrono::Utc::now().timestamp();
+    let Some(owner_id) = portal_queries
+        .use_login_token(&token_hash(&body.token), now)
+        .await
+        .map_err(database_error)?
+    else {
+        return Err(fail(
+            StatusCode::UNAUTHORIZED,
+            "This sign-in link is invalid, already used or expired",
+        ));
+    };
+
+    let session_token = new_token();
+    let expires_at = now + SESSION_TTL;
+    portal_queries
+        .insert_token(
+            &token_hash(&session_token),
+            "session",
+            &owner_id,
+            now,
+            expires_at,
+        )
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Signed in successfully",
+            "session": PortalSession {
+                session_token,
+                expires_at,
+            },
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/portal/session",
+    tag = "portal",
+    security(("portal_session" = [])),
+    responses(
+        (status = 200, description = "Signed out; the session token no longer works", body = MessageResponse),
+        (status = 401, description = "Not signed in", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn delete_portal_session(
+    owner: PortalOwner,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    data.db
+        .portal()
+        .delete_token(&owner.token_hash)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({"status": "success", "message": "Signed out successfully"})),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/portal/me",
+    tag = "portal",
+    security(("portal_session" = [])),
+    responses(
+        (status = 200, description = "The signed-in owner with their pets", body = OwnerAndPetsResponse),
+        (status = 401, description = "Not signed in", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_portal_owner(
+    owner: PortalOwner,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let owner_with_pets = data
+        .db
+        .owners(&owner.clinic_id)
+        .get_owner_and_pets(owner.owner_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Owner and pets fetched successfully",
+            "owner": filter_db_record(&owner_with_pets.owner),
+            "pets": owner_with_pets.pets.into_iter().map(|model| filter_db_record(&model)).collect::<Vec<_>>(),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/portal/me",
+    tag = "portal",
+    security(("portal_session" = [])),
+    request_body = UpdateContactDetails,
+    responses(
+        (status = 200, description = "Contact details updated", body = OwnerResponse),
+        (status = 400, description = "A field is blank", body = MessageResponse),
+        (status = 401, description = "Not signed in", body = MessageResponse),
+        (status = 409, description = "Another owner has this email", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_portal_contact_details(
+    owner: PortalOwner,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<UpdateContactDetails>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_field(&body.owner_email, "Owner email cannot be empty")
+        .and_then(|_| {
+            validate_field(
+                &body.owner_phone_number,
+                "Owner phone number cannot be empty",
+            )
+        })
+        .and_then(|_| validate_field(&body.owner_address, "Owner address cannot be empty"))
+        .map_err(|message| fail(StatusCode::BAD_REQUEST, &message))?;
+
+    let owner_queries = data.db.owners(&owner.clinic_id);
+    owner_queries
+        .update_owner(
+            owner.owner_id.clone(),
+            None,
+            body.owner_email,
+            body.owner_phone_number,
+            body.owner_address,
+            None,
+        )
+        .await
+        .map_err(|err| {
+            match handle_duplicate_entry_error(err, "Another owner with this email") {
+                Ok(response) | Err(response) => response,
+            }
+        })?;
+
+    let updated = owner_queries
+        .select_owner(owner.owner_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Contact details updated successfully",
+            "owner": filter_db_record(&updated),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/portal/pets/{pet_id}/history",
+    tag = "portal",
+    security(("portal_session" = [])),
+    params(("pet_id" = String, Path, description = "Id of one of the signed-in owner's pets"), DateRangeOptions),
+    responses(
+        (status = 200, description = "The pet's visits, from 2000-01-01 to today unless a range is given", body = PetHistoriesResponse),
+        (status = 400, description = "A date is malformed", body = MessageResponse),
+        (status = 401, description = "Not signed in", body = MessageResponse),
+        (status = 404, description = "The owner has no such pet", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_portal_pet_history(
+    owner: PortalOwner,
+    Path(pet_id): Path<String>,
+    State(data): State<Arc<AppState>>,
+    Query(opts): Query<DateRangeOptions>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut range = [
+        chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+        chrono::Local::now().date_naive(),
+    ];
+    for (date, (name, value)) in range.iter_mut().zip([
+        ("start_date", &opts.start_date),
+        ("end_date", &opts.end_date),
+    ]) {
+        let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        match chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+            Ok(parsed) => *date = parsed,
+            Err(_) => {
+                return Err(fail(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid {}: expected YYYY-MM-DD", name),
+                ))
+            }
+        }
+    }
+    let [start_date, end_date] = range;
+
+    // Another owner's pet is reported exactly like one that doesn't exist.
+    let owner_with_pets = data
+        .db
+        .owners(&owner.clinic_id)
+        .get_owner_and_pets(owner.owner_id)
+        .await
+        .map_err(database_error)?;
+    if !owner_with_pets.pets.iter().any(|pet| pet.pet_id == pet_id) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Pet not found"})),
+        ));
+    }
+
+    let service_instances = data
+        .db
+        .service_instances(&owner.clinic_id)
+        .get_services_history_of_pet(pet_id, start_date.to_string(), end_date.to_string())
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Service instances fetched successfully",
+            "service_instances": service_instances,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/portal/followups",
+    tag = "portal",
+    security(("portal_session" = [])),
+    responses(
+        (status = 200, description = "Follow-ups of the owner's pets due today or later, soonest first", body = UpcomingFollowupsResponse),
+        (status = 401, description = "Not signed in", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_portal_followups(
+    owner: PortalOwner,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let followups = data
+        .db
+        .portal()
+        .select_upcoming_followups(&owner.owner_id, chrono::Local::now().date_naive())
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Upcoming follow-ups fetched successfully",
+            "followups": followups,
+        })),
+    ))
+}
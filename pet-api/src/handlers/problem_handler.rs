@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::problem_schema::{AddPetProblem, PetProblemOptions, UpdatePetProblem};
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::problem_list::{apply_update, new_problem, sort_problems};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/problems",
+    tag = "problems",
+    params(("pet_id" = String, Path, description = "Pet id"), PetProblemOptions),
+    responses(
+        (status = 200, description = "The pet's problem list, unresolved and most severe first", body = PetProblemsResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_pet_problems(
+    Path(pet_id): Path<String>,
+    opts: Option<Query<PetProblemOptions>>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    match data
+        .db
+        .pets(&tenant.clinic_id)
+        .select_pet_details(pet_id.clone())
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let mut problems = data
+        .db
+        .problems(&tenant.clinic_id)
+        .select_problems(&pet_id, opts.include_resolved.unwrap_or(false))
+        .await
+        .map_err(database_error)?;
+    sort_problems(&mut problems);
+
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Problem list fetched successfully",
+        "problems": problems,
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pets/{pet_id}/problems",
+    tag = "problems",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddPetProblem,
+    responses(
+        (status = 201, description = "Problem added to the pet's list", body = PetProblemResponse),
+        (status = 400, description = "The description is missing or too long, or a date is malformed or in the future", body = MessageResponse),
+        (status = 404, description = "Pet not found", body = MessageResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_pet_problem(
+    Path(pet_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddPetProblem>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut problem = new_problem(
+        pet_id,
+        body,
+        chrono::Local::now().date_naive(),
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(rule_violation)?;
+
+    problem.problem_id = match data
+        .db
+        .problems(&tenant.clinic_id)
+        .insert_problem(&problem)
+        .await
+    {
+        Ok(problem_id) => problem_id,
+        Err(sqlx::Error::RowNotFound) => return Err(fail(StatusCode::NOT_FOUND, "Pet not found")),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Problem added successfully",
+            "problem": problem,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/pets/{pet_id}/problems/{problem_id}",
+    tag = "problems",
+    params(
+        ("pet_id" = String, Path, description = "Pet id"),
+        ("problem_id" = i32, Path, description = "Problem id"),
+    ),
+    request_body = UpdatePetProblem,
+    responses(
+        (status = 200, description = "Problem updated; a resolved problem no longer raises alerts", body = PetProblemResponse),
+        (status = 400, description = "The description is empty or a date is malformed, in the future or out of order", body = MessageResponse),
+        (status = 404, description = "Problem not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_pet_problem(
+    Path((pet_id, problem_id)): Path<(String, i32)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<UpdatePetProblem>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let problem_queries = data.db.problems(&tenant.clinic_id);
+    let mut problem = match problem_queries.select_problem(&pet_id, problem_id).await {
+        Ok(problem) => problem,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(StatusCode::NOT_FOUND, "Problem not found"))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    apply_update(
+        &mut problem,
+        body,
+        chrono::Local::now().date_naive(),
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(rule_violation)?;
+
+    match problem_queries.update_problem(&problem).await {
+        // Shared pets' problem lists can be read but not changed.
+        Ok(0) => Err(fail(StatusCode::NOT_FOUND, "Problem not found")),
+        Ok(_) => Ok(Json(json!({
+            "status": "success",
+            "message": "Problem updated successfully",
+            "problem": problem,
+        }))),
+        Err(e) => Err(database_error(e)),
+    }
+}
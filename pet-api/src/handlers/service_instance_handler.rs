@@ -1,31 +1,79 @@
 use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use axum::Json;
+use axum::http::{header::ETAG, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
+use axum::Json;
 use serde_json::json;
 
-use crate::AppState;
-use crate::db::queries::service_instance_queries::ServiceInstanceQueries;
-use crate::schemas::helper_schema::FilterOptions;
+use crate::models::problem_model::{ServiceInstanceWithAlerts, ServiceInstanceWithWarnings};
+use crate::schemas::clinical_code_schema::CodeType;
+use crate::schemas::helper_schema::{FilterOptions, ServiceInstanceFilterOptions};
 use crate::schemas::service_instance_schema::{
     AddGroomingToInstance, AddPreventiveCareToExisting, AddServiceInstance, AddSurgery,
-    UpdateServiceInstance, UpdateSurgery,
+    GetServiceInstances, UpdateServiceInstance, UpdateSurgery,
 };
+use crate::utils::clinical_code::{check_code, synonym_key};
+use crate::utils::etag::{check_if_match, is_not_modified, representation_etag, row_etag};
+use crate::utils::pagination::page_window;
+use crate::utils::problem_list::{alerts, allergy_warnings, warning_headers};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
 
+const MAX_BATCH_SIZE: usize = 100;
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/service-instances",
+    tag = "service_instances",
+    params(ServiceInstanceFilterOptions),
+    responses(
+        (status = 200, description = "Page of service instances with their pet", body = ServiceInstancesResponse),
+        (status = 400, description = "A filter or the page is malformed", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn get_all_service_instances(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    opts: Option<Query<FilterOptions>>,
+    Query(mut opts): Query<ServiceInstanceFilterOptions>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
-    match service_instance_queries.get_all_service_instances().await {
+    for (name, date) in [
+        ("start_date", &mut opts.start_date),
+        ("end_date", &mut opts.end_date),
+    ] {
+        if date.as_deref().is_some_and(|d| d.trim().is_empty()) {
+            *date = None;
+        }
+        if let Some(d) = date {
+            if chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").is_err() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "error": format!("Invalid {}: expected YYYY-MM-DD", name) })),
+                ));
+            }
+        }
+    }
+
+    let window = page_window(opts.page, opts.limit, 10)
+        .map_err(|(status, message)| (status, Json(json!({ "error": message }))))?;
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
+    let total_service_instances = service_instance_queries
+        .count_all_service_instances(&opts)
+        .await
+        .unwrap_or_default();
+    let total_pages = window.total_pages(total_service_instances);
+
+    match service_instance_queries
+        .get_all_service_instances(&opts, window.limit, window.offset)
+        .await
+    {
         Ok(service_instances) => {
             let response = json!({
                 "status":"success",
                 "message":"Service instances fetched successfully",
                 "service_instances": service_instances,
+                "total_pages": total_pages,
             });
             Ok((StatusCode::OK, Json(response)))
         }
@@ -36,12 +84,79 @@ pub async fn get_all_service_instances(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances",
+    tag = "service_instances",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddServiceInstance,
+    responses(
+        (status = 201, description = "Service instance created, with a warning for each treatment that names one of the pet's allergies", body = ServiceInstanceWithWarnings),
+        (status = 400, description = "The treatment codes don't match the treatments one to one", body = ServiceErrorResponse),
+        (status = 404, description = "Pet not found", body = ServiceErrorResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The vet doesn't work at this clinic, or a clinical code is unknown or of the wrong type", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn add_service_instance(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    Json(body): Json<AddServiceInstance>,
+    Json(mut body): Json<AddServiceInstance>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let vet_ids = body
+        .preventive_care
+        .iter()
+        .map(|preventive_care| &preventive_care.vet_id)
+        .chain(body.surgery.iter().map(|surgery| &surgery.vet_id));
+    for vet_id in vet_ids {
+        check_vet(&data, &tenant, vet_id).await?;
+    }
+    body.diagnosis_code = clinical_code(
+        &data,
+        CodeType::Diagnosis,
+        &body.general_diagnosis,
+        body.diagnosis_code.take(),
+    )
+    .await?;
+    if let Some(preventive_care) = &mut body.preventive_care {
+        preventive_care.treatment_codes = Some(
+            treatment_codes(
+                &data,
+                &preventive_care.treatment,
+                preventive_care.treatment_codes.take(),
+            )
+            .await?,
+        );
+    }
+    if let Some(surgery) = &mut body.surgery {
+        surgery.surgery_code = clinical_code(
+            &data,
+            CodeType::Procedure,
+            &surgery.surgery_name,
+            surgery.surgery_code.take(),
+        )
+        .await?;
+    }
+    let problems = data
+        .db
+        .problems(&tenant.clinic_id)
+        .select_active_problems(std::slice::from_ref(&body.pet_id))
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            )
+        })?;
+    let treatments = body
+        .preventive_care
+        .as_ref()
+        .map(|preventive_care| preventive_care.treatment.clone())
+        .unwrap_or_default();
+
     let service_instance_id = uuid::Uuid::new_v4().to_string();
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     let current_date = chrono::Local::now().date_naive().to_string();
     match service_instance_queries
         .insert_service_instance(
@@ -50,6 +165,7 @@ pub async fn add_service_instance(
             body.service_type.clone(),
             body.service_reason,
             body.general_diagnosis,
+            body.diagnosis_code,
             body.requires_followup,
             body.followup_date,
             body.pet_id.clone(),
@@ -59,7 +175,17 @@ pub async fn add_service_instance(
         )
         .await
     {
-        Ok(service_instance) => Ok((StatusCode::CREATED, Json(service_instance))),
+        Ok(service_instance) => Ok((
+            StatusCode::CREATED,
+            Json(ServiceInstanceWithWarnings {
+                service_instance,
+                allergy_warnings: allergy_warnings(&problems, &treatments),
+            }),
+        )),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Pet not found" })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -67,8 +193,20 @@ pub async fn add_service_instance(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/pets/{pet_id}/service-instances",
+    tag = "service_instances",
+    params(("pet_id" = String, Path, description = "Pet id"), FilterOptions),
+    responses(
+        (status = 200, description = "Visits of the pet within the date range", body = PetHistoriesResponse),
+        (status = 404, description = "Pet not found", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn get_pet_histories(
     Path(pet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     opts: Option<Query<FilterOptions>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
@@ -76,12 +214,16 @@ pub async fn get_pet_histories(
 
     let start_date = if let Some(sd) = opts.start_date {
         if sd.trim().is_empty() {
-            chrono::NaiveDate::from_ymd(2000, 1, 1).to_string()
+            chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+                .unwrap()
+                .to_string()
         } else {
             sd
         }
     } else {
-        chrono::NaiveDate::from_ymd(2000, 1, 1).to_string()
+        chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .to_string()
     };
 
     let end_date = if let Some(ed) = opts.end_date {
@@ -94,7 +236,7 @@ pub async fn get_pet_histories(
         chrono::Local::now().date_naive().to_string()
     };
 
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
         .get_services_history_of_pet(pet_id.clone(), start_date, end_date)
         .await
@@ -107,22 +249,68 @@ pub async fn get_pet_histories(
             });
             Ok((StatusCode::OK, Json(response)))
         }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Pet not found" })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
         )),
     }
 }
+#[utoipa::path(
+    get,
+    path = "/api/v1/service-instances/{service_instance_id}",
+    tag = "service_instances",
+    params(("service_instance_id" = String, Path, description = "Service instance id")),
+    responses(
+        (status = 200, description = "Service instance with its services and the pet's alerts", body = ServiceInstanceWithAlerts,
+            headers(("ETag" = String, description = "Changes whenever the visit or one of its services does"))),
+        (status = 304, description = "The `If-None-Match` tag is still current"),
+        (status = 404, description = "Service instance not found", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn get_specific_service_instance(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
-        .get_specific_instance(service_instance_id)
+        .get_specific_instance(service_instance_id.clone())
         .await
     {
-        Ok(service_instance) => Ok((StatusCode::OK, Json(service_instance))),
+        Ok(service_instance) => {
+            let problems = data
+                .db
+                .problems(&tenant.clinic_id)
+                .select_active_problems_of_service_instance(&service_instance_id)
+                .await
+                .map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "error": e.to_string() })),
+                    )
+                })?;
+            let response = ServiceInstanceWithAlerts {
+                pet_alerts: alerts(problems),
+                service_instance,
+            };
+            let etag = representation_etag(response.service_instance.row_version, &response);
+
+            if is_not_modified(&headers, &etag) {
+                return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(ETAG, etag)], Json(response)).into_response())
+        }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Service instance not found" })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -130,17 +318,133 @@ pub async fn get_specific_service_instance(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/batch",
+    tag = "service_instances",
+    request_body = GetServiceInstances,
+    responses(
+        (status = 200, description = "Full details of every service instance found", body = ServiceInstanceDetailsResponse),
+        (status = 400, description = "Too many ids were requested", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
+pub async fn get_service_instances_batch(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<GetServiceInstances>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if body.service_instance_ids.len() > MAX_BATCH_SIZE {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("At most {} service instances can be requested at once", MAX_BATCH_SIZE)
+            })),
+        ));
+    }
+
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
+    match service_instance_queries
+        .get_specific_instances(&body.service_instance_ids)
+        .await
+    {
+        Ok(service_instances) => {
+            let not_found = body
+                .service_instance_ids
+                .iter()
+                .filter(|id| {
+                    !service_instances
+                        .iter()
+                        .any(|service_instance| &service_instance.service_instance_id == *id)
+                })
+                .collect::<Vec<_>>();
+            let response = json!({
+                "status":"success",
+                "message":"Service instances fetched successfully",
+                "service_instances": service_instances,
+                "not_found": not_found,
+            });
+            Ok((StatusCode::OK, Json(response)))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": e.to_string() })),
+        )),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/service-instances/{service_instance_id}",
+    tag = "service_instances",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the service instance still has this ETag"),
+    ),
+    request_body = UpdateServiceInstance,
+    responses(
+        (status = 200, description = "Number of updated rows", body = u64,
+            headers(("ETag" = String, description = "Tag of the updated service instance"))),
+        (status = 404, description = "Service instance not found", body = ServiceErrorResponse),
+        (status = 412, description = "The service instance changed since the `If-Match` tag", body = ServiceErrorResponse),
+        (status = 422, description = "The diagnosis code is unknown or not a diagnosis", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn update_service_instance(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    Json(body): Json<UpdateServiceInstance>,
+    headers: HeaderMap,
+    Json(mut body): Json<UpdateServiceInstance>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
+    let row_version = match service_instance_queries
+        .select_service_instance_version(service_instance_id.clone())
+        .await
+    {
+        Ok(row_version) => row_version,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": "Service instance not found" })),
+            ))
+        }
+        Err(e) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            ))
+        }
+    };
+    let expected_version =
+        check_if_match(&headers, row_version).map_err(|_| modified_error("Service instance"))?;
+    if body.general_diagnosis.is_some() || body.diagnosis_code.is_some() {
+        body.diagnosis_code = clinical_code(
+            &data,
+            CodeType::Diagnosis,
+            body.general_diagnosis.as_deref().unwrap_or_default(),
+            body.diagnosis_code.take(),
+        )
+        .await?;
+    }
+
     match service_instance_queries
-        .update_service_instance(body, service_instance_id)
+        .update_service_instance(body, service_instance_id.clone(), expected_version)
         .await
     {
-        Ok(service_instance) => Ok((StatusCode::OK, Json(service_instance))),
+        Ok(0) if expected_version.is_some() => Err(modified_error("Service instance")),
+        Ok(service_instance) => {
+            let row_version = service_instance_queries
+                .select_service_instance_version(service_instance_id)
+                .await
+                .unwrap_or(row_version);
+            Ok((
+                StatusCode::OK,
+                [(ETAG, row_etag(row_version))],
+                Json(service_instance),
+            ))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -148,11 +452,22 @@ pub async fn update_service_instance(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/service-instances/{service_instance_id}",
+    tag = "service_instances",
+    params(("service_instance_id" = String, Path, description = "Service instance id")),
+    responses(
+        (status = 200, description = "Number of deleted rows", body = u64),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn delete_service(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
         .delete_service_instance(service_instance_id)
         .await
@@ -165,11 +480,23 @@ pub async fn delete_service(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/grooming/{grooming_id}",
+    tag = "service_instances",
+    params(("grooming_id" = i32, Path, description = "Grooming id")),
+    responses(
+        (status = 200, description = "Number of deleted rows", body = u64),
+        (status = 400, description = "The id is not a number", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn delete_grooming_from_instance(
     Path(grooming_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     let grooming_id = grooming_id.parse::<i32>().map_err(|e| {
         (
             StatusCode::BAD_REQUEST,
@@ -185,11 +512,23 @@ pub async fn delete_grooming_from_instance(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/preventive-care/{preventive_care_id}",
+    tag = "service_instances",
+    params(("preventive_care_id" = i32, Path, description = "Preventive care id")),
+    responses(
+        (status = 200, description = "Number of deleted rows", body = u64),
+        (status = 400, description = "The id is not a number", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn delete_preventive_care_from_instance(
     Path(preventive_care_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     let preventive_care_id = preventive_care_id.parse::<i32>().map_err(|e| {
         (
             StatusCode::BAD_REQUEST,
@@ -208,11 +547,23 @@ pub async fn delete_preventive_care_from_instance(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/surgeries/{surgery_id}",
+    tag = "service_instances",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    responses(
+        (status = 200, description = "Number of deleted rows", body = u64),
+        (status = 400, description = "The id is not a number", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn delete_surgery_from_instance(
     Path(surgery_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     let surgery_id = surgery_id.parse::<i32>().map_err(|e| {
         (
             StatusCode::BAD_REQUEST,
@@ -228,23 +579,88 @@ pub async fn delete_surgery_from_instance(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/v1/surgeries/{surgery_id}",
+    tag = "service_instances",
+    params(
+        ("surgery_id" = i32, Path, description = "Surgery id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the surgery is still at this `row_version`, as a quoted tag"),
+    ),
+    request_body = UpdateSurgery,
+    responses(
+        (status = 200, description = "Number of updated rows", body = u64,
+            headers(("ETag" = String, description = "Tag of the updated surgery"))),
+        (status = 400, description = "The id is not a number", body = ServiceErrorResponse),
+        (status = 404, description = "Surgery not found", body = ServiceErrorResponse),
+        (status = 412, description = "The surgery changed since the `If-Match` tag", body = ServiceErrorResponse),
+        (status = 422, description = "The vet doesn't work at this clinic, or the surgery code is unknown or not a procedure", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn update_surgery_from_instance(
     Path(surgery_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    Json(body): Json<UpdateSurgery>,
+    headers: HeaderMap,
+    Json(mut body): Json<UpdateSurgery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     let surgery_id = surgery_id.parse::<i32>().map_err(|e| {
         (
             StatusCode::BAD_REQUEST,
             Json(json!({ "error": format!("Invalid surgery_id: {}", e) })),
         )
     })?;
+    let row_version = match service_instance_queries
+        .select_surgery_version(surgery_id)
+        .await
+    {
+        Ok(row_version) => row_version,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": "Surgery not found" })),
+            ))
+        }
+        Err(e) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            ))
+        }
+    };
+    let expected_version =
+        check_if_match(&headers, row_version).map_err(|_| modified_error("Surgery"))?;
+    if let Some(vet_id) = &body.vet_id {
+        check_vet(&data, &tenant, vet_id).await?;
+    }
+    if body.surgery_name.is_some() || body.surgery_code.is_some() {
+        body.surgery_code = clinical_code(
+            &data,
+            CodeType::Procedure,
+            body.surgery_name.as_deref().unwrap_or_default(),
+            body.surgery_code.take(),
+        )
+        .await?;
+    }
+
     match service_instance_queries
-        .update_surgery(body, surgery_id)
+        .update_surgery(body, surgery_id, expected_version)
         .await
     {
-        Ok(service_instance) => Ok((StatusCode::OK, Json(service_instance))),
+        Ok(0) if expected_version.is_some() => Err(modified_error("Surgery")),
+        Ok(service_instance) => {
+            let row_version = service_instance_queries
+                .select_surgery_version(surgery_id)
+                .await
+                .unwrap_or(row_version);
+            Ok((
+                StatusCode::OK,
+                [(ETAG, row_etag(row_version))],
+                Json(service_instance),
+            ))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -252,17 +668,61 @@ pub async fn update_surgery_from_instance(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/{service_instance_id}/preventive-care",
+    tag = "service_instances",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddPreventiveCareToExisting,
+    responses(
+        (status = 201, description = "Number of inserted rows", body = u64,
+            headers(("Warning" = String, description = "`299` for each treatment that names one of the pet's allergies"))),
+        (status = 400, description = "The treatment codes don't match the treatments one to one", body = ServiceErrorResponse),
+        (status = 404, description = "Service instance not found", body = ServiceErrorResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The vet doesn't work at this clinic, or a treatment code is unknown or not a treatment", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn add_preventive_care_to_instance(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    Json(body): Json<AddPreventiveCareToExisting>,
+    Json(mut body): Json<AddPreventiveCareToExisting>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    check_vet(&data, &tenant, &body.vet_id).await?;
+    body.treatment_codes =
+        Some(treatment_codes(&data, &body.treatment, body.treatment_codes.take()).await?);
+    let problems = data
+        .db
+        .problems(&tenant.clinic_id)
+        .select_active_problems_of_service_instance(&service_instance_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            )
+        })?;
+    let warnings = allergy_warnings(&problems, &body.treatment);
+
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
         .add_preventive_care(body, service_instance_id)
         .await
     {
-        Ok(service_instance) => Ok((StatusCode::CREATED, Json(service_instance))),
+        Ok(service_instance) => Ok((
+            StatusCode::CREATED,
+            warning_headers(&warnings),
+            Json(service_instance),
+        )),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Service instance not found" })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -270,17 +730,38 @@ pub async fn add_preventive_care_to_instance(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/{service_instance_id}/grooming",
+    tag = "service_instances",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddGroomingToInstance,
+    responses(
+        (status = 201, description = "Number of inserted rows", body = u64),
+        (status = 404, description = "Service instance not found", body = ServiceErrorResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn add_grooming_to_instance(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     Json(body): Json<AddGroomingToInstance>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
         .add_grooming(service_instance_id, body.grooming_type)
         .await
     {
         Ok(service_instance) => Ok((StatusCode::CREATED, Json(service_instance))),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Service instance not found" })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
@@ -288,20 +769,138 @@ pub async fn add_grooming_to_instance(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/service-instances/{service_instance_id}/surgeries",
+    tag = "service_instances",
+    params(
+        ("service_instance_id" = String, Path, description = "Service instance id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key"),
+    ),
+    request_body = AddSurgery,
+    responses(
+        (status = 201, description = "Number of inserted rows", body = u64),
+        (status = 404, description = "Service instance not found", body = ServiceErrorResponse),
+        (status = 409, description = "The Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 422, description = "The vet doesn't work at this clinic, or the surgery code is unknown or not a procedure", body = ServiceErrorResponse),
+        (status = 500, description = "Database error", body = ServiceErrorResponse),
+    )
+)]
 pub async fn add_surgery_to_instance(
     Path(service_instance_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
-    Json(body): Json<AddSurgery>,
+    Json(mut body): Json<AddSurgery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let service_instance_queries = ServiceInstanceQueries::new(Arc::new(data.db.clone()));
+    check_vet(&data, &tenant, &body.vet_id).await?;
+    body.surgery_code = clinical_code(
+        &data,
+        CodeType::Procedure,
+        &body.surgery_name,
+        body.surgery_code.take(),
+    )
+    .await?;
+
+    let service_instance_queries = data.db.service_instances(&tenant.clinic_id);
     match service_instance_queries
         .add_surgery(body, service_instance_id)
         .await
     {
         Ok(service_instance) => Ok((StatusCode::CREATED, Json(service_instance))),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Service instance not found" })),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": e.to_string() })),
+        )),
+    }
+}
+
+/// Rejects a vet who doesn't work at the clinic the request acts for.
+async fn check_vet(
+    data: &AppState,
+    tenant: &Tenant,
+    vet_id: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .db
+        .vets(&tenant.clinic_id)
+        .select_vet(vet_id.to_string())
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "error": format!("Vet {} doesn't work at this clinic", vet_id) })),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": e.to_string() })),
         )),
     }
 }
+
+/// The catalogue code of a diagnosis, surgery or treatment: the one given once it checks out,
+/// or else the code its text is a synonym of, if any.
+async fn clinical_code(
+    data: &AppState,
+    code_type: CodeType,
+    text: &str,
+    code: Option<String>,
+) -> Result<Option<String>, (StatusCode, Json<serde_json::Value>)> {
+    let code_queries = data.db.clinical_codes();
+    let internal_error = |e: sqlx::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": e.to_string() })),
+        )
+    };
+
+    match code.map(|code| code.trim().to_string()) {
+        Some(code) => {
+            let catalogued = code_queries
+                .select_clinical_codes(std::slice::from_ref(&code))
+                .await
+                .map_err(internal_error)?;
+            check_code(code_type, &code, catalogued.first())
+                .map_err(|(status, message)| (status, Json(json!({ "error": message }))))?;
+            Ok(Some(code))
+        }
+        None => {
+            let synonym = synonym_key(text);
+            let mut codes = code_queries
+                .select_codes_by_synonym(code_type, std::slice::from_ref(&synonym))
+                .await
+                .map_err(internal_error)?;
+            Ok(codes.remove(&synonym))
+        }
+    }
+}
+
+/// A code for each treatment, in order, from those given or the treatment names.
+async fn treatment_codes(
+    data: &AppState,
+    treatments: &[String],
+    codes: Option<Vec<Option<String>>>,
+) -> Result<Vec<Option<String>>, (StatusCode, Json<serde_json::Value>)> {
+    let mut codes = codes.unwrap_or_else(|| vec![None; treatments.len()]);
+    if codes.len() != treatments.len() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "treatment_codes must have one entry per treatment" })),
+        ));
+    }
+    for (treatment, code) in treatments.iter().zip(codes.iter_mut()) {
+        *code = clinical_code(data, CodeType::Treatment, treatment, code.take()).await?;
+    }
+    Ok(codes)
+}
+
+fn modified_error(entity: &str) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        Json(json!({ "error": format!("{} was modified by another request", entity) })),
+    )
+}
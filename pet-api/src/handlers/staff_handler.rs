@@ -0,0 +1,651 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header::ETAG, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::schemas::staff_schema::{
+    AddStaff, AssignStaff, StaffFunction, StaffOptions, StaffRole, StaffedProcedure, UpdateStaff,
+};
+use crate::utils::etag::{check_if_match, row_etag};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::pagination::page_window;
+use crate::utils::tenant::Tenant;
+use crate::utils::validator::validate_field;
+use crate::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/staff",
+    tag = "staff",
+    params(StaffOptions),
+    responses(
+        (status = 200, description = "Page of staff, by name", body = StaffListResponse),
+        (status = 400, description = "The page or limit is out of range", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_staff(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    opts: Option<Query<StaffOptions>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Query(opts) = opts.unwrap_or_default();
+    let role = opts.role.map(StaffRole::as_str);
+
+    let window = page_window(opts.page, opts.limit, 10).map_err(rule_violation)?;
+
+    let staff_queries = data.db.staff(&tenant.clinic_id);
+    let total_staff = staff_queries
+        .count_staff(role, opts.active)
+        .await
+        .map_err(database_error)?;
+    let total_pages = window.total_pages(total_staff);
+
+    let staff = staff_queries
+        .select_all_staff(role, opts.active, window.limit, window.offset)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": "Staff fetched successfully",
+            "staff": staff,
+            "total_pages": total_pages,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/staff",
+    tag = "staff",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddStaff,
+    responses(
+        (status = 201, description = "Staff member added; veterinarians also appear under `/vets`", body = StaffMemberResponse),
+        (status = 400, description = "A field is blank, or a veterinarian has no license number", body = MessageResponse),
+        (status = 409, description = "Email and license already exist, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_staff(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AddStaff>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_field(&Some(body.staff_name.clone()), "Staff name cannot be empty")
+        .and_then(|_| {
+            validate_field(
+                &Some(body.staff_email.clone()),
+                "Staff email cannot be empty",
+            )
+        })
+        .and_then(|_| {
+            validate_field(
+                &Some(body.staff_phone_number.clone()),
+                "Staff phone number cannot be empty",
+            )
+        })
+        .and_then(|_| validate_field(&body.license_number, "License number cannot be empty"))
+        .and_then(|_| validate_field(&body.credentials, "Credentials cannot be empty"))
+        .map_err(|message| fail(StatusCode::BAD_REQUEST, &message))?;
+    if body.staff_role == StaffRole::Veterinarian && body.license_number.is_none() {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "A veterinarian needs a license number",
+        ));
+    }
+
+    let staff_id = uuid::Uuid::new_v4().to_string();
+    let staff_queries = data.db.staff(&tenant.clinic_id);
+    staff_queries
+        .insert_staff(staff_id.clone(), &body)
+        .await
+        .map_err(
+            |err| match handle_duplicate_entry_error(err, "Staff member") {
+                Ok(response) | Err(response) => response,
+            },
+        )?;
+    let staff_member = staff_queries
+        .select_staff(staff_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        [(ETAG, row_etag(staff_member.row_version))],
+        Json(json!({
+            "status": "success",
+            "message": "Staff member added successfully",
+            "staff_member": staff_member,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/staff/{staff_id}",
+    tag = "staff",
+    params(("staff_id" = String, Path, description = "Staff id; a vet's staff id is their vet id")),
+    responses(
+        (status = 200, description = "The staff member", body = StaffMemberResponse,
+            headers(("ETag" = String, description = "Tag of the staff member's current `row_version`"))),
+        (status = 404, description = "Staff member not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_staff_member(
+    Path(staff_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let staff_member = match data
+        .db
+        .staff(&tenant.clinic_id)
+        .select_staff(staff_id)
+        .await
+    {
+        Ok(staff_member) => staff_member,
+        Err(sqlx::Error::RowNotFound) => return Err(staff_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    Ok((
+        StatusCode::OK,
+        [(ETAG, row_etag(staff_member.row_version))],
+        Json(json!({
+            "status": "success",
+            "message": "Staff member fetched successfully",
+            "staff_member": staff_member,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/staff/{staff_id}",
+    tag = "staff",
+    params(
+        ("staff_id" = String, Path, description = "Staff id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the staff member is still at this `row_version`, as a quoted tag"),
+    ),
+    request_body = UpdateStaff,
+    responses(
+        (status = 200, description = "Staff member updated", body = StaffMemberResponse,
+            headers(("ETag" = String, description = "Tag of the updated staff member"))),
+        (status = 400, description = "A field is blank", body = MessageResponse),
+        (status = 404, description = "Staff member not found", body = MessageResponse),
+        (status = 409, description = "Email and license already exist", body = MessageResponse),
+        (status = 412, description = "The staff member changed since the `If-Match` tag", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_staff(
+    Path(staff_id): Path<String>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateStaff>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    validate_field(&body.staff_name, "Staff name cannot be empty")
+        .and_then(|_| validate_field(&body.staff_email, "Staff email cannot be empty"))
+        .and_then(|_| {
+            validate_field(
+                &body.staff_phone_number,
+                "Staff phone number cannot be empty",
+            )
+        })
+        .and_then(|_| validate_field(&body.license_number, "License number cannot be empty"))
+        .and_then(|_| validate_field(&body.credentials, "Credentials cannot be empty"))
+        .map_err(|message| fail(StatusCode::BAD_REQUEST, &message))?;
+
+    let staff_queries = data.db.staff(&tenant.clinic_id);
+    let staff_member = match staff_queries.select_staff(staff_id.clone()).await {
+        Ok(staff_member) => staff_member,
+        Err(sqlx::Error::RowNotFound) => return Err(staff_not_found()),
+        Err(e) => return Err(database_error(e)),
+    };
+    let expected_version =
+        check_if_match(&headers, staff_member.row_version).map_err(|_| staff_modified_error())?;
+
+    match staff_queries
+        .update_staff(staff_id.clone(), &body, expected_version)
+        .await
+    {
+        Ok(0) if expected_version.is_some() => return Err(staff_modified_error()),
+        Ok(_) => {}
+        Err(err) => {
+            return Err(match handle_duplicate_entry_error(err, "Staff member") {
+                Ok(response) | Err(response) => response,
+            })
+        }
+    }
+
+    let staff_member = staff_queries
+        .select_staff(staff_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        [(ETAG, row_etag(staff_member.row_version))],
+        Json(json!({
+            "status": "success",
+            "message": "Staff member updated successfully",
+            "staff_member": staff_member,
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/grooming/{grooming_id}/staff",
+    tag = "staff",
+    params(("grooming_id" = i32, Path, description = "Grooming id")),
+    responses(
+        (status = 200, description = "Who performed and assisted the grooming", body = ProcedureStaffResponse),
+        (status = 404, description = "Grooming not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_grooming_staff(
+    Path(grooming_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    procedure_staff(&data, &tenant, StaffedProcedure::Grooming, grooming_id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/grooming/{grooming_id}/staff",
+    tag = "staff",
+    params(("grooming_id" = i32, Path, description = "Grooming id")),
+    request_body = AssignStaff,
+    responses(
+        (status = 201, description = "Staff member attributed to the grooming", body = ProcedureStaffResponse),
+        (status = 404, description = "Grooming not found", body = MessageResponse),
+        (status = 409, description = "Already assigned, or the grooming already has a performer", body = MessageResponse),
+        (status = 422, description = "The staff member doesn't exist, is inactive or can't take this part", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn assign_grooming_staff(
+    Path(grooming_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AssignStaff>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    assign_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::Grooming,
+        grooming_id,
+        body,
+    )
+    .await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/grooming/{grooming_id}/staff/{staff_id}",
+    tag = "staff",
+    params(
+        ("grooming_id" = i32, Path, description = "Grooming id"),
+        ("staff_id" = String, Path, description = "Staff id"),
+    ),
+    responses(
+        (status = 200, description = "Staff member no longer attributed to the grooming", body = ProcedureStaffResponse),
+        (status = 404, description = "The staff member isn't assigned to the grooming", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn unassign_grooming_staff(
+    Path((grooming_id, staff_id)): Path<(i32, String)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    unassign_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::Grooming,
+        grooming_id,
+        staff_id,
+    )
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/preventive-care/{preventive_care_id}/staff",
+    tag = "staff",
+    params(("preventive_care_id" = i32, Path, description = "Preventive care id")),
+    responses(
+        (status = 200, description = "Who performed and assisted the preventive care", body = ProcedureStaffResponse),
+        (status = 404, description = "Preventive care not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_preventive_care_staff(
+    Path(preventive_care_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    procedure_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::PreventiveCare,
+        preventive_care_id,
+    )
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/preventive-care/{preventive_care_id}/staff",
+    tag = "staff",
+    params(("preventive_care_id" = i32, Path, description = "Preventive care id")),
+    request_body = AssignStaff,
+    responses(
+        (status = 201, description = "Staff member attributed to the preventive care", body = ProcedureStaffResponse),
+        (status = 404, description = "Preventive care not found", body = MessageResponse),
+        (status = 409, description = "Already assigned, or the preventive care already has a performer", body = MessageResponse),
+        (status = 422, description = "The staff member doesn't exist, is inactive or can't take this part", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn assign_preventive_care_staff(
+    Path(preventive_care_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AssignStaff>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    assign_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::PreventiveCare,
+        preventive_care_id,
+        body,
+    )
+    .await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/preventive-care/{preventive_care_id}/staff/{staff_id}",
+    tag = "staff",
+    params(
+        ("preventive_care_id" = i32, Path, description = "Preventive care id"),
+        ("staff_id" = String, Path, description = "Staff id"),
+    ),
+    responses(
+        (status = 200, description = "Staff member no longer attributed to the preventive care", body = ProcedureStaffResponse),
+        (status = 404, description = "The staff member isn't assigned to the preventive care", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn unassign_preventive_care_staff(
+    Path((preventive_care_id, staff_id)): Path<(i32, String)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    unassign_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::PreventiveCare,
+        preventive_care_id,
+        staff_id,
+    )
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/surgeries/{surgery_id}/staff",
+    tag = "staff",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    responses(
+        (status = 200, description = "Who performed and assisted the surgery", body = ProcedureStaffResponse),
+        (status = 404, description = "Surgery not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_surgery_staff(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    procedure_staff(&data, &tenant, StaffedProcedure::Surgery, surgery_id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/surgeries/{surgery_id}/staff",
+    tag = "staff",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    request_body = AssignStaff,
+    responses(
+        (status = 201, description = "Staff member attributed to the surgery", body = ProcedureStaffResponse),
+        (status = 404, description = "Surgery not found", body = MessageResponse),
+        (status = 409, description = "Already assigned, or the surgery already has a performer", body = MessageResponse),
+        (status = 422, description = "The staff member doesn't exist, is inactive or can't take this part; only a veterinarian performs surgery", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn assign_surgery_staff(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<AssignStaff>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    assign_staff(&data, &tenant, StaffedProcedure::Surgery, surgery_id, body).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/surgeries/{surgery_id}/staff/{staff_id}",
+    tag = "staff",
+    params(
+        ("surgery_id" = i32, Path, description = "Surgery id"),
+        ("staff_id" = String, Path, description = "Staff id"),
+    ),
+    responses(
+        (status = 200, description = "Staff member no longer attributed to the surgery", body = ProcedureStaffResponse),
+        (status = 404, description = "The staff member isn't assigned to the surgery", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn unassign_surgery_staff(
+    Path((surgery_id, staff_id)): Path<(i32, String)>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    unassign_staff(
+        &data,
+        &tenant,
+        StaffedProcedure::Surgery,
+        surgery_id,
+        staff_id,
+    )
+    .await
+}
+
+async fn procedure_staff(
+    data: &AppState,
+    tenant: &Tenant,
+    procedure: StaffedProcedure,
+    procedure_id: i32,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let staff_queries = data.db.staff(&tenant.clinic_id);
+    if !staff_queries
+        .procedure_exists(procedure, procedure_id)
+        .await
+        .map_err(database_error)?
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": format!("{} not found", procedure.name())})),
+        ));
+    }
+    staff_response(
+        data,
+        tenant,
+        procedure,
+        procedure_id,
+        "Staff fetched successfully",
+    )
+    .await
+}
+
+async fn assign_staff(
+    data: &AppState,
+    tenant: &Tenant,
+    procedure: StaffedProcedure,
+    procedure_id: i32,
+    body: AssignStaff,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let (_, Json(current)) = procedure_staff(data, tenant, procedure, procedure_id).await?;
+
+    let staff_queries = data.db.staff(&tenant.clinic_id);
+    let staff_member = match staff_queries.select_staff(body.staff_id.clone()).await {
+        Ok(staff_member) => staff_member,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Staff member not found",
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    if !staff_member.is_active {
+        return Err(fail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Inactive staff can't be assigned to procedures",
+        ));
+    }
+    if staff_member.staff_role == StaffRole::Receptionist.as_str() {
+        return Err(fail(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Receptionists can't be assigned to procedures",
+        ));
+    }
+    if body.staff_function == StaffFunction::Performer {
+        if procedure == StaffedProcedure::Surgery
+            && staff_member.staff_role != StaffRole::Veterinarian.as_str()
+        {
+            return Err(fail(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Only a veterinarian can perform surgery",
+            ));
+        }
+        let has_performer = current["staff"].as_array().is_some_and(|staff| {
+            staff
+                .iter()
+                .any(|member| member["staff_function"] == StaffFunction::Performer.as_str())
+        });
+        if has_performer {
+            return Err(fail(
+                StatusCode::CONFLICT,
+                &format!("{} already has a performer", procedure.name()),
+            ));
+        }
+    }
+
+    staff_queries
+        .insert_procedure_staff(
+            procedure,
+            procedure_id,
+            body.staff_id,
+            body.staff_function.as_str(),
+        )
+        .await
+        .map_err(
+            |err| match handle_duplicate_entry_error(err, "Staff assignment") {
+                Ok(response) | Err(response) => response,
+            },
+        )?;
+
+    let (_, response) = staff_response(
+        data,
+        tenant,
+        procedure,
+        procedure_id,
+        "Staff member assigned successfully",
+    )
+    .await?;
+    Ok((StatusCode::CREATED, response))
+}
+
+async fn unassign_staff(
+    data: &AppState,
+    tenant: &Tenant,
+    procedure: StaffedProcedure,
+    procedure_id: i32,
+    staff_id: String,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let removed = data
+        .db
+        .staff(&tenant.clinic_id)
+        .delete_procedure_staff(procedure, procedure_id, staff_id)
+        .await
+        .map_err(database_error)?;
+    if removed == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Staff assignment not found"})),
+        ));
+    }
+
+    staff_response(
+        data,
+        tenant,
+        procedure,
+        procedure_id,
+        "Staff member unassigned successfully",
+    )
+    .await
+}
+
+async fn staff_response(
+    data: &AppState,
+    tenant: &Tenant,
+    procedure: StaffedProcedure,
+    procedure_id: i32,
+    message: &str,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let staff = data
+        .db
+        .staff(&tenant.clinic_id)
+        .select_procedure_staff(procedure, procedure_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": message,
+            "staff": staff,
+        })),
+    ))
+}
+
+fn staff_not_found() -> ErrorResponse {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({"status": "error", "message": "Staff member not found"})),
+    )
+}
+
+fn staff_modified_error() -> ErrorResponse {
+    fail(
+        StatusCode::PRECONDITION_FAILED,
+        "Staff member was modified by another request",
+    )
+}
@@ -1,17 +1,36 @@
 use std::sync::Arc;
 
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use chrono::NaiveDate;
 
-use crate::db::queries::statistic_queries::StatisticQueries;
+use crate::models::vet_model::GetVets;
+use crate::schemas::clinical_code_schema::CodeStatisticsOptions;
+use crate::schemas::helper_schema::{Granularity, StatisticsRangeOptions, VetStatisticsOptions};
+use crate::utils::clinical_code;
+use crate::utils::handler_error::{database_error, fail};
+use crate::utils::statistics::{
+    self, bucket_count, default_start_date, previous_period, DailyStatistics, MAX_BUCKETS,
+};
+use crate::utils::tenant::Tenant;
 use crate::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/services",
+    tag = "statistics",
+    responses(
+        (status = 200, description = "Visit count per service type", body = ServiceCountsResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn counter_services(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let statistic_queries = StatisticQueries::new(Arc::new(data.db.clone()));
+    let statistic_queries = data.db.statistics(&tenant.clinic_id);
 
     let services = statistic_queries.count_services_by_type().await;
 
@@ -35,10 +54,20 @@ pub async fn counter_services(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/pet-type-visits",
+    tag = "statistics",
+    responses(
+        (status = 200, description = "Visit count per pet type", body = PetTypeVisitSummaryResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn pet_type_visit_summery(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let statistic_queries = StatisticQueries::new(Arc::new(data.db.clone()));
+    let statistic_queries = data.db.statistics(&tenant.clinic_id);
 
     let pet_type_visit_summary = statistic_queries.get_pet_type_visit_summary().await;
 
@@ -61,3 +90,203 @@ pub async fn pet_type_visit_summery(
         )),
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/visits",
+    tag = "statistics",
+    params(StatisticsRangeOptions),
+    responses(
+        (status = 200, description = "Visits, new pets, new owners and service types per bucket, with gaps zero-filled, compared with the previous period of the same length", body = VisitStatisticsResponse),
+        (status = 400, description = "A date is malformed, the range is reversed or it has too many buckets", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn visit_statistics(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Query(opts): Query<StatisticsRangeOptions>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let (granularity, start_date, end_date) =
+        statistics_range(&opts.start_date, &opts.end_date, opts.granularity)?;
+
+    let (previous_start_date, previous_end_date) =
+        previous_period(granularity, start_date, end_date);
+    let current = daily_statistics(&data, &tenant, start_date, end_date)
+        .await
+        .map_err(database_error)?;
+    let previous = daily_statistics(&data, &tenant, previous_start_date, previous_end_date)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "success",
+            "message": "Visit statistics fetched successfully",
+            "statistics": statistics::visit_statistics(granularity, start_date, end_date, current, previous),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/vets",
+    tag = "statistics",
+    params(VetStatisticsOptions),
+    responses(
+        (status = 200, description = "Per-vet procedures per bucket, surgeries by name, complication and outcome rates, and patients by species", body = VetStatisticsResponse),
+        (status = 400, description = "A date is malformed, the range is reversed or it has too many buckets", body = MessageResponse),
+        (status = 404, description = "Vet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn vet_statistics(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Query(opts): Query<VetStatisticsOptions>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let (granularity, start_date, end_date) =
+        statistics_range(&opts.start_date, &opts.end_date, opts.granularity)?;
+
+    let vet_queries = data.db.vets(&tenant.clinic_id);
+    let vets = match opts.vet_id {
+        Some(vet_id) => match vet_queries.select_vet(vet_id).await {
+            Ok(vet) => vec![GetVets {
+                vet_id: vet.vet_id,
+                vet_name: vet.vet_name,
+            }],
+            Err(sqlx::Error::RowNotFound) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({"status": "error", "message": "Vet not found"})),
+                ))
+            }
+            Err(e) => return Err(database_error(e)),
+        },
+        None => vet_queries.vet_lists().await.map_err(database_error)?,
+    };
+    let procedures = data
+        .db
+        .statistics(&tenant.clinic_id)
+        .select_vet_procedures(start_date, end_date)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "success",
+            "message": "Vet statistics fetched successfully",
+            "statistics": statistics::vet_statistics(granularity, start_date, end_date, vets, procedures),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/codes",
+    tag = "statistics",
+    params(CodeStatisticsOptions),
+    responses(
+        (status = 200, description = "Diagnoses, surgeries and treatments per catalogue code, most used first, with each type's uncoded records", body = CodeStatisticsResponse),
+        (status = 400, description = "A date is malformed or the range is reversed", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn code_statistics(
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Query(opts): Query<CodeStatisticsOptions>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    // Monthly buckets only set the default range of the last twelve months.
+    let (_, start_date, end_date) =
+        statistics_range(&opts.start_date, &opts.end_date, Some(Granularity::Month))?;
+
+    let counts = data
+        .db
+        .statistics(&tenant.clinic_id)
+        .count_codes(start_date, end_date)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "success",
+            "message": "Code statistics fetched successfully",
+            "start_date": start_date,
+            "end_date": end_date,
+            "statistics": clinical_code::code_statistics(counts, opts.code_type),
+        })),
+    ))
+}
+
+/// Parses a statistics range, defaulting to the buckets leading up to today.
+fn statistics_range(
+    start_date: &Option<String>,
+    end_date: &Option<String>,
+    granularity: Option<Granularity>,
+) -> Result<(Granularity, NaiveDate, NaiveDate), (StatusCode, Json<serde_json::Value>)> {
+    let mut range = [None, None];
+    for (date, (name, value)) in range
+        .iter_mut()
+        .zip([("start_date", start_date), ("end_date", end_date)])
+    {
+        let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        match NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+            Ok(parsed) => *date = Some(parsed),
+            Err(_) => {
+                return Err(fail(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid {}: expected YYYY-MM-DD", name),
+                ))
+            }
+        }
+    }
+    let granularity = granularity.unwrap_or_default();
+    let end_date = range[1].unwrap_or_else(|| chrono::Local::now().date_naive());
+    let start_date = range[0].unwrap_or_else(|| default_start_date(granularity, end_date));
+    if start_date > end_date {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            "start_date is after end_date",
+        ));
+    }
+    let buckets = bucket_count(granularity, start_date, end_date);
+    if buckets > MAX_BUCKETS {
+        return Err(fail(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "The range spans {} buckets; at most {} are allowed, so use a coarser granularity",
+                buckets, MAX_BUCKETS
+            ),
+        ));
+    }
+    Ok((granularity, start_date, end_date))
+}
+
+async fn daily_statistics(
+    data: &AppState,
+    tenant: &Tenant,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<DailyStatistics, sqlx::Error> {
+    let statistic_queries = data.db.statistics(&tenant.clinic_id);
+    Ok(DailyStatistics {
+        visits: statistic_queries
+            .count_visits_by_day(start_date, end_date)
+            .await?,
+        new_pets: statistic_queries
+            .count_new_pets_by_day(start_date, end_date)
+            .await?,
+        new_owners: statistic_queries
+            .count_new_owners_by_day(start_date, end_date)
+            .await?,
+        service_types: statistic_queries
+            .count_service_types_by_day(start_date, end_date)
+            .await?,
+    })
+}
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::models::surgical_case_model::SurgicalCaseModel;
+use crate::schemas::surgical_case_schema::{
+    AddAnesthesiaLogEntry, SurgicalCaseTransition, UpdateSurgicalCase,
+};
+use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::{database_error, fail, rule_violation, ErrorResponse};
+use crate::utils::surgical_case::{
+    apply_update, to_surgical_case, transition, validate_anesthesia_entry,
+};
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/surgeries/{surgery_id}/case",
+    tag = "surgical_cases",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    responses(
+        (status = 201, description = "Surgical case opened as planned", body = SurgicalCaseResponse),
+        (status = 404, description = "Surgery not found", body = MessageResponse),
+        (status = 409, description = "The surgery already has a case", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn open_surgical_case(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .db
+        .service_instances(&tenant.clinic_id)
+        .select_surgery_version(surgery_id)
+        .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::RowNotFound) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({"status": "error", "message": "Surgery not found"})),
+            ))
+        }
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let surgical_case_queries = data.db.surgical_cases(&tenant.clinic_id);
+    surgical_case_queries
+        .insert_case(surgery_id, chrono::Utc::now().timestamp())
+        .await
+        .map_err(|err| {
+            match handle_duplicate_entry_error(err, "A surgical case for this surgery") {
+                Ok(response) | Err(response) => response,
+            }
+        })?;
+    let case = surgical_case_queries
+        .select_case(surgery_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "status": "success",
+            "message": "Surgical case opened successfully",
+            "case": to_surgical_case(case, Vec::new()),
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/surgeries/{surgery_id}/case",
+    tag = "surgical_cases",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    responses(
+        (status = 200, description = "The surgical case with its pre-op checklist and anesthesia log", body = SurgicalCaseResponse),
+        (status = 404, description = "The surgery has no case", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn get_surgical_case(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let case = select_case(&data, &tenant, surgery_id).await?;
+    case_response(&data, &tenant, case, "Surgical case fetched successfully").await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/surgeries/{surgery_id}/case",
+    tag = "surgical_cases",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    request_body = UpdateSurgicalCase,
+    responses(
+        (status = 200, description = "Surgical case updated", body = SurgicalCaseResponse),
+        (status = 400, description = "Consent doesn't name who gave it", body = MessageResponse),
+        (status = 404, description = "The surgery has no case", body = MessageResponse),
+        (status = 409, description = "A field can't change in the case's status, or the case moved on meanwhile", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn update_surgical_case(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<UpdateSurgicalCase>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut case = select_case(&data, &tenant, surgery_id).await?;
+    let status = case.status();
+    apply_update(&mut case, body, chrono::Utc::now().timestamp()).map_err(rule_violation)?;
+    save_case(&data, &tenant, &case, status.as_str()).await?;
+
+    case_response(&data, &tenant, case, "Surgical case updated successfully").await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/surgeries/{surgery_id}/case/anesthesia-log",
+    tag = "surgical_cases",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    request_body = AddAnesthesiaLogEntry,
+    responses(
+        (status = 201, description = "Entry added to the anesthesia log", body = SurgicalCaseResponse),
+        (status = 400, description = "The entry is empty, a vital is out of range or the time is off", body = MessageResponse),
+        (status = 404, description = "The surgery has no case", body = MessageResponse),
+        (status = 409, description = "The case is not in progress or in recovery", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn add_anesthesia_log_entry(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(mut body): Json<AddAnesthesiaLogEntry>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let case = select_case(&data, &tenant, surgery_id).await?;
+    let recorded_at = validate_anesthesia_entry(&case, &mut body, chrono::Utc::now().timestamp())
+        .map_err(rule_violation)?;
+    data.db
+        .surgical_cases(&tenant.clinic_id)
+        .insert_anesthesia_log_entry(surgery_id, recorded_at, &body)
+        .await
+        .map_err(database_error)?;
+
+    let (_, response) = case_response(
+        &data,
+        &tenant,
+        case,
+        "Anesthesia log entry added successfully",
+    )
+    .await?;
+    Ok((StatusCode::CREATED, response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/surgeries/{surgery_id}/case/transitions",
+    tag = "surgical_cases",
+    params(("surgery_id" = i32, Path, description = "Surgery id")),
+    request_body = SurgicalCaseTransition,
+    responses(
+        (status = 200, description = "The case moved to the new status", body = SurgicalCaseResponse),
+        (status = 404, description = "The surgery has no case", body = MessageResponse),
+        (status = 409, description = "The case can't move from its status to this one", body = MessageResponse),
+        (status = 422, description = "The case isn't ready: pre-op checklist, intra-op notes or post-op instructions missing", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
+pub async fn transition_surgical_case(
+    Path(surgery_id): Path<i32>,
+    tenant: Tenant,
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<SurgicalCaseTransition>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut case = select_case(&data, &tenant, surgery_id).await?;
+    let status = case.status();
+    transition(&mut case, body.status, chrono::Utc::now().timestamp()).map_err(rule_violation)?;
+    save_case(&data, &tenant, &case, status.as_str()).await?;
+
+    case_response(
+        &data,
+        &tenant,
+        case,
+        &format!("Surgical case moved to {}", body.status.as_str()),
+    )
+    .await
+}
+
+async fn select_case(
+    data: &AppState,
+    tenant: &Tenant,
+    surgery_id: i32,
+) -> Result<SurgicalCaseModel, ErrorResponse> {
+    match data
+        .db
+        .surgical_cases(&tenant.clinic_id)
+        .select_case(surgery_id)
+        .await
+    {
+        Ok(case) => Ok(case),
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"status": "error", "message": "Surgical case not found"})),
+        )),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/// Writes the case unless another request changed its status since it was read.
+async fn save_case(
+    data: &AppState,
+    tenant: &Tenant,
+    case: &SurgicalCaseModel,
+    expected_status: &str,
+) -> Result<(), ErrorResponse> {
+    match data
+        .db
+        .surgical_cases(&tenant.clinic_id)
+        .update_case(case, expected_status)
+        .await
+    {
+        Ok(0) => Err(fail(
+            StatusCode::CONFLICT,
+            "The surgical case changed status meanwhile; fetch it and try again",
+        )),
+        Ok(_) => Ok(()),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+async fn case_response(
+    data: &AppState,
+    tenant: &Tenant,
+    case: SurgicalCaseModel,
+    message: &str,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let anesthesia_log = data
+        .db
+        .surgical_cases(&tenant.clinic_id)
+        .select_anesthesia_log(case.surgery_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "status": "success",
+            "message": message,
+            "case": to_surgical_case(case, anesthesia_log),
+        })),
+    ))
+}
@@ -1,36 +1,50 @@
 use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{header::ETAG, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde_json::json;
 
-use crate::db::queries::vet_queries::VetQueries;
 use crate::schemas::helper_schema::FilterOptions;
 use crate::schemas::vet_schema::{AddVet, UpdateVet};
+use crate::utils::etag::{check_if_match, row_etag};
 use crate::utils::handle_duplicate_error::handle_duplicate_entry_error;
+use crate::utils::handler_error::rule_violation;
 use crate::utils::model_to_response::filter_db_record;
+use crate::utils::pagination::page_window;
+use crate::utils::tenant::Tenant;
 use crate::utils::validator::validate_field;
 use crate::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/vets",
+    tag = "vets",
+    params(FilterOptions),
+    responses(
+        (status = 200, description = "Page of vets", body = VetsResponse),
+        (status = 400, description = "The page or limit is out of range", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_vets(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     opts: Option<Query<FilterOptions>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let Query(opts) = opts.unwrap_or_default();
 
-    let vet_queries = VetQueries::new(Arc::new(data.db.clone()));
+    let vet_queries = data.db.vets(&tenant.clinic_id);
 
-    let limit = opts.limit.unwrap_or(10);
-    let offset = (opts.page.unwrap_or(1) - 1) * limit;
+    let window = page_window(opts.page, opts.limit, 10).map_err(rule_violation)?;
 
     let total_vets = vet_queries.count_all_vets().await.unwrap_or_default();
 
-    let total_pages = (total_vets as f64 / limit as f64).ceil() as i32;
+    let total_pages = window.total_pages(total_vets);
 
     let vets = vet_queries
-        .select_all_vets(limit as i32, offset as i32)
+        .select_all_vets(window.limit, window.offset)
         .await;
 
     match vets {
@@ -50,10 +64,20 @@ pub async fn get_vets(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/vets/list",
+    tag = "vets",
+    responses(
+        (status = 200, description = "Every vet id and name", body = VetListResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn get_vet_lists(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let vet_queries = VetQueries::new(Arc::new(data.db.clone()));
+    let vet_queries = data.db.vets(&tenant.clinic_id);
 
     let vets = vet_queries.vet_lists().await;
 
@@ -73,12 +97,25 @@ pub async fn get_vet_lists(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/vets",
+    tag = "vets",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the first response to retries that reuse this key")),
+    request_body = AddVet,
+    responses(
+        (status = 201, description = "Vet created", body = VetResponse),
+        (status = 409, description = "Vet email or license already exists, or the Idempotency-Key was used for a different request", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn add_vet(
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
     Json(body): Json<AddVet>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let vet_id = uuid::Uuid::new_v4().to_string();
-    let vet_queries = VetQueries::new(Arc::new(data.db.clone()));
+    let vet_queries = data.db.vets(&tenant.clinic_id);
 
     let _vet = vet_queries
         .insert_vet(
@@ -106,11 +143,23 @@ pub async fn add_vet(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/vets/{vet_id}",
+    tag = "vets",
+    params(("vet_id" = String, Path, description = "Vet id")),
+    responses(
+        (status = 200, description = "Vet deleted", body = MessageResponse),
+        (status = 404, description = "Vet not found", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn delete_vet(
     Path(vet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let vet_queries = VetQueries::new(Arc::new(data.db.clone()));
+    let vet_queries = data.db.vets(&tenant.clinic_id);
     match vet_queries.delete_vet(vet_id.clone()).await {
         Ok(rows_affected) => {
             if rows_affected > 0 {
@@ -133,9 +182,30 @@ pub async fn delete_vet(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/v1/vets/{vet_id}",
+    tag = "vets",
+    params(
+        ("vet_id" = String, Path, description = "Vet id"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the vet is still at this `row_version`, as a quoted tag"),
+    ),
+    request_body = UpdateVet,
+    responses(
+        (status = 200, description = "Vet updated", body = VetResponse,
+            headers(("ETag" = String, description = "Tag of the updated vet"))),
+        (status = 400, description = "A field is blank", body = MessageResponse),
+        (status = 404, description = "Vet not found", body = MessageResponse),
+        (status = 409, description = "Vet email or license already exists", body = MessageResponse),
+        (status = 412, description = "The vet changed since the `If-Match` tag", body = MessageResponse),
+        (status = 500, description = "Database error", body = MessageResponse),
+    )
+)]
 pub async fn update_vet(
     Path(vet_id): Path<String>,
+    tenant: Tenant,
     State(data): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(body): Json<UpdateVet>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     if let Err(message) = validate_vet_fields(&body) {
@@ -145,11 +215,14 @@ pub async fn update_vet(
         ));
     }
 
-    let vet_queries = VetQueries::new(Arc::new(data.db.clone()));
+    let vet_queries = data.db.vets(&tenant.clinic_id);
     let vet = vet_queries.select_vet(vet_id.clone()).await;
 
     match vet {
-        Ok(_) => {
+        Ok(vet) => {
+            let expected_version =
+                check_if_match(&headers, vet.row_version).map_err(|_| vet_modified_error())?;
+
             let vet_name = body.vet_name.clone();
             let vet_email = body.vet_email.clone();
             let vet_phone_number = body.vet_phone_number.clone();
@@ -162,11 +235,17 @@ pub async fn update_vet(
                     vet_email,
                     vet_phone_number,
                     vet_license_number,
+                    expected_version,
                 )
                 .await;
 
-            if let Err(err) = query_result {
-                return handle_duplicate_entry_error(err, "Vet");
+            match query_result {
+                Ok(0) if expected_version.is_some() => return Err(vet_modified_error()),
+                Ok(_) => {}
+                Err(err) => {
+                    return handle_duplicate_entry_error(err, "Vet")
+                        .map(IntoResponse::into_response)
+                }
             }
 
             match vet_queries.select_vet(vet_id.clone()).await {
@@ -177,7 +256,12 @@ pub async fn update_vet(
                         "vet": filter_db_record(&vet)
                     });
 
-                    Ok((StatusCode::OK, Json(response)))
+                    Ok((
+                        StatusCode::OK,
+                        [(ETAG, row_etag(vet.row_version))],
+                        Json(response),
+                    )
+                        .into_response())
                 }
                 Err(e) => Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -192,6 +276,13 @@ pub async fn update_vet(
     }
 }
 
+fn vet_modified_error() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        Json(json!({"status": "fail", "message": "Vet was modified by another request"})),
+    )
+}
+
 fn validate_vet_fields(body: &UpdateVet) -> Result<(), String> {
     validate_field(&body.vet_name, "Vet name cannot be empty")?;
     validate_field(&body.vet_email, "Vet email cannot be empty")?;
@@ -0,0 +1,159 @@
+mod config;
+mod db;
+mod handlers;
+mod models;
+mod openapi;
+mod route;
+mod schemas;
+mod utils;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{
+    get_certificate_signing_key, get_clinic_branding, get_idempotency_key_ttl, get_mailer_config,
+    get_portal_login_url, MailerConfig,
+};
+use crate::db::connection::{connect, connect_sqlite, DbPool};
+use crate::db::queries::init_tables::create_tables;
+use crate::utils::bulk_import::run_import;
+
+pub use crate::config::ClinicBranding;
+pub use crate::models::import_model::{ImportReport, ImportRowError};
+pub use crate::openapi::openapi_json;
+pub use crate::route::create_router;
+pub use crate::schemas::import_schema::{ImportEntity, ImportFormat, ImportMode, ImportRequest};
+pub use crate::utils::bulk_import::ImportFailure;
+pub use crate::utils::mailer::{Email, LogMailer, Mailer, MockMailbox, SmtpMailer};
+pub use crate::utils::tenant::DEFAULT_CLINIC_ID;
+
+const DEFAULT_READINESS_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct AppState {
+    db: DbPool,
+    idempotency_key_ttl: Duration,
+    clinic: ClinicBranding,
+    mailer: Arc<dyn Mailer>,
+    portal_login_url: String,
+    certificate_signing_key: Option<String>,
+    readiness_probe_timeout: Duration,
+}
+
+impl AppState {
+    /// Connects to the database selected by the `DB_DRIVER` environment variable, sending
+    /// email the way `MAILER` selects.
+    pub async fn connect() -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            db: connect().await?,
+            idempotency_key_ttl: idempotency_key_ttl(),
+            clinic: clinic_branding(),
+            mailer: mailer(),
+            portal_login_url: get_portal_login_url(),
+            certificate_signing_key: certificate_signing_key(),
+            readiness_probe_timeout: DEFAULT_READINESS_PROBE_TIMEOUT,
+        })
+    }
+
+    /// Connects to a SQLite database file, or to a private in-memory database for `:memory:`.
+    /// Emails are only logged until `with_mailer` sets a mailer.
+    pub async fn connect_sqlite(db_path: &str) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            db: connect_sqlite(db_path).await?,
+            idempotency_key_ttl: idempotency_key_ttl(),
+            clinic: clinic_branding(),
+            mailer: Arc::new(LogMailer),
+            portal_login_url: get_portal_login_url(),
+            certificate_signing_key: certificate_signing_key(),
+            readiness_probe_timeout: DEFAULT_READINESS_PROBE_TIMEOUT,
+        })
+    }
+
+    /// Overrides how long `Idempotency-Key` responses are kept for replay.
+    pub fn with_idempotency_key_ttl(mut self, idempotency_key_ttl: Duration) -> Self {
+        self.idempotency_key_ttl = idempotency_key_ttl;
+        self
+    }
+
+    /// Overrides the clinic details printed on generated documents.
+    pub fn with_clinic_branding(mut self, clinic: ClinicBranding) -> Self {
+        self.clinic = clinic;
+        self
+    }
+
+    /// Overrides how emails such as owner portal sign-in links are delivered.
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    /// Sets the secret that signs certificate contents, in place of `CERTIFICATE_SIGNING_KEY`.
+    pub fn with_certificate_signing_key(mut self, certificate_signing_key: &str) -> Self {
+        self.certificate_signing_key = Some(certificate_signing_key.to_string());
+        self
+    }
+
+    /// Overrides how long `/api/readyz` waits on each database probe before reporting it down.
+    pub fn with_readiness_probe_timeout(mut self, readiness_probe_timeout: Duration) -> Self {
+        self.readiness_probe_timeout = readiness_probe_timeout;
+        self
+    }
+
+    pub async fn create_tables(&self) -> Result<(), sqlx::Error> {
+        create_tables(&self.db).await
+    }
+
+    /// Runs a bulk import into a clinic outside of a request, as the `pet-import` command does.
+    pub async fn import(
+        &self,
+        clinic_id: &str,
+        request: ImportRequest,
+    ) -> Result<ImportReport, ImportFailure> {
+        if self.db.clinics().select_clinic(clinic_id).await?.is_none() {
+            return Err(ImportFailure::Invalid(format!(
+                "Unknown clinic '{}'",
+                clinic_id
+            )));
+        }
+        run_import(&self.db, clinic_id, request).await
+    }
+}
+
+fn idempotency_key_ttl() -> Duration {
+    match get_idempotency_key_ttl() {
+        Ok(ttl) => ttl,
+        Err(e) => panic!("❌ Failed to get the idempotency key expiry: {}", e),
+    }
+}
+
+/// The configured certificate signing key. Without one, certificates are neither issued nor
+/// verified, since a key that changes on restart would mark every earlier certificate tampered.
+fn certificate_signing_key() -> Option<String> {
+    let key = get_certificate_signing_key();
+    if key.is_none() {
+        log::warn!(
+            "⚠️ CERTIFICATE_SIGNING_KEY is not set; certificates can't be issued or verified"
+        );
+    }
+    key
+}
+
+fn mailer() -> Arc<dyn Mailer> {
+    match get_mailer_config() {
+        Ok(MailerConfig::Smtp { url, from }) => match SmtpMailer::new(&url, &from) {
+            Ok(mailer) => Arc::new(mailer),
+            Err(e) => panic!("❌ Failed to set up the SMTP mailer: {}", e),
+        },
+        Ok(MailerConfig::Log) => {
+            log::warn!("⚠️ MAILER is log; emails such as portal sign-in links are not delivered");
+            Arc::new(LogMailer)
+        }
+        Err(e) => panic!("❌ Failed to get the mail configuration: {}", e),
+    }
+}
+
+fn clinic_branding() -> ClinicBranding {
+    match get_clinic_branding() {
+        Ok(clinic) => clinic,
+        Err(e) => panic!("❌ Failed to get the clinic branding: {}", e),
+    }
+}
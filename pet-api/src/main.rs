@@ -1,39 +1,24 @@
-mod config;
-mod db;
-mod handlers;
-mod models;
-mod route;
-mod schemas;
-mod utils;
-
-use crate::db::connection::connect;
-use crate::route::create_router;
-use db::queries::init_tables::create_tables;
 use log::{error, info, Level};
+use pet_api::{create_router, AppState};
 use simple_logger::SimpleLogger;
-use sqlx::MySqlPool;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 
 use axum::http::{
-    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-    HeaderValue, Method,
+    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH, LINK},
+    HeaderName, HeaderValue, Method,
 };
 
-pub struct AppState {
-    db: MySqlPool,
-}
-
 #[tokio::main]
 async fn main() {
     SimpleLogger::new()
         .with_level(Level::Info.to_level_filter())
         .init()
         .unwrap();
-    let pool = match connect().await {
-        Ok(pool) => {
+    let app_state = match AppState::connect().await {
+        Ok(app_state) => {
             info!("✅  Connected to the Database!");
-            pool
+            app_state
         }
         Err(e) => {
             error!("❌  Failed to connect to the Database: {}", e);
@@ -41,7 +26,7 @@ async fn main() {
         }
     };
 
-    let _ = match create_tables(&pool).await {
+    match app_state.create_tables().await {
         Ok(_) => info!("✅  Tables Ready"),
         Err(e) => {
             error!("❌  Failed to create tables: {}", e);
@@ -53,9 +38,22 @@ async fn main() {
         .allow_origin("http://localhost:3000".parse::<HeaderValue>().unwrap())
         .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
         .allow_credentials(true)
-        .allow_headers([AUTHORIZATION, ACCEPT, CONTENT_TYPE]);
-
-    let app = create_router(Arc::new(AppState { db: pool.clone() })).layer(cors);
+        .allow_headers([
+            AUTHORIZATION,
+            ACCEPT,
+            CONTENT_TYPE,
+            IF_MATCH,
+            IF_NONE_MATCH,
+            HeaderName::from_static("idempotency-key"),
+        ])
+        .expose_headers([
+            HeaderName::from_static("deprecation"),
+            LINK,
+            ETAG,
+            HeaderName::from_static("idempotent-replayed"),
+        ]);
+
+    let app = create_router(Arc::new(app_state)).layer(cors);
 
     info!("🚀 Server stared successfully");
     let listener = tokio::net::TcpListener::bind("0.0.0.0:4000").await.unwrap();
@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::schemas::boarding_schema::BoardingStayStatus;
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct KennelModel {
+    pub kennel_id: String,
+    pub kennel_name: String,
+    pub capacity: i32,
+    #[schema(example = "Any")]
+    pub species: String,
+    pub created_at: i64,
+}
+
+impl KennelModel {
+    pub fn accepts(&self, pet_type: &str) -> bool {
+        self.species == "Any" || self.species == pet_type
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct BoardingStayModel {
+    pub stay_id: String,
+    pub kennel_id: String,
+    pub kennel_name: String,
+    pub pet_id: String,
+    pub pet_name: String,
+    pub pet_type: String,
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    #[schema(example = "reserved")]
+    pub stay_status: String,
+    pub feeding_instructions: Option<String>,
+    pub medication_instructions: Option<String>,
+    pub reserved_at: i64,
+    pub checked_in_at: Option<i64>,
+    pub checked_out_at: Option<i64>,
+}
+
+impl BoardingStayModel {
+    pub fn status(&self) -> BoardingStayStatus {
+        // The column's CHECK constraint only admits the four statuses.
+        BoardingStayStatus::parse(&self.stay_status).unwrap_or(BoardingStayStatus::Reserved)
+    }
+
+    /// Whether the pet is in the kennel the night of `night`.
+    pub fn covers(&self, night: NaiveDate) -> bool {
+        self.check_in_date <= night && night < self.check_out_date
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct CareLogEntry {
+    pub care_log_entry_id: i32,
+    pub care_date: NaiveDate,
+    pub recorded_at: i64,
+    pub fed: bool,
+    pub medication_given: bool,
+    pub exercised: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct OccupancyNight {
+    pub night: NaiveDate,
+    pub occupied: i32,
+    pub available: i32,
+    pub stay_ids: Vec<String>,
+}
+
+/// A kennel's bookings night by night.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct KennelOccupancy {
+    pub kennel_id: String,
+    pub kennel_name: String,
+    pub capacity: i32,
+    pub species: String,
+    pub nights: Vec<OccupancyNight>,
+}
@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::schemas::certificate_schema::CertificateType;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct CertificateModel {
+    pub certificate_id: i32,
+    pub pet_id: String,
+    pub vet_id: String,
+    pub verification_code: String,
+    pub revoked_on: Option<NaiveDate>,
+    /// The `CertificateContent` as issued, in JSON.
+    pub content: String,
+    pub content_digest: String,
+}
+
+/// A preventive care treatment as a certificate lists it.
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct CertifiedTreatment {
+    pub preventive_care_id: i32,
+    pub treatment: String,
+    pub administered_on: NaiveDate,
+    pub vet_name: String,
+    pub vet_license_number: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CertifiedPet {
+    pub pet_name: String,
+    pub pet_type: String,
+    pub pet_breed: String,
+    pub pet_color: String,
+    pub pet_birth_date: NaiveDate,
+    pub owner_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CertifyingVet {
+    pub vet_name: String,
+    pub vet_license_number: String,
+}
+
+/// What a certificate attests to, frozen when it is issued.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CertificateContent {
+    pub certificate_type: CertificateType,
+    pub pet: CertifiedPet,
+    pub issued_by: CertifyingVet,
+    pub treatments: Vec<CertifiedTreatment>,
+    pub remarks: Option<String>,
+    pub issued_on: NaiveDate,
+    pub valid_until: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Certificate {
+    pub certificate_id: i32,
+    #[schema(example = "VC-000001")]
+    pub certificate_number: String,
+    pub pet_id: String,
+    pub vet_id: String,
+    #[schema(example = "7QK2-M9XD-4HPA")]
+    pub verification_code: String,
+    pub revoked_on: Option<NaiveDate>,
+    pub content: CertificateContent,
+    /// HMAC-SHA-256 of the content as issued, under the server's signing key, printed on the
+    /// certificate.
+    pub content_digest: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateStatus {
+    Valid,
+    Expired,
+    Revoked,
+    /// The stored content and digest no longer match under the server's signing key.
+    Tampered,
+}
+
+/// The public answer to a verification code: enough to compare against the paper copy,
+/// without ids or the owner's contact details.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CertificateVerification {
+    pub status: CertificateStatus,
+    pub certificate_number: String,
+    pub revoked_on: Option<NaiveDate>,
+    pub content: CertificateContent,
+    pub content_digest: String,
+}
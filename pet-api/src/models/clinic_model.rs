@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct ClinicModel {
+    /// Send as the `X-Clinic-Id` header to act as this clinic.
+    #[schema(example = "main")]
+    pub clinic_id: String,
+    pub clinic_name: String,
+    pub created_at: i64,
+}
+
+/// Another clinic allowed to read a pet's records.
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct PetShare {
+    pub clinic_id: String,
+    pub clinic_name: String,
+    pub shared_at: i64,
+}
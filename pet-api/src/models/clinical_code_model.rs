@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct ClinicalCode {
+    #[schema(example = "P-OVH")]
+    pub code: String,
+    #[schema(example = "procedure")]
+    pub code_type: String,
+    #[schema(example = "Ovariohysterectomy")]
+    pub term: String,
+}
+
+/// A catalogue entry to store, with the names it is also known by.
+#[derive(Debug, Clone)]
+pub struct NewClinicalCode {
+    pub code: String,
+    pub code_type: String,
+    pub term: String,
+    /// Lower case, including the term itself.
+    pub synonyms: Vec<String>,
+}
+
+/// A code whose term or one of its synonyms matched a search.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CodeMatch {
+    pub code: String,
+    pub code_type: String,
+    pub term: String,
+    pub synonym: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CodeSuggestion {
+    pub code: String,
+    pub code_type: String,
+    pub term: String,
+    /// The synonym that matched, when it wasn't the term.
+    #[schema(example = "spay")]
+    pub matched_synonym: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CatalogueImport {
+    pub codes: usize,
+    pub synonyms: usize,
+    /// Uncoded diagnoses, surgeries and treatments that now match a code.
+    pub recoded: u64,
+}
+
+/// How often a code was used, or uncoded records of a type when `code` is empty.
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct CodeCount {
+    #[schema(example = "procedure")]
+    pub code_type: String,
+    pub code: Option<String>,
+    pub term: Option<String>,
+    pub total: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct CodeStatistics {
+    /// Most used first within each type.
+    pub coded: Vec<CodeCount>,
+    /// Records per type that no catalogue code covers.
+    pub uncoded: Vec<CodeCount>,
+}
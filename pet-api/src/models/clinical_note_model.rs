@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct ClinicalNoteModel {
+    pub note_id: i32,
+    pub service_instance_id: String,
+    pub service_date: NaiveDate,
+    pub pet_id: String,
+    #[schema(example = "assessment")]
+    pub section: String,
+    pub author_id: String,
+    pub author_name: String,
+    #[schema(example = "veterinarian")]
+    pub author_role: String,
+    /// Markdown.
+    pub body: String,
+    pub recorded_at: i64,
+}
+
+/// A visit's note entries by SOAP section, each in the order they were written.
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct SoapNotes {
+    pub subjective: Vec<ClinicalNoteModel>,
+    pub objective: Vec<ClinicalNoteModel>,
+    pub assessment: Vec<ClinicalNoteModel>,
+    pub plan: Vec<ClinicalNoteModel>,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, sqlx::FromRow)]
+pub struct IdempotencyKeyModel {
+    pub request_fingerprint: String,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct PetIdentifierModel {
+    pub identifier_id: i32,
+    pub pet_id: String,
+    #[schema(example = "microchip")]
+    pub identifier_type: String,
+    #[schema(example = "985112003456789")]
+    pub identifier_value: String,
+    pub implant_date: Option<NaiveDate>,
+    pub implant_location: Option<String>,
+    pub recorded_at: i64,
+}
+
+/// A pet found by one of its identifiers, with who to call to reunite it with its owner.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct IdentifiedPetModel {
+    #[schema(example = "microchip")]
+    pub identifier_type: String,
+    pub identifier_value: String,
+    pub pet_id: String,
+    pub pet_name: String,
+    pub pet_type: String,
+    pub pet_breed: String,
+    pub pet_color: String,
+    /// A found pet listed as deceased or transferred usually means the registry is out of date.
+    #[schema(example = "active")]
+    pub pet_status: String,
+    pub owner_name: String,
+    pub owner_email: Option<String>,
+    pub owner_phone_number: Option<String>,
+    /// The clinic the pet is registered with.
+    pub clinic_id: String,
+    pub clinic_name: String,
+}
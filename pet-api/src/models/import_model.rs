@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::schemas::import_schema::{ImportEntity, ImportMode};
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ImportRowError {
+    /// 1-based position of the record, not counting the CSV header.
+    pub row: usize,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ImportReport {
+    pub import_id: String,
+    pub entity: ImportEntity,
+    pub mode: ImportMode,
+    pub dry_run: bool,
+    /// Whether the imported rows were saved.
+    pub committed: bool,
+    pub total_rows: usize,
+    /// Rows saved, or for a dry run, rows that would have been.
+    pub imported_rows: usize,
+    pub failed_rows: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    /// The errors as a `row,field,message` CSV document.
+    pub fn errors_csv(&self) -> String {
+        let mut csv = String::from("row,field,message\n");
+        for error in &self.errors {
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                error.row,
+                csv_field(error.field.as_deref().unwrap_or_default()),
+                csv_field(&error.message)
+            ));
+        }
+        csv
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LabOrderModel {
+    pub lab_order_id: i32,
+    pub service_instance_id: String,
+    pub service_date: NaiveDate,
+    pub pet_id: String,
+    pub pet_type: String,
+    pub vet_id: String,
+    pub vet_name: String,
+    pub panel: String,
+    pub sample_type: String,
+    pub notes: Option<String>,
+    pub ordered_at: i64,
+    pub resulted_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct LabResultModel {
+    pub lab_result_id: i32,
+    pub lab_order_id: i32,
+    pub analyte: String,
+    #[sqlx(rename = "result_value")]
+    pub value: f64,
+    pub unit: String,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    /// `low`, `normal` or `high`; empty when there was no range to compare with.
+    #[schema(example = "high")]
+    pub flag: Option<String>,
+    pub recorded_at: i64,
+}
+
+/// A checked result, ready to be stored.
+#[derive(Debug, Clone)]
+pub struct NewLabResult {
+    pub analyte: String,
+    pub value: f64,
+    pub unit: String,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    pub flag: Option<&'static str>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct ReferenceRange {
+    pub analyte: String,
+    #[schema(example = "Dog")]
+    pub species: String,
+    pub analyte_name: String,
+    pub unit: String,
+    pub reference_low: f64,
+    pub reference_high: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct LabOrder {
+    pub lab_order_id: i32,
+    pub service_instance_id: String,
+    pub service_date: NaiveDate,
+    pub pet_id: String,
+    pub panel: String,
+    #[schema(example = "blood")]
+    pub sample_type: String,
+    pub vet_id: String,
+    pub vet_name: String,
+    pub notes: Option<String>,
+    pub ordered_at: i64,
+    /// When the first results were entered.
+    pub resulted_at: Option<i64>,
+    /// The analytes flagged low or high.
+    pub abnormal_analytes: Vec<String>,
+    pub results: Vec<LabResultModel>,
+}
+
+/// One analyte's result on one visit.
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct TrendPoint {
+    #[serde(skip)]
+    pub analyte: String,
+    pub service_date: NaiveDate,
+    pub service_instance_id: String,
+    pub lab_order_id: i32,
+    #[sqlx(rename = "result_value")]
+    pub value: f64,
+    pub unit: String,
+    pub flag: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct AnalyteTrend {
+    pub analyte: String,
+    /// The species reference range, when there is one.
+    pub reference_range: Option<ReferenceRange>,
+    /// Oldest visit first.
+    pub points: Vec<TrendPoint>,
+}
@@ -1,5 +1,19 @@
+pub(crate) mod boarding_model;
+pub(crate) mod certificate_model;
+pub(crate) mod clinic_model;
+pub(crate) mod clinical_code_model;
+pub(crate) mod clinical_note_model;
+pub(crate) mod health_model;
+pub(crate) mod idempotency_model;
+pub(crate) mod identifier_model;
+pub(crate) mod import_model;
+pub(crate) mod lab_model;
 pub(crate) mod owner_model;
 pub(crate) mod pet_model;
+pub(crate) mod portal_model;
+pub(crate) mod problem_model;
 pub(crate) mod service_instance_model;
+pub(crate) mod staff_model;
 pub(crate) mod statistics_model;
+pub(crate) mod surgical_case_model;
 pub(crate) mod vet_model;
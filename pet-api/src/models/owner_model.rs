@@ -1,23 +1,16 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::models::pet_model::PetModel;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct OwnerModel {
     pub owner_id: String,
     pub owner_name: String,
     pub owner_email: String,
     pub owner_phone_number: String,
     pub owner_address: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct OwnerModelResponse {
-    pub owner_id: String,
-    pub owner_name: String,
-    pub owner_email: String,
-    pub owner_phone_number: String,
-    pub owner_address: String,
+    pub row_version: i32,
 }
 
 pub struct OwnerWithPets {
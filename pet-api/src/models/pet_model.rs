@@ -1,41 +1,86 @@
+use std::fmt;
+
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use utoipa::ToSchema;
+
+/// A pet's weight in kilograms. Each backend stores it its own way; it's always given to two
+/// decimal places, e.g. "6.50".
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct PetWeight(pub Decimal);
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+impl fmt::Display for PetWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+impl Serialize for PetWeight {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct PetModel {
     pub pet_id: String,
     pub pet_name: String,
     pub pet_birth_date: NaiveDate,
     pub pet_type: String,
     pub pet_breed: String,
-    pub pet_weight: Decimal,
+    #[schema(value_type = String, example = "6.50")]
+    pub pet_weight: PetWeight,
     pub pet_color: String,
     pub owner_id: String,
+    #[schema(example = "active")]
+    pub pet_status: String,
+    pub memorial_safe: bool,
+    pub row_version: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct OwnerForPets {
-    pub owner_name: String,
-    pub owner_email: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct PetWithOwner {
-    pub pet: PetModel,
-    pub owner: OwnerForPets,
-}
-
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct PetModelResponse {
     pub pet_id: String,
     pub pet_name: String,
     pub pet_birth_date: NaiveDate,
     pub pet_type: String,
     pub pet_breed: String,
-    pub pet_weight: Decimal,
+    #[schema(value_type = String, example = "6.50")]
+    pub pet_weight: PetWeight,
     pub pet_color: String,
     pub owner_id: String,
     pub owner_name: String,
     pub owner_email: String,
+    #[schema(example = "active")]
+    pub pet_status: String,
+    pub memorial_safe: bool,
+    pub row_version: i32,
+}
+
+/// Where a pet is in its life with the clinic.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PetStatusModel {
+    pub pet_id: String,
+    #[schema(example = "active")]
+    pub pet_status: String,
+    /// When the pet last changed status; the date of death for a deceased pet.
+    pub status_changed_on: Option<NaiveDate>,
+    /// The owner isn't contacted about the pet: no follow-up reminders are shown for it.
+    pub memorial_safe: bool,
+    #[sqlx(skip)]
+    pub death: Option<PetDeathModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PetDeathModel {
+    pub date_of_death: NaiveDate,
+    pub cause_of_death: Option<String>,
+    pub euthanasia: bool,
+    pub consent_given_by: Option<String>,
+    pub consent_given_on: Option<NaiveDate>,
+    /// The vet who performed the euthanasia.
+    pub vet_id: Option<String>,
+    pub recorded_at: i64,
 }
@@ -0,0 +1,21 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct UpcomingFollowup {
+    pub service_instance_id: String,
+    pub pet_id: String,
+    pub pet_name: String,
+    pub service_date: NaiveDate,
+    pub service_reason: Option<String>,
+    pub followup_date: NaiveDate,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PortalSession {
+    /// Send as `Authorization: Bearer <session_token>` on every portal request.
+    pub session_token: String,
+    /// Unix time after which the session token is no longer accepted.
+    pub expires_at: i64,
+}
@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::pet_model::PetModelResponse;
+use crate::models::service_instance_model::ServiceInstanceModel;
+use crate::schemas::problem_schema::ProblemSeverity;
+use crate::schemas::service_instance_schema::ServiceInstance;
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct PetProblemModel {
+    pub problem_id: i32,
+    pub pet_id: String,
+    #[schema(example = "allergy")]
+    pub problem_type: String,
+    pub description: String,
+    #[schema(example = "severe")]
+    pub severity: String,
+    pub onset_date: Option<NaiveDate>,
+    pub resolved_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub recorded_at: i64,
+    pub updated_at: i64,
+}
+
+impl PetProblemModel {
+    pub fn severity(&self) -> ProblemSeverity {
+        // The column's CHECK constraint only admits the three severities.
+        ProblemSeverity::parse(&self.severity).unwrap_or(ProblemSeverity::Mild)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.resolved_date.is_none()
+    }
+
+    pub fn alert(&self) -> PetAlert {
+        PetAlert {
+            problem_id: self.problem_id,
+            problem_type: self.problem_type.clone(),
+            description: self.description.clone(),
+            severity: self.severity.clone(),
+        }
+    }
+}
+
+/// An unresolved problem-list entry, shown as a badge wherever the pet is.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PetAlert {
+    pub problem_id: i32,
+    #[schema(example = "behavioral_caution")]
+    pub problem_type: String,
+    pub description: String,
+    #[schema(example = "severe")]
+    pub severity: String,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PetWithAlerts {
+    #[serde(flatten)]
+    pub pet: PetModelResponse,
+    /// Most severe first.
+    pub alerts: Vec<PetAlert>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ServiceInstanceWithAlerts {
+    #[serde(flatten)]
+    pub service_instance: ServiceInstanceModel,
+    /// The pet's unresolved problems, most severe first.
+    pub pet_alerts: Vec<PetAlert>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ServiceInstanceWithWarnings {
+    #[serde(flatten)]
+    pub service_instance: ServiceInstance,
+    /// Recorded treatments that name one of the pet's unresolved allergies. Left out when
+    /// there are none.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allergy_warnings: Vec<String>,
+}
@@ -1,23 +1,26 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::models::vet_model::VetModelForService;
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct ServiceInstanceModel {
     pub service_instance_id: String,
     pub service_date: chrono::NaiveDate,
     pub service_type: Vec<String>,
     pub service_reason: String,
     pub general_diagnosis: String,
+    pub diagnosis_code: Option<String>,
     pub requires_followup: bool,
     pub pet_id: String,
     pub followup_date: Option<chrono::NaiveDate>,
     pub grooming: Option<Vec<GroomingModel>>,
     pub preventive_care: Option<Vec<PreventiveCareModel>>,
     pub surgery: Option<Vec<SurgeryModel>>,
+    pub row_version: i32,
 }
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct AllServiceInstanceModel {
     pub service_instance_id: String,
     pub service_date: chrono::NaiveDate,
@@ -25,7 +28,7 @@ pub struct AllServiceInstanceModel {
     pub pet: SimplePetModel,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct SimplePetModel {
     pub pet_id: String,
     pub pet_name: String,
@@ -34,31 +37,34 @@ pub struct SimplePetModel {
     pub owner_name: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct GroomingModel {
     pub grooming_id: i32,
     pub grooming_type: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct PreventiveCareModel {
     pub preventive_care_id: i32,
     pub treatment: String,
+    pub treatment_code: Option<String>,
     pub vet: VetModelForService,
 }
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct SurgeryModel {
     pub surgery_id: i32,
     pub surgery_name: String,
+    pub surgery_code: Option<String>,
     pub veterinarian_diagnosis: Option<String>,
     pub anesthesia_used: Option<String>,
     pub complications: Option<String>,
     pub outcome: Option<String>,
     pub vet: VetModelForService,
+    pub row_version: i32,
 }
 
-#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct GetServicesHistoryModel {
     pub service_instance_id: String,
     pub service_date: chrono::NaiveDate,
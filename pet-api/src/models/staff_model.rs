@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct StaffModel {
+    pub staff_id: String,
+    pub staff_name: String,
+    pub staff_email: String,
+    pub staff_phone_number: String,
+    #[schema(example = "technician")]
+    pub staff_role: String,
+    pub license_number: Option<String>,
+    pub credentials: Option<String>,
+    pub is_active: bool,
+    pub row_version: i32,
+}
+
+/// A staff member attributed to a grooming, preventive care or surgery.
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
+pub struct ProcedureStaffMember {
+    pub staff_id: String,
+    pub staff_name: String,
+    pub staff_role: String,
+    #[schema(example = "assistant")]
+    pub staff_function: String,
+}
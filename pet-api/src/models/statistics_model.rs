@@ -1,13 +1,161 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
+#[derive(Deserialize, Serialize, Debug, Clone, sqlx::FromRow, ToSchema)]
 pub struct ServiceTypeCount {
-    service_type_name: String,
-    total: i64,
+    pub service_type_name: String,
+    pub total: i64,
 }
 
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct PetVisitSummary {
     pet_type: String,
     total_visits: i64,
 }
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub total: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct DailyServiceTypeCount {
+    pub day: NaiveDate,
+    pub service_type_name: String,
+    pub total: i64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct StatisticsTotals {
+    pub visits: i64,
+    pub new_pets: i64,
+    pub new_owners: i64,
+    /// Visits per service type, including types with no visits in this bucket.
+    pub service_types: Vec<ServiceTypeCount>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct StatisticsBucket {
+    /// First day of the bucket, clipped to the requested range.
+    pub period_start: NaiveDate,
+    /// Last day of the bucket, clipped to the requested range.
+    pub period_end: NaiveDate,
+    #[serde(flatten)]
+    pub totals: StatisticsTotals,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct MetricChange {
+    pub current: i64,
+    pub previous: i64,
+    pub difference: i64,
+    /// `null` when the previous period had none to compare against.
+    pub percent_change: Option<f64>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PeriodComparison {
+    pub previous_start_date: NaiveDate,
+    pub previous_end_date: NaiveDate,
+    pub visits: MetricChange,
+    pub new_pets: MetricChange,
+    pub new_owners: MetricChange,
+    pub service_types: Vec<ServiceTypeChange>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ServiceTypeChange {
+    pub service_type_name: String,
+    #[serde(flatten)]
+    pub change: MetricChange,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct VisitStatistics {
+    pub granularity: crate::schemas::helper_schema::Granularity,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub buckets: Vec<StatisticsBucket>,
+    pub totals: StatisticsTotals,
+    pub comparison: PeriodComparison,
+}
+
+/// One preventive care or surgery as the vet workload statistics read it.
+#[derive(Debug, sqlx::FromRow)]
+pub struct VetProcedure {
+    pub vet_id: String,
+    /// `preventive_care` or `surgery`.
+    pub procedure_type: String,
+    pub service_date: NaiveDate,
+    pub surgery_name: Option<String>,
+    pub complications: Option<String>,
+    pub outcome: Option<String>,
+    pub pet_id: String,
+    pub pet_type: String,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct VetStatistics {
+    pub granularity: crate::schemas::helper_schema::Granularity,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Busiest vets first.
+    pub vets: Vec<VetWorkload>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct VetWorkload {
+    pub vet_id: String,
+    pub vet_name: String,
+    #[serde(flatten)]
+    pub procedures: ProcedureCounts,
+    pub buckets: Vec<VetWorkloadBucket>,
+    pub surgeries_by_name: Vec<SurgeryCount>,
+    pub surgery_outcomes: SurgeryOutcomes,
+    /// Distinct patients treated, per species.
+    pub patients_by_species: Vec<SpeciesPatientCount>,
+}
+
+#[derive(Serialize, Debug, Default, ToSchema)]
+pub struct ProcedureCounts {
+    pub preventive_care: i64,
+    pub surgeries: i64,
+    pub procedures: i64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct VetWorkloadBucket {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    #[serde(flatten)]
+    pub procedures: ProcedureCounts,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SurgeryCount {
+    pub surgery_name: String,
+    pub total: i64,
+    pub with_complications: i64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SurgeryOutcomes {
+    pub surgeries: i64,
+    /// Surgeries whose `complications` record something other than "none".
+    pub with_complications: i64,
+    /// Percent of surgeries with complications; `null` without surgeries.
+    pub complication_rate: Option<f64>,
+    /// Surgeries whose `outcome` reports a death, euthanasia, failure or poor prognosis.
+    pub unfavorable_outcomes: i64,
+    /// Percent of surgeries with a recorded outcome that were unfavorable.
+    pub unfavorable_outcome_rate: Option<f64>,
+    pub unrecorded_outcomes: i64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SpeciesPatientCount {
+    pub pet_type: String,
+    pub patients: i64,
+}
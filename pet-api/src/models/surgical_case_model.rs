@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::schemas::surgical_case_schema::SurgicalCaseStatus;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SurgicalCaseModel {
+    pub surgery_id: i32,
+    pub case_status: String,
+    pub fasting_confirmed: bool,
+    pub bloodwork_reviewed: bool,
+    pub iv_access_placed: bool,
+    pub surgical_site_prepared: bool,
+    pub consent_given_by: Option<String>,
+    pub consent_given_at: Option<i64>,
+    pub preop_notes: Option<String>,
+    pub intraop_notes: Option<String>,
+    pub postop_instructions: Option<String>,
+    pub opened_at: i64,
+    pub started_at: Option<i64>,
+    pub recovery_at: Option<i64>,
+    pub discharged_at: Option<i64>,
+}
+
+impl SurgicalCaseModel {
+    pub fn status(&self) -> SurgicalCaseStatus {
+        // The column's CHECK constraint only admits the four statuses.
+        SurgicalCaseStatus::parse(&self.case_status).unwrap_or(SurgicalCaseStatus::Planned)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow, ToSchema)]
+pub struct AnesthesiaLogEntry {
+    pub anesthesia_log_entry_id: i32,
+    pub recorded_at: i64,
+    pub drug: Option<String>,
+    pub dose: Option<String>,
+    pub heart_rate: Option<i32>,
+    pub respiratory_rate: Option<i32>,
+    pub temperature_c: Option<f64>,
+    pub spo2: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SurgicalConsent {
+    pub given_by: String,
+    /// Unix seconds.
+    pub given_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PreOpChecklist {
+    pub fasting_confirmed: bool,
+    pub bloodwork_reviewed: bool,
+    pub iv_access_placed: bool,
+    pub surgical_site_prepared: bool,
+    pub consent: Option<SurgicalConsent>,
+    pub notes: Option<String>,
+    /// Every item is checked and consent is signed, so surgery can start.
+    pub complete: bool,
+}
+
+/// When the case entered each status, in unix seconds.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SurgicalCaseTimeline {
+    pub planned_at: i64,
+    pub started_at: Option<i64>,
+    pub recovery_at: Option<i64>,
+    pub discharged_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SurgicalCase {
+    pub surgery_id: i32,
+    pub status: SurgicalCaseStatus,
+    /// The status the case can move to next, if any.
+    pub next_status: Option<SurgicalCaseStatus>,
+    pub pre_op: PreOpChecklist,
+    pub anesthesia_log: Vec<AnesthesiaLogEntry>,
+    pub intraop_notes: Option<String>,
+    pub postop_instructions: Option<String>,
+    pub timeline: SurgicalCaseTimeline,
+}
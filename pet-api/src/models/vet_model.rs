@@ -1,30 +1,23 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct VetModel {
     pub vet_id: String,
     pub vet_name: String,
     pub vet_email: String,
     pub vet_phone_number: String,
     pub vet_license_number: String,
+    pub row_version: i32,
 }
 
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
+#[derive(Deserialize, Serialize, Debug, sqlx::FromRow, ToSchema)]
 pub struct GetVets {
     pub vet_id: String,
     pub vet_name: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
-pub struct VetModelResponse {
-    pub vet_id: String,
-    pub vet_name: String,
-    pub vet_email: String,
-    pub vet_phone_number: String,
-    pub vet_license_number: String,
-}
-
-#[derive(Deserialize, Serialize, Debug, sqlx::FromRow)]
+#[derive(Deserialize, Serialize, Debug, Clone, sqlx::FromRow, ToSchema)]
 pub struct VetModelForService {
     pub vet_id: String,
     pub vet_name: String,
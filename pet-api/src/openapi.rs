@@ -0,0 +1,421 @@
+use utoipa::openapi::path::{ParameterBuilder, ParameterIn};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{ObjectBuilder, Required, SchemaType};
+use utoipa::{Modify, OpenApi};
+
+use crate::handlers::{
+    boarding_handler, certificate_handler, clinic_handler, clinical_code_handler,
+    clinical_note_handler, identifier_handler, import_handler, index_handler, lab_handler,
+    owner_handler, pet_handler, portal_handler, problem_handler, service_instance_handler,
+    staff_handler, statistics_handler, surgical_case_handler, vet_handler,
+};
+use crate::models::{
+    boarding_model, certificate_model, clinic_model, clinical_code_model, clinical_note_model,
+    health_model, identifier_model, import_model, lab_model, owner_model, pet_model, portal_model,
+    problem_model, service_instance_model, staff_model, statistics_model, surgical_case_model,
+    vet_model,
+};
+use crate::schemas::{
+    boarding_schema, certificate_schema, clinic_schema, clinical_code_schema, clinical_note_schema,
+    helper_schema, identifier_schema, import_schema, lab_schema, owner_schema, pet_schema,
+    portal_schema, problem_schema, response_schema, service_instance_schema, staff_schema,
+    surgical_case_schema, vet_schema,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Paws and Claws API",
+        description = "Veterinary clinic records for owners, pets, vets and service visits. \
+            The unversioned RPC-style routes (`/api/pet/get_pets`, ...) are deprecated aliases \
+            of these `/api/v1` routes and answer with a `Deprecation` header.",
+        license(name = "MIT")
+    ),
+    paths(
+        index_handler::index,
+        index_handler::health_check,
+        index_handler::livez,
+        index_handler::readyz,
+        owner_handler::get_owners,
+        owner_handler::get_owner_and_pets,
+        owner_handler::add_owner,
+        owner_handler::update_owner,
+        owner_handler::delete_owner,
+        pet_handler::get_pet,
+        pet_handler::get_pets,
+        pet_handler::add_pet,
+        pet_handler::update_pet,
+        pet_handler::delete_pet,
+        pet_handler::get_pet_record_pdf,
+        pet_handler::get_pet_status,
+        pet_handler::update_pet_status,
+        vet_handler::get_vets,
+        vet_handler::get_vet_lists,
+        vet_handler::add_vet,
+        vet_handler::update_vet,
+        vet_handler::delete_vet,
+        service_instance_handler::get_all_service_instances,
+        service_instance_handler::add_service_instance,
+        service_instance_handler::get_pet_histories,
+        service_instance_handler::get_specific_service_instance,
+        service_instance_handler::get_service_instances_batch,
+        service_instance_handler::update_service_instance,
+        service_instance_handler::add_surgery_to_instance,
+        service_instance_handler::add_grooming_to_instance,
+        service_instance_handler::add_preventive_care_to_instance,
+        service_instance_handler::update_surgery_from_instance,
+        service_instance_handler::delete_service,
+        service_instance_handler::delete_grooming_from_instance,
+        service_instance_handler::delete_preventive_care_from_instance,
+        service_instance_handler::delete_surgery_from_instance,
+        surgical_case_handler::open_surgical_case,
+        surgical_case_handler::get_surgical_case,
+        surgical_case_handler::update_surgical_case,
+        surgical_case_handler::add_anesthesia_log_entry,
+        surgical_case_handler::transition_surgical_case,
+        staff_handler::get_staff,
+        staff_handler::add_staff,
+        staff_handler::get_staff_member,
+        staff_handler::update_staff,
+        staff_handler::get_grooming_staff,
+        staff_handler::assign_grooming_staff,
+        staff_handler::unassign_grooming_staff,
+        staff_handler::get_preventive_care_staff,
+        staff_handler::assign_preventive_care_staff,
+        staff_handler::unassign_preventive_care_staff,
+        staff_handler::get_surgery_staff,
+        staff_handler::assign_surgery_staff,
+        staff_handler::unassign_surgery_staff,
+        statistics_handler::counter_services,
+        statistics_handler::pet_type_visit_summery,
+        portal_handler::request_login_link,
+        portal_handler::create_portal_session,
+        portal_handler::delete_portal_session,
+        portal_handler::get_portal_owner,
+        portal_handler::update_portal_contact_details,
+        portal_handler::get_portal_pet_history,
+        portal_handler::get_portal_followups,
+        statistics_handler::visit_statistics,
+        statistics_handler::vet_statistics,
+        import_handler::import_records,
+        import_handler::get_import_errors,
+        certificate_handler::issue_certificate,
+        certificate_handler::get_pet_certificates,
+        certificate_handler::get_certificate_pdf,
+        certificate_handler::revoke_certificate,
+        certificate_handler::verify_certificate,
+        clinic_handler::get_clinics,
+        clinic_handler::add_clinic,
+        clinic_handler::get_pet_shares,
+        clinic_handler::share_pet,
+        clinic_handler::unshare_pet,
+        boarding_handler::get_kennels,
+        boarding_handler::add_kennel,
+        boarding_handler::get_occupancy,
+        boarding_handler::get_boarding_stays,
+        boarding_handler::add_boarding_stay,
+        boarding_handler::get_boarding_stay,
+        boarding_handler::update_boarding_stay,
+        boarding_handler::transition_boarding_stay,
+        boarding_handler::add_care_log_entry,
+        lab_handler::add_lab_order,
+        lab_handler::get_lab_orders,
+        lab_handler::get_lab_order,
+        lab_handler::add_lab_results,
+        lab_handler::get_lab_trends,
+        lab_handler::get_reference_ranges,
+        problem_handler::get_pet_problems,
+        problem_handler::add_pet_problem,
+        problem_handler::update_pet_problem,
+        clinical_note_handler::add_clinical_note,
+        clinical_note_handler::get_clinical_notes,
+        clinical_note_handler::search_clinical_notes,
+        clinical_code_handler::import_clinical_codes,
+        clinical_code_handler::get_clinical_codes,
+        statistics_handler::code_statistics,
+        identifier_handler::get_pet_identifiers,
+        identifier_handler::add_pet_identifier,
+        identifier_handler::delete_pet_identifier,
+        identifier_handler::get_pet_by_identifier,
+    ),
+    components(schemas(
+        helper_schema::ServiceInstanceSortField,
+        helper_schema::SortOrder,
+        helper_schema::Granularity,
+        health_model::DependencyState,
+        health_model::DependencyStatus,
+        owner_model::OwnerModel,
+        pet_model::PetModel,
+        pet_model::PetModelResponse,
+        pet_model::PetStatusModel,
+        pet_model::PetDeathModel,
+        vet_model::VetModel,
+        vet_model::GetVets,
+        vet_model::VetModelForService,
+        service_instance_model::ServiceInstanceModel,
+        service_instance_model::AllServiceInstanceModel,
+        service_instance_model::SimplePetModel,
+        service_instance_model::GroomingModel,
+        service_instance_model::PreventiveCareModel,
+        service_instance_model::SurgeryModel,
+        service_instance_model::GetServicesHistoryModel,
+        statistics_model::ServiceTypeCount,
+        statistics_model::PetVisitSummary,
+        portal_model::PortalSession,
+        portal_model::UpcomingFollowup,
+        portal_schema::RequestLoginLink,
+        portal_schema::CreatePortalSession,
+        portal_schema::UpdateContactDetails,
+        statistics_model::VisitStatistics,
+        statistics_model::StatisticsBucket,
+        statistics_model::StatisticsTotals,
+        statistics_model::PeriodComparison,
+        statistics_model::MetricChange,
+        statistics_model::ServiceTypeChange,
+        statistics_model::VetStatistics,
+        statistics_model::VetWorkload,
+        statistics_model::ProcedureCounts,
+        statistics_model::VetWorkloadBucket,
+        statistics_model::SurgeryCount,
+        statistics_model::SurgeryOutcomes,
+        statistics_model::SpeciesPatientCount,
+        import_model::ImportRowError,
+        import_model::ImportReport,
+        certificate_model::CertifiedTreatment,
+        certificate_model::CertifiedPet,
+        certificate_model::CertifyingVet,
+        certificate_model::CertificateContent,
+        certificate_model::Certificate,
+        certificate_model::CertificateStatus,
+        certificate_model::CertificateVerification,
+        owner_schema::AddOwner,
+        owner_schema::UpdateOwner,
+        pet_schema::AddPet,
+        pet_schema::UpdatePet,
+        pet_schema::PetStatus,
+        pet_schema::UpdatePetStatus,
+        pet_schema::Euthanasia,
+        vet_schema::AddVet,
+        vet_schema::UpdateVet,
+        service_instance_schema::ServiceInstance,
+        service_instance_schema::AddServiceInstance,
+        service_instance_schema::UpdateServiceInstance,
+        service_instance_schema::Grooming,
+        service_instance_schema::AddGroomingToInstance,
+        service_instance_schema::PreventiveCare,
+        service_instance_schema::Surgery,
+        service_instance_schema::AddSurgery,
+        service_instance_schema::UpdateSurgery,
+        service_instance_schema::AddPreventiveCare,
+        service_instance_schema::AddPreventiveCareToExisting,
+        service_instance_schema::GetServiceInstances,
+        surgical_case_model::SurgicalCase,
+        surgical_case_model::PreOpChecklist,
+        surgical_case_model::SurgicalConsent,
+        surgical_case_model::AnesthesiaLogEntry,
+        surgical_case_model::SurgicalCaseTimeline,
+        surgical_case_schema::SurgicalCaseStatus,
+        surgical_case_schema::UpdateSurgicalCase,
+        surgical_case_schema::AddAnesthesiaLogEntry,
+        surgical_case_schema::SurgicalCaseTransition,
+        staff_model::StaffModel,
+        staff_model::ProcedureStaffMember,
+        staff_schema::StaffRole,
+        staff_schema::StaffFunction,
+        staff_schema::AddStaff,
+        staff_schema::UpdateStaff,
+        staff_schema::AssignStaff,
+        import_schema::ImportEntity,
+        import_schema::ImportFormat,
+        import_schema::ImportMode,
+        import_schema::ImportRequest,
+        certificate_schema::CertificateType,
+        certificate_schema::IssueCertificate,
+        clinic_model::ClinicModel,
+        clinic_model::PetShare,
+        clinic_schema::AddClinic,
+        clinic_schema::SharePet,
+        boarding_model::KennelModel,
+        boarding_model::BoardingStayModel,
+        boarding_model::CareLogEntry,
+        boarding_model::KennelOccupancy,
+        boarding_model::OccupancyNight,
+        boarding_schema::KennelSpecies,
+        boarding_schema::BoardingStayStatus,
+        boarding_schema::AddKennel,
+        boarding_schema::AddBoardingStay,
+        boarding_schema::UpdateBoardingStay,
+        boarding_schema::BoardingStayTransition,
+        boarding_schema::AddCareLogEntry,
+        lab_model::LabOrder,
+        lab_model::LabResultModel,
+        lab_model::ReferenceRange,
+        lab_model::AnalyteTrend,
+        lab_model::TrendPoint,
+        lab_schema::SampleType,
+        lab_schema::LabFlag,
+        lab_schema::AddLabOrder,
+        lab_schema::AddLabResult,
+        lab_schema::AddLabResults,
+        problem_model::PetProblemModel,
+        problem_model::PetAlert,
+        problem_model::PetWithAlerts,
+        problem_model::ServiceInstanceWithAlerts,
+        problem_model::ServiceInstanceWithWarnings,
+        problem_schema::ProblemType,
+        problem_schema::ProblemSeverity,
+        problem_schema::AddPetProblem,
+        problem_schema::UpdatePetProblem,
+        clinical_note_model::ClinicalNoteModel,
+        clinical_note_model::SoapNotes,
+        clinical_note_schema::SoapSection,
+        clinical_note_schema::AddClinicalNote,
+        clinical_code_model::ClinicalCode,
+        clinical_code_model::CodeSuggestion,
+        clinical_code_model::CatalogueImport,
+        clinical_code_model::CodeCount,
+        clinical_code_model::CodeStatistics,
+        clinical_code_schema::CodeType,
+        clinical_code_schema::ImportClinicalCodes,
+        identifier_model::PetIdentifierModel,
+        identifier_model::IdentifiedPetModel,
+        identifier_schema::IdentifierType,
+        identifier_schema::AddPetIdentifier,
+        response_schema::MessageResponse,
+        response_schema::ServiceErrorResponse,
+        response_schema::IndexResponse,
+        response_schema::HealthCheckResponse,
+        response_schema::LivenessResponse,
+        response_schema::ReadinessResponse,
+        response_schema::OwnersResponse,
+        response_schema::OwnerResponse,
+        response_schema::OwnerAndPetsResponse,
+        response_schema::PetsResponse,
+        response_schema::PetResponse,
+        response_schema::PetDetailsResponse,
+        response_schema::PetStatusResponse,
+        response_schema::VetsResponse,
+        response_schema::VetListResponse,
+        response_schema::VetResponse,
+        response_schema::ServiceInstancesResponse,
+        response_schema::ServiceInstanceDetailsResponse,
+        response_schema::PetHistoriesResponse,
+        response_schema::SurgicalCaseResponse,
+        response_schema::StaffListResponse,
+        response_schema::StaffMemberResponse,
+        response_schema::ProcedureStaffResponse,
+        response_schema::ServiceCountsResponse,
+        response_schema::PetTypeVisitSummaryResponse,
+        response_schema::VisitStatisticsResponse,
+        response_schema::VetStatisticsResponse,
+        response_schema::PortalSessionResponse,
+        response_schema::UpcomingFollowupsResponse,
+        response_schema::ImportResponse,
+        response_schema::CertificateResponse,
+        response_schema::CertificatesResponse,
+        response_schema::CertificateVerificationResponse,
+        response_schema::ClinicResponse,
+        response_schema::ClinicsResponse,
+        response_schema::PetSharesResponse,
+        response_schema::KennelResponse,
+        response_schema::KennelsResponse,
+        response_schema::OccupancyResponse,
+        response_schema::BoardingStayResponse,
+        response_schema::BoardingStaysResponse,
+        response_schema::LabOrderResponse,
+        response_schema::LabOrdersResponse,
+        response_schema::LabTrendsResponse,
+        response_schema::ReferenceRangesResponse,
+        response_schema::PetProblemResponse,
+        response_schema::PetProblemsResponse,
+        response_schema::ClinicalNoteResponse,
+        response_schema::ClinicalNotesResponse,
+        response_schema::SoapNotesResponse,
+        response_schema::CatalogueImportResponse,
+        response_schema::CodeSuggestionsResponse,
+        response_schema::CodeStatisticsResponse,
+        response_schema::PetIdentifierResponse,
+        response_schema::PetIdentifiersResponse,
+        response_schema::IdentifiedPetsResponse,
+    )),
+    tags(
+        (name = "index", description = "Liveness, readiness and health checks"),
+        (name = "owners", description = "Pet owners"),
+        (name = "pets", description = "Pets and their owners"),
+        (name = "vets", description = "Veterinarians"),
+        (name = "service_instances", description = "Clinic visits with grooming, preventive care and surgery"),
+        (name = "surgical_cases", description = "Surgical workflow from pre-op checklist and consent through anesthesia monitoring to discharge"),
+        (name = "staff", description = "Vets, technicians, groomers and receptionists, and who performed or assisted each procedure"),
+        (name = "statistics", description = "Dashboard statistics"),
+        (name = "certificates", description = "Vaccination and health certificates and their public verification"),
+        (name = "imports", description = "Bulk CSV and NDJSON imports of owners, pets and vets"),
+        (name = "portal", description = "Owner self-service: sign in with an emailed link, then see only your own pets"),
+        (name = "clinics", description = "The clinics of the group, and sharing a pet's records with another clinic"),
+        (name = "boarding", description = "Kennels and runs, boarding reservations with feeding and medication instructions, and daily care logs"),
+        (name = "labs", description = "Lab orders on visits, results flagged against species reference ranges, and per-pet analyte trends"),
+        (name = "problems", description = "Each pet's problem list of allergies, chronic conditions and handling cautions, shown as alerts wherever the pet is"),
+        (name = "clinical_notes", description = "Subjective, objective, assessment and plan notes on visits, written in Markdown and searchable across a pet's history"),
+        (name = "clinical_codes", description = "Coded diagnosis, procedure and treatment terms with their synonyms, imported from a catalogue file and suggested as you type"),
+        (name = "identifiers", description = "Microchip, tattoo and license tag numbers, and looking up a found animal by any of them"),
+    ),
+    modifiers(&PortalSessionAuth, &ClinicHeader)
+)]
+pub struct ApiDoc;
+
+/// The bearer token returned by `POST /api/v1/portal/session`.
+struct PortalSessionAuth;
+
+impl Modify for PortalSessionAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "portal_session",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
+
+/// The `X-Clinic-Id` header every clinic-scoped operation accepts.
+struct ClinicHeader;
+
+impl Modify for ClinicHeader {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for (path, path_item) in openapi.paths.paths.iter_mut() {
+            let unscoped = [
+                "/api/v1/clinics",
+                "/api/v1/portal/",
+                "/api/v1/certificates/verify/",
+            ]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+                && path != "/api/v1/portal/login-links";
+            if unscoped || !path.starts_with("/api/v1/") {
+                continue;
+            }
+
+            for operation in path_item.operations.values_mut() {
+                let clinic_id = ParameterBuilder::new()
+                    .name("X-Clinic-Id")
+                    .parameter_in(ParameterIn::Header)
+                    .required(Required::False)
+                    .description(Some(
+                        "Clinic to act for; defaults to the main clinic. Portal sessions always act for the owner's clinic",
+                    ))
+                    .schema(Some(ObjectBuilder::new().schema_type(SchemaType::String)))
+                    .build();
+                operation
+                    .parameters
+                    .get_or_insert_with(Vec::new)
+                    .push(clinic_id);
+            }
+        }
+    }
+}
+
+/// Renders the OpenAPI document exactly as served at `/api/openapi.json`.
+pub fn openapi_json() -> String {
+    ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("the OpenAPI document is serializable")
+}
@@ -1,30 +1,288 @@
 use std::sync::Arc;
 
 use axum::{
+    middleware::{from_fn_with_state, map_response},
     routing::{delete, get, patch, post},
     Router,
 };
 
 use crate::{
     handlers::{
+        boarding_handler::{
+            add_boarding_stay, add_care_log_entry, add_kennel, get_boarding_stay,
+            get_boarding_stays, get_kennels, get_occupancy, transition_boarding_stay,
+            update_boarding_stay,
+        },
+        certificate_handler::{
+            get_certificate_pdf, get_pet_certificates, issue_certificate, revoke_certificate,
+            verify_certificate,
+        },
+        clinic_handler::{add_clinic, get_clinics, get_pet_shares, share_pet, unshare_pet},
+        clinical_code_handler::{get_clinical_codes, import_clinical_codes},
+        clinical_note_handler::{add_clinical_note, get_clinical_notes, search_clinical_notes},
+        docs_handler::{openapi_document, swagger_ui, swagger_ui_bundle, swagger_ui_css},
+        identifier_handler::{
+            add_pet_identifier, delete_pet_identifier, get_pet_by_identifier, get_pet_identifiers,
+        },
+        import_handler::{get_import_errors, import_records},
         index_handler::health_check,
         index_handler::index,
+        index_handler::{livez, readyz},
+        lab_handler::{
+            add_lab_order, add_lab_results, get_lab_order, get_lab_orders, get_lab_trends,
+            get_reference_ranges,
+        },
         owner_handler::{add_owner, delete_owner, get_owner_and_pets, get_owners, update_owner},
-        pet_handler::{add_pet, delete_pet, get_pet, get_pets, update_pet},
+        pet_handler::{
+            add_pet, delete_pet, get_pet, get_pet_record_pdf, get_pet_status, get_pets, update_pet,
+            update_pet_status,
+        },
+        portal_handler::{
+            create_portal_session, delete_portal_session, get_portal_followups, get_portal_owner,
+            get_portal_pet_history, request_login_link, update_portal_contact_details,
+        },
+        problem_handler::{add_pet_problem, get_pet_problems, update_pet_problem},
         service_instance_handler::{
             add_grooming_to_instance, add_preventive_care_to_instance, add_service_instance,
             add_surgery_to_instance, delete_grooming_from_instance,
             delete_preventive_care_from_instance, delete_service, delete_surgery_from_instance,
-            get_all_service_instances, get_pet_histories, get_specific_service_instance,
-            update_service_instance, update_surgery_from_instance,
+            get_all_service_instances, get_pet_histories, get_service_instances_batch,
+            get_specific_service_instance, update_service_instance, update_surgery_from_instance,
+        },
+        staff_handler::{
+            add_staff, assign_grooming_staff, assign_preventive_care_staff, assign_surgery_staff,
+            get_grooming_staff, get_preventive_care_staff, get_staff, get_staff_member,
+            get_surgery_staff, unassign_grooming_staff, unassign_preventive_care_staff,
+            unassign_surgery_staff, update_staff,
+        },
+        statistics_handler::{
+            code_statistics, counter_services, pet_type_visit_summery, vet_statistics,
+            visit_statistics,
+        },
+        surgical_case_handler::{
+            add_anesthesia_log_entry, get_surgical_case, open_surgical_case,
+            transition_surgical_case, update_surgical_case,
         },
-        statistics_handler::{counter_services, pet_type_visit_summery},
         vet_handler::{add_vet, delete_vet, get_vet_lists, get_vets, update_vet},
     },
+    utils::{deprecation::mark_deprecated, idempotency::idempotent},
     AppState,
 };
 
 pub fn create_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/api", get(index))
+        .route("/api/health_check", get(health_check))
+        .route("/api/livez", get(livez))
+        .route("/api/readyz", get(readyz))
+        .route("/api/openapi.json", get(openapi_document))
+        .route("/api/docs", get(swagger_ui))
+        .route("/api/docs/swagger-ui.css", get(swagger_ui_css))
+        .route("/api/docs/swagger-ui-bundle.js", get(swagger_ui_bundle))
+        .nest("/api/v1", v1_routes())
+        .merge(legacy_routes().layer(map_response(mark_deprecated)))
+        .layer(from_fn_with_state(app_state.clone(), idempotent))
+        .with_state(app_state)
+}
+
+fn v1_routes() -> Router<Arc<AppState>> {
+    let owner_routes = Router::new()
+        .route("/", get(get_owners).post(add_owner))
+        .route(
+            "/:owner_id",
+            get(get_owner_and_pets)
+                .patch(update_owner)
+                .delete(delete_owner),
+        );
+
+    let pet_routes = Router::new()
+        .route("/", get(get_pets).post(add_pet))
+        .route("/by-identifier/:code", get(get_pet_by_identifier))
+        .route(
+            "/:pet_id",
+            get(get_pet).patch(update_pet).delete(delete_pet),
+        )
+        .route("/:pet_id/service-instances", get(get_pet_histories))
+        .route("/:pet_id/record.pdf", get(get_pet_record_pdf))
+        .route(
+            "/:pet_id/status",
+            get(get_pet_status).put(update_pet_status),
+        )
+        .route(
+            "/:pet_id/certificates",
+            get(get_pet_certificates).post(issue_certificate),
+        )
+        .route("/:pet_id/lab-trends", get(get_lab_trends))
+        .route("/:pet_id/clinical-notes", get(search_clinical_notes))
+        .route(
+            "/:pet_id/problems",
+            get(get_pet_problems).post(add_pet_problem),
+        )
+        .route("/:pet_id/problems/:problem_id", patch(update_pet_problem))
+        .route(
+            "/:pet_id/identifiers",
+            get(get_pet_identifiers).post(add_pet_identifier),
+        )
+        .route(
+            "/:pet_id/identifiers/:identifier_id",
+            delete(delete_pet_identifier),
+        )
+        .route("/:pet_id/shares", get(get_pet_shares).post(share_pet))
+        .route("/:pet_id/shares/:clinic_id", delete(unshare_pet));
+
+    let vet_routes = Router::new()
+        .route("/", get(get_vets).post(add_vet))
+        .route("/list", get(get_vet_lists))
+        .route("/:vet_id", patch(update_vet).delete(delete_vet));
+
+    let service_instance_routes = Router::new()
+        .route(
+            "/",
+            get(get_all_service_instances).post(add_service_instance),
+        )
+        .route("/batch", post(get_service_instances_batch))
+        .route(
+            "/:service_instance_id",
+            get(get_specific_service_instance)
+                .patch(update_service_instance)
+                .delete(delete_service),
+        )
+        .route(
+            "/:service_instance_id/surgeries",
+            post(add_surgery_to_instance),
+        )
+        .route(
+            "/:service_instance_id/grooming",
+            post(add_grooming_to_instance),
+        )
+        .route(
+            "/:service_instance_id/preventive-care",
+            post(add_preventive_care_to_instance),
+        )
+        .route(
+            "/:service_instance_id/lab-orders",
+            get(get_lab_orders).post(add_lab_order),
+        )
+        .route(
+            "/:service_instance_id/clinical-notes",
+            get(get_clinical_notes).post(add_clinical_note),
+        );
+
+    let certificate_routes = Router::new()
+        .route("/:certificate_id/certificate.pdf", get(get_certificate_pdf))
+        .route("/:certificate_id/revoke", post(revoke_certificate))
+        .route("/verify/:verification_code", get(verify_certificate));
+
+    let portal_routes = Router::new()
+        .route("/login-links", post(request_login_link))
+        .route(
+            "/session",
+            post(create_portal_session).delete(delete_portal_session),
+        )
+        .route(
+            "/me",
+            get(get_portal_owner).patch(update_portal_contact_details),
+        )
+        .route("/pets/:pet_id/history", get(get_portal_pet_history))
+        .route("/followups", get(get_portal_followups));
+
+    let staff_routes = Router::new()
+        .route("/", get(get_staff).post(add_staff))
+        .route("/:staff_id", get(get_staff_member).patch(update_staff));
+
+    let statistics_routes = Router::new()
+        .route("/services", get(counter_services))
+        .route("/pet-type-visits", get(pet_type_visit_summery))
+        .route("/visits", get(visit_statistics))
+        .route("/vets", get(vet_statistics))
+        .route("/codes", get(code_statistics));
+
+    let boarding_routes = Router::new()
+        .route("/kennels", get(get_kennels).post(add_kennel))
+        .route("/occupancy", get(get_occupancy))
+        .route("/stays", get(get_boarding_stays).post(add_boarding_stay))
+        .route(
+            "/stays/:stay_id",
+            get(get_boarding_stay).patch(update_boarding_stay),
+        )
+        .route(
+            "/stays/:stay_id/transitions",
+            post(transition_boarding_stay),
+        )
+        .route("/stays/:stay_id/care-log", post(add_care_log_entry));
+
+    Router::new()
+        .route("/clinics", get(get_clinics).post(add_clinic))
+        .nest("/owners", owner_routes)
+        .nest("/pets", pet_routes)
+        .nest("/vets", vet_routes)
+        .nest("/staff", staff_routes)
+        .nest("/service-instances", service_instance_routes)
+        .route(
+            "/surgeries/:surgery_id",
+            patch(update_surgery_from_instance).delete(delete_surgery_from_instance),
+        )
+        .route(
+            "/surgeries/:surgery_id/case",
+            get(get_surgical_case)
+                .post(open_surgical_case)
+                .patch(update_surgical_case),
+        )
+        .route(
+            "/surgeries/:surgery_id/case/anesthesia-log",
+            post(add_anesthesia_log_entry),
+        )
+        .route(
+            "/surgeries/:surgery_id/case/transitions",
+            post(transition_surgical_case),
+        )
+        .route(
+            "/surgeries/:surgery_id/staff",
+            get(get_surgery_staff).post(assign_surgery_staff),
+        )
+        .route(
+            "/surgeries/:surgery_id/staff/:staff_id",
+            delete(unassign_surgery_staff),
+        )
+        .route(
+            "/grooming/:grooming_id",
+            delete(delete_grooming_from_instance),
+        )
+        .route(
+            "/grooming/:grooming_id/staff",
+            get(get_grooming_staff).post(assign_grooming_staff),
+        )
+        .route(
+            "/grooming/:grooming_id/staff/:staff_id",
+            delete(unassign_grooming_staff),
+        )
+        .route(
+            "/preventive-care/:preventive_care_id",
+            delete(delete_preventive_care_from_instance),
+        )
+        .route(
+            "/preventive-care/:preventive_care_id/staff",
+            get(get_preventive_care_staff).post(assign_preventive_care_staff),
+        )
+        .route(
+            "/preventive-care/:preventive_care_id/staff/:staff_id",
+            delete(unassign_preventive_care_staff),
+        )
+        .nest("/certificates", certificate_routes)
+        .nest("/portal", portal_routes)
+        .nest("/statistics", statistics_routes)
+        .nest("/boarding", boarding_routes)
+        .route("/lab-orders/:lab_order_id", get(get_lab_order))
+        .route("/lab-orders/:lab_order_id/results", post(add_lab_results))
+        .route("/lab-reference-ranges", get(get_reference_ranges))
+        .route("/clinical-codes", get(get_clinical_codes))
+        .route("/clinical-codes/imports", post(import_clinical_codes))
+        .route("/imports", post(import_records))
+        .route("/imports/:import_id/errors", get(get_import_errors))
+}
+
+/// The original RPC-style routes, kept as deprecated aliases of `/api/v1`.
+fn legacy_routes() -> Router<Arc<AppState>> {
     let owner_routes = Router::new()
         .route("/get_owners", get(get_owners))
         .route("/get_owner_and_pets/:owner_id", get(get_owner_and_pets))
@@ -37,7 +295,9 @@ pub fn create_router(app_state: Arc<AppState>) -> Router {
         .route("/get_pets", get(get_pets))
         .route("/add_pet", post(add_pet))
         .route("/update_pet/:pet_id", patch(update_pet))
-        .route("/delete_pet/:pet_id", delete(delete_pet));
+        .route("/delete_pet/:pet_id", delete(delete_pet))
+        .route("/by_identifier/:code", get(get_pet_by_identifier))
+        .route("/:pet_id/record.pdf", get(get_pet_record_pdf));
 
     let vet_routes = Router::new()
         .route("/get_vets", get(get_vets))
@@ -93,15 +353,13 @@ pub fn create_router(app_state: Arc<AppState>) -> Router {
 
     let statistics_routes = Router::new()
         .route("/counter_services", get(counter_services))
-        .route("/get_pet_type_visit_summary", get(pet_type_visit_summery));
+        .route("/get_pet_type_visit_summary", get(pet_type_visit_summery))
+        .route("/vets", get(vet_statistics));
 
     Router::new()
-        .route("/api", get(index))
-        .route("/api/health_check", get(health_check))
         .nest("/api/statistics", statistics_routes)
         .nest("/api/owner", owner_routes)
         .nest("/api/pet", pet_routes)
         .nest("/api/vet", vet_routes)
         .nest("/api/service_instance", service_instance_routes)
-        .with_state(app_state)
 }
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Which pets a kennel or run is suitable for.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum KennelSpecies {
+    Dog,
+    Cat,
+    Any,
+}
+
+impl KennelSpecies {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KennelSpecies::Dog => "Dog",
+            KennelSpecies::Cat => "Cat",
+            KennelSpecies::Any => "Any",
+        }
+    }
+}
+
+/// Where a boarding stay is. A reservation is checked in and then out, or cancelled before
+/// the pet arrives.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardingStayStatus {
+    Reserved,
+    /// The pet is in the kennel; care can be logged.
+    CheckedIn,
+    CheckedOut,
+    /// Frees the kennel for the reserved nights.
+    Cancelled,
+}
+
+impl BoardingStayStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BoardingStayStatus::Reserved => "reserved",
+            BoardingStayStatus::CheckedIn => "checked_in",
+            BoardingStayStatus::CheckedOut => "checked_out",
+            BoardingStayStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "reserved" => Some(BoardingStayStatus::Reserved),
+            "checked_in" => Some(BoardingStayStatus::CheckedIn),
+            "checked_out" => Some(BoardingStayStatus::CheckedOut),
+            "cancelled" => Some(BoardingStayStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// The statuses a stay in this one may move to.
+    pub fn next(self) -> &'static [BoardingStayStatus] {
+        match self {
+            BoardingStayStatus::Reserved => {
+                &[BoardingStayStatus::CheckedIn, BoardingStayStatus::Cancelled]
+            }
+            BoardingStayStatus::CheckedIn => &[BoardingStayStatus::CheckedOut],
+            BoardingStayStatus::CheckedOut | BoardingStayStatus::Cancelled => &[],
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddKennel {
+    #[schema(example = "Run 3")]
+    pub kennel_name: String,
+    /// How many pets it holds at once.
+    pub capacity: i32,
+    pub species: KennelSpecies,
+}
+
+/// A reservation of a kennel for a pet. The pet sleeps there every night from the check-in
+/// date up to, but not including, the check-out date.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddBoardingStay {
+    pub pet_id: String,
+    pub kennel_id: String,
+    /// `YYYY-MM-DD`.
+    pub check_in_date: String,
+    /// `YYYY-MM-DD`, after the check-in date.
+    pub check_out_date: String,
+    #[schema(example = "1 cup dry food morning and evening")]
+    pub feeding_instructions: Option<String>,
+    pub medication_instructions: Option<String>,
+}
+
+/// Changes to a stay. The kennel and check-in date can only change while it is reserved,
+/// the check-out date also while the pet is in, and nothing once it is over.
+#[derive(Deserialize, Serialize, Debug, Default, ToSchema)]
+pub struct UpdateBoardingStay {
+    pub kennel_id: Option<String>,
+    pub check_in_date: Option<String>,
+    pub check_out_date: Option<String>,
+    /// A blank value clears it.
+    pub feeding_instructions: Option<String>,
+    /// A blank value clears it.
+    pub medication_instructions: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct BoardingStayTransition {
+    pub status: BoardingStayStatus,
+}
+
+/// What was done for a boarded pet on a day of its stay.
+#[derive(Deserialize, Serialize, Debug, Default, ToSchema)]
+pub struct AddCareLogEntry {
+    /// `YYYY-MM-DD`; today when omitted.
+    pub care_date: Option<String>,
+    #[serde(default)]
+    pub fed: bool,
+    /// Only for stays with medication instructions.
+    #[serde(default)]
+    pub medication_given: bool,
+    #[serde(default)]
+    pub exercised: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BoardingStayOptions {
+    /// 1-based page number; 0 is read as 1.
+    pub page: Option<usize>,
+    /// Rows per page, 1 to 100. Defaults to 10.
+    pub limit: Option<usize>,
+    pub status: Option<BoardingStayStatus>,
+    pub pet_id: Option<String>,
+    pub kennel_id: Option<String>,
+    /// Only stays with the pet in the kennel the night of this date, `YYYY-MM-DD`.
+    pub night: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OccupancyOptions {
+    /// First night shown, `YYYY-MM-DD`. Defaults to today.
+    pub start_date: Option<String>,
+    /// Last night shown, `YYYY-MM-DD`. Defaults to two weeks after the first.
+    pub end_date: Option<String>,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateType {
+    /// Lists the vaccines and other preventive care the pet received.
+    Vaccination,
+    /// States the pet's fitness, e.g. to travel or board, in the remarks.
+    Health,
+}
+
+impl CertificateType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CertificateType::Vaccination => "vaccination",
+            CertificateType::Health => "health",
+        }
+    }
+
+    /// Prefix of the certificate number.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            CertificateType::Vaccination => "VC",
+            CertificateType::Health => "HC",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            CertificateType::Vaccination => "Vaccination Certificate",
+            CertificateType::Health => "Health Certificate",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct IssueCertificate {
+    pub certificate_type: CertificateType,
+    /// The issuing vet, who must have a license number.
+    pub vet_id: String,
+    /// Preventive care records of the pet to list; all of them when omitted.
+    pub preventive_care_ids: Option<Vec<i32>>,
+    /// Last day the certificate is valid, `YYYY-MM-DD`.
+    pub valid_until: Option<String>,
+    /// Free-text statement; required for health certificates.
+    pub remarks: Option<String>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddClinic {
+    pub clinic_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct SharePet {
+    /// The clinic that may read the pet's records from now on.
+    pub clinic_id: String,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::schemas::import_schema::ImportFormat;
+
+/// What a catalogue code describes, and so which records it can be referenced from.
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeType {
+    /// A visit's `general_diagnosis`.
+    Diagnosis,
+    /// A surgery.
+    Procedure,
+    /// A preventive care treatment.
+    Treatment,
+}
+
+impl CodeType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CodeType::Diagnosis => "diagnosis",
+            CodeType::Procedure => "procedure",
+            CodeType::Treatment => "treatment",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "diagnosis" => Some(CodeType::Diagnosis),
+            "procedure" => Some(CodeType::Procedure),
+            "treatment" => Some(CodeType::Treatment),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct ImportClinicalCodes {
+    pub format: ImportFormat,
+    /// A CSV document with a `code,code_type,term,synonyms` header, or one JSON object with
+    /// those fields per line. Synonyms are separated by `|`. Codes already in the catalogue
+    /// are replaced.
+    #[schema(
+        example = "code,code_type,term,synonyms\nP-OVH,procedure,Ovariohysterectomy,spay|OVH"
+    )]
+    pub data: String,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClinicalCodeOptions {
+    /// The start of a code, or part of a term or synonym.
+    pub q: Option<String>,
+    pub code_type: Option<CodeType>,
+    /// At most this many suggestions; 10 by default.
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CodeStatisticsOptions {
+    /// Earliest visit date counted, `YYYY-MM-DD`.
+    pub start_date: Option<String>,
+    /// Latest visit date counted, `YYYY-MM-DD`.
+    pub end_date: Option<String>,
+    pub code_type: Option<CodeType>,
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// The part of a SOAP note an entry belongs to.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SoapSection {
+    Subjective,
+    Objective,
+    Assessment,
+    Plan,
+}
+
+impl SoapSection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SoapSection::Subjective => "subjective",
+            SoapSection::Objective => "objective",
+            SoapSection::Assessment => "assessment",
+            SoapSection::Plan => "plan",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddClinicalNote {
+    pub section: SoapSection,
+    /// The veterinarian or technician writing the entry; must work at the clinic.
+    pub author_id: String,
+    /// Markdown, stored as written.
+    #[schema(example = "Owner reports **vomiting** since Tuesday.\n\n- off food\n- lethargic")]
+    pub body: String,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClinicalNoteOptions {
+    pub section: Option<SoapSection>,
+    pub author_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClinicalNoteSearchOptions {
+    /// 1-based page number; 0 is read as 1.
+    pub page: Option<usize>,
+    /// Rows per page, 1 to 100. Defaults to 20.
+    pub limit: Option<usize>,
+    /// Only entries whose body contains this text, ignoring case.
+    pub q: Option<String>,
+    pub section: Option<SoapSection>,
+    pub author_id: Option<String>,
+    /// Earliest service date, `YYYY-MM-DD`.
+    pub start_date: Option<String>,
+    /// Latest service date, `YYYY-MM-DD`.
+    pub end_date: Option<String>,
+}
@@ -1,14 +1,121 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FilterOptions {
+    /// 1-based page number; 0 is read as 1.
     pub page: Option<usize>,
+    /// Rows per page, 1 to 100. Defaults to 10.
     pub limit: Option<usize>,
     pub search: Option<String>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
 }
 
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DateRangeOptions {
+    /// Earliest date included, `YYYY-MM-DD`.
+    pub start_date: Option<String>,
+    /// Latest date included, `YYYY-MM-DD`.
+    pub end_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatisticsRangeOptions {
+    /// First day counted, `YYYY-MM-DD`. Defaults to 30 days, 12 weeks or 12 months back.
+    pub start_date: Option<String>,
+    /// Last day counted, `YYYY-MM-DD`. Defaults to today.
+    pub end_date: Option<String>,
+    pub granularity: Option<Granularity>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VetStatisticsOptions {
+    /// First visit date counted, `YYYY-MM-DD`. Defaults to 30 days, 12 weeks or 12 months back.
+    pub start_date: Option<String>,
+    /// Last visit date counted, `YYYY-MM-DD`. Defaults to today.
+    pub end_date: Option<String>,
+    pub granularity: Option<Granularity>,
+    /// Only report this vet.
+    pub vet_id: Option<String>,
+}
+
+/// Bucket size of a time series; weeks start on Monday.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ServiceInstanceFilterOptions {
+    /// 1-based page number; 0 is read as 1.
+    pub page: Option<usize>,
+    /// Rows per page, 1 to 100. Defaults to 10.
+    pub limit: Option<usize>,
+    /// Earliest service date, `YYYY-MM-DD`.
+    pub start_date: Option<String>,
+    /// Latest service date, `YYYY-MM-DD`.
+    pub end_date: Option<String>,
+    pub pet_id: Option<String>,
+    pub owner_id: Option<String>,
+    /// Species of the pet, e.g. `Dog`.
+    pub pet_type: Option<String>,
+    pub service_type: Option<String>,
+    /// Vet attending a preventive care or surgery during the visit.
+    pub vet_id: Option<String>,
+    /// `true` leaves out deceased and memorial-safe pets, whose owners aren't reminded.
+    pub requires_followup: Option<bool>,
+    pub sort_by: Option<ServiceInstanceSortField>,
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceInstanceSortField {
+    #[default]
+    ServiceDate,
+    PetName,
+    PetType,
+    OwnerName,
+}
+
+impl ServiceInstanceSortField {
+    pub fn column(self) -> &'static str {
+        match self {
+            ServiceInstanceSortField::ServiceDate => "si.service_date",
+            ServiceInstanceSortField::PetName => "p.pet_name",
+            ServiceInstanceSortField::PetType => "p.pet_type",
+            ServiceInstanceSortField::OwnerName => "o.owner_name",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
 // #[derive(Deserialize, Debug)]
 // pub struct ParamOptions {
 //     pub id: String
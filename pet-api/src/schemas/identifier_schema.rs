@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierType {
+    /// An ISO 11784/11785 transponder, read as a 15-digit number.
+    Microchip,
+    Tattoo,
+    /// A municipal or registry license tag.
+    LicenseTag,
+}
+
+impl IdentifierType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IdentifierType::Microchip => "microchip",
+            IdentifierType::Tattoo => "tattoo",
+            IdentifierType::LicenseTag => "license_tag",
+        }
+    }
+
+    /// How the identifier is named in messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            IdentifierType::Microchip => "Microchip",
+            IdentifierType::Tattoo => "Tattoo",
+            IdentifierType::LicenseTag => "License tag",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddPetIdentifier {
+    pub identifier_type: IdentifierType,
+    /// The number as read or printed; spaces, dashes and dots are ignored and letters are
+    /// stored in upper case.
+    #[schema(example = "985 112 003 456 789")]
+    pub identifier_value: String,
+    /// When the microchip was implanted. Only microchips have one.
+    pub implant_date: Option<NaiveDate>,
+    /// Where the microchip sits, e.g. `Left shoulder`. Only microchips have one.
+    pub implant_location: Option<String>,
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportEntity {
+    Owners,
+    Pets,
+    Vets,
+}
+
+impl ImportEntity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportEntity::Owners => "owners",
+            ImportEntity::Pets => "pets",
+            ImportEntity::Vets => "vets",
+        }
+    }
+
+    /// Fields a row may carry, after column mapping.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            ImportEntity::Owners => &[
+                "owner_name",
+                "owner_email",
+                "owner_phone_number",
+                "owner_address",
+            ],
+            ImportEntity::Pets => &[
+                "pet_name",
+                "pet_birth_date",
+                "pet_type",
+                "pet_breed",
+                "pet_weight",
+                "pet_color",
+                "owner_id",
+                "owner_email",
+            ],
+            ImportEntity::Vets => &[
+                "vet_name",
+                "vet_email",
+                "vet_phone_number",
+                "vet_license_number",
+            ],
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    Ndjson,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Save nothing unless every row is valid.
+    #[default]
+    AllOrNothing,
+    /// Save the valid rows and report the rest.
+    BestEffort,
+}
+
+impl ImportMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportMode::AllOrNothing => "all_or_nothing",
+            ImportMode::BestEffort => "best_effort",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct ImportRequest {
+    pub entity: ImportEntity,
+    pub format: ImportFormat,
+    /// The CSV document, header row first, or one JSON object per line.
+    pub data: String,
+    /// Source column names mapped to field names, e.g. `{"Email": "owner_email"}`.
+    /// Columns already named after a field need no mapping.
+    #[serde(default)]
+    pub mapping: BTreeMap<String, String>,
+    #[serde(default)]
+    pub mode: ImportMode,
+    /// Check every row, including against the database, without saving anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// What was sampled for a lab order.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleType {
+    Blood,
+    Serum,
+    Plasma,
+    Urine,
+    Feces,
+    Tissue,
+    Swab,
+    Other,
+}
+
+impl SampleType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SampleType::Blood => "blood",
+            SampleType::Serum => "serum",
+            SampleType::Plasma => "plasma",
+            SampleType::Urine => "urine",
+            SampleType::Feces => "feces",
+            SampleType::Tissue => "tissue",
+            SampleType::Swab => "swab",
+            SampleType::Other => "other",
+        }
+    }
+}
+
+/// Where a result sits against its reference range.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LabFlag {
+    Low,
+    Normal,
+    High,
+}
+
+impl LabFlag {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LabFlag::Low => "low",
+            LabFlag::Normal => "normal",
+            LabFlag::High => "high",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddLabOrder {
+    /// The panel or test ordered, e.g. `CBC` or `Chem 10`.
+    pub panel: String,
+    pub sample_type: SampleType,
+    /// The vet ordering it; must work at the clinic.
+    pub vet_id: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddLabResult {
+    /// Analyte code, e.g. `HGB`. Stored in upper case.
+    pub analyte: String,
+    pub value: f64,
+    /// Defaults to the unit of the species reference range.
+    pub unit: Option<String>,
+    /// The range the lab reported. Without one, the species reference range is used when the
+    /// units match.
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddLabResults {
+    pub results: Vec<AddLabResult>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LabTrendOptions {
+    /// Only this analyte's trend.
+    pub analyte: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReferenceRangeOptions {
+    /// `Dog` or `Cat`.
+    pub species: Option<String>,
+}
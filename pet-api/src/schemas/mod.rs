@@ -1,5 +1,18 @@
+pub(crate) mod boarding_schema;
+pub(crate) mod certificate_schema;
+pub(crate) mod clinic_schema;
+pub(crate) mod clinical_code_schema;
+pub(crate) mod clinical_note_schema;
 pub(crate) mod helper_schema;
+pub(crate) mod identifier_schema;
+pub(crate) mod import_schema;
+pub(crate) mod lab_schema;
 pub(crate) mod owner_schema;
 pub(crate) mod pet_schema;
+pub(crate) mod portal_schema;
+pub(crate) mod problem_schema;
+pub(crate) mod response_schema;
 pub(crate) mod service_instance_schema;
+pub(crate) mod staff_schema;
+pub(crate) mod surgical_case_schema;
 pub(crate) mod vet_schema;
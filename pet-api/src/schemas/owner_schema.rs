@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct AddOwner {
     pub owner_name: String,
     pub owner_email: String,
@@ -8,7 +9,7 @@ pub struct AddOwner {
     pub owner_address: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct UpdateOwner {
     pub owner_name: Option<String>,
     pub owner_email: Option<String>,
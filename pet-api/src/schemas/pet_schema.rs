@@ -1,6 +1,8 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct AddPet {
     pub pet_name: String,
     pub pet_birth_date: String,
@@ -11,7 +13,7 @@ pub struct AddPet {
     pub owner_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct UpdatePet {
     pub pet_name: Option<String>,
     pub pet_birth_date: Option<String>,
@@ -21,3 +23,67 @@ pub struct UpdatePet {
     pub pet_color: Option<String>,
     pub owner_id: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PetStatus {
+    Active,
+    /// No longer seen, e.g. the owner moved away, but not known to be transferred or deceased.
+    Inactive,
+    /// Final: a deceased pet's status can't change again.
+    Deceased,
+    /// Now cared for by another practice.
+    Transferred,
+}
+
+impl PetStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PetStatus::Active => "active",
+            PetStatus::Inactive => "inactive",
+            PetStatus::Deceased => "deceased",
+            PetStatus::Transferred => "transferred",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "active" => Some(PetStatus::Active),
+            "inactive" => Some(PetStatus::Inactive),
+            "deceased" => Some(PetStatus::Deceased),
+            "transferred" => Some(PetStatus::Transferred),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct UpdatePetStatus {
+    /// Left out to only change `memorial_safe`.
+    pub status: Option<PetStatus>,
+    /// Required when the pet is marked deceased.
+    pub date_of_death: Option<NaiveDate>,
+    pub cause_of_death: Option<String>,
+    /// Given when the pet was euthanized.
+    pub euthanasia: Option<Euthanasia>,
+    /// Keeps the owner from being contacted about the pet. Set when the pet is marked deceased,
+    /// unless this says otherwise.
+    pub memorial_safe: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct Euthanasia {
+    /// Who signed the consent form.
+    pub consent_given_by: String,
+    /// When the consent was signed; the date of death when left out.
+    pub consent_given_on: Option<NaiveDate>,
+    /// The vet who performed it.
+    pub vet_id: String,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PetListOptions {
+    /// Lists only pets with this status. Without it, every pet but the deceased is listed.
+    pub status: Option<PetStatus>,
+}
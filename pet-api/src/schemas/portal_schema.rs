@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RequestLoginLink {
+    pub owner_email: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct CreatePortalSession {
+    /// The token from the emailed sign-in link.
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct UpdateContactDetails {
+    pub owner_email: Option<String>,
+    pub owner_phone_number: Option<String>,
+    pub owner_address: Option<String>,
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemType {
+    /// A drug, food or other substance the pet reacts to. Recorded treatments are checked
+    /// against it.
+    Allergy,
+    ChronicCondition,
+    /// How to handle the pet safely, e.g. bites or needs a muzzle.
+    BehavioralCaution,
+}
+
+impl ProblemType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProblemType::Allergy => "allergy",
+            ProblemType::ChronicCondition => "chronic_condition",
+            ProblemType::BehavioralCaution => "behavioral_caution",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    Mild,
+    Moderate,
+    Severe,
+}
+
+impl ProblemSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProblemSeverity::Mild => "mild",
+            ProblemSeverity::Moderate => "moderate",
+            ProblemSeverity::Severe => "severe",
+        }
+    }
+
+    pub fn parse(severity: &str) -> Option<Self> {
+        match severity {
+            "mild" => Some(ProblemSeverity::Mild),
+            "moderate" => Some(ProblemSeverity::Moderate),
+            "severe" => Some(ProblemSeverity::Severe),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddPetProblem {
+    pub problem_type: ProblemType,
+    /// What the problem is, e.g. `Penicillin` for an allergy or `Bites when restrained`.
+    pub description: String,
+    pub severity: ProblemSeverity,
+    /// `YYYY-MM-DD`.
+    pub onset_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Changes to a problem-list entry. The type can't change; record a new entry instead.
+#[derive(Deserialize, Serialize, Debug, Default, ToSchema)]
+pub struct UpdatePetProblem {
+    pub description: Option<String>,
+    pub severity: Option<ProblemSeverity>,
+    /// `YYYY-MM-DD`; an empty string clears it.
+    pub onset_date: Option<String>,
+    /// `YYYY-MM-DD`; resolves the problem so it no longer raises alerts. An empty string
+    /// reopens it.
+    pub resolved_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PetProblemOptions {
+    /// Also list resolved problems.
+    pub include_resolved: Option<bool>,
+}
@@ -0,0 +1,435 @@
+//! Response envelopes the handlers build with `json!`, described for the OpenAPI document.
+#![allow(dead_code)]
+
+use utoipa::ToSchema;
+
+use crate::models::boarding_model::{
+    BoardingStayModel, CareLogEntry, KennelModel, KennelOccupancy,
+};
+use crate::models::certificate_model::{Certificate, CertificateVerification};
+use crate::models::clinic_model::{ClinicModel, PetShare};
+use crate::models::clinical_code_model::{CatalogueImport, CodeStatistics, CodeSuggestion};
+use crate::models::clinical_note_model::{ClinicalNoteModel, SoapNotes};
+use crate::models::health_model::DependencyStatus;
+use crate::models::identifier_model::{IdentifiedPetModel, PetIdentifierModel};
+use crate::models::import_model::ImportReport;
+use crate::models::lab_model::{AnalyteTrend, LabOrder, ReferenceRange};
+use crate::models::owner_model::OwnerModel;
+use crate::models::pet_model::{PetModel, PetStatusModel};
+use crate::models::portal_model::{PortalSession, UpcomingFollowup};
+use crate::models::problem_model::{PetProblemModel, PetWithAlerts};
+use crate::models::service_instance_model::{
+    AllServiceInstanceModel, GetServicesHistoryModel, ServiceInstanceModel,
+};
+use crate::models::staff_model::{ProcedureStaffMember, StaffModel};
+use crate::models::statistics_model::{
+    PetVisitSummary, ServiceTypeCount, VetStatistics, VisitStatistics,
+};
+use crate::models::surgical_case_model::SurgicalCase;
+use crate::models::vet_model::{GetVets, VetModel};
+use crate::schemas::boarding_schema::BoardingStayStatus;
+
+#[derive(ToSchema)]
+pub struct MessageResponse {
+    #[schema(example = "error")]
+    pub status: String,
+    pub message: String,
+}
+
+#[derive(ToSchema)]
+pub struct ServiceErrorResponse {
+    pub error: String,
+}
+
+#[derive(ToSchema)]
+pub struct IndexResponse {
+    pub message: String,
+}
+
+#[derive(ToSchema)]
+pub struct HealthCheckResponse {
+    pub message: String,
+    pub db_status: String,
+}
+
+#[derive(ToSchema)]
+pub struct LivenessResponse {
+    #[schema(example = "alive")]
+    pub status: String,
+    pub message: String,
+}
+
+#[derive(ToSchema)]
+pub struct ReadinessResponse {
+    #[schema(example = "ready")]
+    pub status: String,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+#[derive(ToSchema)]
+pub struct OwnersResponse {
+    pub status: String,
+    pub message: String,
+    pub owners: Vec<OwnerModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct OwnerResponse {
+    pub status: String,
+    pub message: String,
+    pub owner: OwnerModel,
+}
+
+#[derive(ToSchema)]
+pub struct OwnerAndPetsResponse {
+    pub status: String,
+    pub message: String,
+    pub owner: OwnerModel,
+    pub pets: Vec<PetModel>,
+}
+
+#[derive(ToSchema)]
+pub struct PetsResponse {
+    pub status: String,
+    pub message: String,
+    pub pets: Vec<PetWithAlerts>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct PetResponse {
+    pub status: String,
+    pub message: String,
+    pub pet: PetModel,
+}
+
+#[derive(ToSchema)]
+pub struct PetDetailsResponse {
+    pub status: String,
+    pub message: String,
+    pub pet: PetWithAlerts,
+}
+
+#[derive(ToSchema)]
+pub struct PetStatusResponse {
+    pub status: String,
+    pub message: String,
+    pub pet_status: PetStatusModel,
+}
+
+#[derive(ToSchema)]
+pub struct VetsResponse {
+    pub status: String,
+    pub message: String,
+    pub vets: Vec<VetModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct VetListResponse {
+    pub status: String,
+    pub message: String,
+    pub vets: Vec<GetVets>,
+}
+
+#[derive(ToSchema)]
+pub struct VetResponse {
+    pub status: String,
+    pub message: String,
+    pub vet: VetModel,
+}
+
+#[derive(ToSchema)]
+pub struct ServiceInstancesResponse {
+    pub status: String,
+    pub message: String,
+    pub service_instances: Vec<AllServiceInstanceModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct ServiceInstanceDetailsResponse {
+    pub status: String,
+    pub message: String,
+    pub service_instances: Vec<ServiceInstanceModel>,
+    pub not_found: Vec<String>,
+}
+
+#[derive(ToSchema)]
+pub struct PetHistoriesResponse {
+    pub status: String,
+    pub message: String,
+    pub service_instances: Vec<GetServicesHistoryModel>,
+}
+
+#[derive(ToSchema)]
+pub struct StaffListResponse {
+    pub status: String,
+    pub message: String,
+    pub staff: Vec<StaffModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct StaffMemberResponse {
+    pub status: String,
+    pub message: String,
+    pub staff_member: StaffModel,
+}
+
+#[derive(ToSchema)]
+pub struct ProcedureStaffResponse {
+    pub status: String,
+    pub message: String,
+    pub staff: Vec<ProcedureStaffMember>,
+}
+
+#[derive(ToSchema)]
+pub struct SurgicalCaseResponse {
+    pub status: String,
+    pub message: String,
+    pub case: SurgicalCase,
+}
+
+#[derive(ToSchema)]
+pub struct ServiceCountsResponse {
+    pub status: String,
+    pub message: String,
+    pub services: Vec<ServiceTypeCount>,
+}
+
+#[derive(ToSchema)]
+pub struct PetTypeVisitSummaryResponse {
+    pub status: String,
+    pub message: String,
+    pub pet_type_visit_summary: Vec<PetVisitSummary>,
+}
+
+#[derive(ToSchema)]
+pub struct VisitStatisticsResponse {
+    pub status: String,
+    pub message: String,
+    pub statistics: VisitStatistics,
+}
+
+#[derive(ToSchema)]
+pub struct VetStatisticsResponse {
+    pub status: String,
+    pub message: String,
+    pub statistics: VetStatistics,
+}
+
+#[derive(ToSchema)]
+pub struct PortalSessionResponse {
+    pub status: String,
+    pub message: String,
+    pub session: PortalSession,
+}
+
+#[derive(ToSchema)]
+pub struct UpcomingFollowupsResponse {
+    pub status: String,
+    pub message: String,
+    pub followups: Vec<UpcomingFollowup>,
+}
+
+#[derive(ToSchema)]
+pub struct ImportResponse {
+    pub status: String,
+    pub message: String,
+    pub import: ImportReport,
+}
+
+#[derive(ToSchema)]
+pub struct CertificateResponse {
+    pub status: String,
+    pub message: String,
+    pub certificate: Certificate,
+}
+
+#[derive(ToSchema)]
+pub struct CertificatesResponse {
+    pub status: String,
+    pub message: String,
+    pub certificates: Vec<Certificate>,
+}
+
+#[derive(ToSchema)]
+pub struct CertificateVerificationResponse {
+    pub status: String,
+    pub message: String,
+    pub verification: CertificateVerification,
+}
+
+#[derive(ToSchema)]
+pub struct ClinicResponse {
+    pub status: String,
+    pub message: String,
+    pub clinic: ClinicModel,
+}
+
+#[derive(ToSchema)]
+pub struct ClinicsResponse {
+    pub status: String,
+    pub message: String,
+    pub clinics: Vec<ClinicModel>,
+}
+
+#[derive(ToSchema)]
+pub struct PetSharesResponse {
+    pub status: String,
+    pub message: String,
+    pub shares: Vec<PetShare>,
+}
+
+#[derive(ToSchema)]
+pub struct KennelResponse {
+    pub status: String,
+    pub message: String,
+    pub kennel: KennelModel,
+}
+
+#[derive(ToSchema)]
+pub struct KennelsResponse {
+    pub status: String,
+    pub message: String,
+    pub kennels: Vec<KennelModel>,
+}
+
+#[derive(ToSchema)]
+pub struct OccupancyResponse {
+    pub status: String,
+    pub message: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub kennels: Vec<KennelOccupancy>,
+}
+
+#[derive(ToSchema)]
+pub struct BoardingStayResponse {
+    pub status: String,
+    pub message: String,
+    pub stay: BoardingStayModel,
+    /// The statuses the stay can move to next.
+    pub next_statuses: Vec<BoardingStayStatus>,
+    pub care_log: Vec<CareLogEntry>,
+}
+
+#[derive(ToSchema)]
+pub struct BoardingStaysResponse {
+    pub status: String,
+    pub message: String,
+    pub stays: Vec<BoardingStayModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct LabOrderResponse {
+    pub status: String,
+    pub message: String,
+    pub lab_order: LabOrder,
+}
+
+#[derive(ToSchema)]
+pub struct LabOrdersResponse {
+    pub status: String,
+    pub message: String,
+    pub lab_orders: Vec<LabOrder>,
+}
+
+#[derive(ToSchema)]
+pub struct LabTrendsResponse {
+    pub status: String,
+    pub message: String,
+    pub pet_id: String,
+    pub trends: Vec<AnalyteTrend>,
+}
+
+#[derive(ToSchema)]
+pub struct ReferenceRangesResponse {
+    pub status: String,
+    pub message: String,
+    pub reference_ranges: Vec<ReferenceRange>,
+}
+
+#[derive(ToSchema)]
+pub struct PetProblemResponse {
+    pub status: String,
+    pub message: String,
+    pub problem: PetProblemModel,
+}
+
+#[derive(ToSchema)]
+pub struct PetProblemsResponse {
+    pub status: String,
+    pub message: String,
+    pub problems: Vec<PetProblemModel>,
+}
+
+#[derive(ToSchema)]
+pub struct ClinicalNoteResponse {
+    pub status: String,
+    pub message: String,
+    pub note: ClinicalNoteModel,
+}
+
+#[derive(ToSchema)]
+pub struct ClinicalNotesResponse {
+    pub status: String,
+    pub message: String,
+    pub notes: Vec<ClinicalNoteModel>,
+    pub total_pages: i32,
+}
+
+#[derive(ToSchema)]
+pub struct SoapNotesResponse {
+    pub status: String,
+    pub message: String,
+    pub service_instance_id: String,
+    pub soap: SoapNotes,
+}
+
+#[derive(ToSchema)]
+pub struct CatalogueImportResponse {
+    pub status: String,
+    pub message: String,
+    pub import: CatalogueImport,
+}
+
+#[derive(ToSchema)]
+pub struct CodeSuggestionsResponse {
+    pub status: String,
+    pub message: String,
+    pub codes: Vec<CodeSuggestion>,
+}
+
+#[derive(ToSchema)]
+pub struct CodeStatisticsResponse {
+    pub status: String,
+    pub message: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub statistics: CodeStatistics,
+}
+
+#[derive(ToSchema)]
+pub struct PetIdentifierResponse {
+    pub status: String,
+    pub message: String,
+    pub identifier: PetIdentifierModel,
+}
+
+#[derive(ToSchema)]
+pub struct PetIdentifiersResponse {
+    pub status: String,
+    pub message: String,
+    pub identifiers: Vec<PetIdentifierModel>,
+}
+
+#[derive(ToSchema)]
+pub struct IdentifiedPetsResponse {
+    pub status: String,
+    pub message: String,
+    pub pets: Vec<IdentifiedPetModel>,
+}
@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct ServiceInstance {
     pub service_instance_id: String,
     pub service_date: String,
     pub service_type: Vec<String>,
     pub service_reason: String,
     pub general_diagnosis: String,
+    pub diagnosis_code: Option<String>,
     pub requires_followup: bool,
     pub followup_date: Option<String>,
     pub pet_id: String,
@@ -15,11 +17,13 @@ pub struct ServiceInstance {
     pub surgery: Option<Surgery>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct AddServiceInstance {
     pub service_type: Vec<String>,
     pub service_reason: String,
     pub general_diagnosis: String,
+    /// Catalogue code of the diagnosis; when left out, it is looked up from `general_diagnosis`.
+    pub diagnosis_code: Option<String>,
     pub requires_followup: bool,
     pub followup_date: Option<String>,
     pub grooming_type: Option<Vec<String>>,
@@ -28,47 +32,45 @@ pub struct AddServiceInstance {
     pub pet_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct UpdateServiceInstance {
     pub service_date: Option<chrono::NaiveDate>,
     pub service_type: Option<Vec<String>>,
     pub service_reason: Option<String>,
     pub general_diagnosis: Option<String>,
+    /// Catalogue code of the diagnosis. A new `general_diagnosis` without one is coded from its
+    /// text, or left uncoded.
+    pub diagnosis_code: Option<String>,
     pub requires_followup: Option<bool>,
     pub followup_date: Option<chrono::NaiveDate>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct Grooming {
     pub grooming_id: Option<i32>,
     pub grooming_type: String,
     pub service_instance_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct AddGroomingToInstance {
     pub grooming_type: Vec<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PreventiveCare {
     pub preventive_care_id: Option<i32>,
     pub treatment: String,
+    pub treatment_code: Option<String>,
     pub vet_id: String,
     pub service_instance_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
-pub struct InsertPreventiveCare {
-    pub treatment: Vec<String>,
-    pub vet_id: String,
-    pub service_instance_id: String,
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct Surgery {
     pub surgery_id: Option<i32>,
     pub surgery_name: String,
+    pub surgery_code: Option<String>,
     pub anesthesia_used: String,
     pub veterinarian_diagnosis: String,
     pub complications: String,
@@ -77,9 +79,11 @@ pub struct Surgery {
     pub vet_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct AddSurgery {
     pub surgery_name: String,
+    /// Catalogue code of the procedure; when left out, it is looked up from `surgery_name`.
+    pub surgery_code: Option<String>,
     pub anesthesia_used: String,
     pub veterinarian_diagnosis: String,
     pub complications: String,
@@ -87,9 +91,12 @@ pub struct AddSurgery {
     pub vet_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct UpdateSurgery {
     pub surgery_name: Option<String>,
+    /// Catalogue code of the procedure. A new `surgery_name` without one is coded from its
+    /// text, or left uncoded.
+    pub surgery_code: Option<String>,
     pub anesthesia_used: Option<String>,
     pub veterinarian_diagnosis: Option<String>,
     pub complications: Option<String>,
@@ -97,15 +104,56 @@ pub struct UpdateSurgery {
     pub vet_id: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct AddPreventiveCare {
     pub treatment: Vec<String>,
+    /// Catalogue codes of the treatments, in the same order; a `null` or missing code is looked
+    /// up from the treatment's name.
+    pub treatment_codes: Option<Vec<Option<String>>>,
     pub vet_id: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct AddPreventiveCareToExisting {
     pub treatment: Vec<String>,
+    /// Catalogue codes of the treatments, in the same order; a `null` or missing code is looked
+    /// up from the treatment's name.
+    pub treatment_codes: Option<Vec<Option<String>>>,
     pub vet_id: String,
     pub service_instance_id: String,
 }
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct GetServiceInstances {
+    pub service_instance_ids: Vec<String>,
+}
+
+impl AddPreventiveCare {
+    /// Each treatment with its catalogue code, if it has one.
+    pub fn coded_treatments(&self) -> Vec<(String, Option<String>)> {
+        pair_codes(&self.treatment, &self.treatment_codes)
+    }
+}
+
+impl AddPreventiveCareToExisting {
+    /// Each treatment with its catalogue code, if it has one.
+    pub fn coded_treatments(&self) -> Vec<(String, Option<String>)> {
+        pair_codes(&self.treatment, &self.treatment_codes)
+    }
+}
+
+fn pair_codes(
+    treatments: &[String],
+    codes: &Option<Vec<Option<String>>>,
+) -> Vec<(String, Option<String>)> {
+    treatments
+        .iter()
+        .enumerate()
+        .map(|(index, treatment)| {
+            let code = codes
+                .as_ref()
+                .and_then(|codes| codes.get(index).cloned().flatten());
+            (treatment.clone(), code)
+        })
+        .collect()
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffRole {
+    /// Also listed by the `/vets` endpoints; needs a license number.
+    Veterinarian,
+    Technician,
+    Groomer,
+    Receptionist,
+}
+
+impl StaffRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StaffRole::Veterinarian => "veterinarian",
+            StaffRole::Technician => "technician",
+            StaffRole::Groomer => "groomer",
+            StaffRole::Receptionist => "receptionist",
+        }
+    }
+}
+
+/// What a staff member did in a grooming, preventive care or surgery.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffFunction {
+    /// Did the procedure; a procedure has at most one.
+    Performer,
+    Assistant,
+}
+
+impl StaffFunction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StaffFunction::Performer => "performer",
+            StaffFunction::Assistant => "assistant",
+        }
+    }
+}
+
+/// The procedures staff can be attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaffedProcedure {
+    Grooming,
+    PreventiveCare,
+    Surgery,
+}
+
+impl StaffedProcedure {
+    pub fn table(self) -> &'static str {
+        match self {
+            StaffedProcedure::Grooming => "grooming",
+            StaffedProcedure::PreventiveCare => "preventive_care",
+            StaffedProcedure::Surgery => "surgery",
+        }
+    }
+
+    pub fn id_column(self) -> &'static str {
+        match self {
+            StaffedProcedure::Grooming => "grooming_id",
+            StaffedProcedure::PreventiveCare => "preventive_care_id",
+            StaffedProcedure::Surgery => "surgery_id",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StaffedProcedure::Grooming => "Grooming",
+            StaffedProcedure::PreventiveCare => "Preventive care",
+            StaffedProcedure::Surgery => "Surgery",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddStaff {
+    pub staff_name: String,
+    pub staff_email: String,
+    pub staff_phone_number: String,
+    pub staff_role: StaffRole,
+    pub license_number: Option<String>,
+    /// Certifications and qualifications, e.g. `RVT, Fear Free Certified`.
+    pub credentials: Option<String>,
+}
+
+/// The role can't change: a vet's past surgeries and certificates stay theirs.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct UpdateStaff {
+    pub staff_name: Option<String>,
+    pub staff_email: Option<String>,
+    pub staff_phone_number: Option<String>,
+    pub license_number: Option<String>,
+    pub credentials: Option<String>,
+    /// Inactive staff keep their history but can't be assigned to new procedures.
+    pub is_active: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+pub struct StaffOptions {
+    /// 1-based page number; 0 is read as 1.
+    pub page: Option<usize>,
+    /// Rows per page, 1 to 100. Defaults to 10.
+    pub limit: Option<usize>,
+    #[param(inline)]
+    pub role: Option<StaffRole>,
+    pub active: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AssignStaff {
+    pub staff_id: String,
+    pub staff_function: StaffFunction,
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Where a surgical case is in its workflow. A case only moves forward, one step at a time.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SurgicalCaseStatus {
+    /// Scheduled; the pre-op checklist and consent are filled in.
+    Planned,
+    /// On the table; anesthesia is monitored and intra-op notes are taken.
+    InProgress,
+    /// Out of surgery and still monitored.
+    Recovery,
+    /// Sent home with the post-op instructions. The case is closed.
+    Discharged,
+}
+
+impl SurgicalCaseStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SurgicalCaseStatus::Planned => "planned",
+            SurgicalCaseStatus::InProgress => "in_progress",
+            SurgicalCaseStatus::Recovery => "recovery",
+            SurgicalCaseStatus::Discharged => "discharged",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "planned" => Some(SurgicalCaseStatus::Planned),
+            "in_progress" => Some(SurgicalCaseStatus::InProgress),
+            "recovery" => Some(SurgicalCaseStatus::Recovery),
+            "discharged" => Some(SurgicalCaseStatus::Discharged),
+            _ => None,
+        }
+    }
+
+    /// The only status a case in this one may move to.
+    pub fn next(self) -> Option<Self> {
+        match self {
+            SurgicalCaseStatus::Planned => Some(SurgicalCaseStatus::InProgress),
+            SurgicalCaseStatus::InProgress => Some(SurgicalCaseStatus::Recovery),
+            SurgicalCaseStatus::Recovery => Some(SurgicalCaseStatus::Discharged),
+            SurgicalCaseStatus::Discharged => None,
+        }
+    }
+}
+
+/// Changes to a surgical case. Pre-op fields and consent can only change while the case
+/// is planned, and nothing changes once it is discharged.
+#[derive(Deserialize, Serialize, Debug, Default, ToSchema)]
+pub struct UpdateSurgicalCase {
+    pub fasting_confirmed: Option<bool>,
+    pub bloodwork_reviewed: Option<bool>,
+    pub iv_access_placed: Option<bool>,
+    pub surgical_site_prepared: Option<bool>,
+    /// Who signed the consent form, usually the owner. Records the time of signing.
+    pub consent_given_by: Option<String>,
+    pub preop_notes: Option<String>,
+    /// Once the case is in progress.
+    pub intraop_notes: Option<String>,
+    pub postop_instructions: Option<String>,
+}
+
+/// A timed anesthesia monitoring entry: a drug given, a set of vitals, or both.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AddAnesthesiaLogEntry {
+    /// Unix seconds; now when omitted.
+    pub recorded_at: Option<i64>,
+    pub drug: Option<String>,
+    #[schema(example = "0.2 mg/kg IV")]
+    pub dose: Option<String>,
+    /// Beats per minute.
+    pub heart_rate: Option<i32>,
+    /// Breaths per minute.
+    pub respiratory_rate: Option<i32>,
+    pub temperature_c: Option<f64>,
+    /// Oxygen saturation, in percent.
+    pub spo2: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct SurgicalCaseTransition {
+    pub status: SurgicalCaseStatus,
+}
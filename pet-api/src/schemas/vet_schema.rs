@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct AddVet {
     pub vet_name: String,
     pub vet_email: String,
@@ -8,7 +9,7 @@ pub struct AddVet {
     pub vet_license_number: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct UpdateVet {
     pub vet_name: Option<String>,
     pub vet_email: Option<String>,
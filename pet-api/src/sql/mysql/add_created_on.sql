@@ -0,0 +1,2 @@
+ALTER TABLE owner ADD COLUMN created_on DATE;
+ALTER TABLE pet ADD COLUMN created_on DATE;
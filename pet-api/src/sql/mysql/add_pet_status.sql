@@ -0,0 +1,19 @@
+ALTER TABLE pet ADD COLUMN pet_status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (pet_status IN ('active', 'inactive', 'deceased', 'transferred'));
+ALTER TABLE pet ADD COLUMN status_changed_on DATE;
+ALTER TABLE pet ADD COLUMN memorial_safe BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS pet_death (
+    pet_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    date_of_death DATE NOT NULL,
+    cause_of_death VARCHAR(200),
+    euthanasia BOOLEAN NOT NULL,
+    consent_given_by VARCHAR(100),
+    consent_given_on DATE,
+    vet_id VARCHAR(36),
+    recorded_at BIGINT NOT NULL,
+
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE,
+    FOREIGN KEY (vet_id) REFERENCES staff(staff_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_pet_status ON pet (clinic_id, pet_status);
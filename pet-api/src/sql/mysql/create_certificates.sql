@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS certificate (
+    certificate_id INT PRIMARY KEY AUTO_INCREMENT NOT NULL,
+    certificate_type VARCHAR(20) NOT NULL CHECK (certificate_type IN ('vaccination', 'health')),
+    pet_id VARCHAR(36) NOT NULL,
+    vet_id VARCHAR(36) NOT NULL,
+    verification_code VARCHAR(14) NOT NULL,
+    issued_on DATE NOT NULL,
+    valid_until DATE,
+    revoked_on DATE,
+    content MEDIUMTEXT NOT NULL,
+    content_digest CHAR(64) NOT NULL,
+
+    UNIQUE (verification_code),
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE,
+    FOREIGN KEY (vet_id) REFERENCES veterinarian(vet_id)
+);
+
+CREATE INDEX idx_certificate_pet_id ON certificate (pet_id);
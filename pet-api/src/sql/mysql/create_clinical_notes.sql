@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS clinical_note (
+    note_id INT PRIMARY KEY AUTO_INCREMENT NOT NULL,
+    service_instance_id VARCHAR(36) NOT NULL,
+    author_id VARCHAR(36) NOT NULL,
+    section VARCHAR(10) NOT NULL CHECK (section IN ('subjective', 'objective', 'assessment', 'plan')),
+    body TEXT NOT NULL,
+    recorded_at BIGINT NOT NULL,
+
+    FOREIGN KEY (service_instance_id) REFERENCES service_instance(service_instance_id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES staff(staff_id)
+);
+
+CREATE INDEX idx_clinical_note_service_instance_id ON clinical_note (service_instance_id, recorded_at);
+CREATE INDEX idx_clinical_note_author_id ON clinical_note (author_id);
@@ -0,0 +1,42 @@
+CREATE TABLE IF NOT EXISTS clinic (
+    clinic_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    clinic_name VARCHAR(100) NOT NULL UNIQUE,
+    created_at BIGINT NOT NULL
+);
+
+INSERT INTO clinic (clinic_id, clinic_name, created_at) VALUES ('main', 'Main clinic', UNIX_TIMESTAMP());
+
+ALTER TABLE owner ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE owner ADD FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id);
+ALTER TABLE pet ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE pet ADD FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id);
+ALTER TABLE staff ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE staff ADD FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id);
+ALTER TABLE service_instance ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE service_instance ADD FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id);
+ALTER TABLE import_job ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE import_job ADD FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id);
+
+DROP VIEW veterinarian;
+CREATE VIEW veterinarian AS
+SELECT staff_id AS vet_id, staff_name AS vet_name, staff_email AS vet_email,
+    staff_phone_number AS vet_phone_number, license_number AS vet_license_number, row_version,
+    clinic_id
+FROM staff
+WHERE staff_role = 'veterinarian';
+
+CREATE TABLE IF NOT EXISTS pet_share (
+    pet_id VARCHAR(36) NOT NULL,
+    clinic_id VARCHAR(36) NOT NULL,
+    shared_at BIGINT NOT NULL,
+
+    PRIMARY KEY (pet_id, clinic_id),
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE,
+    FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_owner_clinic ON owner (clinic_id);
+CREATE INDEX idx_pet_clinic ON pet (clinic_id);
+CREATE INDEX idx_staff_clinic ON staff (clinic_id);
+CREATE INDEX idx_service_instance_clinic ON service_instance (clinic_id);
+CREATE INDEX idx_pet_share_clinic ON pet_share (clinic_id);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS idempotency_key (
+    idempotency_key VARCHAR(255) PRIMARY KEY NOT NULL,
+    request_fingerprint CHAR(64) NOT NULL,
+    response_status INT,
+    response_body MEDIUMTEXT,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
+
+CREATE INDEX idx_idempotency_key_expires_at ON idempotency_key (expires_at);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS import_job (
+    import_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    entity VARCHAR(20) NOT NULL,
+    import_mode VARCHAR(20) NOT NULL,
+    dry_run BOOLEAN NOT NULL,
+    committed BOOLEAN NOT NULL,
+    total_rows INT NOT NULL,
+    imported_rows INT NOT NULL,
+    failed_rows INT NOT NULL,
+    error_report MEDIUMTEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS pet_identifier (
+    identifier_id INT PRIMARY KEY AUTO_INCREMENT NOT NULL,
+    pet_id VARCHAR(36) NOT NULL,
+    identifier_type VARCHAR(20) NOT NULL CHECK (identifier_type IN ('microchip', 'tattoo', 'license_tag')),
+    identifier_value VARCHAR(20) NOT NULL,
+    implant_date DATE,
+    implant_location VARCHAR(100),
+    recorded_at BIGINT NOT NULL,
+
+    UNIQUE (identifier_type, identifier_value),
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_pet_identifier_value ON pet_identifier (identifier_value);
+CREATE INDEX idx_pet_identifier_pet_id ON pet_identifier (pet_id);
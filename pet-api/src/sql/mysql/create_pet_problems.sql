@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS pet_problem (
+    problem_id INT PRIMARY KEY AUTO_INCREMENT NOT NULL,
+    pet_id VARCHAR(36) NOT NULL,
+    problem_type VARCHAR(20) NOT NULL CHECK (problem_type IN ('allergy', 'chronic_condition', 'behavioral_caution')),
+    description VARCHAR(200) NOT NULL,
+    severity VARCHAR(10) NOT NULL CHECK (severity IN ('mild', 'moderate', 'severe')),
+    onset_date DATE,
+    resolved_date DATE,
+    notes VARCHAR(500),
+    recorded_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_pet_problem_pet_id ON pet_problem (pet_id, resolved_date);
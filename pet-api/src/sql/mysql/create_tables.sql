@@ -35,6 +35,7 @@ CREATE TABLE IF NOT EXISTS service_instance (
     service_instance_id VARCHAR(36) PRIMARY KEY NOT NULL,
     service_date DATE NOT NULL,
     service_reason VARCHAR(255),
+    general_diagnosis VARCHAR(500),
     requires_followup BOOLEAN,
     followup_date DATE,
 
@@ -0,0 +1,3 @@
+ALTER TABLE owner DROP INDEX owner_email, ADD UNIQUE (clinic_id, owner_email);
+
+ALTER TABLE staff DROP INDEX vet_email, ADD UNIQUE (clinic_id, staff_email, license_number);
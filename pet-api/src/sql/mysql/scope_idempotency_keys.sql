@@ -0,0 +1,4 @@
+ALTER TABLE idempotency_key
+    ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main' FIRST,
+    DROP PRIMARY KEY,
+    ADD PRIMARY KEY (clinic_id, idempotency_key);
@@ -0,0 +1 @@
+ALTER TABLE staff ADD UNIQUE (clinic_id, staff_email);
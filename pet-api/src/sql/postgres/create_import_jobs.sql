@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS import_job (
+    import_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    entity VARCHAR(20) NOT NULL,
+    import_mode VARCHAR(20) NOT NULL,
+    dry_run BOOLEAN NOT NULL,
+    committed BOOLEAN NOT NULL,
+    total_rows INTEGER NOT NULL,
+    imported_rows INTEGER NOT NULL,
+    failed_rows INTEGER NOT NULL,
+    error_report TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS portal_token (
+    token_hash CHAR(64) PRIMARY KEY NOT NULL,
+    token_kind VARCHAR(10) NOT NULL CHECK (token_kind IN ('login', 'session')),
+    owner_id VARCHAR(36) NOT NULL,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    used_at BIGINT,
+
+    FOREIGN KEY (owner_id) REFERENCES owner(owner_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_portal_token_expires_at ON portal_token (expires_at);
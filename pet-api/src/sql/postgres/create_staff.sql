@@ -0,0 +1,47 @@
+ALTER TABLE veterinarian RENAME TO staff;
+ALTER TABLE staff RENAME COLUMN vet_id TO staff_id;
+ALTER TABLE staff RENAME COLUMN vet_name TO staff_name;
+ALTER TABLE staff RENAME COLUMN vet_email TO staff_email;
+ALTER TABLE staff RENAME COLUMN vet_phone_number TO staff_phone_number;
+ALTER TABLE staff RENAME COLUMN vet_license_number TO license_number;
+ALTER TABLE staff ADD COLUMN staff_role VARCHAR(20) NOT NULL DEFAULT 'veterinarian' CHECK (staff_role IN ('veterinarian', 'technician', 'groomer', 'receptionist'));
+ALTER TABLE staff ADD COLUMN credentials VARCHAR(200);
+ALTER TABLE staff ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE VIEW veterinarian AS
+SELECT staff_id AS vet_id, staff_name AS vet_name, staff_email AS vet_email,
+    staff_phone_number AS vet_phone_number, license_number AS vet_license_number, row_version
+FROM staff
+WHERE staff_role = 'veterinarian';
+
+CREATE TABLE IF NOT EXISTS grooming_staff (
+    grooming_id INT NOT NULL,
+    staff_id VARCHAR(36) NOT NULL,
+    staff_function VARCHAR(20) NOT NULL CHECK (staff_function IN ('performer', 'assistant')),
+
+    PRIMARY KEY (grooming_id, staff_id),
+    FOREIGN KEY (grooming_id) REFERENCES grooming(grooming_id) ON DELETE CASCADE,
+    FOREIGN KEY (staff_id) REFERENCES staff(staff_id)
+);
+
+CREATE TABLE IF NOT EXISTS preventive_care_staff (
+    preventive_care_id INT NOT NULL,
+    staff_id VARCHAR(36) NOT NULL,
+    staff_function VARCHAR(20) NOT NULL CHECK (staff_function IN ('performer', 'assistant')),
+
+    PRIMARY KEY (preventive_care_id, staff_id),
+    FOREIGN KEY (preventive_care_id) REFERENCES preventive_care(preventive_care_id) ON DELETE CASCADE,
+    FOREIGN KEY (staff_id) REFERENCES staff(staff_id)
+);
+
+CREATE TABLE IF NOT EXISTS surgery_staff (
+    surgery_id INT NOT NULL,
+    staff_id VARCHAR(36) NOT NULL,
+    staff_function VARCHAR(20) NOT NULL CHECK (staff_function IN ('performer', 'assistant')),
+
+    PRIMARY KEY (surgery_id, staff_id),
+    FOREIGN KEY (surgery_id) REFERENCES surgery(surgery_id) ON DELETE CASCADE,
+    FOREIGN KEY (staff_id) REFERENCES staff(staff_id)
+);
+
+CREATE INDEX idx_staff_role ON staff (staff_role);
@@ -0,0 +1,5 @@
+ALTER TABLE owner DROP CONSTRAINT owner_owner_email_key;
+ALTER TABLE owner ADD UNIQUE (clinic_id, owner_email);
+
+ALTER TABLE staff DROP CONSTRAINT veterinarian_vet_email_vet_license_number_key;
+ALTER TABLE staff ADD UNIQUE (clinic_id, staff_email, license_number);
@@ -0,0 +1,3 @@
+ALTER TABLE idempotency_key ADD COLUMN clinic_id VARCHAR(36) NOT NULL DEFAULT 'main';
+ALTER TABLE idempotency_key DROP CONSTRAINT idempotency_key_pkey;
+ALTER TABLE idempotency_key ADD PRIMARY KEY (clinic_id, idempotency_key);
@@ -0,0 +1,5 @@
+ALTER TABLE owner ADD COLUMN row_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE pet ADD COLUMN row_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE veterinarian ADD COLUMN row_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE service_instance ADD COLUMN row_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE surgery ADD COLUMN row_version INTEGER NOT NULL DEFAULT 1;
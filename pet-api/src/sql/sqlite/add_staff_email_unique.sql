@@ -0,0 +1 @@
+CREATE UNIQUE INDEX idx_staff_clinic_email ON staff (clinic_id, staff_email);
@@ -0,0 +1,46 @@
+CREATE TABLE IF NOT EXISTS kennel (
+    kennel_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    clinic_id VARCHAR(36) NOT NULL,
+    kennel_name VARCHAR(50) NOT NULL,
+    capacity INT NOT NULL CHECK (capacity > 0),
+    species VARCHAR(10) NOT NULL CHECK (species IN ('Dog', 'Cat', 'Any')),
+    created_at BIGINT NOT NULL,
+
+    UNIQUE (clinic_id, kennel_name),
+    FOREIGN KEY (clinic_id) REFERENCES clinic(clinic_id)
+);
+
+CREATE TABLE IF NOT EXISTS boarding_stay (
+    stay_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    kennel_id VARCHAR(36) NOT NULL,
+    pet_id VARCHAR(36) NOT NULL,
+    check_in_date DATE NOT NULL,
+    check_out_date DATE NOT NULL,
+    stay_status VARCHAR(20) NOT NULL CHECK (stay_status IN ('reserved', 'checked_in', 'checked_out', 'cancelled')),
+    feeding_instructions TEXT,
+    medication_instructions TEXT,
+    reserved_at BIGINT NOT NULL,
+    checked_in_at BIGINT,
+    checked_out_at BIGINT,
+
+    FOREIGN KEY (kennel_id) REFERENCES kennel(kennel_id),
+    FOREIGN KEY (pet_id) REFERENCES pet(pet_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_boarding_stay_kennel ON boarding_stay (kennel_id, check_in_date);
+CREATE INDEX idx_boarding_stay_pet ON boarding_stay (pet_id);
+
+CREATE TABLE IF NOT EXISTS care_log_entry (
+    care_log_entry_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    stay_id VARCHAR(36) NOT NULL,
+    care_date DATE NOT NULL,
+    recorded_at BIGINT NOT NULL,
+    fed BOOLEAN NOT NULL DEFAULT FALSE,
+    medication_given BOOLEAN NOT NULL DEFAULT FALSE,
+    exercised BOOLEAN NOT NULL DEFAULT FALSE,
+    notes VARCHAR(500),
+
+    FOREIGN KEY (stay_id) REFERENCES boarding_stay(stay_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_care_log_entry_stay_id ON care_log_entry (stay_id, care_date);
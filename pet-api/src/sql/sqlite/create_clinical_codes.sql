@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS clinical_code (
+    code VARCHAR(20) PRIMARY KEY NOT NULL,
+    code_type VARCHAR(10) NOT NULL CHECK (code_type IN ('diagnosis', 'procedure', 'treatment')),
+    term VARCHAR(100) NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS clinical_code_synonym (
+    code_type VARCHAR(10) NOT NULL,
+    synonym VARCHAR(100) NOT NULL,
+    code VARCHAR(20) NOT NULL,
+
+    PRIMARY KEY (code_type, synonym),
+    FOREIGN KEY (code) REFERENCES clinical_code(code) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_clinical_code_synonym_code ON clinical_code_synonym (code);
+
+ALTER TABLE service_instance ADD COLUMN diagnosis_code VARCHAR(20) REFERENCES clinical_code(code);
+ALTER TABLE surgery ADD COLUMN surgery_code VARCHAR(20) REFERENCES clinical_code(code);
+ALTER TABLE preventive_care ADD COLUMN treatment_code VARCHAR(20) REFERENCES clinical_code(code);
+
+CREATE INDEX idx_service_instance_diagnosis_code ON service_instance (diagnosis_code);
+CREATE INDEX idx_surgery_surgery_code ON surgery (surgery_code);
+CREATE INDEX idx_preventive_care_treatment_code ON preventive_care (treatment_code);
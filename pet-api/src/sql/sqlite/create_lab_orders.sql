@@ -0,0 +1,69 @@
+CREATE TABLE IF NOT EXISTS lab_order (
+    lab_order_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    service_instance_id VARCHAR(36) NOT NULL,
+    vet_id VARCHAR(36) NOT NULL,
+    panel VARCHAR(50) NOT NULL,
+    sample_type VARCHAR(20) NOT NULL CHECK (sample_type IN ('blood', 'serum', 'plasma', 'urine', 'feces', 'tissue', 'swab', 'other')),
+    notes VARCHAR(500),
+    ordered_at BIGINT NOT NULL,
+    resulted_at BIGINT,
+
+    FOREIGN KEY (service_instance_id) REFERENCES service_instance(service_instance_id) ON DELETE CASCADE,
+    FOREIGN KEY (vet_id) REFERENCES staff(staff_id)
+);
+
+CREATE INDEX idx_lab_order_service_instance_id ON lab_order (service_instance_id);
+
+CREATE TABLE IF NOT EXISTS lab_result (
+    lab_result_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    lab_order_id INT NOT NULL,
+    analyte VARCHAR(20) NOT NULL,
+    result_value REAL NOT NULL,
+    unit VARCHAR(20) NOT NULL,
+    reference_low REAL,
+    reference_high REAL,
+    flag VARCHAR(10) CHECK (flag IN ('low', 'normal', 'high')),
+    recorded_at BIGINT NOT NULL,
+
+    UNIQUE (lab_order_id, analyte),
+    FOREIGN KEY (lab_order_id) REFERENCES lab_order(lab_order_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_lab_result_analyte ON lab_result (analyte);
+
+CREATE TABLE IF NOT EXISTS lab_reference_range (
+    analyte VARCHAR(20) NOT NULL,
+    species VARCHAR(10) NOT NULL CHECK (species IN ('Dog', 'Cat')),
+    analyte_name VARCHAR(50) NOT NULL,
+    unit VARCHAR(20) NOT NULL,
+    reference_low REAL NOT NULL,
+    reference_high REAL NOT NULL,
+
+    PRIMARY KEY (analyte, species)
+);
+
+INSERT INTO lab_reference_range (analyte, species, analyte_name, unit, reference_low, reference_high) VALUES
+    ('WBC', 'Dog', 'White blood cells', 'x10^9/L', 5.05, 16.76),
+    ('WBC', 'Cat', 'White blood cells', 'x10^9/L', 2.87, 17.02),
+    ('RBC', 'Dog', 'Red blood cells', 'x10^12/L', 5.65, 8.87),
+    ('RBC', 'Cat', 'Red blood cells', 'x10^12/L', 6.54, 12.2),
+    ('HGB', 'Dog', 'Hemoglobin', 'g/dL', 13.1, 20.5),
+    ('HGB', 'Cat', 'Hemoglobin', 'g/dL', 9.8, 16.2),
+    ('HCT', 'Dog', 'Hematocrit', '%', 37.3, 61.7),
+    ('HCT', 'Cat', 'Hematocrit', '%', 30.3, 52.3),
+    ('PLT', 'Dog', 'Platelets', 'x10^9/L', 148, 484),
+    ('PLT', 'Cat', 'Platelets', 'x10^9/L', 151, 600),
+    ('GLU', 'Dog', 'Glucose', 'mg/dL', 74, 143),
+    ('GLU', 'Cat', 'Glucose', 'mg/dL', 71, 159),
+    ('BUN', 'Dog', 'Blood urea nitrogen', 'mg/dL', 7, 27),
+    ('BUN', 'Cat', 'Blood urea nitrogen', 'mg/dL', 16, 36),
+    ('CREA', 'Dog', 'Creatinine', 'mg/dL', 0.5, 1.8),
+    ('CREA', 'Cat', 'Creatinine', 'mg/dL', 0.8, 2.4),
+    ('ALT', 'Dog', 'Alanine aminotransferase', 'U/L', 10, 125),
+    ('ALT', 'Cat', 'Alanine aminotransferase', 'U/L', 12, 130),
+    ('ALKP', 'Dog', 'Alkaline phosphatase', 'U/L', 23, 212),
+    ('ALKP', 'Cat', 'Alkaline phosphatase', 'U/L', 14, 111),
+    ('TP', 'Dog', 'Total protein', 'g/dL', 5.2, 8.2),
+    ('TP', 'Cat', 'Total protein', 'g/dL', 5.7, 8.9),
+    ('ALB', 'Dog', 'Albumin', 'g/dL', 2.3, 4.0),
+    ('ALB', 'Cat', 'Albumin', 'g/dL', 2.2, 4.0);
@@ -0,0 +1,36 @@
+CREATE TABLE IF NOT EXISTS surgical_case (
+    surgery_id INT PRIMARY KEY NOT NULL,
+    case_status VARCHAR(20) NOT NULL CHECK (case_status IN ('planned', 'in_progress', 'recovery', 'discharged')),
+    fasting_confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+    bloodwork_reviewed BOOLEAN NOT NULL DEFAULT FALSE,
+    iv_access_placed BOOLEAN NOT NULL DEFAULT FALSE,
+    surgical_site_prepared BOOLEAN NOT NULL DEFAULT FALSE,
+    consent_given_by VARCHAR(100),
+    consent_given_at BIGINT,
+    preop_notes TEXT,
+    intraop_notes TEXT,
+    postop_instructions TEXT,
+    opened_at BIGINT NOT NULL,
+    started_at BIGINT,
+    recovery_at BIGINT,
+    discharged_at BIGINT,
+
+    FOREIGN KEY (surgery_id) REFERENCES surgery(surgery_id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS anesthesia_log_entry (
+    anesthesia_log_entry_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    surgery_id INT NOT NULL,
+    recorded_at BIGINT NOT NULL,
+    drug VARCHAR(100),
+    dose VARCHAR(50),
+    heart_rate INT,
+    respiratory_rate INT,
+    temperature_c REAL,
+    spo2 INT,
+    notes VARCHAR(500),
+
+    FOREIGN KEY (surgery_id) REFERENCES surgical_case(surgery_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_anesthesia_log_entry_surgery_id ON anesthesia_log_entry (surgery_id, recorded_at);
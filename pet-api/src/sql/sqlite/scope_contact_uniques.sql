@@ -0,0 +1,51 @@
+DROP VIEW veterinarian;
+
+CREATE TABLE owner_by_clinic (
+    owner_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    owner_name VARCHAR(80) NOT NULL,
+    owner_email VARCHAR(80),
+    owner_phone_number VARCHAR(20),
+    owner_address VARCHAR(120),
+    row_version INTEGER NOT NULL DEFAULT 1,
+    created_on DATE,
+    clinic_id VARCHAR(36) NOT NULL DEFAULT 'main',
+
+    UNIQUE (clinic_id, owner_email)
+);
+
+INSERT INTO owner_by_clinic (owner_id, owner_name, owner_email, owner_phone_number, owner_address, row_version, created_on, clinic_id)
+SELECT owner_id, owner_name, owner_email, owner_phone_number, owner_address, row_version, created_on, clinic_id FROM owner;
+
+DROP TABLE owner;
+ALTER TABLE owner_by_clinic RENAME TO owner;
+CREATE INDEX idx_owner_clinic ON owner (clinic_id);
+
+CREATE TABLE staff_by_clinic (
+    staff_id VARCHAR(36) PRIMARY KEY NOT NULL,
+    staff_name VARCHAR(80) NOT NULL,
+    staff_email VARCHAR(50),
+    staff_phone_number VARCHAR(20),
+    license_number VARCHAR(20),
+    row_version INTEGER NOT NULL DEFAULT 1,
+    staff_role VARCHAR(20) NOT NULL DEFAULT 'veterinarian' CHECK (staff_role IN ('veterinarian', 'technician', 'groomer', 'receptionist')),
+    credentials VARCHAR(200),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    clinic_id VARCHAR(36) NOT NULL DEFAULT 'main',
+
+    UNIQUE (clinic_id, staff_email, license_number)
+);
+
+INSERT INTO staff_by_clinic (staff_id, staff_name, staff_email, staff_phone_number, license_number, row_version, staff_role, credentials, is_active, clinic_id)
+SELECT staff_id, staff_name, staff_email, staff_phone_number, license_number, row_version, staff_role, credentials, is_active, clinic_id FROM staff;
+
+DROP TABLE staff;
+ALTER TABLE staff_by_clinic RENAME TO staff;
+CREATE INDEX idx_staff_role ON staff (staff_role);
+CREATE INDEX idx_staff_clinic ON staff (clinic_id);
+
+CREATE VIEW veterinarian AS
+SELECT staff_id AS vet_id, staff_name AS vet_name, staff_email AS vet_email,
+    staff_phone_number AS vet_phone_number, license_number AS vet_license_number, row_version,
+    clinic_id
+FROM staff
+WHERE staff_role = 'veterinarian';
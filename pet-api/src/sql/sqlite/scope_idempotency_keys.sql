@@ -0,0 +1,22 @@
+CREATE TABLE idempotency_key_by_clinic (
+    clinic_id VARCHAR(36) NOT NULL DEFAULT 'main',
+    idempotency_key VARCHAR(255) NOT NULL,
+    request_fingerprint CHAR(64) NOT NULL,
+    response_status INTEGER,
+    response_body TEXT,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+
+    PRIMARY KEY (clinic_id, idempotency_key)
+);
+
+INSERT INTO idempotency_key_by_clinic (
+    idempotency_key, request_fingerprint, response_status, response_body, created_at, expires_at
+)
+SELECT idempotency_key, request_fingerprint, response_status, response_body, created_at, expires_at
+FROM idempotency_key;
+
+DROP TABLE idempotency_key;
+ALTER TABLE idempotency_key_by_clinic RENAME TO idempotency_key;
+
+CREATE INDEX idx_idempotency_key_expires_at ON idempotency_key (expires_at);
@@ -0,0 +1,252 @@
+use axum::http::StatusCode;
+use chrono::{Days, NaiveDate};
+
+use crate::models::boarding_model::{
+    BoardingStayModel, KennelModel, KennelOccupancy, OccupancyNight,
+};
+use crate::schemas::boarding_schema::{AddCareLogEntry, BoardingStayStatus, UpdateBoardingStay};
+use crate::utils::rules::RuleViolation;
+
+/// Most nights an occupancy calendar spans.
+pub(crate) const MAX_CALENDAR_NIGHTS: u64 = 62;
+
+pub(crate) fn parse_date(name: &str, value: &str) -> Result<NaiveDate, RuleViolation> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| bad_request(&format!("Invalid {}: expected YYYY-MM-DD", name)))
+}
+
+/// Trims free text, treating a blank value as none.
+pub(crate) fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(crate) fn validate_dates(stay: &BoardingStayModel) -> Result<(), RuleViolation> {
+    if stay.check_out_date <= stay.check_in_date {
+        return Err(bad_request("check_out_date must be after check_in_date"));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_species(kennel: &KennelModel, pet_type: &str) -> Result<(), RuleViolation> {
+    if !kennel.accepts(pet_type) {
+        return Err(not_allowed(format!(
+            "{} is for {}s only",
+            kennel.kennel_name,
+            kennel.species.to_lowercase()
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects a stay that would put the pet in two places at once or more pets in its kennel
+/// than it holds. `others` are the stays overlapping it; the stay itself is skipped.
+pub(crate) fn check_conflicts(
+    stay: &BoardingStayModel,
+    kennel: &KennelModel,
+    others: &[BoardingStayModel],
+) -> Result<(), RuleViolation> {
+    let others: Vec<_> = others
+        .iter()
+        .filter(|other| other.stay_id != stay.stay_id)
+        .collect();
+
+    if let Some(other) = others.iter().find(|other| {
+        other.pet_id == stay.pet_id
+            && other.check_in_date < stay.check_out_date
+            && stay.check_in_date < other.check_out_date
+    }) {
+        return Err(conflict(&format!(
+            "{} already stays in {} from {} to {}",
+            stay.pet_name, other.kennel_name, other.check_in_date, other.check_out_date
+        )));
+    }
+
+    for night in nights(stay.check_in_date, stay.check_out_date) {
+        let occupied = others
+            .iter()
+            .filter(|other| other.kennel_id == kennel.kennel_id && other.covers(night))
+            .count();
+        if occupied as i32 >= kennel.capacity {
+            return Err(conflict(&format!(
+                "{} is fully booked the night of {}",
+                kennel.kennel_name, night
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the changes allowed in the stay's status, or rejects all of them. A new kennel is
+/// only set by id; the caller checks it.
+pub(crate) fn apply_update(
+    stay: &mut BoardingStayModel,
+    update: UpdateBoardingStay,
+) -> Result<(), RuleViolation> {
+    let status = stay.status();
+    if matches!(
+        status,
+        BoardingStayStatus::CheckedOut | BoardingStayStatus::Cancelled
+    ) {
+        return Err(conflict("A checked-out or cancelled stay can't be changed"));
+    }
+    if (update.kennel_id.is_some() || update.check_in_date.is_some())
+        && status != BoardingStayStatus::Reserved
+    {
+        return Err(conflict(
+            "The kennel and check-in date can't change once the pet has checked in",
+        ));
+    }
+
+    if let Some(kennel_id) = update.kennel_id {
+        stay.kennel_id = kennel_id.trim().to_string();
+    }
+    if let Some(check_in_date) = update.check_in_date {
+        stay.check_in_date = parse_date("check_in_date", &check_in_date)?;
+    }
+    if let Some(check_out_date) = update.check_out_date {
+        stay.check_out_date = parse_date("check_out_date", &check_out_date)?;
+    }
+    validate_dates(stay)?;
+    if update.feeding_instructions.is_some() {
+        stay.feeding_instructions = clean(update.feeding_instructions);
+    }
+    if update.medication_instructions.is_some() {
+        stay.medication_instructions = clean(update.medication_instructions);
+    }
+
+    Ok(())
+}
+
+/// Moves the stay to `to` if its status allows it, stamping when it did. Checking out early
+/// gives the remaining nights back to the kennel.
+pub(crate) fn transition(
+    stay: &mut BoardingStayModel,
+    to: BoardingStayStatus,
+    today: NaiveDate,
+    now: i64,
+) -> Result<(), RuleViolation> {
+    let from = stay.status();
+    if !from.next().contains(&to) {
+        return Err(conflict(&format!(
+            "A boarding stay can't move from {} to {}",
+            from.as_str(),
+            to.as_str()
+        )));
+    }
+
+    match to {
+        BoardingStayStatus::CheckedIn => {
+            if today < stay.check_in_date {
+                return Err(not_allowed(format!(
+                    "The stay starts on {}; move its check-in date to check in early",
+                    stay.check_in_date
+                )));
+            }
+            stay.checked_in_at = Some(now);
+        }
+        BoardingStayStatus::CheckedOut => {
+            let first_morning = stay.check_in_date + Days::new(1);
+            stay.check_out_date = stay.check_out_date.min(today.max(first_morning));
+            stay.checked_out_at = Some(now);
+        }
+        BoardingStayStatus::Reserved | BoardingStayStatus::Cancelled => {}
+    }
+    stay.stay_status = to.as_str().to_string();
+
+    Ok(())
+}
+
+/// Checks a care log entry and returns the day it is for.
+pub(crate) fn validate_care_entry(
+    stay: &BoardingStayModel,
+    entry: &mut AddCareLogEntry,
+    today: NaiveDate,
+) -> Result<NaiveDate, RuleViolation> {
+    if stay.status() != BoardingStayStatus::CheckedIn {
+        return Err(conflict(
+            "Care can only be logged while the pet is checked in",
+        ));
+    }
+
+    entry.notes = clean(entry.notes.take());
+    if !entry.fed && !entry.medication_given && !entry.exercised && entry.notes.is_none() {
+        return Err(bad_request(
+            "A care log entry needs at least one of fed, medication_given, exercised or notes",
+        ));
+    }
+    if entry.medication_given && stay.medication_instructions.is_none() {
+        return Err(not_allowed(
+            "The stay has no medication instructions".to_string(),
+        ));
+    }
+
+    let care_date = match &entry.care_date {
+        Some(care_date) => parse_date("care_date", care_date)?,
+        None => today,
+    };
+    if care_date > today {
+        return Err(bad_request("care_date is in the future"));
+    }
+    if care_date < stay.check_in_date || care_date > stay.check_out_date {
+        return Err(bad_request("care_date is outside the stay"));
+    }
+
+    Ok(care_date)
+}
+
+/// Each kennel's bookings for every night from `start` to `end`, both included.
+pub(crate) fn occupancy(
+    kennels: Vec<KennelModel>,
+    stays: &[BoardingStayModel],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<KennelOccupancy> {
+    kennels
+        .into_iter()
+        .map(|kennel| {
+            let nights = nights(start, end + Days::new(1))
+                .map(|night| {
+                    let stay_ids: Vec<String> = stays
+                        .iter()
+                        .filter(|stay| stay.kennel_id == kennel.kennel_id && stay.covers(night))
+                        .map(|stay| stay.stay_id.clone())
+                        .collect();
+                    let occupied = stay_ids.len() as i32;
+                    OccupancyNight {
+                        night,
+                        occupied,
+                        available: (kennel.capacity - occupied).max(0),
+                        stay_ids,
+                    }
+                })
+                .collect();
+            KennelOccupancy {
+                kennel_id: kennel.kennel_id,
+                kennel_name: kennel.kennel_name,
+                capacity: kennel.capacity,
+                species: kennel.species,
+                nights,
+            }
+        })
+        .collect()
+}
+
+/// The nights from `from` up to, but not including, `to`.
+fn nights(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    from.iter_days().take_while(move |night| *night < to)
+}
+
+fn conflict(message: &str) -> RuleViolation {
+    (StatusCode::CONFLICT, message.to_string())
+}
+
+fn not_allowed(message: String) -> RuleViolation {
+    (StatusCode::UNPROCESSABLE_ENTITY, message)
+}
+
+fn bad_request(message: &str) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message.to_string())
+}
@@ -0,0 +1,422 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::db::connection::DbPool;
+use crate::models::import_model::{ImportReport, ImportRowError};
+use crate::schemas::import_schema::{ImportEntity, ImportFormat, ImportMode, ImportRequest};
+
+pub const MAX_IMPORT_ROWS: usize = 10_000;
+
+#[derive(Debug)]
+pub enum ImportFailure {
+    /// The document or its mapping can't be imported at all.
+    Invalid(String),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for ImportFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFailure::Invalid(message) => write!(f, "{}", message),
+            ImportFailure::Database(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImportFailure {}
+
+impl From<sqlx::Error> for ImportFailure {
+    fn from(e: sqlx::Error) -> Self {
+        ImportFailure::Database(e)
+    }
+}
+
+/// A record keyed by field name, or why it couldn't be read.
+type Record = Result<HashMap<String, String>, String>;
+
+/// Validates every record of `request`, checks the valid ones against the database and saves
+/// them according to the import mode, then stores the report so its errors can be downloaded.
+pub(crate) async fn run_import(
+    db: &DbPool,
+    clinic_id: &str,
+    request: ImportRequest,
+) -> Result<ImportReport, ImportFailure> {
+    let entity = request.entity;
+    for field in request.mapping.values() {
+        if !entity.fields().contains(&field.as_str()) {
+            return Err(ImportFailure::Invalid(format!(
+                "Unknown {} field '{}' in mapping",
+                entity.as_str(),
+                field
+            )));
+        }
+    }
+
+    let records = match request.format {
+        ImportFormat::Csv => read_csv(&request.data, &request.mapping, entity)?,
+        ImportFormat::Ndjson => read_ndjson(&request.data, &request.mapping),
+    };
+    if records.is_empty() {
+        return Err(ImportFailure::Invalid(
+            "The import contains no rows".to_string(),
+        ));
+    }
+    if records.len() > MAX_IMPORT_ROWS {
+        return Err(ImportFailure::Invalid(format!(
+            "An import is limited to {} rows",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let owner_ids = match entity {
+        ImportEntity::Pets => owner_ids_by_email(db, clinic_id, &records).await?,
+        _ => HashMap::new(),
+    };
+
+    let mut errors = Vec::new();
+    let mut valid_rows = Vec::new();
+    let mut row_numbers = Vec::new();
+    let mut seen_keys = HashSet::new();
+
+    for (index, record) in records.iter().enumerate() {
+        let row = index + 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(message) => {
+                errors.push(row_error(row, None, message));
+                continue;
+            }
+        };
+
+        match validate_record(entity, record, &owner_ids) {
+            Ok(values) => {
+                if let Some((field, key)) = unique_key(entity, &values) {
+                    if !seen_keys.insert(key) {
+                        errors.push(row_error(
+                            row,
+                            Some(field),
+                            "Duplicates an earlier row of this import",
+                        ));
+                        continue;
+                    }
+                }
+                valid_rows.push(values);
+                row_numbers.push(row);
+            }
+            Err(row_errors) => errors.extend(
+                row_errors
+                    .into_iter()
+                    .map(|(field, message)| row_error(row, Some(field), &message)),
+            ),
+        }
+    }
+
+    let atomic = request.mode == ImportMode::AllOrNothing;
+    let commit = !request.dry_run && (!atomic || errors.is_empty());
+    let rejected = db
+        .imports(clinic_id)
+        .insert_rows(entity, &valid_rows, atomic, commit)
+        .await?;
+
+    for (index, e) in &rejected {
+        errors.push(row_error(
+            row_numbers[*index],
+            None,
+            &database_message(entity, e),
+        ));
+    }
+    errors.sort_by_key(|error| error.row);
+
+    let committed = commit && (!atomic || rejected.is_empty());
+    let passed_rows = valid_rows.len() - rejected.len();
+    let failed_rows = errors
+        .iter()
+        .map(|error| error.row)
+        .collect::<HashSet<_>>()
+        .len();
+
+    let report = ImportReport {
+        import_id: uuid::Uuid::new_v4().to_string(),
+        entity,
+        mode: request.mode,
+        dry_run: request.dry_run,
+        committed,
+        total_rows: records.len(),
+        imported_rows: if committed || request.dry_run {
+            passed_rows
+        } else {
+            0
+        },
+        failed_rows,
+        errors,
+    };
+    db.imports(clinic_id).insert_import_job(&report).await?;
+
+    Ok(report)
+}
+
+fn row_error(row: usize, field: Option<&str>, message: &str) -> ImportRowError {
+    ImportRowError {
+        row,
+        field: field.map(str::to_string),
+        message: message.to_string(),
+    }
+}
+
+fn field_name(mapping: &BTreeMap<String, String>, column: &str) -> String {
+    mapping
+        .get(column)
+        .cloned()
+        .unwrap_or_else(|| column.trim().to_string())
+}
+
+fn read_csv(
+    data: &str,
+    mapping: &BTreeMap<String, String>,
+    entity: ImportEntity,
+) -> Result<Vec<Record>, ImportFailure> {
+    let mut lines = parse_csv(data)?.into_iter();
+    let Some(header) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let fields = header
+        .iter()
+        .map(|column| field_name(mapping, column))
+        .collect::<Vec<_>>();
+
+    for field in required_fields(entity) {
+        if !fields.iter().any(|name| name == field) {
+            return Err(ImportFailure::Invalid(format!(
+                "No column is mapped to the '{}' field",
+                field
+            )));
+        }
+    }
+    if entity == ImportEntity::Pets
+        && !fields
+            .iter()
+            .any(|name| name == "owner_id" || name == "owner_email")
+    {
+        return Err(ImportFailure::Invalid(
+            "No column is mapped to the 'owner_id' or 'owner_email' field".to_string(),
+        ));
+    }
+
+    Ok(lines
+        .map(|values| {
+            if values.len() != fields.len() {
+                return Err(format!(
+                    "Expected {} columns but found {}",
+                    fields.len(),
+                    values.len()
+                ));
+            }
+            Ok(fields.iter().cloned().zip(values).collect())
+        })
+        .collect())
+}
+
+/// Splits an RFC 4180 document into records, dropping blank lines.
+pub(crate) fn parse_csv(data: &str) -> Result<Vec<Vec<String>>, ImportFailure> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                value.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => value.push(c),
+            (false, '"') if value.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut value)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut value));
+                if record.iter().any(|value| !value.is_empty()) || record.len() > 1 {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            (false, c) => value.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(ImportFailure::Invalid(
+            "The CSV document ends inside a quoted value".to_string(),
+        ));
+    }
+    if !value.is_empty() || !record.is_empty() {
+        record.push(value);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+fn read_ndjson(data: &str, mapping: &BTreeMap<String, String>) -> Vec<Record> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let object = match serde_json::from_str::<serde_json::Value>(line) {
+                Ok(serde_json::Value::Object(object)) => object,
+                Ok(_) => return Err("Expected a JSON object".to_string()),
+                Err(e) => return Err(format!("Invalid JSON: {}", e)),
+            };
+
+            Ok(object
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::Null => return None,
+                        serde_json::Value::String(value) => value,
+                        value => value.to_string(),
+                    };
+                    Some((field_name(mapping, &key), value))
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn required_fields(entity: ImportEntity) -> &'static [&'static str] {
+    match entity {
+        // A pet names its owner by either `owner_id` or `owner_email`.
+        ImportEntity::Pets => &entity.fields()[..6],
+        _ => entity.fields(),
+    }
+}
+
+async fn owner_ids_by_email(
+    db: &DbPool,
+    clinic_id: &str,
+    records: &[Record],
+) -> Result<HashMap<String, String>, ImportFailure> {
+    let owner_emails = records
+        .iter()
+        .flatten()
+        .filter_map(|record| record.get("owner_email"))
+        .map(|owner_email| owner_email.trim().to_string())
+        .filter(|owner_email| !owner_email.is_empty())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let mut owner_ids = HashMap::new();
+    for chunk in owner_emails.chunks(500) {
+        owner_ids.extend(
+            db.imports(clinic_id)
+                .select_owner_ids_by_email(chunk)
+                .await?,
+        );
+    }
+
+    Ok(owner_ids)
+}
+
+/// The row's column values in table order, led by a new id, or every problem with it.
+fn validate_record(
+    entity: ImportEntity,
+    record: &HashMap<String, String>,
+    owner_ids: &HashMap<String, String>,
+) -> Result<Vec<String>, Vec<(&'static str, String)>> {
+    let mut errors = Vec::new();
+    let mut values = vec![uuid::Uuid::new_v4().to_string()];
+
+    for &field in required_fields(entity) {
+        let value = record
+            .get(field)
+            .map(|value| value.trim())
+            .unwrap_or_default();
+        if value.is_empty() {
+            errors.push((field, "A value is required".to_string()));
+        } else if let Err(message) = validate_value(field, value) {
+            errors.push((field, message));
+        }
+        values.push(value.to_string());
+    }
+
+    if entity == ImportEntity::Pets {
+        let owner_id = record.get("owner_id").map(|value| value.trim());
+        let owner_email = record.get("owner_email").map(|value| value.trim());
+        match (owner_id, owner_email) {
+            (Some(owner_id), _) if !owner_id.is_empty() => values.push(owner_id.to_string()),
+            (_, Some(owner_email)) if !owner_email.is_empty() => match owner_ids.get(owner_email) {
+                Some(owner_id) => values.push(owner_id.clone()),
+                None => errors.push((
+                    "owner_email",
+                    format!("No owner has the email {}", owner_email),
+                )),
+            },
+            _ => errors.push((
+                "owner_id",
+                "An owner_id or owner_email is required".to_string(),
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_value(field: &str, value: &str) -> Result<(), String> {
+    match field {
+        "owner_email" | "vet_email" if !value.contains('@') => {
+            Err("Not a valid email address".to_string())
+        }
+        "pet_birth_date" => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|_| ())
+            .map_err(|_| "Expected a date as YYYY-MM-DD".to_string()),
+        "pet_type" if value != "Dog" && value != "Cat" => Err("Expected Dog or Cat".to_string()),
+        "pet_weight" => match value.parse::<f32>() {
+            Ok(weight) if (0.0..1000.0).contains(&weight) => Ok(()),
+            _ => Err("Expected a weight from 0 to 999.99".to_string()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// The field and value the table keeps unique, to catch repeats within one import.
+fn unique_key(entity: ImportEntity, values: &[String]) -> Option<(&'static str, String)> {
+    match entity {
+        ImportEntity::Owners => Some(("owner_email", values[2].to_lowercase())),
+        ImportEntity::Pets => None,
+        ImportEntity::Vets => Some((
+            "vet_email",
+            format!("{}\n{}", values[2].to_lowercase(), values[4]),
+        )),
+    }
+}
+
+fn database_message(entity: ImportEntity, e: &sqlx::Error) -> String {
+    let Some(db_err) = e.as_database_error() else {
+        return match e {
+            sqlx::Error::RowNotFound => "Owner does not exist".to_string(),
+            e => e.to_string(),
+        };
+    };
+
+    if db_err.is_unique_violation() {
+        match entity {
+            ImportEntity::Owners => "Owner already exists",
+            ImportEntity::Pets => "Pet already exists",
+            ImportEntity::Vets => "Vet already exists",
+        }
+        .to_string()
+    } else if db_err.is_foreign_key_violation() {
+        "Owner does not exist".to_string()
+    } else {
+        db_err.message().to_string()
+    }
+}
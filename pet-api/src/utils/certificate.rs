@@ -0,0 +1,203 @@
+use chrono::NaiveDate;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::ClinicBranding;
+use crate::models::certificate_model::{
+    Certificate, CertificateContent, CertificateModel, CertificateStatus, CertificateVerification,
+};
+use crate::schemas::certificate_schema::CertificateType;
+use crate::utils::medical_record::{letterhead, section};
+use crate::utils::pdf::{Color, Font, PdfDocument};
+
+// Crockford's base32, which leaves out the easily misread I, L, O and U.
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// A random code such as `7QK2-M9XD-4HPA`, carrying 60 bits of the v4 UUID's randomness.
+pub(crate) fn new_verification_code() -> String {
+    let random = u128::from_be_bytes(*uuid::Uuid::new_v4().as_bytes());
+    let chars = (0..12)
+        .map(|i| CODE_ALPHABET[((random >> (5 * i)) & 0x1f) as usize] as char)
+        .collect::<Vec<_>>();
+    chars
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Reads a code the way a person might type it: any case, with or without dashes, and with
+/// the look-alikes `O`, `I` and `L` for `0` and `1`.
+pub(crate) fn normalize_verification_code(code: &str) -> Option<String> {
+    let chars = code
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect::<Vec<_>>();
+    if chars.len() != 12 || !chars.iter().all(|c| CODE_ALPHABET.contains(&(*c as u8))) {
+        return None;
+    }
+    Some(
+        chars
+            .chunks(4)
+            .map(|chunk| chunk.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("-"),
+    )
+}
+
+/// HMAC-SHA-256 of the content under the server's signing key, so that only the server can
+/// produce a digest that verifies.
+pub(crate) fn content_digest(signing_key: &[u8], content: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(signing_key).expect("HMAC accepts keys of any length");
+    mac.update(content.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+pub(crate) fn certificate_number(certificate_type: CertificateType, certificate_id: i32) -> String {
+    format!("{}-{:06}", certificate_type.prefix(), certificate_id)
+}
+
+pub(crate) fn to_certificate(model: CertificateModel) -> Result<Certificate, serde_json::Error> {
+    let content: CertificateContent = serde_json::from_str(&model.content)?;
+    Ok(Certificate {
+        certificate_id: model.certificate_id,
+        certificate_number: certificate_number(content.certificate_type, model.certificate_id),
+        pet_id: model.pet_id,
+        vet_id: model.vet_id,
+        verification_code: model.verification_code,
+        revoked_on: model.revoked_on,
+        content,
+        content_digest: model.content_digest,
+    })
+}
+
+/// Checks the stored certificate as of `today`, against the key it was signed with.
+pub(crate) fn verify(
+    model: CertificateModel,
+    signing_key: &[u8],
+    today: NaiveDate,
+) -> Result<CertificateVerification, serde_json::Error> {
+    let tampered = content_digest(signing_key, &model.content) != model.content_digest;
+    let certificate = to_certificate(model)?;
+
+    let status = if tampered {
+        CertificateStatus::Tampered
+    } else if certificate.revoked_on.is_some() {
+        CertificateStatus::Revoked
+    } else if certificate
+        .content
+        .valid_until
+        .is_some_and(|valid_until| valid_until < today)
+    {
+        CertificateStatus::Expired
+    } else {
+        CertificateStatus::Valid
+    };
+
+    Ok(CertificateVerification {
+        status,
+        certificate_number: certificate.certificate_number,
+        revoked_on: certificate.revoked_on,
+        content: certificate.content,
+        content_digest: certificate.content_digest,
+    })
+}
+
+/// Renders the certificate for printing and signing, with its verification code.
+pub(crate) fn render_certificate(clinic: &ClinicBranding, certificate: &Certificate) -> Vec<u8> {
+    let accent = Color::from_hex(&clinic.accent_color).unwrap_or(Color::BLACK);
+    let content = &certificate.content;
+    let title = content.certificate_type.title();
+    let mut pdf = PdfDocument::new(
+        &format!("{} {}", title, certificate.certificate_number),
+        &format!(
+            "{} - {} {}",
+            clinic.name, title, certificate.certificate_number
+        ),
+    );
+
+    letterhead(&mut pdf, clinic, accent);
+    pdf.paragraph(title, Font::Bold, 20.0, accent, 0.0);
+    pdf.paragraph(
+        &format!("Certificate No. {}", certificate.certificate_number),
+        Font::Bold,
+        11.0,
+        Color::BLACK,
+        0.0,
+    );
+    pdf.space(8.0);
+
+    section(&mut pdf, "Patient", accent);
+    let pet = &content.pet;
+    pdf.field("Name", &pet.pet_name, 0.0);
+    pdf.field("Species", &pet.pet_type, 0.0);
+    pdf.field("Breed", &pet.pet_breed, 0.0);
+    pdf.field("Color", &pet.pet_color, 0.0);
+    pdf.field("Date of birth", &pet.pet_birth_date.to_string(), 0.0);
+    pdf.field("Owner", &pet.owner_name, 0.0);
+    pdf.space(8.0);
+
+    if !content.treatments.is_empty() {
+        section(&mut pdf, "Vaccinations and preventive care", accent);
+        for treatment in &content.treatments {
+            pdf.field(
+                &treatment.administered_on.to_string(),
+                &format!(
+                    "{} - given by {} (license {})",
+                    treatment.treatment, treatment.vet_name, treatment.vet_license_number
+                ),
+                0.0,
+            );
+        }
+        pdf.space(8.0);
+    }
+
+    if let Some(remarks) = &content.remarks {
+        section(&mut pdf, "Remarks", accent);
+        pdf.paragraph(remarks, Font::Regular, 10.0, Color::BLACK, 0.0);
+        pdf.space(8.0);
+    }
+
+    section(&mut pdf, "Issued by", accent);
+    pdf.field("Veterinarian", &content.issued_by.vet_name, 0.0);
+    pdf.field("License No.", &content.issued_by.vet_license_number, 0.0);
+    pdf.field("Issued on", &content.issued_on.to_string(), 0.0);
+    pdf.field(
+        "Valid until",
+        &content
+            .valid_until
+            .map(|date| date.to_string())
+            .unwrap_or_else(|| "No expiry".to_string()),
+        0.0,
+    );
+    pdf.space(36.0);
+    pdf.paragraph(
+        "Signature: ______________________________",
+        Font::Regular,
+        10.0,
+        Color::BLACK,
+        0.0,
+    );
+    pdf.space(16.0);
+
+    section(&mut pdf, "Verification", accent);
+    pdf.field("Verification code", &certificate.verification_code, 0.0);
+    pdf.field(
+        "Verify at",
+        &format!(
+            "{}/api/v1/certificates/verify/{}",
+            clinic.public_api_url.as_deref().unwrap_or_default(),
+            certificate.verification_code
+        ),
+        0.0,
+    );
+    pdf.field("Content digest", &certificate.content_digest, 0.0);
+
+    pdf.finish()
+}
@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use axum::http::StatusCode;
+
+use crate::models::clinical_code_model::{
+    ClinicalCode, CodeCount, CodeMatch, CodeStatistics, CodeSuggestion, NewClinicalCode,
+};
+use crate::schemas::clinical_code_schema::CodeType;
+use crate::schemas::import_schema::ImportFormat;
+use crate::utils::bulk_import::{parse_csv, MAX_IMPORT_ROWS};
+use crate::utils::rules::RuleViolation;
+
+/// Longest code the clinical_code table holds.
+const MAX_CODE_LENGTH: usize = 20;
+
+/// Longest term or synonym the catalogue tables hold.
+const MAX_TERM_LENGTH: usize = 100;
+
+/// Suggestions returned when no limit is asked for, and the most that can be.
+const DEFAULT_SUGGESTIONS: usize = 10;
+const MAX_SUGGESTIONS: usize = 50;
+
+/// How a diagnosis, surgery or treatment text is looked up among synonyms, matching the
+/// `LOWER(TRIM(...))` the catalogue queries use.
+pub(crate) fn synonym_key(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+/// Reads a catalogue file, one code per CSV row or NDJSON line.
+pub(crate) fn read_catalogue(
+    format: ImportFormat,
+    data: &str,
+) -> Result<Vec<NewClinicalCode>, RuleViolation> {
+    let rows = match format {
+        ImportFormat::Csv => read_csv(data)?,
+        ImportFormat::Ndjson => read_ndjson(data)?,
+    };
+    if rows.is_empty() {
+        return Err(bad_request("The catalogue contains no codes".to_string()));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(bad_request(format!(
+            "A catalogue import is limited to {} codes",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let mut codes = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let code = new_code(row).map_err(|message| row_error(index + 1, &message))?;
+        if !seen.insert(code.code.clone()) {
+            return Err(row_error(
+                index + 1,
+                &format!("Code {} is listed twice", code.code),
+            ));
+        }
+        codes.push(code);
+    }
+
+    Ok(codes)
+}
+
+/// Codes keep their type once catalogued, as records referencing them depend on it.
+pub(crate) fn check_code_types(
+    codes: &[NewClinicalCode],
+    catalogued: &[ClinicalCode],
+) -> Result<(), RuleViolation> {
+    let types = catalogued
+        .iter()
+        .map(|code| (code.code.as_str(), code.code_type.as_str()))
+        .collect::<HashMap<_, _>>();
+    for code in codes {
+        match types.get(code.code.as_str()) {
+            Some(&code_type) if code_type != code.code_type => {
+                return Err((
+                    StatusCode::CONFLICT,
+                    format!(
+                        "Code {} is already catalogued as a {}",
+                        code.code, code_type
+                    ),
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a code given for a record is catalogued with the record's type.
+pub(crate) fn check_code(
+    code_type: CodeType,
+    code: &str,
+    catalogued: Option<&ClinicalCode>,
+) -> Result<(), RuleViolation> {
+    match catalogued {
+        None => Err(unprocessable(format!("Unknown clinical code {}", code))),
+        Some(catalogued) if catalogued.code_type != code_type.as_str() => {
+            Err(unprocessable(format!(
+                "{} is a {} code, not a {} code",
+                code,
+                catalogued.code_type,
+                code_type.as_str()
+            )))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Ranks the matches of an autocomplete search, best first: an exact code or synonym, then
+/// code and synonym prefixes, then synonyms containing a word starting with `q`, then any
+/// other synonym containing it. Each code is suggested once.
+pub(crate) fn suggestions(
+    q: &str,
+    matches: Vec<CodeMatch>,
+    limit: Option<usize>,
+) -> Vec<CodeSuggestion> {
+    let q = synonym_key(q);
+    let mut best: HashMap<String, (u8, CodeMatch)> = HashMap::new();
+    for code_match in matches {
+        let code = code_match.code.to_lowercase();
+        let term = code_match.term.to_lowercase();
+        let rank = if code == q {
+            0
+        } else if code_match.synonym == q {
+            1
+        } else if code.starts_with(&q) && code_match.synonym == term {
+            2
+        } else if code_match.synonym.starts_with(&q) {
+            3
+        } else if code_match
+            .synonym
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word.starts_with(&q))
+        {
+            4
+        } else {
+            5
+        };
+        match best.get(&code_match.code) {
+            Some((best_rank, _)) if *best_rank <= rank => {}
+            _ => {
+                best.insert(code_match.code.clone(), (rank, code_match));
+            }
+        }
+    }
+
+    let mut ranked = best.into_values().collect::<Vec<_>>();
+    ranked.sort_by(|(rank, a), (other_rank, b)| {
+        rank.cmp(other_rank)
+            .then_with(|| a.term.len().cmp(&b.term.len()))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    ranked
+        .into_iter()
+        .take(
+            limit
+                .unwrap_or(DEFAULT_SUGGESTIONS)
+                .clamp(1, MAX_SUGGESTIONS),
+        )
+        .map(|(rank, code_match)| CodeSuggestion {
+            matched_synonym: (rank != 0 && code_match.synonym != code_match.term.to_lowercase())
+                .then_some(code_match.synonym),
+            code: code_match.code,
+            code_type: code_match.code_type,
+            term: code_match.term,
+        })
+        .collect()
+}
+
+/// Splits code counts into coded and uncoded records, keeping only `code_type`'s when given.
+pub(crate) fn code_statistics(
+    counts: Vec<CodeCount>,
+    code_type: Option<CodeType>,
+) -> CodeStatistics {
+    let mut statistics = CodeStatistics::default();
+    for count in counts {
+        if code_type.is_some_and(|code_type| code_type.as_str() != count.code_type) {
+            continue;
+        }
+        if count.code.is_some() {
+            statistics.coded.push(count);
+        } else {
+            statistics.uncoded.push(count);
+        }
+    }
+    statistics.coded.sort_by(|a, b| {
+        CodeType::parse(&a.code_type)
+            .cmp(&CodeType::parse(&b.code_type))
+            .then_with(|| b.total.cmp(&a.total))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    statistics
+        .uncoded
+        .sort_by_key(|count| CodeType::parse(&count.code_type));
+    statistics
+}
+
+fn new_code(row: BTreeMap<String, String>) -> Result<NewClinicalCode, String> {
+    let field = |name: &str| {
+        row.get(name)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let code = field("code");
+    if code.is_empty() {
+        return Err("A code is required".to_string());
+    }
+    if code.chars().count() > MAX_CODE_LENGTH {
+        return Err(format!(
+            "Code {} is longer than {} characters",
+            code, MAX_CODE_LENGTH
+        ));
+    }
+    let code_type = CodeType::parse(&field("code_type"))
+        .ok_or_else(|| "Expected a code_type of diagnosis, procedure or treatment".to_string())?;
+    let term = field("term");
+    if term.is_empty() {
+        return Err(format!("Code {} has no term", code));
+    }
+
+    let mut synonyms = vec![synonym_key(&term)];
+    for synonym in field("synonyms").split('|').map(synonym_key) {
+        if !synonym.is_empty() && !synonyms.contains(&synonym) {
+            synonyms.push(synonym);
+        }
+    }
+    if let Some(synonym) = synonyms
+        .iter()
+        .find(|synonym| synonym.chars().count() > MAX_TERM_LENGTH)
+    {
+        return Err(format!(
+            "'{}' is longer than {} characters",
+            synonym, MAX_TERM_LENGTH
+        ));
+    }
+
+    Ok(NewClinicalCode {
+        code,
+        code_type: code_type.as_str().to_string(),
+        term,
+        synonyms,
+    })
+}
+
+fn read_csv(data: &str) -> Result<Vec<BTreeMap<String, String>>, RuleViolation> {
+    let mut lines = parse_csv(data)
+        .map_err(|e| bad_request(e.to_string()))?
+        .into_iter();
+    let Some(header) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns = header
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect::<Vec<_>>();
+    for column in ["code", "code_type", "term"] {
+        if !columns.iter().any(|name| name == column) {
+            return Err(bad_request(format!("The CSV has no '{}' column", column)));
+        }
+    }
+
+    lines
+        .enumerate()
+        .map(|(index, values)| {
+            if values.len() != columns.len() {
+                return Err(row_error(
+                    index + 1,
+                    &format!(
+                        "Expected {} columns but found {}",
+                        columns.len(),
+                        values.len()
+                    ),
+                ));
+            }
+            Ok(columns.iter().cloned().zip(values).collect())
+        })
+        .collect()
+}
+
+/// Reads one object per line; `synonyms` may be a `|`-separated string or an array.
+fn read_ndjson(data: &str) -> Result<Vec<BTreeMap<String, String>>, RuleViolation> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            let object = match serde_json::from_str::<serde_json::Value>(line) {
+                Ok(serde_json::Value::Object(object)) => object,
+                Ok(_) => return Err(row_error(index + 1, "Expected a JSON object")),
+                Err(e) => return Err(row_error(index + 1, &format!("Invalid JSON: {}", e))),
+            };
+
+            Ok(object
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::Null => return None,
+                        serde_json::Value::String(value) => value,
+                        serde_json::Value::Array(values) => values
+                            .iter()
+                            .filter_map(serde_json::Value::as_str)
+                            .collect::<Vec<_>>()
+                            .join("|"),
+                        value => value.to_string(),
+                    };
+                    Some((key, value))
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn row_error(row: usize, message: &str) -> RuleViolation {
+    bad_request(format!("Row {}: {}", row, message))
+}
+
+fn bad_request(message: String) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+fn unprocessable(message: String) -> RuleViolation {
+    (StatusCode::UNPROCESSABLE_ENTITY, message)
+}
@@ -0,0 +1,97 @@
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+
+use crate::models::clinical_note_model::{ClinicalNoteModel, SoapNotes};
+use crate::models::staff_model::StaffModel;
+use crate::schemas::clinical_note_schema::{AddClinicalNote, ClinicalNoteSearchOptions};
+use crate::utils::rules::RuleViolation;
+
+/// Longest note entry accepted, in characters.
+const MAX_BODY_LENGTH: usize = 20_000;
+
+/// Staff roles that write clinical notes.
+const AUTHOR_ROLES: [&str; 2] = ["veterinarian", "technician"];
+
+/// Trims the entry and checks it has a body. Line breaks inside the Markdown are kept.
+pub(crate) fn validate_note(note: &mut AddClinicalNote) -> Result<(), RuleViolation> {
+    note.author_id = note.author_id.trim().to_string();
+    note.body = note.body.trim().to_string();
+    if note.body.is_empty() {
+        return Err(bad_request("Body cannot be empty".to_string()));
+    }
+    if note.body.chars().count() > MAX_BODY_LENGTH {
+        return Err(bad_request(format!(
+            "Body is longer than {} characters",
+            MAX_BODY_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Only active veterinarians and technicians write clinical notes.
+pub(crate) fn check_author(author: &StaffModel) -> Result<(), RuleViolation> {
+    if !author.is_active {
+        return Err(unprocessable(format!(
+            "Staff member {} is inactive",
+            author.staff_id
+        )));
+    }
+    if !AUTHOR_ROLES.contains(&author.staff_role.as_str()) {
+        return Err(unprocessable(format!(
+            "A {} can't write clinical notes",
+            author.staff_role
+        )));
+    }
+    Ok(())
+}
+
+/// Drops blank search fields and checks the dates.
+pub(crate) fn normalize_search(opts: &mut ClinicalNoteSearchOptions) -> Result<(), RuleViolation> {
+    for field in [&mut opts.q, &mut opts.author_id] {
+        *field = field
+            .take()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+    }
+    for (name, date) in [
+        ("start_date", &mut opts.start_date),
+        ("end_date", &mut opts.end_date),
+    ] {
+        *date = date
+            .take()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        if let Some(value) = date {
+            if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() {
+                return Err(bad_request(format!(
+                    "Invalid {}: expected YYYY-MM-DD",
+                    name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Files a visit's entries under their SOAP sections, keeping their order.
+pub(crate) fn soap(notes: Vec<ClinicalNoteModel>) -> SoapNotes {
+    let mut soap = SoapNotes::default();
+    for note in notes {
+        let section = match note.section.as_str() {
+            "subjective" => &mut soap.subjective,
+            "objective" => &mut soap.objective,
+            "assessment" => &mut soap.assessment,
+            _ => &mut soap.plan,
+        };
+        section.push(note);
+    }
+    soap
+}
+
+fn bad_request(message: String) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+fn unprocessable(message: String) -> RuleViolation {
+    (StatusCode::UNPROCESSABLE_ENTITY, message)
+}
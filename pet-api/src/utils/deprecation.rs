@@ -0,0 +1,18 @@
+use axum::http::header::LINK;
+use axum::http::HeaderValue;
+use axum::response::Response;
+
+/// When the legacy routes were deprecated (2026-10-19), as an RFC 9745 `Deprecation` date.
+const DEPRECATED_SINCE: &str = "@1792368000";
+
+pub(crate) async fn mark_deprecated(mut response: Response) -> Response {
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static(DEPRECATED_SINCE));
+    headers.insert(
+        LINK,
+        HeaderValue::from_static(
+            r#"</api/v1>; rel="successor-version", </api/docs>; rel="deprecation""#,
+        ),
+    );
+    response
+}
@@ -0,0 +1,64 @@
+use axum::http::header::{IF_MATCH, IF_NONE_MATCH};
+use axum::http::HeaderMap;
+use serde::Serialize;
+
+pub(crate) struct PreconditionFailed;
+
+/// Entity tag of a single row: its quoted `row_version`.
+pub(crate) fn row_etag(row_version: i32) -> String {
+    format!("\"{}\"", row_version)
+}
+
+/// Entity tag of a body that also carries other rows (a pet with its owner's name, an owner
+/// with their pets). It leads with the row version, so it still works as an `If-Match`, and
+/// ends with a digest of the body so `If-None-Match` sees changes to the joined rows too.
+pub(crate) fn representation_etag<T: Serialize>(row_version: i32, representation: &T) -> String {
+    let body = serde_json::to_vec(representation).unwrap_or_default();
+    // FNV-1a keeps tags stable across restarts and builds, unlike `DefaultHasher`.
+    let digest = body.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("\"{}-{:016x}\"", row_version, digest)
+}
+
+/// Checks `If-Match` against the row's current version, returning the version the update
+/// must still find, or `None` when the request is unconditional.
+pub(crate) fn check_if_match(
+    headers: &HeaderMap,
+    row_version: i32,
+) -> Result<Option<i32>, PreconditionFailed> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().map_err(|_| PreconditionFailed)?;
+
+    let matches = value.trim() == "*"
+        || value
+            .split(',')
+            .filter_map(|tag| tagged_version(tag.trim()))
+            .any(|version| version == row_version);
+
+    if matches {
+        Ok(Some(row_version))
+    } else {
+        Err(PreconditionFailed)
+    }
+}
+
+/// Whether `If-None-Match` already names `etag`, so a GET can answer 304.
+pub(crate) fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(Ok(value)) = headers.get(IF_NONE_MATCH).map(|value| value.to_str()) else {
+        return false;
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+// `If-Match` uses strong comparison, so weak (`W/`) tags never name a version.
+fn tagged_version(tag: &str) -> Option<i32> {
+    let opaque = tag.strip_prefix('"')?.strip_suffix('"')?;
+    opaque.split('-').next()?.parse().ok()
+}
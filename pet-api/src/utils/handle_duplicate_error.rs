@@ -6,8 +6,12 @@ pub(crate) fn handle_duplicate_entry_error(
     err: sqlx::Error,
     entity: &str,
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let is_duplicate = err
+        .as_database_error()
+        .map(|db_err| db_err.is_unique_violation())
+        .unwrap_or(false);
     let err_msg = err.to_string();
-    if err_msg.contains("Duplicate entry") {
+    if is_duplicate {
         let error_response = json!({
             "status":"fail",
             "message": format!("{} already exists", entity)
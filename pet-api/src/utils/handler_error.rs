@@ -0,0 +1,24 @@
+use axum::http::StatusCode;
+use axum::Json;
+use serde_json::json;
+
+use crate::utils::rules::RuleViolation;
+
+/// What a handler answers a request it can't serve with.
+pub(crate) type ErrorResponse = (StatusCode, Json<serde_json::Value>);
+
+/// A request the client can correct, answered with `status` and the message.
+pub(crate) fn fail(status: StatusCode, message: &str) -> ErrorResponse {
+    (status, Json(json!({"status": "fail", "message": message})))
+}
+
+pub(crate) fn rule_violation((status, message): RuleViolation) -> ErrorResponse {
+    fail(status, &message)
+}
+
+pub(crate) fn database_error(e: sqlx::Error) -> ErrorResponse {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"status": "error", "message": format!("{:?}", e)})),
+    )
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::utils::tenant::Tenant;
+use crate::AppState;
+
+pub(crate) const IDEMPOTENCY_KEY: &str = "idempotency-key";
+pub(crate) const IDEMPOTENT_REPLAYED: &str = "idempotent-replayed";
+
+// Sign-in routes answer with portal credentials, which must never be stored for replay.
+const UNREPLAYABLE_PATHS: [&str; 2] = ["/api/v1/portal/login-links", "/api/v1/portal/session"];
+const MAX_KEY_LENGTH: usize = 255;
+// Matches the `Json` extractor's default body limit.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Makes a `POST` carrying an `Idempotency-Key` safe to retry: the first response is stored and
+/// replayed for repeats of the same request until the key expires, while reusing the key for a
+/// different request is a conflict. Server errors are not stored, so those can be retried.
+/// Keys belong to the clinic the request acts for, and portal sign-in routes are never replayed.
+pub(crate) async fn idempotent(
+    State(data): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST || UNREPLAYABLE_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "error",
+                "Idempotency-Key must be 1 to 255 visible ASCII characters",
+            )
+        }
+    };
+
+    let (mut parts, body) = request.into_parts();
+    let clinic_id = match Tenant::from_request_parts(&mut parts, &data).await {
+        Ok(tenant) => tenant.clinic_id,
+        Err(rejection) => return rejection,
+    };
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(_) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "error",
+                "Request body is too large",
+            )
+        }
+    };
+    // A portal owner's key only replays to the same session, never to another owner's.
+    let authorization = parts
+        .headers
+        .get(AUTHORIZATION)
+        .map(HeaderValue::as_bytes)
+        .unwrap_or_default();
+    let fingerprint = request_fingerprint(&parts.method, parts.uri.path(), authorization, &body);
+
+    let idempotency_keys = data.db.idempotency_keys(&clinic_id);
+    let now = chrono::Utc::now().timestamp();
+    let expires_at = now + data.idempotency_key_ttl.as_secs() as i64;
+
+    if let Err(e) = idempotency_keys.delete_expired_keys(now).await {
+        return database_error(e);
+    }
+    match idempotency_keys
+        .insert_key(&key, &fingerprint, now, expires_at)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return match idempotency_keys.select_key(&key).await {
+                Ok(Some(stored)) if stored.request_fingerprint != fingerprint => error_response(
+                    StatusCode::CONFLICT,
+                    "fail",
+                    "Idempotency-Key was already used for a different request",
+                ),
+                Ok(Some(stored)) => match (stored.response_status, stored.response_body) {
+                    (Some(status), Some(body)) => replay(status, body),
+                    _ => error_response(
+                        StatusCode::CONFLICT,
+                        "fail",
+                        "A request with this Idempotency-Key is still being processed",
+                    ),
+                },
+                // Expired between the insert and the lookup; the client can simply retry.
+                Ok(None) => error_response(
+                    StatusCode::CONFLICT,
+                    "fail",
+                    "A request with this Idempotency-Key is still being processed",
+                ),
+                Err(e) => database_error(e),
+            };
+        }
+        Err(e) => return database_error(e),
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    if response.status().is_server_error() {
+        let _ = idempotency_keys.delete_key(&key).await;
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(_) => {
+            let _ = idempotency_keys.delete_key(&key).await;
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error",
+                "Failed to read the response",
+            );
+        }
+    };
+    let saved = idempotency_keys
+        .save_response(
+            &key,
+            i32::from(parts.status.as_u16()),
+            &String::from_utf8_lossy(&body),
+        )
+        .await;
+    if saved.is_err() {
+        let _ = idempotency_keys.delete_key(&key).await;
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn request_fingerprint(method: &Method, path: &str, authorization: &[u8], body: &Bytes) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b"\n");
+    hasher.update(path);
+    hasher.update(b"\n");
+    hasher.update(authorization);
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+fn replay(status: i32, body: String) -> Response {
+    let status = u16::try_from(status)
+        .ok()
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .unwrap_or(StatusCode::OK);
+    let mut response = (status, body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+fn error_response(status: StatusCode, kind: &str, message: &str) -> Response {
+    (status, Json(json!({"status": kind, "message": message}))).into_response()
+}
+
+fn database_error(e: sqlx::Error) -> Response {
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "error",
+        &format!("{:?}", e),
+    )
+}
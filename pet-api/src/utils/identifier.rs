@@ -0,0 +1,126 @@
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+
+use crate::models::identifier_model::PetIdentifierModel;
+use crate::schemas::identifier_schema::{AddPetIdentifier, IdentifierType};
+use crate::utils::rules::RuleViolation;
+
+/// Longest identifier the pet_identifier table holds.
+const MAX_IDENTIFIER_LENGTH: usize = 20;
+
+/// Longest implant location the pet_identifier table holds.
+const MAX_LOCATION_LENGTH: usize = 100;
+
+/// ISO 11784 codes are 15 digits: a 3-digit country or manufacturer code and a 12-digit
+/// national number, which has 38 bits in the transponder.
+const MICROCHIP_LENGTH: usize = 15;
+const MAX_NATIONAL_NUMBER: u64 = (1 << 38) - 1;
+
+/// The country code ISO 11784 reserves for test transponders.
+const TEST_TRANSPONDER_CODE: &str = "999";
+
+/// How identifiers are stored and looked up: letters and digits only, in upper case, so a
+/// number reads the same whether it was typed with spaces, dashes or dots.
+pub(crate) fn normalize_identifier(code: &str) -> String {
+    code.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub(crate) fn new_identifier(
+    pet_id: String,
+    identifier: AddPetIdentifier,
+    today: NaiveDate,
+    now: i64,
+) -> Result<PetIdentifierModel, RuleViolation> {
+    let identifier_type = identifier.identifier_type;
+    if identifier
+        .identifier_value
+        .chars()
+        .any(|c| !(c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '.')))
+    {
+        return Err(bad_request(format!(
+            "{} numbers may only contain letters, digits, spaces, dashes and dots",
+            identifier_type.label()
+        )));
+    }
+    let identifier_value = normalize_identifier(&identifier.identifier_value);
+    match identifier_type {
+        IdentifierType::Microchip => check_microchip(&identifier_value)?,
+        IdentifierType::Tattoo | IdentifierType::LicenseTag => {
+            if identifier_value.is_empty() || identifier_value.len() > MAX_IDENTIFIER_LENGTH {
+                return Err(bad_request(format!(
+                    "{} numbers are 1 to {} letters and digits",
+                    identifier_type.label(),
+                    MAX_IDENTIFIER_LENGTH
+                )));
+            }
+        }
+    }
+
+    let implant_location = identifier
+        .implant_location
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty());
+    if identifier_type != IdentifierType::Microchip
+        && (identifier.implant_date.is_some() || implant_location.is_some())
+    {
+        return Err(bad_request(
+            "Only microchips have an implant date or location".to_string(),
+        ));
+    }
+    if identifier.implant_date.is_some_and(|date| date > today) {
+        return Err(bad_request(
+            "implant_date cannot be in the future".to_string(),
+        ));
+    }
+    if implant_location
+        .as_ref()
+        .is_some_and(|location| location.chars().count() > MAX_LOCATION_LENGTH)
+    {
+        return Err(bad_request(format!(
+            "implant_location is limited to {} characters",
+            MAX_LOCATION_LENGTH
+        )));
+    }
+
+    Ok(PetIdentifierModel {
+        identifier_id: 0,
+        pet_id,
+        identifier_type: identifier_type.as_str().to_string(),
+        identifier_value,
+        implant_date: identifier.implant_date,
+        implant_location,
+        recorded_at: now,
+    })
+}
+
+/// An ISO 11784/11785 microchip: 15 digits whose national number fits the transponder's 38
+/// bits, and not a test transponder.
+fn check_microchip(value: &str) -> Result<(), RuleViolation> {
+    if value.len() != MICROCHIP_LENGTH || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(bad_request(format!(
+            "Microchip numbers are {} digits (ISO 11784/11785)",
+            MICROCHIP_LENGTH
+        )));
+    }
+    let (code, national_number) = value.split_at(3);
+    if code == TEST_TRANSPONDER_CODE {
+        return Err(bad_request(format!(
+            "Microchip {} is a test transponder",
+            value
+        )));
+    }
+    match national_number.parse::<u64>() {
+        Ok(number) if number <= MAX_NATIONAL_NUMBER => Ok(()),
+        _ => Err(bad_request(format!(
+            "Microchip {} is not a valid ISO 11784 code: its national number is out of range",
+            value
+        ))),
+    }
+}
+
+fn bad_request(message: String) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message)
+}
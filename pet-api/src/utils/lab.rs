@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashSet};
+
+use axum::http::StatusCode;
+
+use crate::models::lab_model::{
+    AnalyteTrend, LabOrder, LabOrderModel, LabResultModel, NewLabResult, ReferenceRange, TrendPoint,
+};
+use crate::schemas::lab_schema::{AddLabOrder, AddLabResult, LabFlag};
+use crate::utils::rules::RuleViolation;
+
+/// Longest panel name the lab_order table holds.
+const MAX_PANEL_LENGTH: usize = 50;
+
+/// Trims the order's text fields and checks the panel is named.
+pub(crate) fn validate_order(order: &mut AddLabOrder) -> Result<(), RuleViolation> {
+    order.panel = order.panel.trim().to_string();
+    if order.panel.is_empty() {
+        return Err(bad_request("Panel cannot be empty".to_string()));
+    }
+    if order.panel.chars().count() > MAX_PANEL_LENGTH {
+        return Err(bad_request(format!(
+            "Panel is longer than {} characters",
+            MAX_PANEL_LENGTH
+        )));
+    }
+    order.vet_id = order.vet_id.trim().to_string();
+    order.notes = order
+        .notes
+        .take()
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty());
+    Ok(())
+}
+
+/// Checks each result and flags it against the range the lab reported or, failing that,
+/// the species reference range when it is in the same unit.
+pub(crate) fn prepare_results(
+    entries: Vec<AddLabResult>,
+    ranges: &[ReferenceRange],
+) -> Result<Vec<NewLabResult>, RuleViolation> {
+    if entries.is_empty() {
+        return Err(bad_request("Enter at least one result".to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .map(|entry| {
+            let analyte = entry.analyte.trim().to_uppercase();
+            if analyte.is_empty() {
+                return Err(bad_request("Analyte cannot be empty".to_string()));
+            }
+            if !seen.insert(analyte.clone()) {
+                return Err(bad_request(format!("{} is entered twice", analyte)));
+            }
+
+            let range = ranges.iter().find(|range| range.analyte == analyte);
+            let unit = match entry.unit.as_deref().map(str::trim) {
+                Some(unit) if !unit.is_empty() => unit.to_string(),
+                _ => match range {
+                    Some(range) => range.unit.clone(),
+                    None => return Err(bad_request(format!("{} needs a unit", analyte))),
+                },
+            };
+
+            let (reference_low, reference_high) =
+                if entry.reference_low.is_some() || entry.reference_high.is_some() {
+                    (entry.reference_low, entry.reference_high)
+                } else {
+                    match range.filter(|range| same_unit(&range.unit, &unit)) {
+                        Some(range) => (Some(range.reference_low), Some(range.reference_high)),
+                        None => (None, None),
+                    }
+                };
+            if let (Some(low), Some(high)) = (reference_low, reference_high) {
+                if low > high {
+                    return Err(bad_request(format!(
+                        "{}: reference_low is above reference_high",
+                        analyte
+                    )));
+                }
+            }
+
+            Ok(NewLabResult {
+                flag: flag(entry.value, reference_low, reference_high).map(LabFlag::as_str),
+                analyte,
+                value: entry.value,
+                unit,
+                reference_low,
+                reference_high,
+            })
+        })
+        .collect()
+}
+
+/// Where `value` falls against a range that may be open on either side; none without one.
+pub(crate) fn flag(value: f64, low: Option<f64>, high: Option<f64>) -> Option<LabFlag> {
+    if low.is_none() && high.is_none() {
+        return None;
+    }
+    if low.is_some_and(|low| value < low) {
+        Some(LabFlag::Low)
+    } else if high.is_some_and(|high| value > high) {
+        Some(LabFlag::High)
+    } else {
+        Some(LabFlag::Normal)
+    }
+}
+
+pub(crate) fn to_lab_order(order: LabOrderModel, results: Vec<LabResultModel>) -> LabOrder {
+    let abnormal_analytes = results
+        .iter()
+        .filter(|result| matches!(result.flag.as_deref(), Some("low") | Some("high")))
+        .map(|result| result.analyte.clone())
+        .collect();
+
+    LabOrder {
+        lab_order_id: order.lab_order_id,
+        service_instance_id: order.service_instance_id,
+        service_date: order.service_date,
+        pet_id: order.pet_id,
+        panel: order.panel,
+        sample_type: order.sample_type,
+        vet_id: order.vet_id,
+        vet_name: order.vet_name,
+        notes: order.notes,
+        ordered_at: order.ordered_at,
+        resulted_at: order.resulted_at,
+        abnormal_analytes,
+        results,
+    }
+}
+
+/// Groups a pet's results by analyte, each with the species reference range.
+pub(crate) fn trends(points: Vec<TrendPoint>, ranges: &[ReferenceRange]) -> Vec<AnalyteTrend> {
+    let mut by_analyte: BTreeMap<String, Vec<TrendPoint>> = BTreeMap::new();
+    for point in points {
+        by_analyte
+            .entry(point.analyte.clone())
+            .or_default()
+            .push(point);
+    }
+
+    by_analyte
+        .into_iter()
+        .map(|(analyte, points)| AnalyteTrend {
+            reference_range: ranges
+                .iter()
+                .find(|range| range.analyte == analyte)
+                .cloned(),
+            analyte,
+            points,
+        })
+        .collect()
+}
+
+fn same_unit(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+fn bad_request(message: String) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message)
+}
@@ -0,0 +1,217 @@
+use chrono::NaiveDate;
+
+use crate::config::ClinicBranding;
+use crate::models::owner_model::OwnerModel;
+use crate::models::pet_model::PetModelResponse;
+use crate::models::service_instance_model::ServiceInstanceModel;
+use crate::models::vet_model::VetModelForService;
+use crate::utils::pdf::{Color, Font, PdfDocument, MARGIN, PAGE_HEIGHT, PAGE_WIDTH};
+
+const LETTERHEAD_HEIGHT: f32 = 70.0;
+
+pub(crate) struct MedicalRecord<'a> {
+    pub clinic: &'a ClinicBranding,
+    pub pet: &'a PetModelResponse,
+    pub owner: &'a OwnerModel,
+    /// Visits in the order they are printed.
+    pub visits: &'a [ServiceInstanceModel],
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub generated_on: NaiveDate,
+}
+
+/// Renders the pet's medical record, one section per visit, as a PDF document.
+pub(crate) fn render_medical_record(record: &MedicalRecord) -> Vec<u8> {
+    let accent = Color::from_hex(&record.clinic.accent_color).unwrap_or(Color::BLACK);
+    let title = format!("Medical record of {}", record.pet.pet_name);
+    let mut pdf = PdfDocument::new(
+        &title,
+        &format!(
+            "{} - {} - generated {}",
+            record.clinic.name, title, record.generated_on
+        ),
+    );
+
+    letterhead(&mut pdf, record.clinic, accent);
+    pdf.paragraph(&title, Font::Bold, 18.0, accent, 0.0);
+    pdf.paragraph(
+        &period(record.start_date, record.end_date),
+        Font::Regular,
+        10.0,
+        Color::GREY,
+        0.0,
+    );
+    pdf.space(8.0);
+
+    section(&mut pdf, "Patient", accent);
+    let pet = record.pet;
+    pdf.field("Name", &pet.pet_name, 0.0);
+    pdf.field("Species", &pet.pet_type, 0.0);
+    pdf.field("Breed", &pet.pet_breed, 0.0);
+    pdf.field("Color", &pet.pet_color, 0.0);
+    pdf.field("Date of birth", &pet.pet_birth_date.to_string(), 0.0);
+    pdf.field("Weight", &format!("{} kg", pet.pet_weight), 0.0);
+    pdf.field("Patient ID", &pet.pet_id, 0.0);
+    pdf.space(8.0);
+
+    section(&mut pdf, "Owner", accent);
+    let owner = record.owner;
+    pdf.field("Name", &owner.owner_name, 0.0);
+    pdf.field("Email", &owner.owner_email, 0.0);
+    pdf.field("Phone", &owner.owner_phone_number, 0.0);
+    pdf.field("Address", &owner.owner_address, 0.0);
+    pdf.space(8.0);
+
+    section(
+        &mut pdf,
+        &format!("Visits ({})", record.visits.len()),
+        accent,
+    );
+    if record.visits.is_empty() {
+        pdf.paragraph(
+            "No visits were recorded in this period.",
+            Font::Regular,
+            10.0,
+            Color::GREY,
+            0.0,
+        );
+    }
+    for visit in record.visits {
+        visit_section(&mut pdf, visit, accent);
+    }
+
+    pdf.finish()
+}
+
+pub(crate) fn letterhead(pdf: &mut PdfDocument, clinic: &ClinicBranding, accent: Color) {
+    pdf.fill_rect(
+        0.0,
+        PAGE_HEIGHT - LETTERHEAD_HEIGHT,
+        PAGE_WIDTH,
+        LETTERHEAD_HEIGHT,
+        accent,
+    );
+    pdf.text_at(
+        MARGIN,
+        PAGE_HEIGHT - 38.0,
+        &clinic.name,
+        Font::Bold,
+        16.0,
+        Color::WHITE,
+    );
+    let contact = [
+        clinic.address.as_deref(),
+        clinic.phone_number.as_deref(),
+        clinic.email.as_deref(),
+        clinic.website.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("  |  ");
+    if !contact.is_empty() {
+        pdf.text_at(
+            MARGIN,
+            PAGE_HEIGHT - 56.0,
+            &contact,
+            Font::Regular,
+            9.0,
+            Color::WHITE,
+        );
+    }
+    pdf.space(LETTERHEAD_HEIGHT);
+}
+
+fn period(start_date: Option<NaiveDate>, end_date: Option<NaiveDate>) -> String {
+    match (start_date, end_date) {
+        (Some(start), Some(end)) => format!("Visits from {} to {}", start, end),
+        (Some(start), None) => format!("Visits from {}", start),
+        (None, Some(end)) => format!("Visits up to {}", end),
+        (None, None) => "Complete history".to_string(),
+    }
+}
+
+pub(crate) fn section(pdf: &mut PdfDocument, heading: &str, accent: Color) {
+    // Keep a heading together with at least the first lines below it.
+    pdf.ensure_space(60.0);
+    pdf.paragraph(heading, Font::Bold, 13.0, accent, 0.0);
+    pdf.rule(accent);
+}
+
+fn visit_section(pdf: &mut PdfDocument, visit: &ServiceInstanceModel, accent: Color) {
+    pdf.ensure_space(80.0);
+    pdf.space(4.0);
+    pdf.paragraph(
+        &format!(
+            "{}  -  {}",
+            visit.service_date,
+            visit.service_type.join(", ")
+        ),
+        Font::Bold,
+        11.0,
+        Color::BLACK,
+        0.0,
+    );
+    pdf.field("Reason", &visit.service_reason, 10.0);
+    pdf.field("Diagnosis", &visit.general_diagnosis, 10.0);
+    pdf.field(
+        "Follow-up",
+        &match (visit.requires_followup, visit.followup_date) {
+            (true, Some(date)) => format!("Required by {}", date),
+            (true, None) => "Required".to_string(),
+            (false, _) => "Not required".to_string(),
+        },
+        10.0,
+    );
+
+    let grooming = visit.grooming.as_deref().unwrap_or_default();
+    if !grooming.is_empty() {
+        sub_heading(pdf, "Grooming", accent);
+        pdf.field(
+            "Services",
+            &grooming
+                .iter()
+                .map(|grooming| grooming.grooming_type.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            20.0,
+        );
+    }
+
+    for preventive_care in visit.preventive_care.as_deref().unwrap_or_default() {
+        sub_heading(pdf, "Preventive care", accent);
+        pdf.field("Treatment", &preventive_care.treatment, 20.0);
+        pdf.field("Vet", &vet_line(&preventive_care.vet), 20.0);
+    }
+
+    for surgery in visit.surgery.as_deref().unwrap_or_default() {
+        sub_heading(pdf, &format!("Surgery: {}", surgery.surgery_name), accent);
+        for (label, value) in [
+            ("Vet diagnosis", &surgery.veterinarian_diagnosis),
+            ("Anesthesia", &surgery.anesthesia_used),
+            ("Complications", &surgery.complications),
+            ("Outcome", &surgery.outcome),
+        ] {
+            if let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) {
+                pdf.field(label, value, 20.0);
+            }
+        }
+        pdf.field("Surgeon", &vet_line(&surgery.vet), 20.0);
+    }
+
+    pdf.space(4.0);
+    pdf.rule(Color(0.85, 0.85, 0.85));
+}
+
+fn sub_heading(pdf: &mut PdfDocument, heading: &str, accent: Color) {
+    pdf.ensure_space(40.0);
+    pdf.space(2.0);
+    pdf.paragraph(heading, Font::Bold, 10.0, accent, 10.0);
+}
+
+fn vet_line(vet: &VetModelForService) -> String {
+    format!(
+        "{} (license {}, {}, {})",
+        vet.vet_name, vet.vet_license_number, vet.vet_phone_number, vet.vet_email
+    )
+}
@@ -1,3 +1,25 @@
+pub(crate) mod boarding;
+pub(crate) mod bulk_import;
+pub(crate) mod certificate;
+pub(crate) mod clinical_code;
+pub(crate) mod clinical_note;
+pub(crate) mod deprecation;
+pub(crate) mod etag;
 pub(crate) mod handle_duplicate_error;
+pub(crate) mod handler_error;
+pub(crate) mod idempotency;
+pub(crate) mod identifier;
+pub(crate) mod lab;
+pub(crate) mod mailer;
+pub(crate) mod medical_record;
 pub(crate) mod model_to_response;
+pub(crate) mod pagination;
+pub(crate) mod pdf;
+pub(crate) mod pet_status;
+pub(crate) mod portal;
+pub(crate) mod problem_list;
+pub(crate) mod rules;
+pub(crate) mod statistics;
+pub(crate) mod surgical_case;
+pub(crate) mod tenant;
 pub(crate) mod validator;
@@ -0,0 +1,44 @@
+use axum::http::StatusCode;
+
+use crate::utils::rules::RuleViolation;
+
+/// Most rows a single list page returns.
+pub(crate) const MAX_PAGE_SIZE: usize = 100;
+
+/// `LIMIT` and `OFFSET` of a requested page, ready to bind.
+pub(crate) struct PageWindow {
+    pub limit: i32,
+    pub offset: i32,
+}
+
+impl PageWindow {
+    /// Pages needed to list `total` rows at this page size.
+    pub(crate) fn total_pages(&self, total: i64) -> i64 {
+        (total.max(0) + i64::from(self.limit) - 1) / i64::from(self.limit)
+    }
+}
+
+/// Resolves `page` and `limit` query values, treating page 0 as the first page. A limit
+/// outside `1..=MAX_PAGE_SIZE`, or a page whose offset the database can't bind, is refused.
+pub(crate) fn page_window(
+    page: Option<usize>,
+    limit: Option<usize>,
+    default_limit: usize,
+) -> Result<PageWindow, RuleViolation> {
+    let limit = limit.unwrap_or(default_limit);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("limit must be between 1 and {}", MAX_PAGE_SIZE),
+        ));
+    }
+    let offset = (page.unwrap_or(1).max(1) - 1)
+        .checked_mul(limit)
+        .and_then(|offset| i32::try_from(offset).ok())
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "page is out of range".to_string()))?;
+
+    Ok(PageWindow {
+        limit: limit as i32,
+        offset,
+    })
+}
@@ -0,0 +1,369 @@
+//! A small PDF 1.4 writer for text documents: A4 pages set in the standard Helvetica fonts,
+//! which every viewer has built in, so no font files or native libraries are needed.
+
+use std::fmt::Write as _;
+
+pub(crate) const PAGE_WIDTH: f32 = 595.0;
+pub(crate) const PAGE_HEIGHT: f32 = 842.0;
+pub(crate) const MARGIN: f32 = 50.0;
+const FOOTER_HEIGHT: f32 = 30.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    /// Advance width of `c` in thousandths of the font size, from the Helvetica AFM metrics.
+    fn width(self, c: char) -> u16 {
+        const REGULAR: [u16; 95] = [
+            278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556,
+            556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667,
+            667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722,
+            667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500,
+            556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278,
+            556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+        ];
+        const BOLD: [u16; 95] = [
+            278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556,
+            556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722,
+            722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722,
+            667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556,
+            611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, 611, 611, 389, 556, 333,
+            611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+        ];
+        let table = match self {
+            Font::Regular => &REGULAR,
+            Font::Bold => &BOLD,
+        };
+        match c {
+            ' '..='~' => table[c as usize - 32],
+            _ => 556,
+        }
+    }
+}
+
+/// An RGB colour with components from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+    pub const GREY: Color = Color(0.4, 0.4, 0.4);
+    pub const WHITE: Color = Color(1.0, 1.0, 1.0);
+
+    /// Parses `#RRGGBB`.
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.trim().strip_prefix('#')?;
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .ok()
+                .map(|v| f32::from(v) / 255.0)
+        };
+        Some(Color(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Width of `text` set in `font` at `size` points.
+pub(crate) fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    text.chars().map(|c| f32::from(font.width(c))).sum::<f32>() * size / 1000.0
+}
+
+/// Breaks `text` into lines no wider than `max_width`, splitting overlong words.
+pub(crate) fn wrap(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if text_width(&candidate, font, size) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                if !line.is_empty() && text_width(&format!("{}{}", line, c), font, size) > max_width
+                {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Lays text out top to bottom, starting a new page whenever the current one is full.
+pub(crate) struct PdfDocument {
+    title: String,
+    footer: String,
+    pages: Vec<String>,
+    y: f32,
+}
+
+impl PdfDocument {
+    pub fn new(title: &str, footer: &str) -> Self {
+        let mut document = Self {
+            title: title.to_string(),
+            footer: footer.to_string(),
+            pages: Vec::new(),
+            y: 0.0,
+        };
+        document.new_page();
+        document
+    }
+
+    pub fn new_page(&mut self) {
+        self.pages.push(String::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Starts a new page unless `height` more points fit above the footer.
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN + FOOTER_HEIGHT {
+            self.new_page();
+        }
+    }
+
+    pub fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Writes wrapped text at `indent` points in from the left margin.
+    pub fn paragraph(&mut self, text: &str, font: Font, size: f32, color: Color, indent: f32) {
+        let leading = size * 1.35;
+        for line in wrap(text, font, size, PAGE_WIDTH - 2.0 * MARGIN - indent) {
+            self.ensure_space(leading);
+            self.y -= leading;
+            self.text_at(MARGIN + indent, self.y, &line, font, size, color);
+        }
+    }
+
+    /// Writes `label` in bold followed by `value`, wrapping the value under itself.
+    pub fn field(&mut self, label: &str, value: &str, indent: f32) {
+        const SIZE: f32 = 10.0;
+        const LABEL_WIDTH: f32 = 120.0;
+        let leading = SIZE * 1.35;
+        let lines = wrap(
+            value,
+            Font::Regular,
+            SIZE,
+            PAGE_WIDTH - 2.0 * MARGIN - indent - LABEL_WIDTH,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            self.ensure_space(leading);
+            self.y -= leading;
+            if i == 0 {
+                self.text_at(
+                    MARGIN + indent,
+                    self.y,
+                    label,
+                    Font::Bold,
+                    SIZE,
+                    Color::BLACK,
+                );
+            }
+            self.text_at(
+                MARGIN + indent + LABEL_WIDTH,
+                self.y,
+                line,
+                Font::Regular,
+                SIZE,
+                Color::BLACK,
+            );
+        }
+    }
+
+    /// Draws a horizontal rule across the text column.
+    pub fn rule(&mut self, color: Color) {
+        self.ensure_space(8.0);
+        self.y -= 4.0;
+        let y = self.y;
+        let Color(r, g, b) = color;
+        let _ = writeln!(
+            self.page(),
+            "{} {} {} RG 0.75 w {} {} m {} {} l S",
+            r,
+            g,
+            b,
+            MARGIN,
+            y,
+            PAGE_WIDTH - MARGIN,
+            y
+        );
+        self.y -= 4.0;
+    }
+
+    /// Fills a rectangle whose bottom-left corner is at `x`, `y`.
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let Color(r, g, b) = color;
+        let _ = writeln!(
+            self.page(),
+            "{} {} {} rg {} {} {} {} re f",
+            r,
+            g,
+            b,
+            x,
+            y,
+            width,
+            height
+        );
+    }
+
+    pub fn text_at(&mut self, x: f32, y: f32, text: &str, font: Font, size: f32, color: Color) {
+        let Color(r, g, b) = color;
+        let _ = writeln!(
+            self.page(),
+            "BT /{} {} Tf {} {} {} rg {} {} Td ({}) Tj ET",
+            font.resource(),
+            size,
+            r,
+            g,
+            b,
+            x,
+            y,
+            escape(text)
+        );
+    }
+
+    fn page(&mut self) -> &mut String {
+        self.pages.last_mut().expect("a document has a page")
+    }
+
+    /// Serializes the document, stamping each page with the footer and its page number.
+    pub fn finish(mut self) -> Vec<u8> {
+        let page_count = self.pages.len();
+        let footer = self.footer.clone();
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            let number = format!("Page {} of {}", index + 1, page_count);
+            let _ = writeln!(
+                page,
+                "BT /F1 8 Tf 0.4 0.4 0.4 rg {} {} Td ({}) Tj ET",
+                MARGIN,
+                MARGIN - 10.0,
+                escape(&footer)
+            );
+            let _ = writeln!(
+                page,
+                "BT /F1 8 Tf 0.4 0.4 0.4 rg {} {} Td ({}) Tj ET",
+                PAGE_WIDTH - MARGIN - text_width(&number, Font::Regular, 8.0),
+                MARGIN - 10.0,
+                number
+            );
+        }
+
+        // Objects 1-4 are the catalog, page tree and fonts; each page then takes two objects,
+        // the page and its content stream, and the document information dictionary comes last.
+        let mut objects = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                (0..page_count)
+                    .map(|i| format!("{} 0 R", 5 + 2 * i))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                page_count
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        ];
+        for (index, content) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    6 + 2 * index
+                )
+                .into_bytes(),
+            );
+            let content = encode(content);
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend(content);
+            stream.extend(b"\nendstream");
+            objects.push(stream);
+        }
+        let mut info = b"<< /Title (".to_vec();
+        info.extend(encode(&escape(&self.title)));
+        info.extend(b") /Producer (pet-api) >>");
+        objects.push(info);
+
+        let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+            pdf.extend(object);
+            pdf.extend(b"\nendobj\n");
+        }
+        let xref = pdf.len();
+        pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                objects.len(),
+                xref
+            )
+            .into_bytes(),
+        );
+        pdf
+    }
+}
+
+/// Escapes the delimiters of a PDF literal string.
+fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .flat_map(|c| match c {
+            '(' | ')' | '\\' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
+
+/// Encodes content as WinAnsi bytes, the encoding the fonts are declared with.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20ac}' => 0x80,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            c if (c as u32) < 0x80 || (0xa0..=0xff).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
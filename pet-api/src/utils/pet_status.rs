@@ -0,0 +1,127 @@
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+
+use crate::models::pet_model::{PetDeathModel, PetStatusModel};
+use crate::schemas::pet_schema::{PetStatus, UpdatePetStatus};
+use crate::utils::rules::RuleViolation;
+
+/// Longest cause of death the pet_death table holds.
+const MAX_CAUSE_LENGTH: usize = 200;
+
+/// Longest consent signatory the pet_death table holds.
+const MAX_SIGNATORY_LENGTH: usize = 100;
+
+/// Moves the pet to its new status. Marking it deceased records its death and, unless told
+/// otherwise, makes it memorial-safe; a deceased pet stays deceased and its death record can't
+/// be rewritten.
+pub(crate) fn apply_status_change(
+    status: &mut PetStatusModel,
+    change: UpdatePetStatus,
+    birth_date: NaiveDate,
+    today: NaiveDate,
+    now: i64,
+) -> Result<(), RuleViolation> {
+    // The column's CHECK constraint only admits the four statuses.
+    let current = PetStatus::parse(&status.pet_status).unwrap_or(PetStatus::Active);
+    let next = change.status.unwrap_or(current);
+    let death_given = change.date_of_death.is_some()
+        || change.cause_of_death.is_some()
+        || change.euthanasia.is_some();
+
+    if current == PetStatus::Deceased {
+        if next != PetStatus::Deceased {
+            return Err(conflict("A deceased pet's status can't be changed"));
+        }
+        if death_given {
+            return Err(conflict("The pet's death is already recorded"));
+        }
+    } else if next == PetStatus::Deceased {
+        let death = new_death(&change, birth_date, today, now)?;
+        status.status_changed_on = Some(death.date_of_death);
+        status.memorial_safe = true;
+        status.death = Some(death);
+    } else if death_given {
+        return Err(bad_request(
+            "Only a deceased pet has a date or cause of death or a euthanasia record",
+        ));
+    } else if next != current {
+        status.status_changed_on = Some(today);
+    }
+
+    status.pet_status = next.as_str().to_string();
+    if let Some(memorial_safe) = change.memorial_safe {
+        status.memorial_safe = memorial_safe;
+    }
+    Ok(())
+}
+
+fn new_death(
+    change: &UpdatePetStatus,
+    birth_date: NaiveDate,
+    today: NaiveDate,
+    now: i64,
+) -> Result<PetDeathModel, RuleViolation> {
+    let Some(date_of_death) = change.date_of_death else {
+        return Err(bad_request("date_of_death is required for a deceased pet"));
+    };
+    if date_of_death > today {
+        return Err(bad_request("date_of_death cannot be in the future"));
+    }
+    if date_of_death < birth_date {
+        return Err(bad_request(
+            "date_of_death cannot be before the pet was born",
+        ));
+    }
+    let cause_of_death = change
+        .cause_of_death
+        .as_ref()
+        .map(|cause| cause.trim().to_string())
+        .filter(|cause| !cause.is_empty());
+    if cause_of_death
+        .as_ref()
+        .is_some_and(|cause| cause.chars().count() > MAX_CAUSE_LENGTH)
+    {
+        return Err(bad_request(&format!(
+            "cause_of_death is limited to {} characters",
+            MAX_CAUSE_LENGTH
+        )));
+    }
+
+    let mut death = PetDeathModel {
+        date_of_death,
+        cause_of_death,
+        euthanasia: false,
+        consent_given_by: None,
+        consent_given_on: None,
+        vet_id: None,
+        recorded_at: now,
+    };
+    if let Some(euthanasia) = &change.euthanasia {
+        let consent_given_by = euthanasia.consent_given_by.trim();
+        if consent_given_by.is_empty() || consent_given_by.chars().count() > MAX_SIGNATORY_LENGTH {
+            return Err(bad_request(&format!(
+                "consent_given_by must be 1 to {} characters",
+                MAX_SIGNATORY_LENGTH
+            )));
+        }
+        let consent_given_on = euthanasia.consent_given_on.unwrap_or(date_of_death);
+        if consent_given_on > date_of_death {
+            return Err(bad_request(
+                "Euthanasia consent must be given on or before the date of death",
+            ));
+        }
+        death.euthanasia = true;
+        death.consent_given_by = Some(consent_given_by.to_string());
+        death.consent_given_on = Some(consent_given_on);
+        death.vet_id = Some(euthanasia.vet_id.clone());
+    }
+    Ok(death)
+}
+
+fn bad_request(message: &str) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message.to_string())
+}
+
+fn conflict(message: &str) -> RuleViolation {
+    (StatusCode::CONFLICT, message.to_string())
+}
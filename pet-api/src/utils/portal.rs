@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::config::ClinicBranding;
+use crate::utils::mailer::Email;
+use crate::AppState;
+
+/// How long an emailed sign-in link works, in seconds.
+pub(crate) const LOGIN_LINK_TTL: i64 = 15 * 60;
+/// How long a portal session lasts after signing in, in seconds.
+pub(crate) const SESSION_TTL: i64 = 7 * 24 * 60 * 60;
+
+/// A random token of 64 hex digits. Only its hash is stored.
+pub(crate) fn new_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub(crate) fn token_hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.trim().as_bytes()))
+}
+
+pub(crate) fn login_link_email(
+    clinic: &ClinicBranding,
+    owner_name: &str,
+    owner_email: &str,
+    link: &str,
+) -> Email {
+    Email {
+        to: owner_email.to_string(),
+        subject: format!("Sign in to the {} owner portal", clinic.name),
+        body: format!(
+            "Hello {},\n\nUse this link to see your pets' records at {}:\n\n{}\n\n\
+            The link works once and expires in {} minutes. If you did not ask to sign in, \
+            you can ignore this email.",
+            owner_name,
+            clinic.name,
+            link,
+            LOGIN_LINK_TTL / 60
+        ),
+    }
+}
+
+/// The owner signed in to the portal, from the `Authorization: Bearer` session token.
+/// Portal handlers read and change only this owner's records.
+pub(crate) struct PortalOwner {
+    pub owner_id: String,
+    pub clinic_id: String,
+    pub token_hash: String,
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for PortalOwner {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim())
+            .filter(|token| !token.is_empty())
+            .ok_or_else(unauthorized)?;
+
+        let token_hash = token_hash(token);
+        match state
+            .db
+            .portal()
+            .select_session_owner(&token_hash, chrono::Utc::now().timestamp())
+            .await
+        {
+            Ok(Some((owner_id, clinic_id))) => Ok(PortalOwner {
+                owner_id,
+                clinic_id,
+                token_hash,
+            }),
+            Ok(None) => Err(unauthorized()),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"status": "error", "message": format!("{:?}", e)})),
+            )
+                .into_response()),
+        }
+    }
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(WWW_AUTHENTICATE, "Bearer")],
+        Json(json!({
+            "status": "fail",
+            "message": "Sign in to the owner portal to continue",
+        })),
+    )
+        .into_response()
+}
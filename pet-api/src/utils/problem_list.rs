@@ -0,0 +1,179 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use chrono::NaiveDate;
+
+use crate::models::problem_model::{PetAlert, PetProblemModel};
+use crate::schemas::problem_schema::{AddPetProblem, ProblemType, UpdatePetProblem};
+use crate::utils::rules::RuleViolation;
+
+/// Longest description the pet_problem table holds.
+const MAX_DESCRIPTION_LENGTH: usize = 200;
+
+pub(crate) fn new_problem(
+    pet_id: String,
+    problem: AddPetProblem,
+    today: NaiveDate,
+    now: i64,
+) -> Result<PetProblemModel, RuleViolation> {
+    let problem = PetProblemModel {
+        problem_id: 0,
+        pet_id,
+        problem_type: problem.problem_type.as_str().to_string(),
+        description: problem.description.trim().to_string(),
+        severity: problem.severity.as_str().to_string(),
+        onset_date: optional_date("onset_date", problem.onset_date)?,
+        resolved_date: None,
+        notes: clean(problem.notes),
+        recorded_at: now,
+        updated_at: now,
+    };
+    validate(&problem, today)?;
+    Ok(problem)
+}
+
+pub(crate) fn apply_update(
+    problem: &mut PetProblemModel,
+    update: UpdatePetProblem,
+    today: NaiveDate,
+    now: i64,
+) -> Result<(), RuleViolation> {
+    if let Some(description) = update.description {
+        problem.description = description.trim().to_string();
+    }
+    if let Some(severity) = update.severity {
+        problem.severity = severity.as_str().to_string();
+    }
+    if update.onset_date.is_some() {
+        problem.onset_date = optional_date("onset_date", update.onset_date)?;
+    }
+    if update.resolved_date.is_some() {
+        problem.resolved_date = optional_date("resolved_date", update.resolved_date)?;
+    }
+    if update.notes.is_some() {
+        problem.notes = clean(update.notes);
+    }
+    problem.updated_at = now;
+    validate(problem, today)
+}
+
+/// Active problems first, then the most severe, then in the order they were recorded.
+pub(crate) fn sort_problems(problems: &mut [PetProblemModel]) {
+    problems.sort_by_key(|problem| {
+        (
+            !problem.is_active(),
+            Reverse(problem.severity()),
+            problem.problem_id,
+        )
+    });
+}
+
+/// The badges for the given pet's unresolved problems, most severe first.
+pub(crate) fn alerts(mut problems: Vec<PetProblemModel>) -> Vec<PetAlert> {
+    problems.retain(PetProblemModel::is_active);
+    sort_problems(&mut problems);
+    problems.iter().map(PetProblemModel::alert).collect()
+}
+
+/// Splits unresolved problems of several pets into each pet's badges.
+pub(crate) fn alerts_by_pet(problems: Vec<PetProblemModel>) -> HashMap<String, Vec<PetAlert>> {
+    let mut by_pet: HashMap<String, Vec<PetProblemModel>> = HashMap::new();
+    for problem in problems {
+        by_pet
+            .entry(problem.pet_id.clone())
+            .or_default()
+            .push(problem);
+    }
+
+    by_pet
+        .into_iter()
+        .map(|(pet_id, problems)| (pet_id, alerts(problems)))
+        .collect()
+}
+
+/// A warning for every treatment that names one of the pet's unresolved allergies.
+pub(crate) fn allergy_warnings(problems: &[PetProblemModel], treatments: &[String]) -> Vec<String> {
+    let allergies: Vec<_> = problems
+        .iter()
+        .filter(|problem| {
+            problem.is_active() && problem.problem_type == ProblemType::Allergy.as_str()
+        })
+        .collect();
+
+    treatments
+        .iter()
+        .flat_map(|treatment| {
+            let lowered = treatment.to_lowercase();
+            allergies
+                .iter()
+                .filter(move |allergy| lowered.contains(&allergy.description.to_lowercase()))
+                .map(move |allergy| {
+                    format!(
+                        "{} matches a recorded {} allergy to {}",
+                        treatment.trim(),
+                        allergy.severity,
+                        allergy.description
+                    )
+                })
+        })
+        .collect()
+}
+
+/// The warnings as `Warning: 299` headers, for responses whose body has no room for them.
+pub(crate) fn warning_headers(warnings: &[String]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for warning in warnings {
+        let value = format!("299 - \"{}\"", warning.replace('"', "'"));
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.append("warning", value);
+        }
+    }
+    headers
+}
+
+fn validate(problem: &PetProblemModel, today: NaiveDate) -> Result<(), RuleViolation> {
+    if problem.description.is_empty() {
+        return Err(bad_request("Description cannot be empty".to_string()));
+    }
+    if problem.description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(bad_request(format!(
+            "Description is longer than {} characters",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+    if problem.onset_date.is_some_and(|onset| onset > today) {
+        return Err(bad_request("onset_date is in the future".to_string()));
+    }
+    if let Some(resolved) = problem.resolved_date {
+        if resolved > today {
+            return Err(bad_request("resolved_date is in the future".to_string()));
+        }
+        if problem.onset_date.is_some_and(|onset| resolved < onset) {
+            return Err(bad_request(
+                "resolved_date is before onset_date".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses an optional `YYYY-MM-DD` date, treating a blank value as none.
+fn optional_date(name: &str, value: Option<String>) -> Result<Option<NaiveDate>, RuleViolation> {
+    match clean(value) {
+        None => Ok(None),
+        Some(value) => NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| bad_request(format!("Invalid {}: expected YYYY-MM-DD", name))),
+    }
+}
+
+fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn bad_request(message: String) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message)
+}
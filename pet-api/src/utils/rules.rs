@@ -0,0 +1,4 @@
+use axum::http::StatusCode;
+
+/// A request the domain rules don't allow, with the status to answer it with.
+pub(crate) type RuleViolation = (StatusCode, String);
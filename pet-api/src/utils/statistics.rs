@@ -0,0 +1,389 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::models::statistics_model::{
+    DailyCount, DailyServiceTypeCount, MetricChange, PeriodComparison, ProcedureCounts,
+    ServiceTypeChange, ServiceTypeCount, SpeciesPatientCount, StatisticsBucket, StatisticsTotals,
+    SurgeryCount, SurgeryOutcomes, VetProcedure, VetStatistics, VetWorkload, VetWorkloadBucket,
+    VisitStatistics,
+};
+use crate::models::vet_model::GetVets;
+use crate::schemas::helper_schema::Granularity;
+
+/// Enough for a year of daily buckets.
+pub(crate) const MAX_BUCKETS: i64 = 366;
+
+/// The per-day counts of one period, as the statistics queries return them.
+pub(crate) struct DailyStatistics {
+    pub visits: Vec<DailyCount>,
+    pub new_pets: Vec<DailyCount>,
+    pub new_owners: Vec<DailyCount>,
+    pub service_types: Vec<DailyServiceTypeCount>,
+}
+
+/// 30 days, 12 weeks or 12 months ending with `end_date`'s bucket.
+pub(crate) fn default_start_date(granularity: Granularity, end_date: NaiveDate) -> NaiveDate {
+    match granularity {
+        Granularity::Day => end_date - Days::new(29),
+        Granularity::Week => bucket_start(granularity, end_date) - Days::new(7 * 11),
+        Granularity::Month => bucket_start(granularity, end_date) - Months::new(11),
+    }
+}
+
+pub(crate) fn bucket_count(
+    granularity: Granularity,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> i64 {
+    let first = bucket_start(granularity, start_date);
+    let last = bucket_start(granularity, end_date);
+    match granularity {
+        Granularity::Day => (last - first).num_days() + 1,
+        Granularity::Week => (last - first).num_days() / 7 + 1,
+        Granularity::Month => months_between(first, last) + 1,
+    }
+}
+
+/// The period of the same length that ends the day before `start_date`. A range of whole
+/// months is compared with the same number of whole months, whatever their lengths.
+pub(crate) fn previous_period(
+    granularity: Granularity,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> (NaiveDate, NaiveDate) {
+    let previous_end = start_date - Days::new(1);
+    let whole_months = granularity == Granularity::Month
+        && start_date.day() == 1
+        && (end_date + Days::new(1)).day() == 1;
+    let previous_start = if whole_months {
+        start_date - Months::new(months_between(start_date, end_date) as u32 + 1)
+    } else {
+        previous_end - Days::new((end_date - start_date).num_days() as u64)
+    };
+    (previous_start, previous_end)
+}
+
+/// Buckets the daily counts of `start_date..=end_date`, filling days without records with
+/// zeros, and compares the totals with `previous`.
+pub(crate) fn visit_statistics(
+    granularity: Granularity,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    current: DailyStatistics,
+    previous: DailyStatistics,
+) -> VisitStatistics {
+    let (previous_start_date, previous_end_date) =
+        previous_period(granularity, start_date, end_date);
+    let names = service_type_names(&current.service_types);
+
+    let buckets = bucket_ranges(granularity, start_date, end_date)
+        .into_iter()
+        .map(|(period_start, period_end)| StatisticsBucket {
+            period_start,
+            period_end,
+            totals: totals(&current, &names, period_start, period_end),
+        })
+        .collect();
+
+    let current_totals = totals(&current, &names, start_date, end_date);
+    let previous_totals = totals(&previous, &names, previous_start_date, previous_end_date);
+    let mut compared_names = names;
+    compared_names.extend(service_type_names(&previous.service_types));
+    let service_types = compared_names
+        .into_iter()
+        .map(|name| ServiceTypeChange {
+            change: change(
+                sum_service_type(&current.service_types, &name, start_date, end_date),
+                sum_service_type(
+                    &previous.service_types,
+                    &name,
+                    previous_start_date,
+                    previous_end_date,
+                ),
+            ),
+            service_type_name: name,
+        })
+        .collect();
+
+    VisitStatistics {
+        granularity,
+        start_date,
+        end_date,
+        buckets,
+        comparison: PeriodComparison {
+            previous_start_date,
+            previous_end_date,
+            visits: change(current_totals.visits, previous_totals.visits),
+            new_pets: change(current_totals.new_pets, previous_totals.new_pets),
+            new_owners: change(current_totals.new_owners, previous_totals.new_owners),
+            service_types,
+        },
+        totals: current_totals,
+    }
+}
+
+/// Counts each vet's procedures per bucket, their surgeries and how those went, and the
+/// patients they saw. Vets without procedures in the range are listed with zeros.
+pub(crate) fn vet_statistics(
+    granularity: Granularity,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    vets: Vec<GetVets>,
+    procedures: Vec<VetProcedure>,
+) -> VetStatistics {
+    let ranges = bucket_ranges(granularity, start_date, end_date);
+    let mut vets = vets
+        .into_iter()
+        .map(|vet| {
+            let procedures = procedures
+                .iter()
+                .filter(|procedure| procedure.vet_id == vet.vet_id)
+                .collect::<Vec<_>>();
+            vet_workload(vet, &procedures, (start_date, end_date), &ranges)
+        })
+        .collect::<Vec<_>>();
+    vets.sort_by(|a, b| {
+        b.procedures
+            .procedures
+            .cmp(&a.procedures.procedures)
+            .then_with(|| a.vet_name.cmp(&b.vet_name))
+    });
+
+    VetStatistics {
+        granularity,
+        start_date,
+        end_date,
+        vets,
+    }
+}
+
+fn vet_workload(
+    vet: GetVets,
+    procedures: &[&VetProcedure],
+    range: (NaiveDate, NaiveDate),
+    ranges: &[(NaiveDate, NaiveDate)],
+) -> VetWorkload {
+    let surgeries = procedures
+        .iter()
+        .filter(|procedure| procedure.procedure_type == "surgery")
+        .collect::<Vec<_>>();
+
+    let mut surgeries_by_name = BTreeMap::<&str, (i64, i64)>::new();
+    for surgery in &surgeries {
+        let counts = surgeries_by_name
+            .entry(surgery.surgery_name.as_deref().unwrap_or_default())
+            .or_default();
+        counts.0 += 1;
+        counts.1 += has_complications(surgery.complications.as_deref()) as i64;
+    }
+    let mut surgeries_by_name = surgeries_by_name
+        .into_iter()
+        .map(|(surgery_name, (total, with_complications))| SurgeryCount {
+            surgery_name: surgery_name.to_string(),
+            total,
+            with_complications,
+        })
+        .collect::<Vec<_>>();
+    surgeries_by_name.sort_by_key(|count| std::cmp::Reverse(count.total));
+
+    let with_complications = surgeries
+        .iter()
+        .filter(|surgery| has_complications(surgery.complications.as_deref()))
+        .count() as i64;
+    let outcomes = surgeries
+        .iter()
+        .filter_map(|surgery| is_unfavorable(surgery.outcome.as_deref()))
+        .collect::<Vec<_>>();
+    let unfavorable_outcomes = outcomes.iter().filter(|unfavorable| **unfavorable).count() as i64;
+
+    let mut patients = BTreeMap::<&str, BTreeSet<&str>>::new();
+    for procedure in procedures {
+        patients
+            .entry(&procedure.pet_type)
+            .or_default()
+            .insert(&procedure.pet_id);
+    }
+
+    VetWorkload {
+        vet_id: vet.vet_id,
+        vet_name: vet.vet_name,
+        procedures: procedure_counts(procedures, range),
+        buckets: ranges
+            .iter()
+            .map(|&(period_start, period_end)| VetWorkloadBucket {
+                period_start,
+                period_end,
+                procedures: procedure_counts(procedures, (period_start, period_end)),
+            })
+            .collect(),
+        surgeries_by_name,
+        surgery_outcomes: SurgeryOutcomes {
+            surgeries: surgeries.len() as i64,
+            with_complications,
+            complication_rate: percent(with_complications, surgeries.len() as i64),
+            unfavorable_outcomes,
+            unfavorable_outcome_rate: percent(unfavorable_outcomes, outcomes.len() as i64),
+            unrecorded_outcomes: (surgeries.len() - outcomes.len()) as i64,
+        },
+        patients_by_species: patients
+            .into_iter()
+            .map(|(pet_type, pets)| SpeciesPatientCount {
+                pet_type: pet_type.to_string(),
+                patients: pets.len() as i64,
+            })
+            .collect(),
+    }
+}
+
+fn procedure_counts(
+    procedures: &[&VetProcedure],
+    (from, to): (NaiveDate, NaiveDate),
+) -> ProcedureCounts {
+    let mut counts = ProcedureCounts::default();
+    for procedure in procedures {
+        if !(from..=to).contains(&procedure.service_date) {
+            continue;
+        }
+        if procedure.procedure_type == "surgery" {
+            counts.surgeries += 1;
+        } else {
+            counts.preventive_care += 1;
+        }
+        counts.procedures += 1;
+    }
+    counts
+}
+
+// What vets write in `complications` when there were none.
+const NO_COMPLICATIONS: [&str; 9] = [
+    "",
+    "-",
+    "n/a",
+    "na",
+    "nil",
+    "no",
+    "none",
+    "none noted",
+    "no complications",
+];
+
+fn has_complications(complications: Option<&str>) -> bool {
+    let complications = complications.unwrap_or_default().trim().to_lowercase();
+    !NO_COMPLICATIONS.contains(&complications.trim_end_matches('.'))
+}
+
+const UNFAVORABLE_OUTCOMES: [&str; 7] = [
+    "died", "death", "deceased", "euthan", "fail", "poor", "critical",
+];
+
+/// `None` when no outcome was recorded.
+fn is_unfavorable(outcome: Option<&str>) -> Option<bool> {
+    let outcome = outcome.unwrap_or_default().trim().to_lowercase();
+    if outcome.is_empty() {
+        return None;
+    }
+    Some(
+        UNFAVORABLE_OUTCOMES
+            .iter()
+            .any(|word| outcome.contains(word)),
+    )
+}
+
+/// Splits `start_date..=end_date` into calendar buckets, clipping the first and last.
+fn bucket_ranges(
+    granularity: Granularity,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut ranges = Vec::new();
+    let mut bucket = bucket_start(granularity, start_date);
+    while bucket <= end_date {
+        let next = next_bucket_start(granularity, bucket);
+        ranges.push((bucket.max(start_date), (next - Days::new(1)).min(end_date)));
+        bucket = next;
+    }
+    ranges
+}
+
+fn bucket_start(granularity: Granularity, day: NaiveDate) -> NaiveDate {
+    match granularity {
+        Granularity::Day => day,
+        Granularity::Week => day - Days::new(day.weekday().num_days_from_monday() as u64),
+        Granularity::Month => day.with_day(1).unwrap_or(day),
+    }
+}
+
+fn next_bucket_start(granularity: Granularity, bucket: NaiveDate) -> NaiveDate {
+    match granularity {
+        Granularity::Day => bucket + Days::new(1),
+        Granularity::Week => bucket + Days::new(7),
+        Granularity::Month => bucket + Months::new(1),
+    }
+}
+
+fn months_between(from: NaiveDate, to: NaiveDate) -> i64 {
+    (to.year() as i64 - from.year() as i64) * 12 + to.month() as i64 - from.month() as i64
+}
+
+fn service_type_names(counts: &[DailyServiceTypeCount]) -> BTreeSet<String> {
+    counts
+        .iter()
+        .map(|count| count.service_type_name.clone())
+        .collect()
+}
+
+fn totals(
+    statistics: &DailyStatistics,
+    service_type_names: &BTreeSet<String>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> StatisticsTotals {
+    StatisticsTotals {
+        visits: sum(&statistics.visits, from, to),
+        new_pets: sum(&statistics.new_pets, from, to),
+        new_owners: sum(&statistics.new_owners, from, to),
+        service_types: service_type_names
+            .iter()
+            .map(|name| ServiceTypeCount {
+                service_type_name: name.clone(),
+                total: sum_service_type(&statistics.service_types, name, from, to),
+            })
+            .collect(),
+    }
+}
+
+fn sum(counts: &[DailyCount], from: NaiveDate, to: NaiveDate) -> i64 {
+    counts
+        .iter()
+        .filter(|count| (from..=to).contains(&count.day))
+        .map(|count| count.total)
+        .sum()
+}
+
+fn sum_service_type(
+    counts: &[DailyServiceTypeCount],
+    name: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> i64 {
+    counts
+        .iter()
+        .filter(|count| count.service_type_name == name && (from..=to).contains(&count.day))
+        .map(|count| count.total)
+        .sum()
+}
+
+fn change(current: i64, previous: i64) -> MetricChange {
+    MetricChange {
+        current,
+        previous,
+        difference: current - previous,
+        percent_change: percent(current - previous, previous),
+    }
+}
+
+/// `part` as a percentage of `whole`, to one decimal place.
+fn percent(part: i64, whole: i64) -> Option<f64> {
+    (whole != 0).then(|| (part as f64 * 1000.0 / whole as f64).round() / 10.0)
+}
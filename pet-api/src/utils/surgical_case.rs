@@ -0,0 +1,246 @@
+use axum::http::StatusCode;
+
+use crate::models::surgical_case_model::{
+    AnesthesiaLogEntry, PreOpChecklist, SurgicalCase, SurgicalCaseModel, SurgicalCaseTimeline,
+    SurgicalConsent,
+};
+use crate::schemas::surgical_case_schema::{
+    AddAnesthesiaLogEntry, SurgicalCaseStatus, UpdateSurgicalCase,
+};
+use crate::utils::rules::RuleViolation;
+
+/// How far ahead of the server clock a monitoring entry may be timed, in seconds.
+const CLOCK_SKEW: i64 = 60;
+
+/// The pre-op items still missing before surgery can start.
+pub(crate) fn missing_preop_items(case: &SurgicalCaseModel) -> Vec<&'static str> {
+    [
+        (case.fasting_confirmed, "fasting_confirmed"),
+        (case.bloodwork_reviewed, "bloodwork_reviewed"),
+        (case.iv_access_placed, "iv_access_placed"),
+        (case.surgical_site_prepared, "surgical_site_prepared"),
+        (case.consent_given_by.is_some(), "consent"),
+    ]
+    .into_iter()
+    .filter(|(done, _)| !done)
+    .map(|(_, item)| item)
+    .collect()
+}
+
+pub(crate) fn to_surgical_case(
+    case: SurgicalCaseModel,
+    anesthesia_log: Vec<AnesthesiaLogEntry>,
+) -> SurgicalCase {
+    let status = case.status();
+    let complete = missing_preop_items(&case).is_empty();
+    SurgicalCase {
+        surgery_id: case.surgery_id,
+        status,
+        next_status: status.next(),
+        pre_op: PreOpChecklist {
+            fasting_confirmed: case.fasting_confirmed,
+            bloodwork_reviewed: case.bloodwork_reviewed,
+            iv_access_placed: case.iv_access_placed,
+            surgical_site_prepared: case.surgical_site_prepared,
+            consent: case
+                .consent_given_by
+                .zip(case.consent_given_at)
+                .map(|(given_by, given_at)| SurgicalConsent { given_by, given_at }),
+            notes: case.preop_notes,
+            complete,
+        },
+        anesthesia_log,
+        intraop_notes: case.intraop_notes,
+        postop_instructions: case.postop_instructions,
+        timeline: SurgicalCaseTimeline {
+            planned_at: case.opened_at,
+            started_at: case.started_at,
+            recovery_at: case.recovery_at,
+            discharged_at: case.discharged_at,
+        },
+    }
+}
+
+/// Applies the changes allowed in the case's current status, or rejects all of them.
+pub(crate) fn apply_update(
+    case: &mut SurgicalCaseModel,
+    update: UpdateSurgicalCase,
+    now: i64,
+) -> Result<(), RuleViolation> {
+    let status = case.status();
+    if status == SurgicalCaseStatus::Discharged {
+        return Err(conflict("A discharged surgical case can't be changed"));
+    }
+
+    let changes_preop = update.fasting_confirmed.is_some()
+        || update.bloodwork_reviewed.is_some()
+        || update.iv_access_placed.is_some()
+        || update.surgical_site_prepared.is_some()
+        || update.consent_given_by.is_some()
+        || update.preop_notes.is_some();
+    if changes_preop && status != SurgicalCaseStatus::Planned {
+        return Err(conflict(
+            "The pre-op checklist and consent can't change once surgery has started",
+        ));
+    }
+    if update.intraop_notes.is_some() && status == SurgicalCaseStatus::Planned {
+        return Err(conflict(
+            "Intra-op notes can only be recorded once surgery has started",
+        ));
+    }
+
+    if let Some(consent_given_by) = update.consent_given_by {
+        let consent_given_by = consent_given_by.trim();
+        if consent_given_by.is_empty() {
+            return Err(bad_request("Consent must name who gave it"));
+        }
+        case.consent_given_by = Some(consent_given_by.to_string());
+        case.consent_given_at = Some(now);
+    }
+    case.fasting_confirmed = update.fasting_confirmed.unwrap_or(case.fasting_confirmed);
+    case.bloodwork_reviewed = update.bloodwork_reviewed.unwrap_or(case.bloodwork_reviewed);
+    case.iv_access_placed = update.iv_access_placed.unwrap_or(case.iv_access_placed);
+    case.surgical_site_prepared = update
+        .surgical_site_prepared
+        .unwrap_or(case.surgical_site_prepared);
+    // A blank note clears it.
+    for (field, value) in [
+        (&mut case.preop_notes, update.preop_notes),
+        (&mut case.intraop_notes, update.intraop_notes),
+        (&mut case.postop_instructions, update.postop_instructions),
+    ] {
+        if let Some(value) = value {
+            let value = value.trim();
+            *field = (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the case one step forward if it is ready for it, stamping when it did.
+pub(crate) fn transition(
+    case: &mut SurgicalCaseModel,
+    to: SurgicalCaseStatus,
+    now: i64,
+) -> Result<(), RuleViolation> {
+    let from = case.status();
+    if from.next() != Some(to) {
+        return Err(conflict(&format!(
+            "A surgical case can't move from {} to {}",
+            from.as_str(),
+            to.as_str()
+        )));
+    }
+
+    match to {
+        SurgicalCaseStatus::InProgress => {
+            let missing = missing_preop_items(case);
+            if !missing.is_empty() {
+                return Err(not_ready(format!(
+                    "Surgery can't start until the pre-op checklist is complete; missing: {}",
+                    missing.join(", ")
+                )));
+            }
+            case.started_at = Some(now);
+        }
+        SurgicalCaseStatus::Recovery => {
+            if case.intraop_notes.is_none() {
+                return Err(not_ready(
+                    "Record the intra-op notes before moving to recovery".to_string(),
+                ));
+            }
+            case.recovery_at = Some(now);
+        }
+        SurgicalCaseStatus::Discharged => {
+            if case.postop_instructions.is_none() {
+                return Err(not_ready(
+                    "Write the post-op instructions before discharging".to_string(),
+                ));
+            }
+            case.discharged_at = Some(now);
+        }
+        SurgicalCaseStatus::Planned => {}
+    }
+    case.case_status = to.as_str().to_string();
+
+    Ok(())
+}
+
+/// Checks a monitoring entry and returns when it was recorded.
+pub(crate) fn validate_anesthesia_entry(
+    case: &SurgicalCaseModel,
+    entry: &mut AddAnesthesiaLogEntry,
+    now: i64,
+) -> Result<i64, RuleViolation> {
+    if !matches!(
+        case.status(),
+        SurgicalCaseStatus::InProgress | SurgicalCaseStatus::Recovery
+    ) {
+        return Err(conflict(
+            "Anesthesia can only be logged while surgery is in progress or in recovery",
+        ));
+    }
+
+    for field in [&mut entry.drug, &mut entry.dose, &mut entry.notes] {
+        *field = field
+            .take()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+    }
+    if entry.dose.is_some() && entry.drug.is_none() {
+        return Err(bad_request("A dose needs the drug it was given of"));
+    }
+    let has_vitals = entry.heart_rate.is_some()
+        || entry.respiratory_rate.is_some()
+        || entry.temperature_c.is_some()
+        || entry.spo2.is_some();
+    if entry.drug.is_none() && !has_vitals {
+        return Err(bad_request(
+            "An anesthesia log entry needs a drug or at least one vital sign",
+        ));
+    }
+    for (name, value, min, max) in [
+        ("heart_rate", entry.heart_rate, 1, 400),
+        ("respiratory_rate", entry.respiratory_rate, 1, 200),
+        ("spo2", entry.spo2, 0, 100),
+    ] {
+        if value.is_some_and(|value| !(min..=max).contains(&value)) {
+            return Err(bad_request(&format!(
+                "{} must be between {} and {}",
+                name, min, max
+            )));
+        }
+    }
+    if entry
+        .temperature_c
+        .is_some_and(|temperature| !(20.0..=46.0).contains(&temperature))
+    {
+        return Err(bad_request("temperature_c must be between 20 and 46"));
+    }
+
+    let recorded_at = entry.recorded_at.unwrap_or(now);
+    if recorded_at > now + CLOCK_SKEW {
+        return Err(bad_request("recorded_at is in the future"));
+    }
+    if case
+        .started_at
+        .is_some_and(|started_at| recorded_at < started_at)
+    {
+        return Err(bad_request("recorded_at is before surgery started"));
+    }
+
+    Ok(recorded_at)
+}
+
+fn conflict(message: &str) -> RuleViolation {
+    (StatusCode::CONFLICT, message.to_string())
+}
+
+fn not_ready(message: String) -> RuleViolation {
+    (StatusCode::UNPROCESSABLE_ENTITY, message)
+}
+
+fn bad_request(message: &str) -> RuleViolation {
+    (StatusCode::BAD_REQUEST, message.to_string())
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+
+use crate::utils::portal::PortalOwner;
+use crate::AppState;
+
+/// The clinic a single-clinic deployment keeps all of its records in.
+pub const DEFAULT_CLINIC_ID: &str = "main";
+pub(crate) const CLINIC_ID_HEADER: &str = "x-clinic-id";
+
+/// The clinic a request acts for, and so the only clinic whose records it can see or change.
+///
+/// A signed-in portal owner always acts for their own clinic. Otherwise the clinic is taken from
+/// the `X-Clinic-Id` header, falling back to the default clinic when there is none.
+pub(crate) struct Tenant {
+    pub clinic_id: String,
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Tenant {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if parts.headers.contains_key(AUTHORIZATION) {
+            let owner = PortalOwner::from_request_parts(parts, state).await?;
+            return Ok(Tenant {
+                clinic_id: owner.clinic_id,
+            });
+        }
+
+        let Some(clinic_id) = parts.headers.get(CLINIC_ID_HEADER) else {
+            return Ok(Tenant {
+                clinic_id: DEFAULT_CLINIC_ID.to_string(),
+            });
+        };
+        let clinic_id = clinic_id.to_str().unwrap_or_default().trim();
+        match state.db.clinics().select_clinic(clinic_id).await {
+            Ok(Some(clinic)) => Ok(Tenant {
+                clinic_id: clinic.clinic_id,
+            }),
+            Ok(None) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"status": "fail", "message": "Unknown clinic in X-Clinic-Id"})),
+            )
+                .into_response()),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"status": "error", "message": format!("{:?}", e)})),
+            )
+                .into_response()),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Days, NaiveDate};
+use common::fixtures::{OwnerBuilder, PetBuilder};
+use common::TestApp;
+use serde_json::{json, Value};
+
+fn day(offset: u64) -> NaiveDate {
+    chrono::Local::now().date_naive() + Days::new(offset)
+}
+
+async fn add_kennel(app: &TestApp, kennel_name: &str, capacity: i32, species: &str) -> String {
+    let (status, body) = app
+        .post(
+            "/api/v1/boarding/kennels",
+            json!({ "kennel_name": kennel_name, "capacity": capacity, "species": species }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    body["kennel"]["kennel_id"].as_str().unwrap().to_string()
+}
+
+async fn reserve(
+    app: &TestApp,
+    pet_id: &str,
+    kennel_id: &str,
+    nights: (u64, u64),
+    extra: Value,
+) -> (StatusCode, Value) {
+    let mut body = json!({
+        "pet_id": pet_id,
+        "kennel_id": kennel_id,
+        "check_in_date": day(nights.0).to_string(),
+        "check_out_date": day(nights.1).to_string(),
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    app.post("/api/v1/boarding/stays", body).await
+}
+
+fn created_stay(response: (StatusCode, Value)) -> String {
+    let (status, body) = response;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    body["stay"]["stay_id"].as_str().unwrap().to_string()
+}
+
+async fn move_stay(app: &TestApp, stay_id: &str, status: &str) -> (StatusCode, Value) {
+    app.post(
+        &format!("/api/v1/boarding/stays/{}/transitions", stay_id),
+        json!({ "status": status }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn reservations_respect_species_and_capacity() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let luna = PetBuilder::new(&owner_id)
+        .name("Luna")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let kiki = PetBuilder::new(&owner_id)
+        .name("Kiki")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let cattery = add_kennel(&app, "Cattery 1", 1, "Cat").await;
+    let run = add_kennel(&app, "Run 1", 2, "Any").await;
+
+    let (status, body) = reserve(&app, &rex, &cattery, (1, 3), json!({})).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Cattery 1 is for cats only");
+
+    let (status, body) = reserve(&app, &luna, &cattery, (3, 3), json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "check_out_date must be after check_in_date"
+    );
+
+    created_stay(reserve(&app, &luna, &cattery, (1, 4), json!({})).await);
+    let (status, body) = reserve(&app, &kiki, &cattery, (3, 5), json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        format!("Cattery 1 is fully booked the night of {}", day(3))
+    );
+
+    // Luna leaves on the morning Kiki arrives.
+    created_stay(reserve(&app, &kiki, &cattery, (4, 6), json!({})).await);
+
+    let (status, body) = reserve(&app, &luna, &run, (2, 3), json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Luna already stays in Cattery 1"));
+
+    let (status, body) = app
+        .post(
+            "/api/v1/boarding/kennels",
+            json!({ "kennel_name": "Run 1", "capacity": 1, "species": "Dog" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{}", body);
+}
+
+#[tokio::test]
+async fn occupancy_calendar_shows_each_night() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let max = PetBuilder::new(&owner_id).name("Max").create(&app).await;
+    let run = add_kennel(&app, "Run 1", 2, "Dog").await;
+    let rex_stay = created_stay(reserve(&app, &rex, &run, (0, 2), json!({})).await);
+    let max_stay = created_stay(reserve(&app, &max, &run, (1, 3), json!({})).await);
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/v1/boarding/occupancy?start_date={}&end_date={}",
+            day(0),
+            day(3)
+        ))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let nights = body["kennels"][0]["nights"].as_array().unwrap();
+    let occupied: Vec<_> = nights
+        .iter()
+        .map(|night| night["occupied"].as_i64().unwrap())
+        .collect();
+    assert_eq!(occupied, [1, 2, 1, 0]);
+    assert_eq!(nights[1]["available"], 0);
+    assert_eq!(nights[1]["stay_ids"], json!([rex_stay, max_stay]));
+
+    let (status, body) = app
+        .get(&format!("/api/v1/boarding/stays?night={}", day(2)))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stays"].as_array().unwrap().len(), 1);
+    assert_eq!(body["stays"][0]["pet_name"], "Max");
+
+    let (status, _) = app
+        .get(&format!(
+            "/api/v1/boarding/occupancy?start_date={}&end_date={}",
+            day(0),
+            day(90)
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn a_stay_is_checked_in_cared_for_and_checked_out() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let run = add_kennel(&app, "Run 1", 1, "Dog").await;
+    let stay_id = created_stay(
+        reserve(
+            &app,
+            &rex,
+            &run,
+            (0, 4),
+            json!({
+                "feeding_instructions": "1 cup twice a day",
+                "medication_instructions": "Half a tablet at night",
+            }),
+        )
+        .await,
+    );
+    let care_log_uri = format!("/api/v1/boarding/stays/{}/care-log", stay_id);
+
+    let (status, _) = app.post(&care_log_uri, json!({ "fed": true })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = move_stay(&app, &stay_id, "checked_in").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["stay"]["stay_status"], "checked_in");
+    assert_eq!(body["next_statuses"], json!(["checked_out"]));
+
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/boarding/stays/{}", stay_id),
+            json!({ "check_in_date": day(1).to_string() }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = app.post(&care_log_uri, json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = app
+        .post(
+            &care_log_uri,
+            json!({ "fed": true, "medication_given": true, "notes": " Ate everything " }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["care_log"][0]["care_date"], day(0).to_string());
+    assert_eq!(body["care_log"][0]["notes"], "Ate everything");
+
+    let (status, body) = move_stay(&app, &stay_id, "checked_out").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    // Picked up early, so the rest of the nights are free again.
+    assert_eq!(body["stay"]["check_out_date"], day(1).to_string());
+    let (status, _) = move_stay(&app, &stay_id, "checked_in").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let max = PetBuilder::new(&owner_id).name("Max").create(&app).await;
+    created_stay(reserve(&app, &max, &run, (1, 4), json!({})).await);
+}
+
+#[tokio::test]
+async fn cancelling_or_moving_a_reservation_frees_its_nights() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let max = PetBuilder::new(&owner_id).name("Max").create(&app).await;
+    let run_1 = add_kennel(&app, "Run 1", 1, "Dog").await;
+    let run_2 = add_kennel(&app, "Run 2", 1, "Dog").await;
+    let rex_stay = created_stay(reserve(&app, &rex, &run_1, (2, 5), json!({})).await);
+    let max_stay = created_stay(reserve(&app, &max, &run_2, (2, 5), json!({})).await);
+
+    let uri = format!("/api/v1/boarding/stays/{}", max_stay);
+    let (status, _) = app.patch(&uri, json!({ "kennel_id": run_1 })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = move_stay(&app, &rex_stay, "checked_in").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    let (status, body) = move_stay(&app, &rex_stay, "cancelled").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["next_statuses"], json!([]));
+
+    let (status, body) = app.patch(&uri, json!({ "kennel_id": run_1 })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["stay"]["kennel_name"], "Run 1");
+
+    let (status, body) = app
+        .get(&format!("/api/v1/boarding/stays?kennel_id={}", run_1))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stays"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_reservations_cannot_overbook_a_kennel() {
+    let app = TestApp::spawn_on_file().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let run = add_kennel(&app, "Run 1", 1, "Any").await;
+
+    let mut pets = Vec::new();
+    for pet_name in ["Rex", "Bantay", "Choco", "Max", "Brownie", "Tagpi"] {
+        pets.push(PetBuilder::new(&owner_id).name(pet_name).create(&app).await);
+    }
+
+    let mut reservations = Vec::new();
+    for pet_id in pets {
+        let (app, run) = (app.clone(), run.clone());
+        reservations.push(tokio::spawn(async move {
+            reserve(&app, &pet_id, &run, (1, 3), json!({})).await.0
+        }));
+    }
+    let mut statuses = Vec::new();
+    for reservation in reservations {
+        statuses.push(reservation.await.unwrap());
+    }
+
+    assert_eq!(
+        statuses
+            .iter()
+            .filter(|status| **status == StatusCode::CREATED)
+            .count(),
+        1,
+        "{:?}",
+        statuses
+    );
+    assert!(statuses
+        .iter()
+        .all(|status| *status == StatusCode::CREATED || *status == StatusCode::CONFLICT));
+}
+
+#[tokio::test]
+async fn page_zero_lists_the_first_page() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let run = add_kennel(&app, "Run 1", 1, "Any").await;
+    created_stay(reserve(&app, &rex, &run, (1, 3), json!({})).await);
+
+    let (status, body) = app.get("/api/v1/boarding/stays?page=0").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["stays"][0]["pet_name"], "Rex");
+}
+
+#[tokio::test]
+async fn pages_and_limits_out_of_range_are_rejected() {
+    let app = TestApp::spawn().await;
+
+    for query in ["page=18446744073709551615", "limit=0", "limit=4294967296"] {
+        let (status, _) = app.get(&format!("/api/v1/boarding/stays?{}", query)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+    }
+}
@@ -0,0 +1,325 @@
+mod common;
+
+use std::path::Path;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Method, StatusCode};
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::{temp_db_path, TestApp};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+const SIGNING_KEY: &str = "test-certificate-signing-key";
+
+async fn spawn() -> TestApp {
+    TestApp::spawn_with(|state| state.with_certificate_signing_key(SIGNING_KEY)).await
+}
+
+async fn spawn_at(db_path: &Path) -> TestApp {
+    TestApp::spawn_at_with(db_path, |state| {
+        state.with_certificate_signing_key(SIGNING_KEY)
+    })
+    .await
+}
+
+struct VaccinatedPet {
+    pet_id: String,
+    vet_id: String,
+}
+
+async fn vaccinated_pet(app: &TestApp) -> VaccinatedPet {
+    let owner_id = OwnerBuilder::new().name("Ana Reyes").create(app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Mochi").create(app).await;
+    let vet_id = VetBuilder::new().name("Dr. Lim").create(app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .preventive_care(&vet_id, &["Rabies Vaccine", "Deworming"])
+        .create(app)
+        .await;
+    VaccinatedPet { pet_id, vet_id }
+}
+
+async fn issue(app: &TestApp, pet_id: &str, body: Value) -> (StatusCode, Value) {
+    app.post(&format!("/api/v1/pets/{}/certificates", pet_id), body)
+        .await
+}
+
+#[tokio::test]
+async fn issues_a_numbered_vaccination_certificate_that_verifies_publicly() {
+    let app = spawn().await;
+    let pet = vaccinated_pet(&app).await;
+
+    let (status, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let certificate = &body["certificate"];
+    assert_eq!(certificate["certificate_number"], "VC-000001");
+    assert_eq!(certificate["content"]["pet"]["pet_name"], "Mochi");
+    assert_eq!(certificate["content"]["issued_by"]["vet_name"], "Dr. Lim");
+    let treatments = certificate["content"]["treatments"].as_array().unwrap();
+    assert_eq!(treatments.len(), 2);
+    assert_eq!(treatments[0]["treatment"], "Rabies Vaccine");
+
+    let code = certificate["verification_code"].as_str().unwrap();
+    assert_eq!(code.len(), 14);
+    let (status, body) = app
+        .get(&format!(
+            "/api/v1/certificates/verify/{}",
+            code.to_lowercase().replace('-', "")
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let verification = &body["verification"];
+    assert_eq!(verification["status"], "valid");
+    assert_eq!(verification["certificate_number"], "VC-000001");
+    assert_eq!(verification["content"], certificate["content"]);
+    assert_eq!(
+        verification["content_digest"],
+        certificate["content_digest"]
+    );
+    assert!(verification.get("pet_id").is_none());
+    assert!(!body.to_string().contains("owner_email"));
+
+    let (status, _) = app.get("/api/v1/certificates/verify/0000-0000-0000").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn lists_chosen_records_only_and_requires_remarks_for_health_certificates() {
+    let app = spawn().await;
+    let pet = vaccinated_pet(&app).await;
+
+    let (status, _) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "health", "vet_id": pet.vet_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({
+            "certificate_type": "health",
+            "vet_id": pet.vet_id,
+            "preventive_care_ids": [],
+            "remarks": "Healthy and fit to travel.",
+            "valid_until": "2999-12-31",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["certificate"]["certificate_number"], "HC-000001");
+    assert_eq!(body["certificate"]["content"]["treatments"], json!([]));
+    assert_eq!(body["certificate"]["content"]["valid_until"], "2999-12-31");
+
+    let (status, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({
+            "certificate_type": "vaccination",
+            "vet_id": pet.vet_id,
+            "preventive_care_ids": [999],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Preventive care 999 is not a record of this pet"
+    );
+
+    let (_, body) = app
+        .get(&format!("/api/v1/pets/{}/certificates", pet.pet_id))
+        .await;
+    assert_eq!(body["certificates"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn rejects_pets_without_records_and_unknown_vets() {
+    let app = spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let (status, body) = issue(
+        &app,
+        &pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": vet_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "The pet has no preventive care records to certify"
+    );
+
+    let (status, _) = issue(
+        &app,
+        &pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": "missing" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, _) = issue(
+        &app,
+        "missing",
+        json!({ "certificate_type": "vaccination", "vet_id": vet_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn a_revoked_certificate_verifies_as_revoked() {
+    let app = spawn().await;
+    let pet = vaccinated_pet(&app).await;
+    let (_, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    let certificate_id = &body["certificate"]["certificate_id"];
+    let code = body["certificate"]["verification_code"].as_str().unwrap();
+
+    let revoke_uri = format!("/api/v1/certificates/{}/revoke", certificate_id);
+    let (status, _) = app.post(&revoke_uri, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.post(&revoke_uri, json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, body) = app
+        .get(&format!("/api/v1/certificates/verify/{}", code))
+        .await;
+    assert_eq!(body["verification"]["status"], "revoked");
+}
+
+#[tokio::test]
+async fn rewriting_the_content_and_its_digest_still_verifies_as_tampered() {
+    let db_path = temp_db_path();
+    let app = spawn_at(&db_path).await;
+    let pet = vaccinated_pet(&app).await;
+    let (_, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    let certificate_id = body["certificate"]["certificate_id"].as_i64().unwrap();
+    let code = body["certificate"]["verification_code"].as_str().unwrap();
+
+    let db = SqlitePool::connect(db_path.to_str().unwrap())
+        .await
+        .unwrap();
+    let content: String =
+        sqlx::query_scalar("SELECT content FROM certificate WHERE certificate_id = ?")
+            .bind(certificate_id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+    let forged = content.replace("Mochi", "Bantay");
+    sqlx::query("UPDATE certificate SET content = ?, content_digest = ? WHERE certificate_id = ?")
+        .bind(&forged)
+        .bind(format!("{:x}", Sha256::digest(forged.as_bytes())))
+        .bind(certificate_id)
+        .execute(&db)
+        .await
+        .unwrap();
+
+    let (_, body) = app
+        .get(&format!("/api/v1/certificates/verify/{}", code))
+        .await;
+    assert_eq!(body["verification"]["status"], "tampered");
+    assert_eq!(body["verification"]["content"]["pet"]["pet_name"], "Bantay");
+}
+
+#[tokio::test]
+async fn renders_the_certificate_as_a_pdf_with_its_verification_code() {
+    let app = spawn().await;
+    let pet = vaccinated_pet(&app).await;
+    let (_, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    let certificate = &body["certificate"];
+
+    let response = app
+        .response(
+            Method::GET,
+            &format!(
+                "/api/v1/certificates/{}/certificate.pdf",
+                certificate["certificate_id"]
+            ),
+            None,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/pdf");
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let pdf = String::from_utf8_lossy(&bytes);
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.contains("(Vaccination Certificate)"));
+    assert!(pdf.contains("(Certificate No. VC-000001)"));
+    assert!(pdf.contains(certificate["verification_code"].as_str().unwrap()));
+    assert!(pdf.contains(
+        certificate["content"]["issued_by"]["vet_license_number"]
+            .as_str()
+            .unwrap()
+    ));
+
+    let (status, _) = app.get("/api/v1/certificates/999/certificate.pdf").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn certificates_still_verify_after_a_restart_with_the_same_key() {
+    let db_path = temp_db_path();
+    let app = spawn_at(&db_path).await;
+    let pet = vaccinated_pet(&app).await;
+    let (_, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    let code = body["certificate"]["verification_code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    drop(app);
+
+    let restarted = spawn_at(&db_path).await;
+    let (status, body) = restarted
+        .get(&format!("/api/v1/certificates/verify/{}", code))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["verification"]["status"], "valid");
+}
+
+#[tokio::test]
+async fn certificates_are_refused_without_a_signing_key() {
+    let app = TestApp::spawn().await;
+    let pet = vaccinated_pet(&app).await;
+
+    let (status, body) = issue(
+        &app,
+        &pet.pet_id,
+        json!({ "certificate_type": "vaccination", "vet_id": pet.vet_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["message"], "Certificate signing is not configured");
+
+    let (status, _) = app.get("/api/v1/certificates/verify/7QK2-M9XD-4HPA").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, StaffBuilder,
+};
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn clinics_only_see_their_own_records() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, body) = as_clinic(&app, &north, Method::GET, "/api/v1/owners", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owners"].as_array().unwrap().len(), 0);
+
+    let uri = format!("/api/v1/pets/{}", pet_id);
+    let (status, _) = as_clinic(&app, &north, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/pets",
+        Some(PetBuilder::new(&owner_id).body()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Owner not found");
+
+    let (status, _) = app.get(&uri).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn an_unknown_clinic_header_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = as_clinic(&app, "nowhere", Method::GET, "/api/v1/pets", None).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Unknown clinic in X-Clinic-Id");
+
+    let (status, body) = app.get("/api/v1/clinics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["clinics"][0]["clinic_id"], "main");
+}
+
+#[tokio::test]
+async fn a_shared_pet_can_be_read_but_not_changed_by_the_other_clinic() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Bantay").create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    let shares_uri = format!("/api/v1/pets/{}/shares", pet_id);
+
+    let (status, body) = app.post(&shares_uri, json!({ "clinic_id": "main" })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "A pet can't be shared with its own clinic");
+
+    let (status, body) = app.post(&shares_uri, json!({ "clinic_id": north })).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["shares"][0]["clinic_name"], "North Branch");
+
+    let (status, _) = app.post(&shares_uri, json!({ "clinic_id": north })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let uri = format!("/api/v1/pets/{}", pet_id);
+    let (status, body) = as_clinic(&app, &north, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet"]["pet_name"], "Bantay");
+
+    let history_uri = format!("/api/v1/pets/{}/service-instances", pet_id);
+    let (status, body) = as_clinic(&app, &north, Method::GET, &history_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"].as_array().unwrap().len(), 1);
+
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::PATCH,
+        &uri,
+        Some(json!({ "pet_name": "Changed" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = as_clinic(&app, &north, Method::GET, &shares_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app.delete(&format!("{}/{}", shares_uri, north)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["shares"].as_array().unwrap().len(), 0);
+    let (status, _) = as_clinic(&app, &north, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn statistics_are_per_clinic() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Checkup"])
+        .create(&app)
+        .await;
+
+    let (_, body) = app.get("/api/v1/statistics/services").await;
+    assert_eq!(body["services"].as_array().unwrap().len(), 1);
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::GET,
+        "/api/v1/statistics/services",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["services"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn owner_and_staff_contacts_are_unique_per_clinic() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner = OwnerBuilder::new().email("ana@example.com");
+    let staff_member = StaffBuilder::new("veterinarian").body();
+
+    let (status, body) = app.post("/api/v1/owners", owner.body()).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let (status, body) = app.post("/api/v1/staff", staff_member.clone()).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/owners",
+        Some(owner.body()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/staff",
+        Some(staff_member.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+    let (status, body) = app.post("/api/v1/owners", owner.body()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "Owner already exists");
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/staff",
+        Some(staff_member),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "Staff member already exists");
+}
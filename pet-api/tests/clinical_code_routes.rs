@@ -0,0 +1,276 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{
+    surgery_body, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::{json, Value};
+
+const CATALOGUE: &str = "code,code_type,term,synonyms
+D-GAST,diagnosis,Gastritis,acute gastritis|stomach upset
+D-OTEX,diagnosis,Otitis externa,ear infection
+P-OVH,procedure,Ovariohysterectomy,spay|OVH
+T-RAB,treatment,Rabies vaccination,rabies shot
+";
+
+async fn import_catalogue(app: &TestApp, format: &str, data: &str) -> (StatusCode, Value) {
+    app.post(
+        "/api/v1/clinical-codes/imports",
+        json!({ "format": format, "data": data }),
+    )
+    .await
+}
+
+fn codes(suggestions: &Value) -> Vec<&str> {
+    suggestions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion["code"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn catalogue_imports_from_csv_and_ndjson() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = import_catalogue(&app, "csv", CATALOGUE).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["import"]["codes"], 4);
+    assert_eq!(body["import"]["synonyms"], 10);
+
+    let (status, body) = import_catalogue(
+        &app,
+        "ndjson",
+        r#"{"code":"D-OTEX","code_type":"diagnosis","term":"Otitis externa","synonyms":["otitis","ear infection"]}"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+    let (_, body) = app.get("/api/v1/clinical-codes?q=otitis").await;
+    assert_eq!(codes(&body["codes"]), ["D-OTEX"]);
+}
+
+#[tokio::test]
+async fn catalogue_rows_are_checked_before_import() {
+    let app = TestApp::spawn().await;
+    import_catalogue(&app, "csv", CATALOGUE).await;
+
+    let (status, body) = import_catalogue(
+        &app,
+        "csv",
+        "code,code_type,term\nD-1,diagnosis,Fever\nD-1,diagnosis,Pyrexia\n",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["message"].as_str().unwrap().starts_with("Row 2:"));
+
+    let (status, _) =
+        import_catalogue(&app, "csv", "code,code_type,term\nD-2,symptom,Cough\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = import_catalogue(
+        &app,
+        "csv",
+        "code,code_type,term\nP-OVH,diagnosis,Ovarian disease\n",
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        "Code P-OVH is already catalogued as a procedure"
+    );
+}
+
+#[tokio::test]
+async fn autocomplete_ranks_exact_and_prefix_matches_first() {
+    let app = TestApp::spawn().await;
+    import_catalogue(&app, "csv", CATALOGUE).await;
+
+    let (status, body) = app.get("/api/v1/clinical-codes?q=spay").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(codes(&body["codes"]), ["P-OVH"]);
+    assert_eq!(body["codes"][0]["term"], "Ovariohysterectomy");
+    assert_eq!(body["codes"][0]["matched_synonym"], "spay");
+
+    let (_, body) = app.get("/api/v1/clinical-codes?q=gast").await;
+    assert_eq!(codes(&body["codes"]), ["D-GAST"]);
+    assert_eq!(body["codes"][0]["matched_synonym"], Value::Null);
+
+    let (_, body) = app
+        .get("/api/v1/clinical-codes?q=in&code_type=diagnosis")
+        .await;
+    assert_eq!(codes(&body["codes"]), ["D-OTEX"]);
+
+    let (status, _) = app.get("/api/v1/clinical-codes?q=%20").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn records_are_coded_from_explicit_codes_or_synonyms() {
+    let app = TestApp::spawn().await;
+    import_catalogue(&app, "csv", CATALOGUE).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let mut service_instance = ServiceInstanceBuilder::new(&pet_id)
+        .preventive_care(&vet_id, &["Rabies Shot", "Deworming"])
+        .surgery(&vet_id, "Spay")
+        .body();
+    service_instance["general_diagnosis"] = json!("Ear infection");
+    let (status, body) = app
+        .post("/api/v1/service-instances", service_instance)
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let service_instance_id = body["service_instance_id"].as_str().unwrap();
+
+    let (_, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(body["diagnosis_code"], "D-OTEX");
+    assert_eq!(body["surgery"][0]["surgery_code"], "P-OVH");
+    let treatment_codes = body["preventive_care"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|care| (care["treatment"].clone(), care["treatment_code"].clone()))
+        .collect::<Vec<_>>();
+    assert!(treatment_codes.contains(&(json!("Rabies Shot"), json!("T-RAB"))));
+    assert!(treatment_codes.contains(&(json!("Deworming"), Value::Null)));
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/service-instances/{}", service_instance_id),
+            json!({ "general_diagnosis": "Vomiting, suspected gastritis", "diagnosis_code": "D-GAST" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (_, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(body["diagnosis_code"], "D-GAST");
+}
+
+#[tokio::test]
+async fn unknown_or_mistyped_codes_are_rejected() {
+    let app = TestApp::spawn().await;
+    import_catalogue(&app, "csv", CATALOGUE).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    let surgeries_uri = format!(
+        "/api/v1/service-instances/{}/surgeries",
+        service_instance_id
+    );
+
+    let mut surgery = surgery_body(&vet_id, "Gastrotomy");
+    surgery["surgery_code"] = json!("P-NOPE");
+    let (status, body) = app.post(&surgeries_uri, surgery.clone()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "Unknown clinical code P-NOPE");
+
+    surgery["surgery_code"] = json!("D-GAST");
+    let (status, body) = app.post(&surgeries_uri, surgery).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["error"],
+        "D-GAST is a diagnosis code, not a procedure code"
+    );
+
+    let (status, _) = app
+        .post(
+            &format!(
+                "/api/v1/service-instances/{}/preventive-care",
+                service_instance_id
+            ),
+            json!({
+                "treatment": ["Rabies vaccination", "Deworming"],
+                "treatment_codes": ["T-RAB"],
+                "vet_id": vet_id,
+                "service_instance_id": service_instance_id,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn importing_a_catalogue_codes_earlier_records() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let mut service_instance = ServiceInstanceBuilder::new(&pet_id)
+        .surgery(&vet_id, "OVH")
+        .body();
+    service_instance["general_diagnosis"] = json!(" Stomach upset ");
+    let (status, body) = app
+        .post("/api/v1/service-instances", service_instance)
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let service_instance_id = body["service_instance_id"].as_str().unwrap();
+
+    let (status, body) = import_catalogue(&app, "csv", CATALOGUE).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["import"]["recoded"], 2);
+
+    let (_, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(body["diagnosis_code"], "D-GAST");
+    assert_eq!(body["surgery"][0]["surgery_code"], "P-OVH");
+}
+
+#[tokio::test]
+async fn code_statistics_count_coded_and_uncoded_records() {
+    let app = TestApp::spawn().await;
+    import_catalogue(&app, "csv", CATALOGUE).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    for diagnosis in ["Gastritis", "acute gastritis", "Ear infection", "Healthy"] {
+        let mut service_instance = ServiceInstanceBuilder::new(&pet_id)
+            .surgery(&vet_id, "Spay")
+            .body();
+        service_instance["general_diagnosis"] = json!(diagnosis);
+        let (status, body) = app
+            .post("/api/v1/service-instances", service_instance)
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{}", body);
+    }
+
+    let (status, body) = app.get("/api/v1/statistics/codes").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let coded = body["statistics"]["coded"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|count| {
+            (
+                count["code"].as_str().unwrap(),
+                count["total"].as_i64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(coded, [("D-GAST", 2), ("D-OTEX", 1), ("P-OVH", 4)]);
+    assert_eq!(body["statistics"]["uncoded"][0]["code_type"], "diagnosis");
+    assert_eq!(body["statistics"]["uncoded"][0]["total"], 1);
+
+    let (_, body) = app
+        .get("/api/v1/statistics/codes?code_type=procedure")
+        .await;
+    assert_eq!(body["statistics"]["coded"].as_array().unwrap().len(), 1);
+    assert_eq!(body["statistics"]["uncoded"], json!([]));
+}
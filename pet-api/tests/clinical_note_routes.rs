@@ -0,0 +1,263 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, StaffBuilder,
+    VetBuilder,
+};
+use common::TestApp;
+use serde_json::{json, Value};
+
+async fn add_note(
+    app: &TestApp,
+    service_instance_id: &str,
+    section: &str,
+    author_id: &str,
+    body: &str,
+) -> (StatusCode, Value) {
+    app.post(
+        &format!(
+            "/api/v1/service-instances/{}/clinical-notes",
+            service_instance_id
+        ),
+        json!({ "section": section, "author_id": author_id, "body": body }),
+    )
+    .await
+}
+
+fn bodies(notes: &Value) -> Vec<&str> {
+    notes
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|note| note["body"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn note_entries_are_filed_under_their_soap_section() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let technician_id = StaffBuilder::new("technician")
+        .name("Lea Tan")
+        .create(&app)
+        .await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    let history = "Owner reports **vomiting** since Tuesday.\n\n- off food\n- lethargic";
+
+    let (status, body) = add_note(
+        &app,
+        &service_instance_id,
+        "subjective",
+        &technician_id,
+        &format!("  {}\n", history),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["note"]["body"], history);
+    assert_eq!(body["note"]["author_role"], "technician");
+    add_note(
+        &app,
+        &service_instance_id,
+        "objective",
+        &vet_id,
+        "T 39.4 C, mild dehydration",
+    )
+    .await;
+    add_note(
+        &app,
+        &service_instance_id,
+        "subjective",
+        &vet_id,
+        "No access to toxins.",
+    )
+    .await;
+    add_note(
+        &app,
+        &service_instance_id,
+        "assessment",
+        &vet_id,
+        "Acute gastritis",
+    )
+    .await;
+
+    let notes_uri = format!(
+        "/api/v1/service-instances/{}/clinical-notes",
+        service_instance_id
+    );
+    let (status, body) = app.get(&notes_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        bodies(&body["soap"]["subjective"]),
+        [history, "No access to toxins."]
+    );
+    assert_eq!(
+        bodies(&body["soap"]["objective"]),
+        ["T 39.4 C, mild dehydration"]
+    );
+    assert_eq!(bodies(&body["soap"]["assessment"]), ["Acute gastritis"]);
+    assert_eq!(body["soap"]["plan"], json!([]));
+
+    let (_, body) = app
+        .get(&format!("{}?author_id={}", notes_uri, technician_id))
+        .await;
+    assert_eq!(bodies(&body["soap"]["subjective"]), [history]);
+    assert_eq!(body["soap"]["assessment"], json!([]));
+
+    let (status, body) = add_note(&app, &service_instance_id, "plan", &vet_id, "  ").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Body cannot be empty");
+
+    let receptionist_id = StaffBuilder::new("receptionist")
+        .name("Ana Cruz")
+        .create(&app)
+        .await;
+    let (status, body) = add_note(
+        &app,
+        &service_instance_id,
+        "plan",
+        &receptionist_id,
+        "Recheck",
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "A receptionist can't write clinical notes");
+
+    let (status, _) = add_note(&app, "missing", "plan", &vet_id, "Recheck").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn a_pets_notes_can_be_searched_across_visits() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let first_visit = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/service-instances/{}", first_visit),
+            json!({ "service_date": "2024-03-01" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let second_visit = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+
+    add_note(
+        &app,
+        &first_visit,
+        "subjective",
+        &vet_id,
+        "Vomiting twice overnight",
+    )
+    .await;
+    add_note(
+        &app,
+        &first_visit,
+        "plan",
+        &vet_id,
+        "Bland diet for three days",
+    )
+    .await;
+    add_note(
+        &app,
+        &second_visit,
+        "subjective",
+        &vet_id,
+        "No more vomiting",
+    )
+    .await;
+
+    let search_uri = format!("/api/v1/pets/{}/clinical-notes", pet_id);
+    let (status, body) = app.get(&format!("{}?q=VOMIT", search_uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        bodies(&body["notes"]),
+        ["No more vomiting", "Vomiting twice overnight"]
+    );
+    assert_eq!(body["notes"][1]["service_date"], "2024-03-01");
+
+    let (_, body) = app
+        .get(&format!("{}?section=plan&end_date=2024-12-31", search_uri))
+        .await;
+    assert_eq!(bodies(&body["notes"]), ["Bland diet for three days"]);
+
+    let (_, body) = app.get(&format!("{}?limit=2&page=2", search_uri)).await;
+    assert_eq!(body["total_pages"], 2);
+    assert_eq!(body["notes"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app.get(&format!("{}?start_date=March", search_uri)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Invalid start_date: expected YYYY-MM-DD");
+
+    let (status, body) = app
+        .get(&format!("{}?page=18446744073709551615", search_uri))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "page is out of range");
+
+    let (status, _) = app.get("/api/v1/pets/missing/clinical-notes").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn a_sharing_clinic_reads_the_notes_but_cannot_add_to_them() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    add_note(
+        &app,
+        &service_instance_id,
+        "assessment",
+        &vet_id,
+        "Otitis externa",
+    )
+    .await;
+    app.post(
+        &format!("/api/v1/pets/{}/shares", pet_id),
+        json!({ "clinic_id": north }),
+    )
+    .await;
+    let notes_uri = format!(
+        "/api/v1/service-instances/{}/clinical-notes",
+        service_instance_id
+    );
+
+    let (status, body) = as_clinic(&app, &north, Method::GET, &notes_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bodies(&body["soap"]["assessment"]), ["Otitis externa"]);
+
+    let (_, body) = as_clinic(
+        &app,
+        &north,
+        Method::GET,
+        &format!("/api/v1/pets/{}/clinical-notes?q=otitis", pet_id),
+        None,
+    )
+    .await;
+    assert_eq!(body["notes"].as_array().unwrap().len(), 1);
+
+    let (_, body) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/vets",
+        Some(VetBuilder::new().body()),
+    )
+    .await;
+    let north_vet = body["vet"]["vet_id"].clone();
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        &notes_uri,
+        Some(json!({ "section": "plan", "author_id": north_vet, "body": "Recheck" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
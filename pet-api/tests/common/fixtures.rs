@@ -0,0 +1,339 @@
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+
+use super::{read_json, TestApp};
+
+fn unique_suffix() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+pub struct OwnerBuilder {
+    owner_name: String,
+    owner_email: String,
+    owner_phone_number: String,
+    owner_address: String,
+}
+
+impl OwnerBuilder {
+    pub fn new() -> Self {
+        Self {
+            owner_name: "Jamie Rivera".to_string(),
+            owner_email: format!("owner-{}@example.com", unique_suffix()),
+            owner_phone_number: "09171234567".to_string(),
+            owner_address: "12 Mabini St, Manila".to_string(),
+        }
+    }
+
+    pub fn name(mut self, owner_name: &str) -> Self {
+        self.owner_name = owner_name.to_string();
+        self
+    }
+
+    pub fn email(mut self, owner_email: &str) -> Self {
+        self.owner_email = owner_email.to_string();
+        self
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "owner_name": self.owner_name,
+            "owner_email": self.owner_email,
+            "owner_phone_number": self.owner_phone_number,
+            "owner_address": self.owner_address,
+        })
+    }
+
+    /// Creates the owner and returns its id.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app.post("/api/owner/add_owner", self.body()).await;
+        assert!(status.is_success(), "add_owner failed: {} {}", status, body);
+        body["owner"]["owner_id"].as_str().unwrap().to_string()
+    }
+}
+
+pub struct PetBuilder {
+    pet_name: String,
+    pet_birth_date: String,
+    pet_type: String,
+    pet_breed: String,
+    pet_weight: f32,
+    pet_color: String,
+    owner_id: String,
+}
+
+impl PetBuilder {
+    pub fn new(owner_id: &str) -> Self {
+        Self {
+            pet_name: "Mochi".to_string(),
+            pet_birth_date: "2021-03-14".to_string(),
+            pet_type: "Dog".to_string(),
+            pet_breed: "Shih Tzu".to_string(),
+            pet_weight: 6.5,
+            pet_color: "White".to_string(),
+            owner_id: owner_id.to_string(),
+        }
+    }
+
+    pub fn name(mut self, pet_name: &str) -> Self {
+        self.pet_name = pet_name.to_string();
+        self
+    }
+
+    pub fn pet_type(mut self, pet_type: &str) -> Self {
+        self.pet_type = pet_type.to_string();
+        self
+    }
+
+    pub fn weight(mut self, pet_weight: f32) -> Self {
+        self.pet_weight = pet_weight;
+        self
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "pet_name": self.pet_name,
+            "pet_birth_date": self.pet_birth_date,
+            "pet_type": self.pet_type,
+            "pet_breed": self.pet_breed,
+            "pet_weight": self.pet_weight,
+            "pet_color": self.pet_color,
+            "owner_id": self.owner_id,
+        })
+    }
+
+    /// Creates the pet and returns its id.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app.post("/api/pet/add_pet", self.body()).await;
+        assert!(status.is_success(), "add_pet failed: {} {}", status, body);
+        body["pet"]["pet_id"].as_str().unwrap().to_string()
+    }
+}
+
+pub struct VetBuilder {
+    vet_name: String,
+    vet_email: String,
+    vet_phone_number: String,
+    vet_license_number: String,
+}
+
+impl VetBuilder {
+    pub fn new() -> Self {
+        let suffix = unique_suffix();
+        Self {
+            vet_name: "Dr. Santos".to_string(),
+            vet_email: format!("vet-{}@example.com", suffix),
+            vet_phone_number: "09187654321".to_string(),
+            vet_license_number: format!("LIC-{}", suffix),
+        }
+    }
+
+    pub fn name(mut self, vet_name: &str) -> Self {
+        self.vet_name = vet_name.to_string();
+        self
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "vet_name": self.vet_name,
+            "vet_email": self.vet_email,
+            "vet_phone_number": self.vet_phone_number,
+            "vet_license_number": self.vet_license_number,
+        })
+    }
+
+    /// Creates the vet and returns its id.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app.post("/api/vet/add_vet", self.body()).await;
+        assert!(status.is_success(), "add_vet failed: {} {}", status, body);
+        body["vet"]["vet_id"].as_str().unwrap().to_string()
+    }
+}
+
+pub struct StaffBuilder {
+    staff_name: String,
+    staff_email: String,
+    staff_phone_number: String,
+    staff_role: String,
+    license_number: Option<String>,
+}
+
+impl StaffBuilder {
+    /// A staff member in `staff_role`, licensed if a veterinarian.
+    pub fn new(staff_role: &str) -> Self {
+        let suffix = unique_suffix();
+        Self {
+            staff_name: "Lea Tan".to_string(),
+            staff_email: format!("staff-{}@example.com", suffix),
+            staff_phone_number: "09171234567".to_string(),
+            staff_role: staff_role.to_string(),
+            license_number: (staff_role == "veterinarian").then(|| format!("LIC-{}", suffix)),
+        }
+    }
+
+    pub fn name(mut self, staff_name: &str) -> Self {
+        self.staff_name = staff_name.to_string();
+        self
+    }
+
+    pub fn email(mut self, staff_email: &str) -> Self {
+        self.staff_email = staff_email.to_string();
+        self
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "staff_name": self.staff_name,
+            "staff_email": self.staff_email,
+            "staff_phone_number": self.staff_phone_number,
+            "staff_role": self.staff_role,
+            "license_number": self.license_number,
+        })
+    }
+
+    /// Creates the staff member and returns their id.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app.post("/api/v1/staff", self.body()).await;
+        assert_eq!(status, StatusCode::CREATED, "add staff failed: {}", body);
+        body["staff_member"]["staff_id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+}
+
+pub struct ClinicBuilder {
+    clinic_name: String,
+}
+
+impl ClinicBuilder {
+    pub fn new() -> Self {
+        Self {
+            clinic_name: format!("Branch {}", unique_suffix()),
+        }
+    }
+
+    pub fn name(mut self, clinic_name: &str) -> Self {
+        self.clinic_name = clinic_name.to_string();
+        self
+    }
+
+    /// Creates the clinic and returns its id, to send as `X-Clinic-Id`.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app
+            .post(
+                "/api/v1/clinics",
+                json!({ "clinic_name": self.clinic_name }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "add clinic failed: {}", body);
+        body["clinic"]["clinic_id"].as_str().unwrap().to_string()
+    }
+}
+
+/// Sends a request on behalf of the clinic, as its `X-Clinic-Id`.
+pub async fn as_clinic(
+    app: &TestApp,
+    clinic_id: &str,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let response = app
+        .response_with_headers(method, uri, body, &[("X-Clinic-Id", clinic_id)])
+        .await;
+    let status = response.status();
+    (status, read_json(response).await)
+}
+
+pub struct ServiceInstanceBuilder {
+    pet_id: String,
+    service_type: Vec<String>,
+    service_reason: String,
+    general_diagnosis: String,
+    requires_followup: bool,
+    followup_date: Option<String>,
+    grooming_type: Option<Vec<String>>,
+    preventive_care: Option<Value>,
+    surgery: Option<Value>,
+}
+
+impl ServiceInstanceBuilder {
+    pub fn new(pet_id: &str) -> Self {
+        Self {
+            pet_id: pet_id.to_string(),
+            service_type: vec!["Checkup".to_string()],
+            service_reason: "Annual visit".to_string(),
+            general_diagnosis: "Healthy".to_string(),
+            requires_followup: false,
+            followup_date: None,
+            grooming_type: None,
+            preventive_care: None,
+            surgery: None,
+        }
+    }
+
+    pub fn service_types(mut self, service_type: &[&str]) -> Self {
+        self.service_type = service_type.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn followup(mut self, followup_date: &str) -> Self {
+        self.requires_followup = true;
+        self.followup_date = Some(followup_date.to_string());
+        self
+    }
+
+    pub fn grooming(mut self, grooming_type: &[&str]) -> Self {
+        self.grooming_type = Some(grooming_type.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    pub fn preventive_care(mut self, vet_id: &str, treatment: &[&str]) -> Self {
+        self.preventive_care = Some(json!({ "treatment": treatment, "vet_id": vet_id }));
+        self
+    }
+
+    pub fn surgery(mut self, vet_id: &str, surgery_name: &str) -> Self {
+        self.surgery = Some(surgery_body(vet_id, surgery_name));
+        self
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "pet_id": self.pet_id,
+            "service_type": self.service_type,
+            "service_reason": self.service_reason,
+            "general_diagnosis": self.general_diagnosis,
+            "requires_followup": self.requires_followup,
+            "followup_date": self.followup_date,
+            "grooming_type": self.grooming_type,
+            "preventive_care": self.preventive_care,
+            "surgery": self.surgery,
+        })
+    }
+
+    /// Creates the service instance and returns its id.
+    pub async fn create(self, app: &TestApp) -> String {
+        let (status, body) = app
+            .post("/api/service_instance/add_service_instance", self.body())
+            .await;
+        assert!(
+            status.is_success(),
+            "add_service_instance failed: {} {}",
+            status,
+            body
+        );
+        body["service_instance_id"].as_str().unwrap().to_string()
+    }
+}
+
+pub fn surgery_body(vet_id: &str, surgery_name: &str) -> Value {
+    json!({
+        "surgery_name": surgery_name,
+        "anesthesia_used": "Isoflurane",
+        "veterinarian_diagnosis": "Elective procedure",
+        "complications": "None",
+        "outcome": "Successful",
+        "vet_id": vet_id,
+    })
+}
@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+
+pub mod fixtures;
+pub mod query_counter;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header::CONTENT_TYPE, Method, Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use http_body_util::BodyExt;
+use pet_api::{create_router, AppState};
+use serde_json::Value;
+use tower::ServiceExt;
+
+/// An application router backed by its own in-memory SQLite database.
+#[derive(Clone)]
+pub struct TestApp {
+    router: Router,
+}
+
+impl TestApp {
+    pub async fn spawn() -> Self {
+        Self::spawn_with(|app_state| app_state).await
+    }
+
+    /// Like `spawn`, letting the test adjust the state before the router is built.
+    pub async fn spawn_with(configure: impl FnOnce(AppState) -> AppState) -> Self {
+        let app_state = AppState::connect_sqlite(":memory:")
+            .await
+            .expect("failed to open the test database");
+        let app_state = configure(app_state);
+        app_state
+            .create_tables()
+            .await
+            .expect("failed to create the test schema");
+
+        Self {
+            router: create_router(Arc::new(app_state)),
+        }
+    }
+
+    /// Like `spawn`, but on a fresh database file, so that concurrent requests each get their
+    /// own connection the way they would in production.
+    pub async fn spawn_on_file() -> Self {
+        Self::spawn_at(&temp_db_path()).await
+    }
+
+    /// Like `spawn_on_file`, on a file the test names so it can open the database itself.
+    pub async fn spawn_at(db_path: &Path) -> Self {
+        Self::spawn_at_with(db_path, |app_state| app_state).await
+    }
+
+    /// Like `spawn_at`, letting the test adjust the state before the router is built.
+    pub async fn spawn_at_with(
+        db_path: &Path,
+        configure: impl FnOnce(AppState) -> AppState,
+    ) -> Self {
+        let app_state = AppState::connect_sqlite(db_path.to_str().unwrap())
+            .await
+            .expect("failed to open the test database");
+        let app_state = configure(app_state);
+        app_state
+            .create_tables()
+            .await
+            .expect("failed to create the test schema");
+
+        Self {
+            router: create_router(Arc::new(app_state)),
+        }
+    }
+
+    pub async fn get(&self, uri: &str) -> (StatusCode, Value) {
+        self.request(Method::GET, uri, None).await
+    }
+
+    pub async fn post(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::POST, uri, Some(body)).await
+    }
+
+    pub async fn patch(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::PATCH, uri, Some(body)).await
+    }
+
+    pub async fn delete(&self, uri: &str) -> (StatusCode, Value) {
+        self.request(Method::DELETE, uri, None).await
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let response = self.response(method, uri, body).await;
+        let status = response.status();
+        (status, read_json(response).await)
+    }
+
+    pub async fn response(&self, method: Method, uri: &str, body: Option<Value>) -> Response {
+        self.response_with_headers(method, uri, body, &[]).await
+    }
+
+    pub async fn response_with_headers(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+        headers: &[(&str, &str)],
+    ) -> Response {
+        let mut request = Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let request = match body {
+            Some(body) => request
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .expect("failed to build the request");
+
+        self.router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("the router failed to respond")
+    }
+}
+
+/// A database file path in the temporary directory that no other test uses.
+pub fn temp_db_path() -> PathBuf {
+    std::env::temp_dir().join(format!("pet-api-{}.db", uuid::Uuid::new_v4()))
+}
+
+pub async fn read_json(response: Response) -> Value {
+    let bytes = response
+        .into_body()
+        .collect()
+        .await
+        .expect("failed to read the response body")
+        .to_bytes();
+    if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
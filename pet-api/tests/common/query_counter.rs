@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Counts the statements sqlx logs under the `sqlx::query` target.
+///
+/// The logger is process-wide, so a binary using it should run a single test at a time.
+pub struct QueryCounter;
+
+static QUERIES: AtomicUsize = AtomicUsize::new(0);
+static INSTALL: Once = Once::new();
+
+impl QueryCounter {
+    pub fn install() -> Self {
+        INSTALL.call_once(|| {
+            log::set_logger(&QueryLogger).expect("another logger is already installed");
+            log::set_max_level(LevelFilter::Debug);
+        });
+        QueryCounter
+    }
+
+    pub fn reset(&self) {
+        QUERIES.store(0, Ordering::SeqCst);
+    }
+
+    pub fn count(&self) -> usize {
+        QUERIES.load(Ordering::SeqCst)
+    }
+}
+
+struct QueryLogger;
+
+impl Log for QueryLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "sqlx::query"
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            QUERIES.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn flush(&self) {}
+}
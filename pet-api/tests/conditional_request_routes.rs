@@ -0,0 +1,221 @@
+mod common;
+
+use axum::http::header::{ETAG, IF_MATCH, IF_NONE_MATCH};
+use axum::http::{Method, StatusCode};
+use axum::response::Response;
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::{read_json, TestApp};
+use serde_json::{json, Value};
+
+fn etag_of(response: &Response) -> String {
+    response.headers()[ETAG].to_str().unwrap().to_string()
+}
+
+async fn patch_if_match(app: &TestApp, uri: &str, etag: &str, body: Value) -> Response {
+    app.response_with_headers(Method::PATCH, uri, Some(body), &[(IF_MATCH.as_str(), etag)])
+        .await
+}
+
+#[tokio::test]
+async fn get_pet_answers_not_modified_until_the_pet_or_its_owner_changes() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let pet_uri = format!("/api/v1/pets/{}", pet_id);
+
+    let response = app.response(Method::GET, &pet_uri, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = etag_of(&response);
+    assert!(etag.starts_with("\"1-"));
+
+    let response = app
+        .response_with_headers(
+            Method::GET,
+            &pet_uri,
+            None,
+            &[(IF_NONE_MATCH.as_str(), &etag)],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(etag_of(&response), etag);
+    assert_eq!(read_json(response).await, Value::Null);
+
+    app.patch(
+        &format!("/api/v1/owners/{}", owner_id),
+        json!({ "owner_name": "Ana Reyes" }),
+    )
+    .await;
+
+    let response = app
+        .response_with_headers(
+            Method::GET,
+            &pet_uri,
+            None,
+            &[(IF_NONE_MATCH.as_str(), &etag)],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(etag_of(&response), etag);
+    assert_eq!(read_json(response).await["pet"]["owner_name"], "Ana Reyes");
+}
+
+#[tokio::test]
+async fn update_pet_rejects_a_stale_if_match() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let pet_uri = format!("/api/v1/pets/{}", pet_id);
+    let etag = etag_of(&app.response(Method::GET, &pet_uri, None).await);
+
+    let response = patch_if_match(&app, &pet_uri, &etag, json!({ "pet_color": "Cream" })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(etag_of(&response), "\"2\"");
+    assert_eq!(read_json(response).await["pet"]["row_version"], 2);
+
+    let response = patch_if_match(&app, &pet_uri, &etag, json!({ "pet_color": "Black" })).await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(
+        read_json(response).await["message"],
+        "Pet was modified by another request"
+    );
+
+    let (_, body) = app.get(&pet_uri).await;
+    assert_eq!(body["pet"]["pet_color"], "Cream");
+}
+
+#[tokio::test]
+async fn updates_without_if_match_still_apply_and_bump_the_version() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let owner_uri = format!("/api/v1/owners/{}", owner_id);
+
+    let response = app
+        .response(
+            Method::PATCH,
+            &owner_uri,
+            Some(json!({ "owner_name": "Ana Reyes" })),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(etag_of(&response), "\"2\"");
+
+    let response = patch_if_match(&app, &owner_uri, "*", json!({ "owner_name": "Ana Cruz" })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_json(response).await["owner"]["row_version"], 3);
+}
+
+#[tokio::test]
+async fn update_vet_accepts_only_a_strong_tag_of_the_current_version() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let vet_uri = format!("/api/v1/vets/{}", vet_id);
+
+    let response =
+        patch_if_match(&app, &vet_uri, "W/\"1\"", json!({ "vet_name": "Dr. Lim" })).await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+    let response = patch_if_match(&app, &vet_uri, "\"1\"", json!({ "vet_name": "Dr. Lim" })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(etag_of(&response), "\"2\"");
+}
+
+#[tokio::test]
+async fn concurrent_surgery_edits_do_not_clobber_each_other() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .surgery(&vet_id, "Neuter")
+        .create(&app)
+        .await;
+    let instance_uri = format!("/api/v1/service-instances/{}", service_instance_id);
+
+    let (_, body) = app.get(&instance_uri).await;
+    let surgery = &body["surgery"][0];
+    let surgery_uri = format!("/api/v1/surgeries/{}", surgery["surgery_id"]);
+    let seen = format!("\"{}\"", surgery["row_version"]);
+
+    let response = patch_if_match(
+        &app,
+        &surgery_uri,
+        &seen,
+        json!({ "outcome": "Recovered well" }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(etag_of(&response), "\"2\"");
+
+    let response = patch_if_match(
+        &app,
+        &surgery_uri,
+        &seen,
+        json!({ "complications": "Bleeding" }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(
+        read_json(response).await["error"],
+        "Surgery was modified by another request"
+    );
+
+    let (_, body) = app.get(&instance_uri).await;
+    assert_eq!(body["surgery"][0]["outcome"], "Recovered well");
+    assert_ne!(body["surgery"][0]["complications"], "Bleeding");
+
+    let (status, _) = app
+        .patch("/api/v1/surgeries/999", json!({ "outcome": "Recovered" }))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn service_instance_etag_guards_updates_and_tracks_added_services() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Consultation"])
+        .create(&app)
+        .await;
+    let instance_uri = format!("/api/v1/service-instances/{}", service_instance_id);
+    let etag = etag_of(&app.response(Method::GET, &instance_uri, None).await);
+
+    app.post(
+        &format!("{}/grooming", instance_uri),
+        json!({ "grooming_type": ["Bath"] }),
+    )
+    .await;
+    let response = app
+        .response_with_headers(
+            Method::GET,
+            &instance_uri,
+            None,
+            &[(IF_NONE_MATCH.as_str(), &etag)],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = etag_of(&response);
+
+    let response = patch_if_match(
+        &app,
+        &instance_uri,
+        &etag,
+        json!({ "service_type": ["Grooming"] }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = patch_if_match(
+        &app,
+        &instance_uri,
+        &etag,
+        json!({ "service_type": ["Surgery"], "service_reason": "Overwrite" }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+    let (_, body) = app.get(&instance_uri).await;
+    assert_eq!(body["service_type"], json!(["Grooming"]));
+    assert_eq!(body["row_version"], 2);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::{Method, StatusCode};
+use axum::response::Response;
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder,
+};
+use common::{read_json, TestApp};
+use serde_json::Value;
+
+async fn post_with_key(app: &TestApp, uri: &str, key: &str, body: Value) -> Response {
+    app.response_with_headers(Method::POST, uri, Some(body), &[("Idempotency-Key", key)])
+        .await
+}
+
+#[tokio::test]
+async fn a_retried_create_replays_the_original_response() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let body = PetBuilder::new(&owner_id).name("Mochi").body();
+
+    let first = post_with_key(&app, "/api/v1/pets", "retry-1", body.clone()).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let first = read_json(first).await;
+
+    let retry = post_with_key(&app, "/api/v1/pets", "retry-1", body).await;
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(read_json(retry).await, first);
+
+    let (_, pets) = app.get("/api/v1/pets").await;
+    assert_eq!(pets["pets"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn legacy_create_routes_honor_the_key_too() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let body = ServiceInstanceBuilder::new(&pet_id).body();
+    let uri = "/api/service_instance/add_service_instance";
+
+    let first = read_json(post_with_key(&app, uri, "visit-1", body.clone()).await).await;
+    let retry = read_json(post_with_key(&app, uri, "visit-1", body).await).await;
+
+    assert_eq!(retry["service_instance_id"], first["service_instance_id"]);
+    let (_, feed) = app.get("/api/v1/service-instances").await;
+    assert_eq!(feed["service_instances"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn reusing_a_key_for_a_different_request_is_a_conflict() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+
+    let response = post_with_key(
+        &app,
+        "/api/v1/pets",
+        "shared",
+        PetBuilder::new(&owner_id).name("Mochi").body(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = post_with_key(
+        &app,
+        "/api/v1/pets",
+        "shared",
+        PetBuilder::new(&owner_id).name("Biscuit").body(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        read_json(response).await["message"],
+        "Idempotency-Key was already used for a different request"
+    );
+
+    let response = post_with_key(
+        &app,
+        "/api/v1/owners",
+        "shared",
+        PetBuilder::new(&owner_id).name("Mochi").body(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn each_clinic_has_its_own_keys() {
+    let app = TestApp::spawn().await;
+    let branch_id = ClinicBuilder::new().create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let (_, branch_owner) = as_clinic(
+        &app,
+        &branch_id,
+        Method::POST,
+        "/api/v1/owners",
+        Some(OwnerBuilder::new().body()),
+    )
+    .await;
+    let branch_owner_id = branch_owner["owner"]["owner_id"].as_str().unwrap();
+
+    let response = post_with_key(
+        &app,
+        "/api/v1/pets",
+        "visit-2024-001",
+        PetBuilder::new(&owner_id).name("Mochi").body(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .response_with_headers(
+            Method::POST,
+            "/api/v1/pets",
+            Some(PetBuilder::new(branch_owner_id).name("Biscuit").body()),
+            &[
+                ("Idempotency-Key", "visit-2024-001"),
+                ("X-Clinic-Id", &branch_id),
+            ],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(response.headers().get("idempotent-replayed").is_none());
+    assert_eq!(read_json(response).await["pet"]["pet_name"], "Biscuit");
+}
+
+#[tokio::test]
+async fn requests_without_a_key_are_not_deduplicated() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let body = PetBuilder::new(&owner_id).body();
+
+    app.post("/api/v1/pets", body.clone()).await;
+    app.post("/api/v1/pets", body).await;
+
+    let (_, pets) = app.get("/api/v1/pets").await;
+    assert_eq!(pets["pets"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn an_expired_key_starts_a_new_request() {
+    let app = TestApp::spawn_with(|state| state.with_idempotency_key_ttl(Duration::ZERO)).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let body = PetBuilder::new(&owner_id).body();
+
+    post_with_key(&app, "/api/v1/pets", "short-lived", body.clone()).await;
+    let retry = post_with_key(&app, "/api/v1/pets", "short-lived", body).await;
+
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+    let (_, pets) = app.get("/api/v1/pets").await;
+    assert_eq!(pets["pets"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn an_overlong_key_is_rejected() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+
+    let response = post_with_key(
+        &app,
+        "/api/v1/pets",
+        &"k".repeat(256),
+        PetBuilder::new(&owner_id).body(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::fixtures::{as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder};
+use common::TestApp;
+use serde_json::{json, Value};
+
+const MICROCHIP: &str = "985112003456789";
+
+async fn add_identifier(app: &TestApp, pet_id: &str, identifier: Value) -> (StatusCode, Value) {
+    app.post(&format!("/api/v1/pets/{}/identifiers", pet_id), identifier)
+        .await
+}
+
+#[tokio::test]
+async fn microchips_are_stored_as_their_fifteen_digits() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, body) = add_identifier(
+        &app,
+        &pet_id,
+        json!({
+            "identifier_type": "microchip",
+            "identifier_value": "985 112-003.456 789",
+            "implant_date": "2024-03-01",
+            "implant_location": " Left shoulder ",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["identifier"]["identifier_value"], MICROCHIP);
+    assert_eq!(body["identifier"]["implant_location"], "Left shoulder");
+
+    let (status, body) = add_identifier(
+        &app,
+        &pet_id,
+        json!({ "identifier_type": "license_tag", "identifier_value": "mnl-2024-0042" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["identifier"]["identifier_value"], "MNL20240042");
+
+    let (status, body) = app
+        .get(&format!("/api/v1/pets/{}/identifiers", pet_id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let identifiers = body["identifiers"].as_array().unwrap();
+    assert_eq!(identifiers.len(), 2);
+    assert_eq!(identifiers[0]["identifier_type"], "microchip");
+    assert_eq!(identifiers[0]["implant_date"], "2024-03-01");
+    assert_eq!(identifiers[1]["identifier_type"], "license_tag");
+}
+
+#[tokio::test]
+async fn malformed_identifiers_are_rejected() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    for (identifier, message) in [
+        (
+            json!({ "identifier_type": "microchip", "identifier_value": "98511200345678" }),
+            "Microchip numbers are 15 digits (ISO 11784/11785)",
+        ),
+        (
+            json!({ "identifier_type": "microchip", "identifier_value": "999000000000001" }),
+            "Microchip 999000000000001 is a test transponder",
+        ),
+        (
+            json!({ "identifier_type": "microchip", "identifier_value": "985300000000000" }),
+            "Microchip 985300000000000 is not a valid ISO 11784 code: its national number is out of range",
+        ),
+        (
+            json!({ "identifier_type": "tattoo", "identifier_value": "AB/123" }),
+            "Tattoo numbers may only contain letters, digits, spaces, dashes and dots",
+        ),
+        (
+            json!({ "identifier_type": "tattoo", "identifier_value": "AB123", "implant_location": "Left ear" }),
+            "Only microchips have an implant date or location",
+        ),
+        (
+            json!({ "identifier_type": "microchip", "identifier_value": MICROCHIP, "implant_date": "2999-01-01" }),
+            "implant_date cannot be in the future",
+        ),
+    ] {
+        let (status, body) = add_identifier(&app, &pet_id, identifier).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+        assert_eq!(body["message"], message);
+    }
+
+    let (status, _) = add_identifier(
+        &app,
+        "missing-pet",
+        json!({ "identifier_type": "microchip", "identifier_value": MICROCHIP }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn an_identifier_belongs_to_one_pet() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let other_pet_id = PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    let microchip = json!({ "identifier_type": "microchip", "identifier_value": MICROCHIP });
+
+    let (status, _) = add_identifier(&app, &pet_id, microchip.clone()).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = add_identifier(&app, &other_pet_id, microchip).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        format!("Microchip {} is already registered to a pet", MICROCHIP)
+    );
+
+    let (status, body) = add_identifier(
+        &app,
+        &other_pet_id,
+        json!({ "identifier_type": "tattoo", "identifier_value": MICROCHIP }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+}
+
+#[tokio::test]
+async fn found_pets_are_looked_up_across_clinics() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Bantay").create(&app).await;
+    add_identifier(
+        &app,
+        &pet_id,
+        json!({ "identifier_type": "microchip", "identifier_value": MICROCHIP }),
+    )
+    .await;
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::GET,
+        "/api/v1/pets/by-identifier/985-112-003-456-789",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let found = &body["pets"][0];
+    assert_eq!(found["pet_id"], pet_id.as_str());
+    assert_eq!(found["pet_name"], "Bantay");
+    assert_eq!(found["owner_name"], "Ana Cruz");
+    assert_eq!(found["owner_phone_number"], "09171234567");
+    assert_eq!(found["clinic_name"], "Main clinic");
+
+    let response = app
+        .response(
+            Method::GET,
+            &format!("/api/pet/by_identifier/{}", MICROCHIP),
+            None,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("deprecation"));
+
+    let (status, body) = app.get("/api/v1/pets/by-identifier/900000000000001").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "No pet carries identifier 900000000000001");
+}
+
+#[tokio::test]
+async fn only_the_pets_clinic_removes_its_identifiers() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let (_, body) = add_identifier(
+        &app,
+        &pet_id,
+        json!({ "identifier_type": "tattoo", "identifier_value": "RX 77" }),
+    )
+    .await;
+    let uri = format!(
+        "/api/v1/pets/{}/identifiers/{}",
+        pet_id, body["identifier"]["identifier_id"]
+    );
+    app.post(
+        &format!("/api/v1/pets/{}/shares", pet_id),
+        json!({ "clinic_id": north }),
+    )
+    .await;
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::GET,
+        &format!("/api/v1/pets/{}/identifiers", pet_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["identifiers"][0]["identifier_value"], "RX77");
+
+    let (status, _) = as_clinic(&app, &north, Method::DELETE, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = app.delete(&uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = app.get("/api/v1/pets/by-identifier/RX77").await;
+    assert_eq!(body["message"], "No pet carries identifier RX77");
+}
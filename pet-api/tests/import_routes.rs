@@ -0,0 +1,216 @@
+mod common;
+
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::{Method, StatusCode};
+use common::fixtures::OwnerBuilder;
+use common::{read_json, TestApp};
+use serde_json::json;
+
+const OWNERS_CSV: &str = "Name,Email,Phone,Address\n\
+Ana Reyes,ana@example.com,09170000001,\"12 Mabini St, Manila\"\n\
+Ben Cruz,ben@example.com,09170000002,4 Rizal Ave\n";
+
+#[tokio::test]
+async fn imports_mapped_csv_owners() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({
+                "entity": "owners",
+                "format": "csv",
+                "data": OWNERS_CSV,
+                "mapping": {
+                    "Name": "owner_name",
+                    "Email": "owner_email",
+                    "Phone": "owner_phone_number",
+                    "Address": "owner_address",
+                },
+            }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["import"]["committed"], true);
+    assert_eq!(body["import"]["imported_rows"], 2);
+    assert_eq!(body["import"]["errors"], json!([]));
+
+    let (_, owners) = app.get("/api/v1/owners").await;
+    let owners = owners["owners"].as_array().unwrap();
+    assert_eq!(owners.len(), 2);
+    assert!(owners
+        .iter()
+        .any(|owner| owner["owner_address"] == "12 Mabini St, Manila"));
+}
+
+#[tokio::test]
+async fn dry_run_reports_row_errors_without_saving() {
+    let app = TestApp::spawn().await;
+    let data = "owner_name,owner_email,owner_phone_number,owner_address\n\
+        Ana Reyes,ana@example.com,0917,Manila\n\
+        Ben Cruz,not-an-email,0918,Cebu\n\
+        Cara Lim,ana@example.com,0919,Davao\n";
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({ "entity": "owners", "format": "csv", "data": data, "dry_run": true }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let report = &body["import"];
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["total_rows"], 3);
+    assert_eq!(report["imported_rows"], 1);
+    assert_eq!(report["failed_rows"], 2);
+    assert_eq!(report["errors"][0]["row"], 2);
+    assert_eq!(report["errors"][0]["field"], "owner_email");
+    assert_eq!(report["errors"][1]["row"], 3);
+
+    let (_, owners) = app.get("/api/v1/owners").await;
+    assert_eq!(owners["owners"], json!([]));
+}
+
+#[tokio::test]
+async fn all_or_nothing_saves_nothing_when_the_database_rejects_a_row() {
+    let app = TestApp::spawn().await;
+    OwnerBuilder::new()
+        .email("ben@example.com")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({
+                "entity": "owners",
+                "format": "csv",
+                "data": OWNERS_CSV,
+                "mapping": {
+                    "Name": "owner_name",
+                    "Email": "owner_email",
+                    "Phone": "owner_phone_number",
+                    "Address": "owner_address",
+                },
+            }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["import"]["imported_rows"], 0);
+    assert_eq!(body["import"]["errors"][0]["row"], 2);
+    assert_eq!(
+        body["import"]["errors"][0]["message"],
+        "Owner already exists"
+    );
+
+    let (_, owners) = app.get("/api/v1/owners").await;
+    assert_eq!(owners["owners"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn best_effort_ndjson_pets_link_owners_by_email() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new()
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+    let pet = |name: &str, owner_email: &str| {
+        json!({
+            "pet_name": name,
+            "pet_birth_date": "2021-04-01",
+            "pet_type": "Cat",
+            "pet_breed": "Siamese",
+            "pet_weight": 4.2,
+            "pet_color": "Cream",
+            "owner_email": owner_email,
+        })
+        .to_string()
+    };
+    let data = [
+        pet("Mochi", "ana@example.com"),
+        pet("Biscuit", "nobody@example.com"),
+        "{not json".to_string(),
+    ]
+    .join("\n");
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({ "entity": "pets", "format": "ndjson", "data": data, "mode": "best_effort" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    let report = &body["import"];
+    assert_eq!(report["imported_rows"], 1);
+    assert_eq!(report["failed_rows"], 2);
+    assert_eq!(report["errors"][0]["field"], "owner_email");
+
+    let (_, owner) = app.get(&format!("/api/v1/owners/{}", owner_id)).await;
+    assert_eq!(owner["pets"][0]["pet_name"], "Mochi");
+
+    let response = app
+        .response(
+            Method::GET,
+            &format!(
+                "/api/v1/imports/{}/errors",
+                report["import_id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+    assert!(response.headers()[CONTENT_DISPOSITION]
+        .to_str()
+        .unwrap()
+        .starts_with("attachment;"));
+    let csv = read_json(response).await;
+    let lines = csv.as_str().unwrap().lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "row,field,message");
+    assert_eq!(
+        lines[1],
+        "2,owner_email,No owner has the email nobody@example.com"
+    );
+    assert_eq!(lines.len(), 3);
+}
+
+#[tokio::test]
+async fn rejects_unusable_documents() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({
+                "entity": "vets",
+                "format": "csv",
+                "data": "Name\nDr. Lim\n",
+                "mapping": { "Name": "vet_nickname" },
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Unknown vets field 'vet_nickname' in mapping"
+    );
+
+    let (status, body) = app
+        .post(
+            "/api/v1/imports",
+            json!({ "entity": "vets", "format": "csv", "data": "vet_name\nDr. Lim\n" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "No column is mapped to the 'vet_email' field"
+    );
+
+    let (status, _) = app.get("/api/v1/imports/missing/errors").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use common::{temp_db_path, TestApp};
+use sqlx::{Connection, SqliteConnection};
+
+#[tokio::test]
+async fn index_reports_the_server_is_running() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("Server is running"));
+}
+
+#[tokio::test]
+async fn health_check_reports_a_healthy_database() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/health_check").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["message"], "Health check is successful!");
+}
+
+#[tokio::test]
+async fn livez_does_not_depend_on_the_database() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/livez").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "alive");
+}
+
+#[tokio::test]
+async fn readyz_lists_database_and_schema_dependencies() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/readyz").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    let dependencies = body["dependencies"].as_array().unwrap();
+    assert_eq!(dependencies[0]["name"], "database");
+    assert_eq!(dependencies[0]["status"], "up");
+    assert!(dependencies[0]["latency_ms"].as_f64().is_some());
+    assert_eq!(dependencies[1]["name"], "schema");
+    assert_eq!(dependencies[1]["status"], "up");
+    assert_eq!(
+        dependencies[1]["details"]["applied_version"],
+        dependencies[1]["details"]["expected_version"]
+    );
+}
+
+#[tokio::test]
+async fn readyz_reports_a_hung_probe_as_not_ready() {
+    let db_path = temp_db_path();
+    let app = TestApp::spawn_at_with(&db_path, |state| {
+        state.with_readiness_probe_timeout(Duration::from_millis(200))
+    })
+    .await;
+    // An exclusive lock keeps the schema probe waiting on SQLite's busy timeout.
+    let mut lock = SqliteConnection::connect(db_path.to_str().unwrap())
+        .await
+        .unwrap();
+    sqlx::query("BEGIN EXCLUSIVE")
+        .execute(&mut lock)
+        .await
+        .unwrap();
+
+    let started = Instant::now();
+    let (status, body) = app.get("/api/readyz").await;
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "not_ready");
+    assert_eq!(body["dependencies"][1]["status"], "down");
+    assert_eq!(
+        body["dependencies"][1]["message"],
+        "Probe query timed out after 200 ms"
+    );
+}
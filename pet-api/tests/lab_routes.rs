@@ -0,0 +1,262 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::{json, Value};
+
+async fn order_labs(app: &TestApp, service_instance_id: &str, vet_id: &str) -> i64 {
+    let (status, body) = app
+        .post(
+            &format!(
+                "/api/v1/service-instances/{}/lab-orders",
+                service_instance_id
+            ),
+            json!({ "panel": "CBC + Chem", "sample_type": "blood", "vet_id": vet_id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    body["lab_order"]["lab_order_id"].as_i64().unwrap()
+}
+
+async fn enter_results(app: &TestApp, lab_order_id: i64, results: Value) -> (StatusCode, Value) {
+    app.post(
+        &format!("/api/v1/lab-orders/{}/results", lab_order_id),
+        json!({ "results": results }),
+    )
+    .await
+}
+
+fn flags(body: &Value) -> Vec<(String, Value)> {
+    body["lab_order"]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| {
+            (
+                result["analyte"].as_str().unwrap().to_string(),
+                result["flag"].clone(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn results_are_flagged_against_the_species_reference_range() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let visit = ServiceInstanceBuilder::new(&rex).create(&app).await;
+    let orders_uri = format!("/api/v1/service-instances/{}/lab-orders", visit);
+
+    let (status, _) = app
+        .post(
+            &orders_uri,
+            json!({ "panel": " ", "sample_type": "blood", "vet_id": vet_id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = app
+        .post(
+            &orders_uri,
+            json!({ "panel": "CBC", "sample_type": "blood", "vet_id": "nobody" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let lab_order_id = order_labs(&app, &visit, &vet_id).await;
+    let (status, body) = enter_results(
+        &app,
+        lab_order_id,
+        json!([
+            { "analyte": "hgb", "value": 10.2 },
+            { "analyte": "WBC", "value": 12.0 },
+            { "analyte": "PLT", "value": 520 },
+            { "analyte": "GLU", "value": 5.1, "unit": "mmol/L" },
+            { "analyte": "Lipase", "value": 400, "unit": "U/L", "reference_high": 300 },
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(
+        flags(&body),
+        [
+            ("HGB".to_string(), json!("low")),
+            ("WBC".to_string(), json!("normal")),
+            ("PLT".to_string(), json!("high")),
+            // No dog range in mmol/L to compare with.
+            ("GLU".to_string(), Value::Null),
+            ("LIPASE".to_string(), json!("high")),
+        ]
+    );
+    assert_eq!(body["lab_order"]["results"][0]["unit"], "g/dL");
+    assert_eq!(
+        body["lab_order"]["abnormal_analytes"],
+        json!(["HGB", "PLT", "LIPASE"])
+    );
+    assert!(body["lab_order"]["resulted_at"].is_i64());
+
+    let (status, _) = enter_results(
+        &app,
+        lab_order_id,
+        json!([{ "analyte": "HGB", "value": 11 }]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = enter_results(
+        &app,
+        lab_order_id,
+        json!([{ "analyte": "T4", "value": 2.1 }]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "T4 needs a unit");
+    let (status, _) = enter_results(
+        &app,
+        lab_order_id,
+        json!([{ "analyte": "ALT", "value": 20 }, { "analyte": "alt", "value": 21 }]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app.get(&orders_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["lab_orders"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        body["lab_orders"][0]["results"].as_array().unwrap().len(),
+        5
+    );
+    assert_eq!(body["lab_orders"][0]["panel"], "CBC + Chem");
+}
+
+#[tokio::test]
+async fn cats_are_compared_with_feline_ranges() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let luna = PetBuilder::new(&owner_id)
+        .name("Luna")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let visit = ServiceInstanceBuilder::new(&luna).create(&app).await;
+    let lab_order_id = order_labs(&app, &visit, &vet_id).await;
+
+    let (status, body) = enter_results(
+        &app,
+        lab_order_id,
+        json!([{ "analyte": "HGB", "value": 10.2 }, { "analyte": "BUN", "value": 12 }]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(
+        flags(&body),
+        [
+            ("HGB".to_string(), json!("normal")),
+            ("BUN".to_string(), json!("low")),
+        ]
+    );
+
+    let (status, body) = app.get("/api/v1/lab-reference-ranges?species=Cat").await;
+    assert_eq!(status, StatusCode::OK);
+    let ranges = body["reference_ranges"].as_array().unwrap();
+    assert!(!ranges.is_empty());
+    assert!(ranges.iter().all(|range| range["species"] == "Cat"));
+}
+
+#[tokio::test]
+async fn trends_follow_each_analyte_across_visits() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    for (creatinine, glucose) in [(1.2, 90), (2.3, 95)] {
+        let visit = ServiceInstanceBuilder::new(&rex).create(&app).await;
+        let lab_order_id = order_labs(&app, &visit, &vet_id).await;
+        let (status, _) = enter_results(
+            &app,
+            lab_order_id,
+            json!([
+                { "analyte": "CREA", "value": creatinine },
+                { "analyte": "GLU", "value": glucose },
+            ]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, body) = app.get(&format!("/api/v1/pets/{}/lab-trends", rex)).await;
+    assert_eq!(status, StatusCode::OK);
+    let trends = body["trends"].as_array().unwrap();
+    assert_eq!(trends.len(), 2);
+    assert_eq!(trends[0]["analyte"], "CREA");
+    assert_eq!(trends[0]["reference_range"]["species"], "Dog");
+    let points: Vec<_> = trends[0]["points"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| (point["value"].as_f64().unwrap(), point["flag"].clone()))
+        .collect();
+    assert_eq!(points, [(1.2, json!("normal")), (2.3, json!("high"))]);
+
+    let (status, body) = app
+        .get(&format!("/api/v1/pets/{}/lab-trends?analyte=glu", rex))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["trends"].as_array().unwrap().len(), 1);
+    assert_eq!(body["trends"][0]["points"].as_array().unwrap().len(), 2);
+
+    let (status, _) = app.get("/api/v1/pets/nobody/lab-trends").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn a_sharing_clinic_reads_lab_results_but_cannot_add_them() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let visit = ServiceInstanceBuilder::new(&rex).create(&app).await;
+    let lab_order_id = order_labs(&app, &visit, &vet_id).await;
+    let order_uri = format!("/api/v1/lab-orders/{}", lab_order_id);
+
+    let (status, _) = as_clinic(&app, &north, Method::GET, &order_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/pets/{}/shares", rex),
+            json!({ "clinic_id": north }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = as_clinic(&app, &north, Method::GET, &order_uri, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["lab_order"]["panel"], "CBC + Chem");
+
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        &format!("{}/results", order_uri),
+        Some(json!({ "results": [{ "analyte": "HGB", "value": 15 }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = as_clinic(
+        &app,
+        &north,
+        Method::GET,
+        &format!("/api/v1/pets/{}/lab-trends", rex),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["trends"].as_array().unwrap().len(), 0);
+}
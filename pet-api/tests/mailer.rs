@@ -0,0 +1,82 @@
+use pet_api::{Email, Mailer, SmtpMailer};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Accepts one SMTP session and returns everything the client sent.
+async fn smtp_relay() -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("smtp://{}", listener.local_addr().unwrap());
+
+    let session = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut transcript = String::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 relay ready\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            transcript.push_str(&line);
+            transcript.push('\n');
+            let reply: &[u8] = if in_data {
+                if line != "." {
+                    continue;
+                }
+                in_data = false;
+                b"250 queued\r\n"
+            } else if line.starts_with("DATA") {
+                in_data = true;
+                b"354 go ahead\r\n"
+            } else if line.starts_with("QUIT") {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 ok\r\n"
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+        transcript
+    });
+
+    (url, session)
+}
+
+#[tokio::test]
+async fn smtp_mailer_delivers_the_email_to_the_relay() {
+    let (url, session) = smtp_relay().await;
+    let mailer = SmtpMailer::new(&url, "Paws and Claws <clinic@example.com>").unwrap();
+
+    mailer
+        .send(Email {
+            to: "ana@example.com".to_string(),
+            subject: "Your sign-in link".to_string(),
+            body: "Open https://portal.example.com/login?token=abc".to_string(),
+        })
+        .await
+        .unwrap();
+    drop(mailer);
+
+    let transcript = session.await.unwrap();
+    assert!(
+        transcript.contains("MAIL FROM:<clinic@example.com>"),
+        "{}",
+        transcript
+    );
+    assert!(
+        transcript.contains("RCPT TO:<ana@example.com>"),
+        "{}",
+        transcript
+    );
+    assert!(
+        transcript.contains("Subject: Your sign-in link"),
+        "{}",
+        transcript
+    );
+    assert!(transcript.contains("token=abc"), "{}", transcript);
+}
+
+#[tokio::test]
+async fn smtp_mailer_rejects_a_malformed_configuration() {
+    assert!(SmtpMailer::new("ftp://relay.example.com", "clinic@example.com").is_err());
+    assert!(SmtpMailer::new("smtp://relay.example.com", "not an address").is_err());
+}
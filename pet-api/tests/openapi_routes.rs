@@ -0,0 +1,66 @@
+mod common;
+
+use std::path::Path;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+
+const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+/// Fails when the generated document drifts from the committed `openapi.json`.
+/// Run with `UPDATE_OPENAPI_SNAPSHOT=1` to accept the new contract.
+#[test]
+fn openapi_document_matches_the_committed_snapshot() {
+    let document = pet_api::openapi_json() + "\n";
+
+    if std::env::var_os("UPDATE_OPENAPI_SNAPSHOT").is_some() {
+        std::fs::write(SNAPSHOT_PATH, &document).expect("failed to write openapi.json");
+        return;
+    }
+
+    let snapshot = std::fs::read_to_string(Path::new(SNAPSHOT_PATH))
+        .expect("openapi.json is missing; run with UPDATE_OPENAPI_SNAPSHOT=1");
+    assert!(
+        snapshot == document,
+        "openapi.json is out of date; run `UPDATE_OPENAPI_SNAPSHOT=1 cargo test --test openapi_routes` and commit the result"
+    );
+}
+
+#[tokio::test]
+async fn openapi_json_documents_every_route() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/openapi.json").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["openapi"].as_str().unwrap().starts_with("3."));
+    let paths = body["paths"].as_object().unwrap();
+    assert!(paths["/api/v1/pets/{pet_id}"]["delete"].is_object());
+    assert!(
+        paths["/api/v1/pets/{pet_id}/service-instances"]["get"]["responses"]["404"].is_object()
+    );
+}
+
+#[tokio::test]
+async fn docs_page_loads_the_served_document_and_vendored_assets() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/docs").await;
+    assert_eq!(status, StatusCode::OK);
+    let page = body.as_str().unwrap();
+    assert!(page.contains("/api/openapi.json"));
+    assert!(!page.contains("https://"), "{}", page);
+
+    for (uri, content_type) in [
+        ("/api/docs/swagger-ui.css", "text/css; charset=utf-8"),
+        (
+            "/api/docs/swagger-ui-bundle.js",
+            "text/javascript; charset=utf-8",
+        ),
+    ] {
+        let response = app.response(Method::GET, uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+        assert_eq!(response.headers()[CONTENT_TYPE], content_type);
+    }
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder};
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn add_owner_returns_the_created_owner() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/owner/add_owner",
+            OwnerBuilder::new().name("Ana Cruz").body(),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["owner"]["owner_name"], "Ana Cruz");
+    assert!(body["owner"]["owner_id"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn add_owner_rejects_a_duplicate_email() {
+    let app = TestApp::spawn().await;
+    OwnerBuilder::new()
+        .email("same@example.com")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .post(
+            "/api/owner/add_owner",
+            OwnerBuilder::new().email("same@example.com").body(),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "Owner already exists");
+}
+
+#[tokio::test]
+async fn get_owners_paginates_and_searches_by_name() {
+    let app = TestApp::spawn().await;
+    OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+    OwnerBuilder::new().name("Ben Reyes").create(&app).await;
+    OwnerBuilder::new().name("Carla Dizon").create(&app).await;
+
+    let (status, body) = app.get("/api/owner/get_owners?page=1&limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owners"].as_array().unwrap().len(), 2);
+    assert_eq!(body["total_pages"], 2);
+
+    let (status, body) = app.get("/api/owner/get_owners?search=Ben").await;
+    assert_eq!(status, StatusCode::OK);
+    let owners = body["owners"].as_array().unwrap();
+    assert_eq!(owners.len(), 1);
+    assert_eq!(owners[0]["owner_name"], "Ben Reyes");
+    assert_eq!(body["total_pages"], 1);
+}
+
+#[tokio::test]
+async fn get_owners_reads_page_zero_as_the_first_page() {
+    let app = TestApp::spawn().await;
+    OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+
+    let (status, body) = app.get("/api/v1/owners?page=0").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["owners"][0]["owner_name"], "Ana Cruz");
+
+    let (status, body) = app.get("/api/v1/owners?limit=101").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "limit must be between 1 and 100");
+}
+
+#[tokio::test]
+async fn get_owner_and_pets_returns_the_owner_with_their_pets() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    PetBuilder::new(&owner_id)
+        .name("Biscuit")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .get(&format!("/api/owner/get_owner_and_pets/{}", owner_id))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owner"]["owner_id"], owner_id.as_str());
+    let pet_names: Vec<_> = body["pets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|pet| pet["pet_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(pet_names, vec!["Biscuit", "Mochi"]);
+}
+
+#[tokio::test]
+async fn get_owner_and_pets_returns_not_found_for_a_missing_owner() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/owner/get_owner_and_pets/missing").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Owner not found");
+}
+
+#[tokio::test]
+async fn update_owner_changes_only_the_given_fields() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/owner/update_owner/{}", owner_id),
+            json!({ "owner_address": "99 Rizal Ave" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owner"]["owner_name"], "Ana Cruz");
+    assert_eq!(body["owner"]["owner_address"], "99 Rizal Ave");
+}
+
+#[tokio::test]
+async fn update_owner_rejects_blank_fields() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/owner/update_owner/{}", owner_id),
+            json!({ "owner_name": "  " }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Owner name cannot be empty");
+}
+
+#[tokio::test]
+async fn update_owner_returns_not_found_for_a_missing_owner() {
+    let app = TestApp::spawn().await;
+
+    let (status, _) = app
+        .patch(
+            "/api/owner/update_owner/missing",
+            json!({ "owner_name": "Nobody" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_owner_removes_the_owner_and_their_pets() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, _) = app
+        .delete(&format!("/api/owner/delete_owner/{}", owner_id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.get(&format!("/api/pet/get_pet/{}", pet_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app
+        .delete(&format!("/api/owner/delete_owner/{}", owner_id))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Owner not found");
+}
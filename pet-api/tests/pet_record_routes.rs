@@ -0,0 +1,120 @@
+mod common;
+
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::{Method, StatusCode};
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::TestApp;
+use http_body_util::BodyExt;
+use pet_api::ClinicBranding;
+
+async fn record_pdf(app: &TestApp, uri: &str) -> (StatusCode, String) {
+    let response = app.response(Method::GET, uri, None).await;
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[tokio::test]
+async fn renders_the_pet_owner_and_every_visit_with_clinic_branding() {
+    let app = TestApp::spawn_with(|state| {
+        state.with_clinic_branding(ClinicBranding {
+            name: "Northside Animal Hospital".to_string(),
+            phone_number: Some("(02) 8123 4567".to_string()),
+            ..ClinicBranding::default()
+        })
+    })
+    .await;
+    let owner_id = OwnerBuilder::new().name("Ana Reyes").create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    let vet_id = VetBuilder::new().name("Dr. Lim").create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .grooming(&["Nail Trim"])
+        .preventive_care(&vet_id, &["Rabies Vaccine"])
+        .surgery(&vet_id, "Dental Extraction")
+        .create(&app)
+        .await;
+
+    let response = app
+        .response(
+            Method::GET,
+            &format!("/api/v1/pets/{}/record.pdf", pet_id),
+            None,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/pdf");
+    assert_eq!(
+        response.headers()[CONTENT_DISPOSITION],
+        "inline; filename=\"Mochi-medical-record.pdf\""
+    );
+
+    let (_, pdf) = record_pdf(&app, &format!("/api/v1/pets/{}/record.pdf", pet_id)).await;
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.trim_end().ends_with("%%EOF"));
+    for text in [
+        "(Northside Animal Hospital)",
+        "\\(02\\) 8123 4567",
+        "(Medical record of Mochi)",
+        "(Ana Reyes)",
+        "(Nail Trim)",
+        "(Rabies Vaccine)",
+        "(Surgery: Dental Extraction)",
+        "(Dr. Lim \\(license",
+        "(Complete history)",
+    ] {
+        assert!(pdf.contains(text), "the record is missing {}", text);
+    }
+}
+
+#[tokio::test]
+async fn a_date_range_leaves_out_visits_outside_it() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Consultation"])
+        .create(&app)
+        .await;
+
+    let (status, pdf) = record_pdf(
+        &app,
+        &format!(
+            "/api/pet/{}/record.pdf?start_date=2001-01-01&end_date=2001-12-31",
+            pet_id
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(pdf.contains("(Visits from 2001-01-01 to 2001-12-31)"));
+    assert!(pdf.contains("(Visits \\(0\\))"));
+    assert!(pdf.contains("(No visits were recorded in this period.)"));
+    assert!(!pdf.contains("Consultation"));
+}
+
+#[tokio::test]
+async fn rejects_unknown_pets_and_malformed_ranges() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, _) = app.get("/api/v1/pets/missing/record.pdf").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/v1/pets/{}/record.pdf?start_date=01/02/2024",
+            pet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Invalid start_date: expected YYYY-MM-DD");
+
+    let (status, _) = app
+        .get(&format!(
+            "/api/v1/pets/{}/record.pdf?start_date=2024-02-01&end_date=2024-01-01",
+            pet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
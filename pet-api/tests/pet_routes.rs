@@ -0,0 +1,166 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder};
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn add_pet_returns_the_created_pet() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+
+    let (status, body) = app
+        .post(
+            "/api/pet/add_pet",
+            PetBuilder::new(&owner_id).name("Mochi").weight(6.5).body(),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["pet"]["pet_name"], "Mochi");
+    assert_eq!(body["pet"]["pet_weight"], "6.50");
+    assert_eq!(body["pet"]["owner_id"], owner_id.as_str());
+}
+
+#[tokio::test]
+async fn get_pet_includes_owner_details() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, body) = app.get(&format!("/api/pet/get_pet/{}", pet_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet"]["pet_id"], pet_id.as_str());
+    assert_eq!(body["pet"]["owner_name"], "Ana Cruz");
+}
+
+#[tokio::test]
+async fn get_pet_gives_the_weight_to_two_decimal_places() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).weight(7.0).create(&app).await;
+
+    let (status, body) = app.get(&format!("/api/pet/get_pet/{}", pet_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet"]["pet_weight"], "7.00");
+}
+
+#[tokio::test]
+async fn get_pet_returns_not_found_for_a_missing_pet() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app.get("/api/pet/get_pet/missing").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Pet not found");
+}
+
+#[tokio::test]
+async fn get_pets_paginates_and_searches_by_name() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    PetBuilder::new(&owner_id)
+        .name("Biscuit")
+        .create(&app)
+        .await;
+    PetBuilder::new(&owner_id)
+        .name("Luna")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+
+    let (status, body) = app.get("/api/pet/get_pets?page=2&limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pets"].as_array().unwrap().len(), 1);
+    assert_eq!(body["total_pages"], 2);
+
+    let (status, body) = app.get("/api/pet/get_pets?search=Lun").await;
+    assert_eq!(status, StatusCode::OK);
+    let pets = body["pets"].as_array().unwrap();
+    assert_eq!(pets.len(), 1);
+    assert_eq!(pets[0]["pet_name"], "Luna");
+}
+
+#[tokio::test]
+async fn get_pets_reads_page_zero_as_the_first_page() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+
+    let (status, body) = app.get("/api/v1/pets?page=0").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pets"][0]["pet_name"], "Mochi");
+
+    for query in ["page=18446744073709551615", "limit=0", "limit=4294967296"] {
+        let (status, _) = app.get(&format!("/api/v1/pets?{}", query)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn update_pet_changes_only_the_given_fields() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/pet/update_pet/{}", pet_id),
+            json!({ "pet_weight": 7.25, "pet_color": "Cream" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet"]["pet_name"], "Mochi");
+    assert_eq!(body["pet"]["pet_weight"], "7.25");
+    assert_eq!(body["pet"]["pet_color"], "Cream");
+}
+
+#[tokio::test]
+async fn update_pet_rejects_a_negative_weight() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/pet/update_pet/{}", pet_id),
+            json!({ "pet_weight": -1.0 }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Pet weight cannot be negative");
+}
+
+#[tokio::test]
+async fn update_pet_returns_not_found_for_a_missing_pet() {
+    let app = TestApp::spawn().await;
+
+    let (status, _) = app
+        .patch(
+            "/api/pet/update_pet/missing",
+            json!({ "pet_name": "Ghost" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_pet_removes_the_pet() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, _) = app.delete(&format!("/api/pet/delete_pet/{}", pet_id)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app.delete(&format!("/api/pet/delete_pet/{}", pet_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Pet not found");
+}
@@ -0,0 +1,288 @@
+mod common;
+
+use axum::http::header::{ETAG, IF_MATCH};
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::{read_json, TestApp};
+use serde_json::{json, Value};
+
+async fn set_status(app: &TestApp, pet_id: &str, change: Value) -> (StatusCode, Value) {
+    app.request(
+        Method::PUT,
+        &format!("/api/v1/pets/{}/status", pet_id),
+        Some(change),
+    )
+    .await
+}
+
+fn pet_names(body: &Value) -> Vec<&str> {
+    body["pets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|pet| pet["pet_name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn euthanasia_is_recorded_with_consent_and_vet() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let (status, body) = app.get(&format!("/api/v1/pets/{}/status", pet_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet_status"]["pet_status"], "active");
+    assert_eq!(body["pet_status"]["memorial_safe"], false);
+    assert_eq!(body["pet_status"]["death"], Value::Null);
+
+    let (status, body) = set_status(
+        &app,
+        &pet_id,
+        json!({
+            "status": "deceased",
+            "date_of_death": "2025-09-30",
+            "cause_of_death": " Renal failure ",
+            "euthanasia": { "consent_given_by": "Jamie Rivera", "vet_id": vet_id },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let pet_status = &body["pet_status"];
+    assert_eq!(pet_status["pet_status"], "deceased");
+    assert_eq!(pet_status["status_changed_on"], "2025-09-30");
+    assert_eq!(pet_status["memorial_safe"], true);
+    assert_eq!(pet_status["death"]["cause_of_death"], "Renal failure");
+    assert_eq!(pet_status["death"]["euthanasia"], true);
+    assert_eq!(pet_status["death"]["consent_given_on"], "2025-09-30");
+    assert_eq!(pet_status["death"]["vet_id"], vet_id.as_str());
+
+    let (_, body) = app.get(&format!("/api/v1/pets/{}/status", pet_id)).await;
+    assert_eq!(
+        body["pet_status"]["death"]["consent_given_by"],
+        "Jamie Rivera"
+    );
+    let (_, body) = app.get(&format!("/api/v1/pets/{}", pet_id)).await;
+    assert_eq!(body["pet"]["pet_status"], "deceased");
+}
+
+#[tokio::test]
+async fn status_changes_are_validated() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    for (change, message) in [
+        (
+            json!({ "status": "deceased" }),
+            "date_of_death is required for a deceased pet",
+        ),
+        (
+            json!({ "status": "deceased", "date_of_death": "2999-01-01" }),
+            "date_of_death cannot be in the future",
+        ),
+        (
+            json!({ "status": "deceased", "date_of_death": "2020-01-01" }),
+            "date_of_death cannot be before the pet was born",
+        ),
+        (
+            json!({
+                "status": "deceased",
+                "date_of_death": "2025-09-30",
+                "euthanasia": { "consent_given_by": "Jamie Rivera", "consent_given_on": "2025-10-01", "vet_id": vet_id },
+            }),
+            "Euthanasia consent must be given on or before the date of death",
+        ),
+        (
+            json!({ "status": "inactive", "cause_of_death": "Unknown" }),
+            "Only a deceased pet has a date or cause of death or a euthanasia record",
+        ),
+    ] {
+        let (status, body) = set_status(&app, &pet_id, change).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+        assert_eq!(body["message"], message);
+    }
+
+    let (status, body) = set_status(
+        &app,
+        &pet_id,
+        json!({
+            "status": "deceased",
+            "date_of_death": "2025-09-30",
+            "euthanasia": { "consent_given_by": "Jamie Rivera", "vet_id": "missing-vet" },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Vet missing-vet doesn't work at this clinic"
+    );
+
+    let (status, _) = set_status(&app, "missing-pet", json!({ "status": "inactive" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn a_deceased_pet_stays_deceased() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let (status, body) = set_status(
+        &app,
+        &pet_id,
+        json!({ "status": "deceased", "date_of_death": "2025-09-30" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pet_status"]["death"]["euthanasia"], false);
+
+    let (status, body) = set_status(&app, &pet_id, json!({ "status": "active" })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "A deceased pet's status can't be changed");
+
+    let (status, body) = set_status(&app, &pet_id, json!({ "date_of_death": "2025-09-29" })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "The pet's death is already recorded");
+
+    let (status, body) = set_status(&app, &pet_id, json!({ "memorial_safe": false })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pet_status"]["memorial_safe"], false);
+    assert_eq!(body["pet_status"]["death"]["date_of_death"], "2025-09-30");
+}
+
+#[tokio::test]
+async fn deceased_pets_leave_the_pet_list_and_follow_ups() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let bantay = PetBuilder::new(&owner_id).name("Bantay").create(&app).await;
+    let mochi = PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    let tala = PetBuilder::new(&owner_id).name("Tala").create(&app).await;
+    for pet_id in [&bantay, &mochi, &tala] {
+        ServiceInstanceBuilder::new(pet_id)
+            .followup("2030-01-15")
+            .create(&app)
+            .await;
+    }
+
+    set_status(
+        &app,
+        &bantay,
+        json!({ "status": "deceased", "date_of_death": "2025-09-30" }),
+    )
+    .await;
+    let (status, body) = set_status(
+        &app,
+        &tala,
+        json!({ "status": "transferred", "memorial_safe": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pet_status"]["memorial_safe"], true);
+
+    let (_, body) = app.get("/api/v1/pets").await;
+    let mut names = pet_names(&body);
+    names.sort();
+    assert_eq!(names, vec!["Mochi", "Tala"]);
+    assert_eq!(body["total_pages"], 1);
+
+    let (_, body) = app.get("/api/v1/pets?status=deceased").await;
+    assert_eq!(pet_names(&body), vec!["Bantay"]);
+    let (_, body) = app.get("/api/v1/pets?status=transferred").await;
+    assert_eq!(pet_names(&body), vec!["Tala"]);
+
+    let (_, body) = app
+        .get("/api/v1/service-instances?requires_followup=true")
+        .await;
+    let followups = body["service_instances"].as_array().unwrap();
+    assert_eq!(followups.len(), 1, "{}", body);
+    assert_eq!(followups[0]["pet"]["pet_id"], mochi.as_str());
+}
+
+#[tokio::test]
+async fn only_the_pets_clinic_changes_its_status() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    app.post(
+        &format!("/api/v1/pets/{}/shares", pet_id),
+        json!({ "clinic_id": north }),
+    )
+    .await;
+    let uri = format!("/api/v1/pets/{}/status", pet_id);
+
+    let (status, body) = as_clinic(&app, &north, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet_status"]["pet_status"], "active");
+
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::PUT,
+        &uri,
+        Some(json!({ "status": "inactive" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, body) = app.get(&uri).await;
+    assert_eq!(body["pet_status"]["pet_status"], "active");
+}
+
+#[tokio::test]
+async fn a_status_change_against_a_stale_etag_is_rejected() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let status_uri = format!("/api/v1/pets/{}/status", pet_id);
+    let response = app
+        .response(Method::GET, &format!("/api/v1/pets/{}", pet_id), None)
+        .await;
+    let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/pets/{}", pet_id),
+            json!({ "pet_color": "Brown" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response = app
+        .response_with_headers(
+            Method::PUT,
+            &status_uri,
+            Some(json!({ "status": "inactive" })),
+            &[(IF_MATCH.as_str(), &etag)],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    let (_, body) = app.get(&status_uri).await;
+    assert_eq!(body["pet_status"]["pet_status"], "active");
+
+    let response = app
+        .response(Method::GET, &format!("/api/v1/pets/{}", pet_id), None)
+        .await;
+    let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+    let response = app
+        .response_with_headers(
+            Method::PUT,
+            &status_uri,
+            Some(json!({ "status": "inactive" })),
+            &[(IF_MATCH.as_str(), &etag)],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[ETAG]
+        .to_str()
+        .unwrap()
+        .starts_with("\"3"));
+    let body = read_json(response).await;
+    assert_eq!(body["pet_status"]["pet_status"], "inactive");
+}
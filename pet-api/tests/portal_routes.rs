@@ -0,0 +1,346 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::header::WWW_AUTHENTICATE;
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder,
+};
+use common::{read_json, TestApp};
+use pet_api::MockMailbox;
+use serde_json::{json, Value};
+
+async fn portal() -> (TestApp, MockMailbox) {
+    let mailbox = MockMailbox::new();
+    let mailer = mailbox.clone();
+    let app = TestApp::spawn_with(move |state| state.with_mailer(Arc::new(mailer))).await;
+    (app, mailbox)
+}
+
+/// Requests a sign-in link for `owner_email` and returns the token it carries.
+async fn login_token(app: &TestApp, mailbox: &MockMailbox, owner_email: &str) -> String {
+    let (status, _) = app
+        .post(
+            "/api/v1/portal/login-links",
+            json!({ "owner_email": owner_email }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let email = mailbox
+        .last_email_to(owner_email)
+        .expect("no sign-in email was sent");
+    email
+        .body
+        .split_whitespace()
+        .find_map(|word| {
+            word.split_once("?token=")
+                .map(|(_, token)| token.to_string())
+        })
+        .expect("the email has no sign-in link")
+}
+
+async fn sign_in(app: &TestApp, mailbox: &MockMailbox, owner_email: &str) -> String {
+    let token = login_token(app, mailbox, owner_email).await;
+    let (status, body) = app
+        .post("/api/v1/portal/session", json!({ "token": token }))
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    body["session"]["session_token"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn as_owner(
+    app: &TestApp,
+    session_token: &str,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let authorization = format!("Bearer {}", session_token);
+    let response = app
+        .response_with_headers(method, uri, body, &[("authorization", &authorization)])
+        .await;
+    let status = response.status();
+    (status, read_json(response).await)
+}
+
+#[tokio::test]
+async fn an_emailed_link_signs_the_owner_in_once() {
+    let (app, mailbox) = portal().await;
+    OwnerBuilder::new()
+        .name("Ana Reyes")
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+
+    let token = login_token(&app, &mailbox, "ANA@example.com").await;
+    let email = mailbox.last_email_to("ana@example.com").unwrap();
+    assert!(email.subject.contains("owner portal"));
+    assert!(email.body.contains("Hello Ana Reyes"));
+    assert!(email
+        .body
+        .contains("http://localhost:3000/portal/login?token="));
+
+    let (status, body) = app
+        .post("/api/v1/portal/session", json!({ "token": token }))
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let session_token = body["session"]["session_token"].as_str().unwrap();
+
+    let (status, body) = app
+        .post("/api/v1/portal/session", json!({ "token": token }))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body["message"],
+        "This sign-in link is invalid, already used or expired"
+    );
+
+    let (status, body) =
+        as_owner(&app, session_token, Method::GET, "/api/v1/portal/me", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owner"]["owner_name"], "Ana Reyes");
+
+    let (status, _) = as_owner(
+        &app,
+        session_token,
+        Method::DELETE,
+        "/api/v1/portal/session",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = as_owner(&app, session_token, Method::GET, "/api/v1/portal/me", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn sign_ins_are_never_replayed_from_an_idempotency_key() {
+    let (app, mailbox) = portal().await;
+    OwnerBuilder::new()
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+    let token = login_token(&app, &mailbox, "ana@example.com").await;
+    let sign_in = || {
+        app.response_with_headers(
+            Method::POST,
+            "/api/v1/portal/session",
+            Some(json!({ "token": token })),
+            &[("Idempotency-Key", "sign-in-1")],
+        )
+    };
+
+    let response = sign_in().await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = sign_in().await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.headers().get("idempotent-replayed").is_none());
+    assert!(read_json(response).await["session"].is_null());
+}
+
+#[tokio::test]
+async fn unknown_emails_get_the_same_answer_and_no_email() {
+    let (app, mailbox) = portal().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/portal/login-links",
+            json!({ "owner_email": "nobody@example.com" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(
+        body["message"],
+        "If an owner has this email, a sign-in link is on its way"
+    );
+    assert!(mailbox.emails().is_empty());
+}
+
+#[tokio::test]
+async fn a_link_is_sent_only_for_the_owner_in_the_requesting_clinic() {
+    let (app, mailbox) = portal().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    OwnerBuilder::new()
+        .name("Ana Reyes")
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+    let owner = OwnerBuilder::new()
+        .name("Ana Santos")
+        .email("ana@example.com");
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/owners",
+        Some(owner.body()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        "/api/v1/portal/login-links",
+        Some(json!({ "owner_email": "ana@example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let emails = mailbox.emails();
+    assert_eq!(emails.len(), 1);
+    assert!(emails[0].body.contains("Hello Ana Santos"));
+}
+
+#[tokio::test]
+async fn portal_routes_require_a_session() {
+    let (app, _) = portal().await;
+
+    for (method, uri) in [
+        (Method::GET, "/api/v1/portal/me"),
+        (Method::PATCH, "/api/v1/portal/me"),
+        (Method::GET, "/api/v1/portal/followups"),
+        (Method::GET, "/api/v1/portal/pets/any/history"),
+    ] {
+        let response = app
+            .response_with_headers(
+                method,
+                uri,
+                Some(json!({})),
+                &[("authorization", "Bearer not-a-session")],
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+        assert_eq!(response.headers()[WWW_AUTHENTICATE], "Bearer");
+    }
+    let (status, _) = app.get("/api/v1/portal/me").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn an_owner_sees_only_their_own_pets_visits_and_followups() {
+    let (app, mailbox) = portal().await;
+    let ana_id = OwnerBuilder::new()
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+    let ben_id = OwnerBuilder::new()
+        .email("ben@example.com")
+        .create(&app)
+        .await;
+    let mochi_id = PetBuilder::new(&ana_id).name("Mochi").create(&app).await;
+    let rex_id = PetBuilder::new(&ben_id).name("Rex").create(&app).await;
+    ServiceInstanceBuilder::new(&mochi_id)
+        .followup("2999-01-15")
+        .create(&app)
+        .await;
+    ServiceInstanceBuilder::new(&rex_id)
+        .followup("2999-01-10")
+        .create(&app)
+        .await;
+    let session_token = sign_in(&app, &mailbox, "ana@example.com").await;
+
+    let (_, body) = as_owner(&app, &session_token, Method::GET, "/api/v1/portal/me", None).await;
+    let pets = body["pets"].as_array().unwrap();
+    assert_eq!(pets.len(), 1);
+    assert_eq!(pets[0]["pet_name"], "Mochi");
+
+    let (status, body) = as_owner(
+        &app,
+        &session_token,
+        Method::GET,
+        &format!("/api/v1/portal/pets/{}/history", mochi_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"].as_array().unwrap().len(), 1);
+
+    let (status, body) = as_owner(
+        &app,
+        &session_token,
+        Method::GET,
+        &format!("/api/v1/portal/pets/{}/history", rex_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Pet not found");
+
+    let (status, body) = as_owner(
+        &app,
+        &session_token,
+        Method::GET,
+        "/api/v1/portal/followups",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let followups = body["followups"].as_array().unwrap();
+    assert_eq!(followups.len(), 1);
+    assert_eq!(followups[0]["pet_name"], "Mochi");
+    assert_eq!(followups[0]["followup_date"], "2999-01-15");
+}
+
+#[tokio::test]
+async fn an_owner_updates_only_their_own_contact_details() {
+    let (app, mailbox) = portal().await;
+    let ana_id = OwnerBuilder::new()
+        .name("Ana Reyes")
+        .email("ana@example.com")
+        .create(&app)
+        .await;
+    let ben_id = OwnerBuilder::new()
+        .email("ben@example.com")
+        .create(&app)
+        .await;
+    let session_token = sign_in(&app, &mailbox, "ana@example.com").await;
+
+    let (status, body) = as_owner(
+        &app,
+        &session_token,
+        Method::PATCH,
+        "/api/v1/portal/me",
+        Some(json!({
+            "owner_phone_number": "0917 555 0101",
+            "owner_name": "Someone Else",
+            "owner_id": ben_id,
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owner"]["owner_id"], ana_id);
+    assert_eq!(body["owner"]["owner_phone_number"], "0917 555 0101");
+    assert_eq!(body["owner"]["owner_name"], "Ana Reyes");
+
+    let (_, body) = app.get(&format!("/api/v1/owners/{}", ben_id)).await;
+    assert_ne!(body["owner"]["owner_phone_number"], "0917 555 0101");
+
+    let (status, _) = as_owner(
+        &app,
+        &session_token,
+        Method::PATCH,
+        "/api/v1/portal/me",
+        Some(json!({ "owner_email": "ben@example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = as_owner(
+        &app,
+        &session_token,
+        Method::PATCH,
+        "/api/v1/portal/me",
+        Some(json!({ "owner_address": "  " })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Owner address cannot be empty");
+}
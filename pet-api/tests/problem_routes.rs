@@ -0,0 +1,214 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    as_clinic, ClinicBuilder, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::{json, Value};
+
+async fn add_problem(app: &TestApp, pet_id: &str, problem: Value) -> i64 {
+    let (status, body) = app
+        .post(&format!("/api/v1/pets/{}/problems", pet_id), problem)
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    body["problem"]["problem_id"].as_i64().unwrap()
+}
+
+fn penicillin() -> Value {
+    json!({ "problem_type": "allergy", "description": "Penicillin", "severity": "severe" })
+}
+
+fn descriptions(alerts: &Value) -> Vec<&str> {
+    alerts
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|alert| alert["description"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn unresolved_problems_show_as_alerts_on_the_pet() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let problems_uri = format!("/api/v1/pets/{}/problems", rex);
+
+    let (status, body) = app
+        .post(
+            &problems_uri,
+            json!({
+                "problem_type": "chronic_condition",
+                "description": "Hip dysplasia",
+                "severity": "mild",
+                "onset_date": "2999-01-01",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "onset_date is in the future");
+
+    let bites = add_problem(
+        &app,
+        &rex,
+        json!({
+            "problem_type": "behavioral_caution",
+            "description": "Bites when restrained",
+            "severity": "moderate",
+        }),
+    )
+    .await;
+    add_problem(&app, &rex, penicillin()).await;
+
+    let (status, body) = app.get(&format!("/api/v1/pets/{}", rex)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        descriptions(&body["pet"]["alerts"]),
+        ["Penicillin", "Bites when restrained"]
+    );
+    assert_eq!(body["pet"]["pet_name"], "Rex");
+
+    let today = chrono::Local::now().date_naive().to_string();
+    let (status, body) = app
+        .patch(
+            &format!("{}/{}", problems_uri, bites),
+            json!({ "resolved_date": today }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = app.get("/api/v1/pets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(descriptions(&body["pets"][0]["alerts"]), ["Penicillin"]);
+
+    let (_, body) = app.get(&problems_uri).await;
+    assert_eq!(body["problems"].as_array().unwrap().len(), 1);
+    let (_, body) = app
+        .get(&format!("{}?include_resolved=true", problems_uri))
+        .await;
+    assert_eq!(body["problems"][1]["resolved_date"], today);
+
+    let (status, body) = app
+        .patch(
+            &format!("{}/{}", problems_uri, bites),
+            json!({ "resolved_date": "" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["problem"]["resolved_date"], Value::Null);
+
+    let (status, _) = app
+        .patch(
+            &format!("{}/999", problems_uri),
+            json!({ "severity": "mild" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn recording_a_treatment_the_pet_is_allergic_to_warns() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let luna = PetBuilder::new(&owner_id)
+        .name("Luna")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    add_problem(&app, &luna, penicillin()).await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances",
+            ServiceInstanceBuilder::new(&luna)
+                .preventive_care(&vet_id, &["Penicillin G injection", "Deworming"])
+                .body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(
+        body["allergy_warnings"],
+        json!(["Penicillin G injection matches a recorded severe allergy to Penicillin"])
+    );
+    let service_instance_id = body["service_instance_id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances",
+            ServiceInstanceBuilder::new(&luna)
+                .preventive_care(&vet_id, &["Deworming"])
+                .body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(body.get("allergy_warnings").is_none());
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(descriptions(&body["pet_alerts"]), ["Penicillin"]);
+
+    let response = app
+        .response(
+            Method::POST,
+            &format!(
+                "/api/v1/service-instances/{}/preventive-care",
+                service_instance_id
+            ),
+            Some(json!({
+                "treatment": ["Penicillin booster"],
+                "vet_id": vet_id,
+                "service_instance_id": service_instance_id,
+            })),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response.headers()["warning"],
+        "299 - \"Penicillin booster matches a recorded severe allergy to Penicillin\""
+    );
+}
+
+#[tokio::test]
+async fn a_sharing_clinic_sees_the_problem_list_but_cannot_change_it() {
+    let app = TestApp::spawn().await;
+    let north = ClinicBuilder::new().name("North Branch").create(&app).await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let rex = PetBuilder::new(&owner_id).name("Rex").create(&app).await;
+    let problem_id = add_problem(&app, &rex, penicillin()).await;
+    app.post(
+        &format!("/api/v1/pets/{}/shares", rex),
+        json!({ "clinic_id": north }),
+    )
+    .await;
+    let problems_uri = format!("/api/v1/pets/{}/problems", rex);
+
+    let (status, body) = as_clinic(&app, &north, Method::GET, &problems_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["problems"][0]["description"], "Penicillin");
+
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::POST,
+        &problems_uri,
+        Some(penicillin()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = as_clinic(
+        &app,
+        &north,
+        Method::PATCH,
+        &format!("{}/{}", problems_uri, problem_id),
+        Some(json!({ "severity": "mild" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn batch_returns_details_in_request_order_and_reports_missing_ids() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+    let first = ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Grooming"])
+        .grooming(&["Bath"])
+        .create(&app)
+        .await;
+    let second = ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Surgery"])
+        .surgery(&vet_id, "Spay")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances/batch",
+            json!({ "service_instance_ids": [second, "missing", first] }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let service_instances = body["service_instances"].as_array().unwrap();
+    assert_eq!(service_instances.len(), 2);
+    assert_eq!(service_instances[0]["service_instance_id"], second.as_str());
+    assert_eq!(
+        service_instances[0]["surgery"][0]["vet"]["vet_name"],
+        "Dr. Santos"
+    );
+    assert_eq!(service_instances[1]["service_instance_id"], first.as_str());
+    assert_eq!(service_instances[1]["grooming"][0]["grooming_type"], "Bath");
+    assert_eq!(body["not_found"], json!(["missing"]));
+}
+
+#[tokio::test]
+async fn batch_accepts_an_empty_list() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances/batch",
+            json!({ "service_instance_ids": [] }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"], json!([]));
+}
+
+#[tokio::test]
+async fn batch_rejects_too_many_ids() {
+    let app = TestApp::spawn().await;
+    let ids: Vec<String> = (0..101).map(|i| i.to_string()).collect();
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances/batch",
+            json!({ "service_instance_ids": ids }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"],
+        "At most 100 service instances can be requested at once"
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::query_counter::QueryCounter;
+use common::TestApp;
+use serde_json::json;
+
+// Everything lives in one test because the query counter is process-wide.
+#[tokio::test]
+async fn detail_loading_uses_a_constant_number_of_queries() {
+    let counter = QueryCounter::install();
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+
+    let mut detail_counts = Vec::new();
+    let mut service_instance_ids = Vec::new();
+    for size in [1, 5, 20] {
+        let mut vet_ids = Vec::new();
+        for _ in 0..size {
+            vet_ids.push(VetBuilder::new().create(&app).await);
+        }
+        let treatments = vec!["Vaccine"; size];
+        let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+            .service_types(&["Preventive Care", "Surgery"])
+            .grooming(&vec!["Bath"; size])
+            .preventive_care(&vet_ids[0], &treatments)
+            .surgery(&vet_ids[size - 1], "Spay")
+            .create(&app)
+            .await;
+        for vet_id in &vet_ids {
+            let (status, _) = app
+                .post(
+                    &format!(
+                        "/api/v1/service-instances/{}/preventive-care",
+                        service_instance_id
+                    ),
+                    json!({
+                        "treatment": ["Booster"],
+                        "vet_id": vet_id,
+                        "service_instance_id": service_instance_id,
+                    }),
+                )
+                .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        counter.reset();
+        let (status, body) = app
+            .get(&format!(
+                "/api/v1/service-instances/{}",
+                service_instance_id
+            ))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["preventive_care"].as_array().unwrap().len(), size * 2);
+        assert_eq!(body["grooming"].as_array().unwrap().len(), size);
+        detail_counts.push(counter.count());
+        service_instance_ids.push(service_instance_id);
+    }
+
+    assert!(
+        detail_counts[0] > 0,
+        "sqlx statement logging is not captured"
+    );
+    assert!(
+        detail_counts.iter().all(|count| *count == detail_counts[0]),
+        "query count grew with sub-records: {:?}",
+        detail_counts
+    );
+
+    counter.reset();
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances/batch",
+            json!({ "service_instance_ids": service_instance_ids }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"].as_array().unwrap().len(), 3);
+    // The single-instance view also loads the pet's alerts.
+    assert_eq!(counter.count() + 1, detail_counts[0]);
+}
@@ -0,0 +1,284 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::TestApp;
+use serde_json::{json, Value};
+
+struct Feed {
+    app: TestApp,
+    ana_id: String,
+    mochi_id: String,
+    luna_id: String,
+    santos_id: String,
+    spay_id: String,
+    grooming_id: String,
+    untyped_id: String,
+}
+
+/// Mochi (Ana's dog) had a spay by Dr. Santos and a grooming visit; Luna (Ben's cat)
+/// had a visit with no service types that needs a follow-up.
+async fn feed() -> Feed {
+    let app = TestApp::spawn().await;
+    let ana_id = OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+    let ben_id = OwnerBuilder::new().name("Ben Reyes").create(&app).await;
+    let mochi_id = PetBuilder::new(&ana_id)
+        .name("Mochi")
+        .pet_type("Dog")
+        .create(&app)
+        .await;
+    let luna_id = PetBuilder::new(&ben_id)
+        .name("Luna")
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let santos_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+    let aquino_id = VetBuilder::new().name("Dr. Aquino").create(&app).await;
+
+    let spay_id = ServiceInstanceBuilder::new(&mochi_id)
+        .service_types(&["Surgery", "Preventive Care"])
+        .surgery(&santos_id, "Spay")
+        .preventive_care(&aquino_id, &["Rabies Vaccine"])
+        .create(&app)
+        .await;
+    let grooming_id = ServiceInstanceBuilder::new(&mochi_id)
+        .service_types(&["Grooming"])
+        .grooming(&["Bath"])
+        .create(&app)
+        .await;
+    let untyped_id = ServiceInstanceBuilder::new(&luna_id)
+        .service_types(&[])
+        .followup("2030-01-15")
+        .create(&app)
+        .await;
+
+    // Spread the visits over distinct dates so date filters and sorting are meaningful.
+    for (service_instance_id, service_date) in [
+        (&spay_id, "2026-10-12"),
+        (&grooming_id, "2026-10-01"),
+        (&untyped_id, "2026-09-20"),
+    ] {
+        let (status, _) = app
+            .patch(
+                &format!("/api/v1/service-instances/{}", service_instance_id),
+                json!({ "service_date": service_date }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    Feed {
+        app,
+        ana_id,
+        mochi_id,
+        luna_id,
+        santos_id,
+        spay_id,
+        grooming_id,
+        untyped_id,
+    }
+}
+
+fn ids(body: &Value) -> Vec<&str> {
+    body["service_instances"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|service_instance| service_instance["service_instance_id"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn feed_includes_visits_without_service_types_newest_first() {
+    let feed = feed().await;
+
+    let (status, body) = feed.app.get("/api/v1/service-instances").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        ids(&body),
+        vec![
+            feed.spay_id.as_str(),
+            feed.grooming_id.as_str(),
+            feed.untyped_id.as_str()
+        ]
+    );
+    assert_eq!(
+        body["service_instances"][0]["service_type"],
+        json!(["Surgery", "Preventive Care"])
+    );
+    assert_eq!(body["service_instances"][2]["service_type"], json!([]));
+    assert_eq!(body["total_pages"], 1);
+}
+
+#[tokio::test]
+async fn feed_filters_by_date_range() {
+    let feed = feed().await;
+
+    let (status, body) = feed
+        .app
+        .get("/api/v1/service-instances?start_date=2026-09-30&end_date=2026-10-05")
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![feed.grooming_id.as_str()]);
+}
+
+#[tokio::test]
+async fn feed_filters_by_pet_owner_and_species() {
+    let feed = feed().await;
+
+    let (_, body) = feed
+        .app
+        .get(&format!(
+            "/api/v1/service-instances?pet_id={}",
+            feed.luna_id
+        ))
+        .await;
+    assert_eq!(ids(&body), vec![feed.untyped_id.as_str()]);
+
+    let (_, body) = feed
+        .app
+        .get(&format!(
+            "/api/v1/service-instances?owner_id={}",
+            feed.ana_id
+        ))
+        .await;
+    assert_eq!(
+        ids(&body),
+        vec![feed.spay_id.as_str(), feed.grooming_id.as_str()]
+    );
+
+    let (_, body) = feed.app.get("/api/v1/service-instances?pet_type=Cat").await;
+    assert_eq!(ids(&body), vec![feed.untyped_id.as_str()]);
+}
+
+#[tokio::test]
+async fn feed_answers_all_surgeries_last_week_by_a_vet() {
+    let feed = feed().await;
+
+    let (status, body) = feed
+        .app
+        .get(&format!(
+            "/api/v1/service-instances?service_type=Surgery&vet_id={}&start_date=2026-10-10&end_date=2026-10-16",
+            feed.santos_id
+        ))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![feed.spay_id.as_str()]);
+    assert_eq!(
+        body["service_instances"][0]["service_type"],
+        json!(["Surgery", "Preventive Care"])
+    );
+
+    let (_, body) = feed
+        .app
+        .get(&format!(
+            "/api/v1/service-instances?service_type=Grooming&vet_id={}",
+            feed.santos_id
+        ))
+        .await;
+    assert_eq!(ids(&body), Vec::<&str>::new());
+}
+
+#[tokio::test]
+async fn feed_filters_by_follow_up_status() {
+    let feed = feed().await;
+
+    let (_, body) = feed
+        .app
+        .get("/api/v1/service-instances?requires_followup=true")
+        .await;
+    assert_eq!(ids(&body), vec![feed.untyped_id.as_str()]);
+
+    let (_, body) = feed
+        .app
+        .get("/api/v1/service-instances?requires_followup=false")
+        .await;
+    assert_eq!(
+        ids(&body),
+        vec![feed.spay_id.as_str(), feed.grooming_id.as_str()]
+    );
+}
+
+#[tokio::test]
+async fn feed_sorts_and_paginates() {
+    let feed = feed().await;
+
+    let (_, body) = feed
+        .app
+        .get("/api/v1/service-instances?sort_by=service_date&order=asc&limit=2")
+        .await;
+    assert_eq!(
+        ids(&body),
+        vec![feed.untyped_id.as_str(), feed.grooming_id.as_str()]
+    );
+    assert_eq!(body["total_pages"], 2);
+
+    let (_, body) = feed
+        .app
+        .get("/api/v1/service-instances?sort_by=service_date&order=asc&limit=2&page=2")
+        .await;
+    assert_eq!(ids(&body), vec![feed.spay_id.as_str()]);
+
+    let (_, body) = feed
+        .app
+        .get("/api/v1/service-instances?sort_by=pet_name&order=asc")
+        .await;
+    assert_eq!(body["service_instances"][0]["pet"]["pet_name"], "Luna");
+    assert_eq!(
+        body["service_instances"][2]["pet"]["pet_id"],
+        feed.mochi_id.as_str()
+    );
+}
+
+#[tokio::test]
+async fn feed_rejects_malformed_filters() {
+    let feed = feed().await;
+
+    let (status, body) = feed
+        .app
+        .get("/api/v1/service-instances?start_date=last-week")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Invalid start_date: expected YYYY-MM-DD");
+
+    let (status, _) = feed
+        .app
+        .get("/api/v1/service-instances?sort_by=vet_name")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = feed
+        .app
+        .get("/api/v1/service-instances?start_date=&end_date=")
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body).len(), 3);
+}
+
+#[tokio::test]
+async fn feed_rejects_pages_it_cannot_address() {
+    let feed = feed().await;
+
+    let (status, body) = feed
+        .app
+        .get("/api/v1/service-instances?page=18446744073709551615")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "page is out of range");
+
+    for limit in ["0", "101", "4294967296"] {
+        let (status, body) = feed
+            .app
+            .get(&format!("/api/v1/service-instances?limit={}", limit))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", limit);
+        assert_eq!(body["error"], "limit must be between 1 and 100");
+    }
+
+    let (status, body) = feed.app.get("/api/v1/service-instances?page=0").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body).len(), 3);
+}
@@ -0,0 +1,379 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{
+    surgery_body, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::json;
+
+struct Clinic {
+    app: TestApp,
+    pet_id: String,
+    vet_id: String,
+}
+
+async fn clinic() -> Clinic {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().name("Ana Cruz").create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).name("Mochi").create(&app).await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+
+    Clinic {
+        app,
+        pet_id,
+        vet_id,
+    }
+}
+
+#[tokio::test]
+async fn add_service_instance_stores_every_service() {
+    let Clinic {
+        app,
+        pet_id,
+        vet_id,
+    } = clinic().await;
+
+    let (status, body) = app
+        .post(
+            "/api/service_instance/add_service_instance",
+            ServiceInstanceBuilder::new(&pet_id)
+                .service_types(&["Grooming", "Preventive Care", "Surgery"])
+                .grooming(&["Bath", "Nail Trim"])
+                .preventive_care(&vet_id, &["Rabies Vaccine"])
+                .surgery(&vet_id, "Spay")
+                .followup("2030-01-15")
+                .body(),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["pet_id"], pet_id.as_str());
+    assert_eq!(body["grooming"].as_array().unwrap().len(), 2);
+    assert_eq!(body["preventive_care"][0]["treatment"], "Rabies Vaccine");
+    assert_eq!(body["surgery"]["surgery_name"], "Spay");
+    assert_eq!(body["followup_date"], "2030-01-15");
+}
+
+#[tokio::test]
+async fn get_specific_service_instance_returns_nested_services() {
+    let Clinic {
+        app,
+        pet_id,
+        vet_id,
+    } = clinic().await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Grooming", "Preventive Care", "Surgery"])
+        .grooming(&["Bath"])
+        .preventive_care(&vet_id, &["Deworming"])
+        .surgery(&vet_id, "Dental Extraction")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/service_instance/get_specific_service_instance/{}",
+            service_instance_id
+        ))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instance_id"], service_instance_id.as_str());
+    assert_eq!(body["service_type"].as_array().unwrap().len(), 3);
+    assert_eq!(body["grooming"][0]["grooming_type"], "Bath");
+    assert_eq!(body["preventive_care"][0]["treatment"], "Deworming");
+    assert_eq!(body["preventive_care"][0]["vet"]["vet_name"], "Dr. Santos");
+    assert_eq!(body["surgery"][0]["surgery_name"], "Dental Extraction");
+    assert_eq!(body["surgery"][0]["vet"]["vet_id"], vet_id.as_str());
+}
+
+#[tokio::test]
+async fn get_specific_service_instance_returns_not_found_for_a_missing_instance() {
+    let Clinic { app, .. } = clinic().await;
+
+    let (status, body) = app
+        .get("/api/service_instance/get_specific_service_instance/missing")
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Service instance not found");
+}
+
+#[tokio::test]
+async fn get_all_service_instances_includes_pet_and_owner() {
+    let Clinic { app, pet_id, .. } = clinic().await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+
+    let (status, body) = app
+        .get("/api/service_instance/get_all_service_instances")
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let service_instances = body["service_instances"].as_array().unwrap();
+    assert_eq!(service_instances.len(), 1);
+    assert_eq!(
+        service_instances[0]["service_instance_id"],
+        service_instance_id.as_str()
+    );
+    assert_eq!(service_instances[0]["service_type"], json!(["Checkup"]));
+    assert_eq!(service_instances[0]["pet"]["pet_name"], "Mochi");
+    assert_eq!(service_instances[0]["pet"]["owner_name"], "Ana Cruz");
+}
+
+#[tokio::test]
+async fn get_pet_histories_filters_by_date_range() {
+    let Clinic { app, pet_id, .. } = clinic().await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Checkup", "Grooming"])
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/service_instance/get_pet_histories/{}",
+            pet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let history = body["service_instances"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["service_type"].as_array().unwrap().len(), 2);
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/service_instance/get_pet_histories/{}?start_date=2000-01-01&end_date=2000-12-31",
+            pet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"], json!([]));
+}
+
+#[tokio::test]
+async fn get_pet_histories_returns_not_found_for_a_missing_pet() {
+    let Clinic { app, .. } = clinic().await;
+
+    let (status, body) = app
+        .get("/api/service_instance/get_pet_histories/missing")
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Pet not found");
+}
+
+#[tokio::test]
+async fn update_service_instance_replaces_the_given_fields() {
+    let Clinic { app, pet_id, .. } = clinic().await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+
+    let (status, _) = app
+        .patch(
+            &format!(
+                "/api/service_instance/update_service_instance/{}",
+                service_instance_id
+            ),
+            json!({
+                "service_type": ["Consultation"],
+                "general_diagnosis": "Mild otitis",
+                "requires_followup": true,
+                "followup_date": "2030-02-01",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app
+        .get(&format!(
+            "/api/service_instance/get_specific_service_instance/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(body["service_type"], json!(["Consultation"]));
+    assert_eq!(body["general_diagnosis"], "Mild otitis");
+    assert_eq!(body["requires_followup"], true);
+    assert_eq!(body["followup_date"], "2030-02-01");
+    assert_eq!(body["service_reason"], "Annual visit");
+}
+
+#[tokio::test]
+async fn services_can_be_added_to_and_removed_from_an_instance() {
+    let Clinic {
+        app,
+        pet_id,
+        vet_id,
+    } = clinic().await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+
+    let (status, _) = app
+        .post(
+            &format!(
+                "/api/service_instance/add_grooming_to_instance/{}",
+                service_instance_id
+            ),
+            json!({ "grooming_type": ["Haircut"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = app
+        .post(
+            &format!(
+                "/api/service_instance/add_preventive_care_to_instance/{}",
+                service_instance_id
+            ),
+            json!({
+                "treatment": ["Flea Treatment"],
+                "vet_id": vet_id,
+                "service_instance_id": service_instance_id,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = app
+        .post(
+            &format!(
+                "/api/service_instance/add_surgery_to_instance/{}",
+                service_instance_id
+            ),
+            surgery_body(&vet_id, "Neuter"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let detail_uri = format!(
+        "/api/service_instance/get_specific_service_instance/{}",
+        service_instance_id
+    );
+    let (_, body) = app.get(&detail_uri).await;
+    let grooming_id = body["grooming"][0]["grooming_id"].as_i64().unwrap();
+    let preventive_care_id = body["preventive_care"][0]["preventive_care_id"]
+        .as_i64()
+        .unwrap();
+    let surgery_id = body["surgery"][0]["surgery_id"].as_i64().unwrap();
+    assert_eq!(body["grooming"][0]["grooming_type"], "Haircut");
+    assert_eq!(body["preventive_care"][0]["treatment"], "Flea Treatment");
+    assert_eq!(body["surgery"][0]["surgery_name"], "Neuter");
+
+    let (status, _) = app
+        .patch(
+            &format!(
+                "/api/service_instance/update_surgery_from_instance/{}",
+                surgery_id
+            ),
+            json!({ "outcome": "Recovered well" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = app.get(&detail_uri).await;
+    assert_eq!(body["surgery"][0]["outcome"], "Recovered well");
+    assert_eq!(body["surgery"][0]["surgery_name"], "Neuter");
+
+    for uri in [
+        format!(
+            "/api/service_instance/delete_grooming_from_instance/{}",
+            grooming_id
+        ),
+        format!(
+            "/api/service_instance/delete_preventive_care_from_instance/{}",
+            preventive_care_id
+        ),
+        format!(
+            "/api/service_instance/delete_surgery_from_instance/{}",
+            surgery_id
+        ),
+    ] {
+        let (status, body) = app.delete(&uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!(1));
+    }
+
+    let (_, body) = app.get(&detail_uri).await;
+    assert!(body["grooming"].as_array().is_none_or(Vec::is_empty));
+    assert!(body["preventive_care"].as_array().is_none_or(Vec::is_empty));
+    assert!(body["surgery"].as_array().is_none_or(Vec::is_empty));
+}
+
+#[tokio::test]
+async fn service_routes_reject_non_numeric_ids() {
+    let Clinic { app, .. } = clinic().await;
+
+    let (status, body) = app
+        .patch(
+            "/api/service_instance/update_surgery_from_instance/abc",
+            json!({ "outcome": "Recovered" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid surgery_id"));
+
+    let (status, _) = app
+        .delete("/api/service_instance/delete_grooming_from_instance/abc")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .delete("/api/service_instance/delete_preventive_care_from_instance/abc")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .delete("/api/service_instance/delete_surgery_from_instance/abc")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn delete_service_removes_the_instance() {
+    let Clinic {
+        app,
+        pet_id,
+        vet_id,
+    } = clinic().await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Grooming", "Surgery"])
+        .grooming(&["Bath"])
+        .surgery(&vet_id, "Spay")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .delete(&format!(
+            "/api/service_instance/delete_service/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!(1));
+
+    let (status, _) = app
+        .get(&format!(
+            "/api/service_instance/get_specific_service_instance/{}",
+            service_instance_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn pet_histories_include_visits_without_a_service_type() {
+    let Clinic { app, pet_id, .. } = clinic().await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&[])
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/service_instance/get_pet_histories/{}",
+            pet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let history = body["service_instances"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["service_type"], json!([]));
+}
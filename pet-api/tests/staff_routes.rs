@@ -0,0 +1,248 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{
+    OwnerBuilder, PetBuilder, ServiceInstanceBuilder, StaffBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::json;
+
+/// Books a spay with the given vet and returns its staff URI.
+async fn surgery_staff_uri(app: &TestApp, vet_id: &str) -> String {
+    let owner_id = OwnerBuilder::new().create(app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .surgery(vet_id, "Spay")
+        .create(app)
+        .await;
+    let (_, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    format!(
+        "/api/v1/surgeries/{}/staff",
+        body["surgery"][0]["surgery_id"]
+    )
+}
+
+#[tokio::test]
+async fn vets_are_the_veterinarians_on_the_staff_roster() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+    StaffBuilder::new("veterinarian")
+        .name("Dr Cruz")
+        .create(&app)
+        .await;
+    StaffBuilder::new("technician")
+        .name("Lea Tan")
+        .create(&app)
+        .await;
+
+    let (status, body) = app.get(&format!("/api/v1/staff/{}", vet_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["staff_member"]["staff_role"], "veterinarian");
+    assert_eq!(body["staff_member"]["is_active"], true);
+
+    let (_, body) = app.get("/api/v1/vets").await;
+    let vets: Vec<_> = body["vets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|vet| vet["vet_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(vets, ["Dr Cruz", "Dr. Santos"]);
+
+    let (_, body) = app.get("/api/v1/staff?role=technician").await;
+    assert_eq!(body["staff"].as_array().unwrap().len(), 1);
+    assert_eq!(body["staff"][0]["staff_name"], "Lea Tan");
+    assert_eq!(body["total_pages"], 1);
+}
+
+#[tokio::test]
+async fn a_veterinarian_needs_a_license_number() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/staff",
+            json!({
+                "staff_name": "Dr Cruz",
+                "staff_email": "cruz@example.com",
+                "staff_phone_number": "09171234567",
+                "staff_role": "veterinarian",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "A veterinarian needs a license number");
+}
+
+#[tokio::test]
+async fn staff_without_a_license_cannot_share_an_email() {
+    let app = TestApp::spawn().await;
+    StaffBuilder::new("technician")
+        .email("lea@example.com")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/staff",
+            StaffBuilder::new("groomer").email("lea@example.com").body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{}", body);
+    assert_eq!(body["message"], "Staff member already exists");
+}
+
+#[tokio::test]
+async fn a_surgery_has_one_performer_and_any_number_of_assistants() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+    let technician_id = StaffBuilder::new("technician")
+        .name("Lea Tan")
+        .create(&app)
+        .await;
+    let receptionist_id = StaffBuilder::new("receptionist")
+        .name("Mia Lim")
+        .create(&app)
+        .await;
+    let staff_uri = surgery_staff_uri(&app, &vet_id).await;
+
+    let (status, body) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": technician_id, "staff_function": "performer" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Only a veterinarian can perform surgery");
+
+    let (status, _) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": technician_id, "staff_function": "assistant" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": vet_id, "staff_function": "performer" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(
+        body["staff"],
+        json!([
+            { "staff_id": vet_id, "staff_name": "Dr. Santos", "staff_role": "veterinarian", "staff_function": "performer" },
+            { "staff_id": technician_id, "staff_name": "Lea Tan", "staff_role": "technician", "staff_function": "assistant" },
+        ])
+    );
+
+    let (status, body) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": StaffBuilder::new("veterinarian").name("Dr Cruz").create(&app).await, "staff_function": "performer" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "Surgery already has a performer");
+    let (status, _) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": technician_id, "staff_function": "assistant" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = app
+        .post(
+            &staff_uri,
+            json!({ "staff_id": receptionist_id, "staff_function": "assistant" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, body) = app
+        .delete(&format!("{}/{}", staff_uri, technician_id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["staff"].as_array().unwrap().len(), 1);
+    let (status, _) = app
+        .delete(&format!("{}/{}", staff_uri, technician_id))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app.get("/api/v1/grooming/999/staff").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Grooming not found");
+}
+
+#[tokio::test]
+async fn inactive_staff_keep_their_history_but_cannot_be_assigned() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    let technician_id = StaffBuilder::new("technician")
+        .name("Lea Tan")
+        .create(&app)
+        .await;
+    let staff_uri = surgery_staff_uri(&app, &vet_id).await;
+    app.post(
+        &staff_uri,
+        json!({ "staff_id": technician_id, "staff_function": "assistant" }),
+    )
+    .await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/staff/{}", technician_id),
+            json!({ "is_active": false, "credentials": "RVT" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["staff_member"]["is_active"], false);
+    assert_eq!(body["staff_member"]["credentials"], "RVT");
+
+    let (_, body) = app.get(&staff_uri).await;
+    assert_eq!(body["staff"][0]["staff_id"], technician_id);
+    let (_, body) = app.get("/api/v1/staff?active=false").await;
+    assert_eq!(body["staff"][0]["staff_id"], technician_id);
+
+    let other_surgery = surgery_staff_uri(&app, &vet_id).await;
+    let (status, body) = app
+        .post(
+            &other_surgery,
+            json!({ "staff_id": technician_id, "staff_function": "assistant" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Inactive staff can't be assigned to procedures"
+    );
+}
+
+#[tokio::test]
+async fn page_zero_lists_the_first_page() {
+    let app = TestApp::spawn().await;
+    StaffBuilder::new("technician")
+        .name("Lea Tan")
+        .create(&app)
+        .await;
+
+    let (status, body) = app.get("/api/v1/staff?page=0&role=technician").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["staff"][0]["staff_name"], "Lea Tan");
+}
+
+#[tokio::test]
+async fn pages_and_limits_out_of_range_are_rejected() {
+    let app = TestApp::spawn().await;
+
+    for query in ["page=18446744073709551615", "limit=0", "limit=4294967296"] {
+        let (status, _) = app.get(&format!("/api/v1/staff?{}", query)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+    }
+}
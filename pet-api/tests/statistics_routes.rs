@@ -0,0 +1,341 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{
+    surgery_body, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::{json, Value};
+
+async fn visit_on(app: &TestApp, pet_id: &str, service_date: &str, service_types: &[&str]) {
+    let service_instance_id = ServiceInstanceBuilder::new(pet_id)
+        .service_types(service_types)
+        .create(app)
+        .await;
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/service-instances/{}", service_instance_id),
+            json!({ "service_date": service_date }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+fn bucket_visits(statistics: &Value) -> Vec<(String, i64)> {
+    statistics["buckets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|bucket| {
+            (
+                bucket["period_start"].as_str().unwrap().to_string(),
+                bucket["visits"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn counter_services_counts_visits_per_service_type() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Checkup", "Grooming"])
+        .create(&app)
+        .await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .service_types(&["Checkup"])
+        .create(&app)
+        .await;
+
+    let (status, body) = app.get("/api/statistics/counter_services").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let mut services = body["services"].as_array().unwrap().clone();
+    services.sort_by_key(|service| service["service_type_name"].as_str().unwrap().to_string());
+    assert_eq!(
+        services,
+        vec![
+            json!({ "service_type_name": "Checkup", "total": 2 }),
+            json!({ "service_type_name": "Grooming", "total": 1 }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pet_type_visit_summary_only_reports_pet_types_with_repeat_visits() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let dog_id = PetBuilder::new(&owner_id)
+        .pet_type("Dog")
+        .create(&app)
+        .await;
+    let cat_id = PetBuilder::new(&owner_id)
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    for pet_id in [&dog_id, &dog_id, &dog_id, &cat_id] {
+        ServiceInstanceBuilder::new(pet_id).create(&app).await;
+    }
+
+    let (status, body) = app.get("/api/statistics/get_pet_type_visit_summary").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["pet_type_visit_summary"],
+        json!([{ "pet_type": "Dog", "total_visits": 3 }])
+    );
+}
+
+#[tokio::test]
+async fn visit_statistics_zero_fill_weekly_buckets_and_compare_with_the_previous_period() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    visit_on(&app, &pet_id, "2024-01-02", &["Checkup"]).await;
+    visit_on(&app, &pet_id, "2024-01-03", &["Checkup", "Grooming"]).await;
+    visit_on(&app, &pet_id, "2024-01-17", &["Vaccination"]).await;
+    visit_on(&app, &pet_id, "2023-12-20", &["Checkup"]).await;
+
+    let (status, body) = app
+        .get("/api/v1/statistics/visits?start_date=2024-01-01&end_date=2024-01-21&granularity=week")
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let statistics = &body["statistics"];
+    assert_eq!(statistics["granularity"], "week");
+    assert_eq!(
+        bucket_visits(statistics),
+        vec![
+            ("2024-01-01".to_string(), 2),
+            ("2024-01-08".to_string(), 0),
+            ("2024-01-15".to_string(), 1),
+        ]
+    );
+    assert_eq!(statistics["buckets"][0]["period_end"], "2024-01-07");
+    assert_eq!(
+        statistics["buckets"][1]["service_types"],
+        json!([
+            { "service_type_name": "Checkup", "total": 0 },
+            { "service_type_name": "Grooming", "total": 0 },
+            { "service_type_name": "Vaccination", "total": 0 },
+        ])
+    );
+    assert_eq!(statistics["totals"]["visits"], 3);
+    assert_eq!(statistics["totals"]["new_pets"], 0);
+
+    let comparison = &statistics["comparison"];
+    assert_eq!(comparison["previous_start_date"], "2023-12-11");
+    assert_eq!(comparison["previous_end_date"], "2023-12-31");
+    assert_eq!(
+        comparison["visits"],
+        json!({ "current": 3, "previous": 1, "difference": 2, "percent_change": 200.0 })
+    );
+    assert_eq!(
+        comparison["service_types"][0],
+        json!({
+            "service_type_name": "Checkup",
+            "current": 2,
+            "previous": 1,
+            "difference": 1,
+            "percent_change": 100.0,
+        })
+    );
+}
+
+#[tokio::test]
+async fn visit_statistics_default_to_the_last_30_days_and_count_new_pets_and_owners() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+
+    let (status, body) = app.get("/api/v1/statistics/visits").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let statistics = &body["statistics"];
+    let buckets = statistics["buckets"].as_array().unwrap();
+    assert_eq!(buckets.len(), 30);
+    let today = buckets.last().unwrap();
+    assert_eq!(today["period_start"], statistics["end_date"]);
+    assert_eq!(
+        (&today["visits"], &today["new_pets"], &today["new_owners"]),
+        (&json!(1), &json!(1), &json!(1))
+    );
+    assert_eq!(statistics["comparison"]["new_owners"]["previous"], 0);
+    assert_eq!(
+        statistics["comparison"]["new_owners"]["percent_change"],
+        Value::Null
+    );
+}
+
+#[tokio::test]
+async fn visit_statistics_clip_monthly_buckets_to_the_range() {
+    let app = TestApp::spawn().await;
+
+    let (_, body) = app
+        .get(
+            "/api/v1/statistics/visits?start_date=2024-01-15&end_date=2024-03-10&granularity=month",
+        )
+        .await;
+    let buckets = body["statistics"]["buckets"].as_array().unwrap();
+    assert_eq!(buckets.len(), 3);
+    assert_eq!(buckets[0]["period_start"], "2024-01-15");
+    assert_eq!(buckets[0]["period_end"], "2024-01-31");
+    assert_eq!(buckets[2]["period_start"], "2024-03-01");
+    assert_eq!(buckets[2]["period_end"], "2024-03-10");
+
+    let (_, body) = app
+        .get(
+            "/api/v1/statistics/visits?start_date=2024-03-01&end_date=2024-04-30&granularity=month",
+        )
+        .await;
+    let comparison = &body["statistics"]["comparison"];
+    assert_eq!(comparison["previous_start_date"], "2024-01-01");
+    assert_eq!(comparison["previous_end_date"], "2024-02-29");
+}
+
+#[tokio::test]
+async fn visit_statistics_reject_bad_ranges() {
+    let app = TestApp::spawn().await;
+
+    for (uri, message) in [
+        (
+            "/api/v1/statistics/visits?start_date=2024-13-01",
+            "Invalid start_date: expected YYYY-MM-DD",
+        ),
+        (
+            "/api/v1/statistics/visits?start_date=2024-02-01&end_date=2024-01-01",
+            "start_date is after end_date",
+        ),
+        (
+            "/api/v1/statistics/visits?start_date=2020-01-01&end_date=2024-01-01",
+            "The range spans 1462 buckets; at most 366 are allowed, so use a coarser granularity",
+        ),
+    ] {
+        let (status, body) = app.get(uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(body["message"], message);
+    }
+
+    let (status, _) = app.get("/api/v1/statistics/visits?granularity=year").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn vet_statistics_report_each_vets_procedures_surgeries_and_patients() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let dog_id = PetBuilder::new(&owner_id)
+        .pet_type("Dog")
+        .create(&app)
+        .await;
+    let cat_id = PetBuilder::new(&owner_id)
+        .pet_type("Cat")
+        .create(&app)
+        .await;
+    let surgeon_id = VetBuilder::new().name("Dr. Cruz").create(&app).await;
+    let idle_vet_id = VetBuilder::new().name("Dr. Bautista").create(&app).await;
+
+    let spay_id = ServiceInstanceBuilder::new(&dog_id)
+        .preventive_care(&surgeon_id, &["Rabies Vaccine", "Deworming"])
+        .surgery(&surgeon_id, "Spay")
+        .create(&app)
+        .await;
+    let mut complicated = surgery_body(&surgeon_id, "Spay");
+    complicated["complications"] = json!("Post-operative bleeding");
+    complicated["outcome"] = json!("Died during recovery");
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/service-instances/{}/surgeries", spay_id),
+            complicated,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    ServiceInstanceBuilder::new(&cat_id)
+        .surgery(&surgeon_id, "Dental Extraction")
+        .create(&app)
+        .await;
+
+    let (status, body) = app.get("/api/statistics/vets?granularity=month").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let vets = body["statistics"]["vets"].as_array().unwrap();
+    assert_eq!(vets.len(), 2);
+    let surgeon = &vets[0];
+    assert_eq!(surgeon["vet_name"], "Dr. Cruz");
+    assert_eq!(
+        (
+            &surgeon["preventive_care"],
+            &surgeon["surgeries"],
+            &surgeon["procedures"]
+        ),
+        (&json!(2), &json!(3), &json!(5))
+    );
+    assert_eq!(surgeon["buckets"].as_array().unwrap().len(), 12);
+    assert_eq!(surgeon["buckets"][11]["procedures"], 5);
+    assert_eq!(surgeon["buckets"][0]["procedures"], 0);
+    assert_eq!(
+        surgeon["surgeries_by_name"],
+        json!([
+            { "surgery_name": "Spay", "total": 2, "with_complications": 1 },
+            { "surgery_name": "Dental Extraction", "total": 1, "with_complications": 0 },
+        ])
+    );
+    assert_eq!(
+        surgeon["surgery_outcomes"],
+        json!({
+            "surgeries": 3,
+            "with_complications": 1,
+            "complication_rate": 33.3,
+            "unfavorable_outcomes": 1,
+            "unfavorable_outcome_rate": 33.3,
+            "unrecorded_outcomes": 0,
+        })
+    );
+    assert_eq!(
+        surgeon["patients_by_species"],
+        json!([
+            { "pet_type": "Cat", "patients": 1 },
+            { "pet_type": "Dog", "patients": 1 },
+        ])
+    );
+
+    let idle_vet = &vets[1];
+    assert_eq!(idle_vet["vet_id"], idle_vet_id);
+    assert_eq!(idle_vet["procedures"], 0);
+    assert_eq!(
+        idle_vet["surgery_outcomes"]["complication_rate"],
+        Value::Null
+    );
+}
+
+#[tokio::test]
+async fn vet_statistics_filter_by_vet_and_date_range() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+    VetBuilder::new().create(&app).await;
+    ServiceInstanceBuilder::new(&pet_id)
+        .surgery(&vet_id, "Spay")
+        .create(&app)
+        .await;
+
+    let (status, body) = app
+        .get(&format!(
+            "/api/v1/statistics/vets?vet_id={}&start_date=2001-01-01&end_date=2001-01-31",
+            vet_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let vets = body["statistics"]["vets"].as_array().unwrap();
+    assert_eq!(vets.len(), 1);
+    assert_eq!(vets[0]["surgeries"], 0);
+    assert_eq!(vets[0]["buckets"].as_array().unwrap().len(), 31);
+
+    let (status, body) = app.get("/api/v1/statistics/vets?vet_id=missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Vet not found");
+}
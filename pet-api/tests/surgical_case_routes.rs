@@ -0,0 +1,233 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::{OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder};
+use common::TestApp;
+use serde_json::json;
+
+/// Opens a surgical case for a new spay and returns the case's URI.
+async fn planned_case(app: &TestApp) -> String {
+    let owner_id = OwnerBuilder::new().create(app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(app).await;
+    let vet_id = VetBuilder::new().create(app).await;
+    let service_instance_id = ServiceInstanceBuilder::new(&pet_id)
+        .surgery(&vet_id, "Spay")
+        .create(app)
+        .await;
+    let (_, body) = app
+        .get(&format!(
+            "/api/v1/service-instances/{}",
+            service_instance_id
+        ))
+        .await;
+    let case_uri = format!(
+        "/api/v1/surgeries/{}/case",
+        body["surgery"][0]["surgery_id"]
+    );
+
+    let (status, body) = app.post(&case_uri, json!({})).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["case"]["status"], "planned");
+    assert_eq!(body["case"]["next_status"], "in_progress");
+    case_uri
+}
+
+async fn move_to(app: &TestApp, case_uri: &str, status: &str) -> (StatusCode, serde_json::Value) {
+    app.post(
+        &format!("{}/transitions", case_uri),
+        json!({ "status": status }),
+    )
+    .await
+}
+
+async fn start_surgery(app: &TestApp, case_uri: &str) {
+    let (status, _) = app
+        .patch(
+            case_uri,
+            json!({
+                "fasting_confirmed": true,
+                "bloodwork_reviewed": true,
+                "iv_access_placed": true,
+                "surgical_site_prepared": true,
+                "consent_given_by": "Ana Reyes",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = move_to(app, case_uri, "in_progress").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn a_case_runs_from_planned_to_discharged() {
+    let app = TestApp::spawn().await;
+    let case_uri = planned_case(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &case_uri,
+            json!({
+                "fasting_confirmed": true,
+                "bloodwork_reviewed": true,
+                "iv_access_placed": true,
+                "surgical_site_prepared": true,
+                "consent_given_by": "  Ana Reyes ",
+                "preop_notes": "ASA II",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["case"]["pre_op"]["complete"], true);
+    assert_eq!(body["case"]["pre_op"]["consent"]["given_by"], "Ana Reyes");
+
+    let (status, body) = move_to(&app, &case_uri, "in_progress").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["case"]["timeline"]["started_at"].is_i64());
+
+    for entry in [
+        json!({ "drug": "Propofol", "dose": "4 mg/kg IV" }),
+        json!({ "heart_rate": 96, "respiratory_rate": 14, "temperature_c": 37.8, "spo2": 98 }),
+    ] {
+        let (status, _) = app
+            .post(&format!("{}/anesthesia-log", case_uri), entry)
+            .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, _) = app
+        .patch(
+            &case_uri,
+            json!({ "intraop_notes": "Routine ovariohysterectomy" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = move_to(&app, &case_uri, "recovery").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = move_to(&app, &case_uri, "discharged").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Write the post-op instructions before discharging"
+    );
+    let (status, _) = app
+        .patch(
+            &case_uri,
+            json!({ "postop_instructions": "Cone for 10 days, recheck in 2 weeks" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = move_to(&app, &case_uri, "discharged").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app.get(&case_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let case = &body["case"];
+    assert_eq!(case["status"], "discharged");
+    assert!(case["next_status"].is_null());
+    assert_eq!(case["anesthesia_log"].as_array().unwrap().len(), 2);
+    assert_eq!(case["anesthesia_log"][0]["drug"], "Propofol");
+    assert_eq!(case["anesthesia_log"][1]["spo2"], 98);
+    assert!(case["timeline"]["discharged_at"].is_i64());
+
+    let (status, _) = app
+        .patch(&case_uri, json!({ "postop_instructions": "Changed" }))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn only_the_next_status_is_allowed() {
+    let app = TestApp::spawn().await;
+    let case_uri = planned_case(&app).await;
+
+    let (status, body) = move_to(&app, &case_uri, "recovery").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        "A surgical case can't move from planned to recovery"
+    );
+
+    let (status, body) = move_to(&app, &case_uri, "in_progress").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Surgery can't start until the pre-op checklist is complete; missing: fasting_confirmed, \
+         bloodwork_reviewed, iv_access_placed, surgical_site_prepared, consent"
+    );
+
+    start_surgery(&app, &case_uri).await;
+    let (status, _) = move_to(&app, &case_uri, "planned").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = move_to(&app, &case_uri, "recovery").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["message"],
+        "Record the intra-op notes before moving to recovery"
+    );
+}
+
+#[tokio::test]
+async fn each_phase_only_takes_its_own_records() {
+    let app = TestApp::spawn().await;
+    let case_uri = planned_case(&app).await;
+    let log_uri = format!("{}/anesthesia-log", case_uri);
+
+    let (status, _) = app.post(&log_uri, json!({ "drug": "Propofol" })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = app
+        .patch(&case_uri, json!({ "intraop_notes": "Too early" }))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = app
+        .patch(&case_uri, json!({ "consent_given_by": " " }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Consent must name who gave it");
+
+    start_surgery(&app, &case_uri).await;
+    let (status, body) = app
+        .patch(&case_uri, json!({ "fasting_confirmed": false }))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        "The pre-op checklist and consent can't change once surgery has started"
+    );
+
+    for (entry, message) in [
+        (
+            json!({ "notes": "Looks fine" }),
+            "An anesthesia log entry needs a drug or at least one vital sign",
+        ),
+        (
+            json!({ "dose": "2 mg" }),
+            "A dose needs the drug it was given of",
+        ),
+        (json!({ "spo2": 120 }), "spo2 must be between 0 and 100"),
+        (
+            json!({ "heart_rate": 80, "recorded_at": 32503680000i64 }),
+            "recorded_at is in the future",
+        ),
+    ] {
+        let (status, body) = app.post(&log_uri, entry).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["message"], message);
+    }
+}
+
+#[tokio::test]
+async fn a_surgery_has_at_most_one_case() {
+    let app = TestApp::spawn().await;
+    let case_uri = planned_case(&app).await;
+
+    let (status, _) = app.post(&case_uri, json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app.post("/api/v1/surgeries/999999/case", json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Surgery not found");
+    let (status, body) = app.get("/api/v1/surgeries/999999/case").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Surgical case not found");
+}
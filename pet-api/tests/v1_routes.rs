@@ -0,0 +1,230 @@
+mod common;
+
+use axum::http::header::LINK;
+use axum::http::{Method, StatusCode};
+use common::fixtures::{
+    surgery_body, OwnerBuilder, PetBuilder, ServiceInstanceBuilder, VetBuilder,
+};
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn owners_are_managed_through_resource_paths() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/owners",
+            OwnerBuilder::new().name("Ana Cruz").body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let owner_id = body["owner"]["owner_id"].as_str().unwrap().to_string();
+    let owner_uri = format!("/api/v1/owners/{}", owner_id);
+
+    let (status, body) = app.get("/api/v1/owners?search=Ana").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owners"][0]["owner_id"], owner_id.as_str());
+
+    let (status, body) = app
+        .patch(&owner_uri, json!({ "owner_name": "Ana Reyes" }))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["owner"]["owner_name"], "Ana Reyes");
+
+    let (status, body) = app.get(&owner_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pets"], json!([]));
+
+    let (status, _) = app.delete(&owner_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.get(&owner_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn pets_and_their_history_are_managed_through_resource_paths() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/pets",
+            PetBuilder::new(&owner_id).name("Mochi").body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let pet_id = body["pet"]["pet_id"].as_str().unwrap().to_string();
+    let pet_uri = format!("/api/v1/pets/{}", pet_id);
+
+    let (status, body) = app.get("/api/v1/pets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pets"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app.patch(&pet_uri, json!({ "pet_color": "Cream" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pet"]["pet_color"], "Cream");
+
+    ServiceInstanceBuilder::new(&pet_id).create(&app).await;
+    let (status, body) = app.get(&format!("{}/service-instances", pet_uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app.get("/api/v1/pets/missing/service-instances").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "Pet not found");
+
+    let (status, _) = app.delete(&pet_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.get(&pet_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn vets_are_managed_through_resource_paths() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post("/api/v1/vets", VetBuilder::new().name("Dr. Santos").body())
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let vet_id = body["vet"]["vet_id"].as_str().unwrap().to_string();
+
+    let (status, body) = app.get("/api/v1/vets/list").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["vets"][0]["vet_id"], vet_id.as_str());
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/vets/{}", vet_id),
+            json!({ "vet_name": "Dr. Aquino" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["vet"]["vet_name"], "Dr. Aquino");
+
+    let (status, body) = app.get("/api/v1/vets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_pages"], 1);
+
+    let (status, _) = app.delete(&format!("/api/v1/vets/{}", vet_id)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn service_instances_nest_their_services() {
+    let app = TestApp::spawn().await;
+    let owner_id = OwnerBuilder::new().create(&app).await;
+    let pet_id = PetBuilder::new(&owner_id).create(&app).await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/service-instances",
+            ServiceInstanceBuilder::new(&pet_id).body(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let service_instance_id = body["service_instance_id"].as_str().unwrap().to_string();
+    let instance_uri = format!("/api/v1/service-instances/{}", service_instance_id);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/surgeries", instance_uri),
+            surgery_body(&vet_id, "Spay"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = app
+        .post(
+            &format!("{}/grooming", instance_uri),
+            json!({ "grooming_type": ["Bath"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = app
+        .post(
+            &format!("{}/preventive-care", instance_uri),
+            json!({
+                "treatment": ["Deworming"],
+                "vet_id": vet_id,
+                "service_instance_id": service_instance_id,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = app.get(&instance_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let surgery_id = body["surgery"][0]["surgery_id"].as_i64().unwrap();
+    let grooming_id = body["grooming"][0]["grooming_id"].as_i64().unwrap();
+    let preventive_care_id = body["preventive_care"][0]["preventive_care_id"]
+        .as_i64()
+        .unwrap();
+
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/surgeries/{}", surgery_id),
+            json!({ "outcome": "Recovered" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    for uri in [
+        format!("/api/v1/surgeries/{}", surgery_id),
+        format!("/api/v1/grooming/{}", grooming_id),
+        format!("/api/v1/preventive-care/{}", preventive_care_id),
+    ] {
+        let (status, body) = app.delete(&uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!(1));
+    }
+
+    let (status, _) = app
+        .patch(&instance_uri, json!({ "service_reason": "Follow-up" }))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = app.get("/api/v1/service-instances").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_instances"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app.get("/api/v1/statistics/services").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["services"][0]["total"], 1);
+    let (status, _) = app.get("/api/v1/statistics/pet-type-visits").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.delete(&instance_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.get(&instance_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn legacy_routes_are_marked_deprecated() {
+    let app = TestApp::spawn().await;
+
+    let response = app.response(Method::GET, "/api/pet/get_pets", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["deprecation"], "@1792368000");
+    assert!(response.headers()[LINK]
+        .to_str()
+        .unwrap()
+        .contains(r#"</api/v1>; rel="successor-version""#));
+
+    let response = app
+        .response(Method::GET, "/api/pet/get_pet/missing", None)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().contains_key("deprecation"));
+}
+
+#[tokio::test]
+async fn versioned_and_probe_routes_are_not_deprecated() {
+    let app = TestApp::spawn().await;
+
+    for uri in ["/api/v1/pets", "/api/livez", "/api/health_check"] {
+        let response = app.response(Method::GET, uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key("deprecation"), "{}", uri);
+    }
+}
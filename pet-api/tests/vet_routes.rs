@@ -0,0 +1,129 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::fixtures::VetBuilder;
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn add_vet_returns_the_created_vet() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .post(
+            "/api/vet/add_vet",
+            VetBuilder::new().name("Dr. Santos").body(),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["vet"]["vet_name"], "Dr. Santos");
+}
+
+#[tokio::test]
+async fn get_vets_paginates_by_name() {
+    let app = TestApp::spawn().await;
+    VetBuilder::new().name("Dr. Santos").create(&app).await;
+    VetBuilder::new().name("Dr. Aquino").create(&app).await;
+    VetBuilder::new().name("Dr. Mendoza").create(&app).await;
+
+    let (status, body) = app.get("/api/vet/get_vets?page=1&limit=2").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_pages"], 2);
+    let names: Vec<_> = body["vets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|vet| vet["vet_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Dr. Aquino", "Dr. Mendoza"]);
+}
+
+#[tokio::test]
+async fn get_vets_reads_page_zero_as_the_first_page() {
+    let app = TestApp::spawn().await;
+    VetBuilder::new().name("Dr. Santos").create(&app).await;
+
+    let (status, body) = app.get("/api/v1/vets?page=0").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["vets"][0]["vet_name"], "Dr. Santos");
+
+    let (status, body) = app.get("/api/v1/vets?page=18446744073709551615").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "page is out of range");
+}
+
+#[tokio::test]
+async fn get_vet_lists_returns_ids_and_names() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+
+    let (status, body) = app.get("/api/vet/get_vet_lists").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["vets"],
+        json!([{ "vet_id": vet_id, "vet_name": "Dr. Santos" }])
+    );
+}
+
+#[tokio::test]
+async fn update_vet_changes_only_the_given_fields() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().name("Dr. Santos").create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/vet/update_vet/{}", vet_id),
+            json!({ "vet_phone_number": "0281234567" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["vet"]["vet_name"], "Dr. Santos");
+    assert_eq!(body["vet"]["vet_phone_number"], "0281234567");
+}
+
+#[tokio::test]
+async fn update_vet_rejects_blank_fields() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/vet/update_vet/{}", vet_id),
+            json!({ "vet_license_number": "" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Vet license number cannot be empty");
+}
+
+#[tokio::test]
+async fn update_vet_returns_not_found_for_a_missing_vet() {
+    let app = TestApp::spawn().await;
+
+    let (status, _) = app
+        .patch(
+            "/api/vet/update_vet/missing",
+            json!({ "vet_name": "Dr. Who" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_vet_removes_the_vet() {
+    let app = TestApp::spawn().await;
+    let vet_id = VetBuilder::new().create(&app).await;
+
+    let (status, _) = app.delete(&format!("/api/vet/delete_vet/{}", vet_id)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app.delete(&format!("/api/vet/delete_vet/{}", vet_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Vet not found");
+}
@@ -173,11 +173,12 @@ export class ServiceInstanceService extends AxiosService {
         }
     }
 
-    async getAllServiceInstances() {
+    async getAllServiceInstances(page_number: number = 1) {
+        const limit = 10;
         try {
             const response = await this.request(
                 'GET',
-                `${this.url}/get_all_service_instances`
+                `${this.url}/get_all_service_instances?page=${page_number}&limit=${limit}`
             );
             const { data, status, statusText } = response;
             return { data, status, statusText };